@@ -11,6 +11,7 @@ import android.os.Build
 import app.tauri.Logger
 import app.tauri.annotation.InvokeArg
 import app.tauri.plugin.Invoke
+import com.fasterxml.jackson.annotation.JsonAlias
 import com.fasterxml.jackson.annotation.JsonValue
 
 enum class Importance(@JsonValue val value: Int) {
@@ -34,15 +35,53 @@ class Channel {
   var description: String? = null
   var sound: String? = null
   var lights: Boolean? = null
+  // Rust and JS send `lightColor`.
+  @set:JsonAlias("lightColor")
   var lightsColor: String? = null
   var vibration: Boolean? = null
   var importance: Importance? = null
   var visibility: Visibility? = null
 }
 
+@InvokeArg
+class CreateChannelsArgs {
+  lateinit var channels: List<Channel>
+}
+
 @InvokeArg
 class DeleteChannelArgs {
   lateinit var id: String
+  var onPending: OnPendingPolicy? = null
+}
+
+@InvokeArg
+class OnPendingPolicy {
+  var policy: String = "leave"
+  var toChannel: String? = null
+}
+
+/**
+ * Ids of the channels blocked between two importance snapshots: present in
+ * both and moved to [Importance.None]. Channels created or deleted in between
+ * are ignored.
+ */
+fun blockedChannels(before: Map<String, Int>, after: Map<String, Int>): List<String> {
+  return after.filter { (id, importance) ->
+    val previous = before[id]
+    importance == Importance.None.value && previous != null && previous != Importance.None.value
+  }.keys.sorted()
+}
+
+/**
+ * Channel a notification is posted on: [channelId] if it exists, else
+ * [fallback] if that exists, else `null`.
+ */
+fun resolveChannelId(channelId: String, fallback: String?, existing: Set<String>): String? {
+  return when {
+    channelId in existing -> channelId
+    fallback != null && fallback in existing -> fallback
+    else -> null
+  }
 }
 
 class ChannelManager(private var context: Context) {
@@ -63,6 +102,18 @@ class ChannelManager(private var context: Context) {
     }
   }
 
+  fun createChannels(invoke: Invoke) {
+    if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.O) {
+      val args = invoke.parseArgs(CreateChannelsArgs::class.java)
+      for (channel in args.channels) {
+        createChannel(channel)
+      }
+      invoke.resolve()
+    } else {
+      invoke.reject("channel not available")
+    }
+  }
+
   private fun createChannel(channel: Channel) {
     if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.O) {
       val notificationChannel = NotificationChannel(
@@ -103,14 +154,20 @@ class ChannelManager(private var context: Context) {
     }
   }
 
-  fun deleteChannel(invoke: Invoke) {
+  fun deleteChannel(id: String): Boolean {
     if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.O) {
-      val args = invoke.parseArgs(DeleteChannelArgs::class.java)
-      notificationManager?.deleteNotificationChannel(args.id)
-      invoke.resolve()
-    } else {
-      invoke.reject("channel not available")
+      notificationManager?.deleteNotificationChannel(id)
+      return true
+    }
+    return false
+  }
+
+  /** Importance of every channel by id; empty below Android 8. */
+  fun channelImportances(): Map<String, Int> {
+    if (Build.VERSION.SDK_INT < Build.VERSION_CODES.O) {
+      return emptyMap()
     }
+    return notificationManager?.notificationChannels?.associate { it.id to it.importance } ?: emptyMap()
   }
 
   fun listChannels(invoke: Invoke) {
@@ -0,0 +1,119 @@
+package app.tauri.notification
+
+import android.content.BroadcastReceiver
+import android.content.Context
+import android.content.Intent
+import android.support.v4.media.MediaMetadataCompat
+import android.support.v4.media.session.MediaSessionCompat
+import android.support.v4.media.session.PlaybackStateCompat
+import app.tauri.Logger
+import app.tauri.plugin.JSObject
+import org.json.JSONException
+import org.json.JSONObject
+
+const val MEDIA_ACTION_PREVIOUS = "media:previous"
+const val MEDIA_ACTION_PLAY = "media:play"
+const val MEDIA_ACTION_PAUSE = "media:pause"
+const val MEDIA_ACTION_NEXT = "media:next"
+
+/**
+ * `actionPerformed` payload for a media button, in the same shape
+ * [TauriNotificationManager.handleNotificationActionPerformed] produces.
+ */
+fun mediaActionPayload(actionId: String, notificationJson: String?): JSObject {
+  val data = JSObject()
+  data.put("actionId", actionId)
+  data.put("inputValue", JSONObject.NULL)
+  var notification: JSONObject? = null
+  try {
+    if (notificationJson != null) {
+      notification = JSObject(notificationJson)
+    }
+  } catch (e: JSONException) {
+    Logger.error(Logger.tags(TAG), "Failed to parse notification JSON: ${e.message}", e)
+  }
+  data.put("notification", notification ?: JSONObject.NULL)
+  return data
+}
+
+/** `PlaybackStateCompat` action flags for the media buttons in [controls]. */
+fun playbackActions(controls: MediaControls): Long {
+  var actions = 0L
+  for (action in controls.actions) {
+    actions = actions or when (action) {
+      MEDIA_ACTION_PREVIOUS -> PlaybackStateCompat.ACTION_SKIP_TO_PREVIOUS
+      MEDIA_ACTION_PLAY -> PlaybackStateCompat.ACTION_PLAY
+      MEDIA_ACTION_PAUSE -> PlaybackStateCompat.ACTION_PAUSE
+      MEDIA_ACTION_NEXT -> PlaybackStateCompat.ACTION_SKIP_TO_NEXT
+      else -> 0L
+    }
+  }
+  return actions
+}
+
+/**
+ * The media session backing MediaStyle notifications.
+ *
+ * Android 13+ builds the player controls from the session's playback state
+ * rather than the notification's actions, so the session mirrors the
+ * requested buttons and forwards its callbacks as `actionPerformed` events.
+ * One session is shared by the whole process; the most recently shown media
+ * notification owns it.
+ */
+object TauriMediaSession {
+  private var session: MediaSessionCompat? = null
+
+  fun attach(context: Context, notification: Notification, controls: MediaControls): MediaSessionCompat.Token {
+    val session = session
+      ?: MediaSessionCompat(context.applicationContext, "TauriNotifications").also { session = it }
+    val notificationJson = notification.sourceJson
+    session.setCallback(object : MediaSessionCompat.Callback() {
+      override fun onPlay() = emit(MEDIA_ACTION_PLAY)
+      override fun onPause() = emit(MEDIA_ACTION_PAUSE)
+      override fun onSkipToNext() = emit(MEDIA_ACTION_NEXT)
+      override fun onSkipToPrevious() = emit(MEDIA_ACTION_PREVIOUS)
+
+      private fun emit(actionId: String) {
+        NotificationPlugin.triggerActionPerformed(mediaActionPayload(actionId, notificationJson))
+      }
+    })
+    session.setMetadata(
+      MediaMetadataCompat.Builder()
+        .putString(MediaMetadataCompat.METADATA_KEY_TITLE, notification.title)
+        .putString(MediaMetadataCompat.METADATA_KEY_ARTIST, notification.body)
+        .build()
+    )
+    // A pause button means something is playing.
+    val state = if (controls.actions.contains(MEDIA_ACTION_PAUSE)) {
+      PlaybackStateCompat.STATE_PLAYING
+    } else {
+      PlaybackStateCompat.STATE_PAUSED
+    }
+    session.setPlaybackState(
+      PlaybackStateCompat.Builder()
+        .setActions(playbackActions(controls))
+        .setState(state, PlaybackStateCompat.PLAYBACK_POSITION_UNKNOWN, 1f)
+        .build()
+    )
+    session.isActive = true
+    return session.sessionToken
+  }
+}
+
+/**
+ * Receives media button presses on Android 12 and earlier, where the
+ * notification's own actions are shown. Unlike the other actions it doesn't
+ * launch the activity or dismiss the notification.
+ */
+class MediaActionReceiver : BroadcastReceiver() {
+  override fun onReceive(context: Context, intent: Intent) {
+    val actionId = intent.getStringExtra(ACTION_INTENT_KEY)
+    if (actionId == null) {
+      Logger.error(Logger.tags(TAG), "Media action without an action id", null)
+      return
+    }
+    NotificationPlugin.triggerActionPerformed(
+      mediaActionPayload(actionId, intent.getStringExtra(NOTIFICATION_OBJ_INTENT_KEY))
+    )
+  }
+}
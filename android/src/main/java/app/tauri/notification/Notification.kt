@@ -9,11 +9,13 @@ import android.service.notification.StatusBarNotification
 import androidx.annotation.RequiresApi
 import app.tauri.annotation.InvokeArg
 import app.tauri.plugin.JSObject
+import com.fasterxml.jackson.annotation.JsonFormat
 import com.fasterxml.jackson.core.JsonParser
 import com.fasterxml.jackson.databind.DeserializationContext
 import com.fasterxml.jackson.databind.JsonDeserializer
 import com.fasterxml.jackson.databind.JsonNode
 import com.fasterxml.jackson.databind.annotation.JsonDeserialize
+import java.util.Date
 
 /**
  * Jackson can't reflect into `JSObject` (zero bean properties), so loading a
@@ -48,13 +50,50 @@ class Notification {
   var isAutoCancel = false
   @JsonDeserialize(using = JSObjectDeserializer::class)
   var extra: JSObject? = null
+  /**
+   * `extra` as sent by the Rust side: one JSON string, so nested objects,
+   * arrays and numbers survive instead of being flattened into bundle
+   * strings. Setting it populates [extra].
+   */
+  var extraJson: String? = null
+    set(value) {
+      field = value
+      if (!value.isNullOrEmpty()) {
+        extra = JSObject(value)
+      }
+    }
   var attachments: List<NotificationAttachment>? = null
+  var mediaControls: MediaControls? = null
+  /**
+   * Deadline shown as a count-down chronometer. When it passes, the
+   * notification is re-posted with the alarm category.
+   */
+  @JsonFormat(shape = JsonFormat.Shape.STRING, pattern = JS_DATE_FORMAT)
+  var countDownTo: Date? = null
+  /** Filled fraction of a progress bar, from 0 to 1. Ignored with [countDownTo]. */
+  var progress: Float? = null
   var schedule: NotificationSchedule? = null
   var channelId: String? = null
+  /** Channel used when [channelId] doesn't exist. */
+  var channelIdFallback: String? = null
   var sourceJson: String? = null
   var visibility: Int? = null
   var number: Int? = null
   var silent: Boolean? = null
+  /** Account or profile the notification belongs to, see `clearScope`. */
+  var scope: String? = null
+  /** Post without sound, vibration or heads-up, set during quiet hours. */
+  var quiet = false
+  /** Text accessibility services read when the notification is posted. */
+  var ticker: String? = null
+  /** Deliver a screen-reader announcement instead of posting a notification. */
+  var announceOnly = false
+  /** Orders the notification within its group. */
+  var sortKey: String? = null
+  /** Keep the notification off bridged devices such as watches. */
+  var localOnly = false
+  /** Actions shown only on a paired watch. */
+  var wearableActions: List<NotificationAction>? = null
 
   fun getSound(context: Context, defaultSound: Int): String? {
     var soundPath: String? = null
@@ -73,6 +112,10 @@ class Notification {
     return soundPath
   }
 
+  /** The announced text: the ticker, or the title followed by the body. */
+  fun getAnnouncementText(): String =
+    ticker ?: listOfNotNull(title, body).filter { it.isNotEmpty() }.joinToString(". ")
+
   fun getIconColor(globalColor: String): String {
     // use the one defined local before trying for a globally defined color
     return iconColor ?: globalColor
@@ -106,6 +149,7 @@ class Notification {
           title = notification.title
           body = notification.body
           schedule = notification.schedule
+          channelId = notification.channelId
           extra = notification.extra
         }
         pendingNotifications.add(pendingNotification)
@@ -121,6 +165,7 @@ class Notification {
         val extractedData = mutableMapOf<String, String>()
         if (notification != null) {
           for (key in notification.extras.keySet()) {
+            if (key == NOTIFICATION_SCOPE_EXTRA) continue
             notification.extras.getString(key)?.let { value ->
               extractedData[key] = value
             }
@@ -135,6 +180,8 @@ class Notification {
           group = notification?.group
           groupSummary = notification?.let { 0 != it.flags and android.app.Notification.FLAG_GROUP_SUMMARY } ?: false
           data = extractedData
+          sortKey = notification?.sortKey
+          deliveredAt = statusBarNotification.postTime
         }
         activeNotifications.add(activeNotification)
       }
@@ -144,11 +191,21 @@ class Notification {
 }
 
 @InvokeArg
+/**
+ * Playback buttons of a media notification. [actions] holds the well-known
+ * action ids (`media:play`, `media:pause`, ...) reported in `actionPerformed`.
+ */
+class MediaControls {
+  var actions: List<String> = listOf()
+  var showInCompact: List<Int> = listOf()
+}
+
 class PendingNotification {
   var id: Int = 0
   var title: String? = null
   var body: String? = null
   var schedule: NotificationSchedule? = null
+  var channelId: String? = null
   @JsonDeserialize(using = JSObjectDeserializer::class)
   var extra: JSObject? = null
 }
@@ -167,6 +224,9 @@ class ActiveNotificationInfo {
   var actionTypeId: String? = null
   var schedule: NotificationSchedule? = null
   var sound: String? = null
+  var sortKey: String? = null
+  /** When the notification was posted, in milliseconds since the epoch. */
+  var deliveredAt: Long? = null
 }
 
 @InvokeArg
@@ -7,6 +7,10 @@ import android.app.NotificationManager
 import android.content.Context
 import android.content.Intent
 import android.os.Build
+import android.os.Handler
+import android.os.Looper
+import android.view.accessibility.AccessibilityEvent
+import android.view.accessibility.AccessibilityManager
 import android.webkit.WebView
 import app.tauri.PermissionState
 import app.tauri.annotation.Command
@@ -19,10 +23,17 @@ import app.tauri.plugin.Invoke
 import app.tauri.plugin.JSArray
 import app.tauri.plugin.JSObject
 import app.tauri.plugin.Plugin
+import com.google.firebase.FirebaseApp
 import com.google.firebase.messaging.FirebaseMessaging
+import java.util.concurrent.ConcurrentHashMap
+import java.util.concurrent.atomic.AtomicBoolean
 
 const val LOCAL_NOTIFICATIONS = "permissionState"
 
+// How long a firing scheduled notification waits for the app to reply to
+// scheduledNotificationTriggered
+const val SCHEDULED_TRIGGER_TIMEOUT_MS = 200L
+
 @InvokeArg
 class PluginConfig {
   var icon: String? = null
@@ -30,6 +41,11 @@ class PluginConfig {
   var iconColor: String? = null
 }
 
+@InvokeArg
+class RequestPermissionsArgs {
+  var skipRationale: Boolean = false
+}
+
 @InvokeArg
 class BatchArgs {
   lateinit var notifications: List<Notification>
@@ -40,6 +56,23 @@ class CancelArgs {
   lateinit var notifications: List<Int>
 }
 
+@InvokeArg
+class DismissArgs {
+  var id: Int = 0
+}
+
+@InvokeArg
+class ClearScopeArgs {
+  lateinit var scope: String
+}
+
+@InvokeArg
+class NotificationsCoalescedArgs {
+  var digestId: Int = 0
+  var group: String? = null
+  lateinit var ids: List<Int>
+}
+
 @InvokeArg
 class NotificationAction {
   lateinit var id: String
@@ -51,6 +84,8 @@ class NotificationAction {
 class ActionType {
   lateinit var id: String
   lateinit var actions: List<NotificationAction>
+  var hiddenPreviewsBodyPlaceholder: String? = null
+  var hiddenPreviewsShowTitle: Boolean? = null
 }
 
 @InvokeArg
@@ -58,6 +93,12 @@ class RegisterActionTypesArgs {
   lateinit var types: List<ActionType>
 }
 
+@InvokeArg
+class UpdateScheduledNotificationArgs {
+  var id: Int = 0
+  lateinit var notification: Notification
+}
+
 @InvokeArg
 class SetClickListenerActiveArgs {
   var active: Boolean = false
@@ -93,6 +134,10 @@ class NotificationPlugin(private val activity: Activity): Plugin(activity) {
   private var hasClickedListener = false
   private var pendingNotificationClick: JSObject? = null
 
+  // Channel importances when the app last resumed; null until a
+  // channelBlocked listener registers
+  private var channelImportances: Map<String, Int>? = null
+
   // onNewIntent can fire before load() during a cold start triggered
   // by a notification tap (Android delivers the launch intent via
   // both onCreate's activity.intent AND onNewIntent in certain launch
@@ -102,9 +147,49 @@ class NotificationPlugin(private val activity: Activity): Plugin(activity) {
   // intent and drain in load() instead.
   private var pendingIntent: Intent? = null
 
+  // How the activity was started, read from its launch intent in load()
+  private var launchSource = JSObject().apply { put("type", "normal") }
+
   companion object {
     var instance: NotificationPlugin? = null
 
+    // Scheduled notifications waiting for a reply to
+    // scheduledNotificationTriggered, by id
+    private val scheduledReplies = ConcurrentHashMap<Int, (Notification?) -> Unit>()
+
+    /**
+     * Emits `scheduledNotificationTriggered` for [notification] and calls
+     * [proceed] on the main thread with the replacement the app replied with
+     * through `updateScheduledNotification`, or with null when it doesn't
+     * reply within [SCHEDULED_TRIGGER_TIMEOUT_MS] or isn't running.
+     */
+    fun triggerScheduled(notification: Notification, proceed: (Notification?) -> Unit) {
+      val plugin = instance
+      val json = notification.sourceJson
+      if (plugin == null || json == null) {
+        proceed(null)
+        return
+      }
+      val handler = Handler(Looper.getMainLooper())
+      val done = AtomicBoolean(false)
+      val finish = { replacement: Notification? ->
+        if (done.compareAndSet(false, true)) {
+          scheduledReplies.remove(notification.id)
+          handler.post { proceed(replacement) }
+        }
+      }
+      scheduledReplies[notification.id] = finish
+      plugin.trigger("scheduledNotificationTriggered", JSObject(json))
+      handler.postDelayed({ finish(null) }, SCHEDULED_TRIGGER_TIMEOUT_MS)
+    }
+
+    /** Shows [notification] right away, for a scheduled one the app replaced. */
+    fun presentScheduled(notification: Notification): Boolean {
+      val plugin = instance ?: return false
+      plugin.manager.present(notification)
+      return true
+    }
+
     fun triggerNotification(notification: Notification, source: String = "local") {
       val data = JSObject()
       data.put("source", source)
@@ -135,6 +220,27 @@ class NotificationPlugin(private val activity: Activity): Plugin(activity) {
       }
       instance?.trigger("notification", data)
     }
+
+    fun triggerActionPerformed(data: JSObject) {
+      recordInteraction(data)
+      instance?.trigger("actionPerformed", data)
+    }
+
+    // actionPerformed payloads kept for the Rust interaction stats, which
+    // can't listen to them from the start of a cold launch.
+    private const val MAX_INTERACTIONS = 256
+    private val interactions = ArrayDeque<JSObject>()
+
+    fun recordInteraction(data: JSObject) {
+      synchronized(interactions) {
+        if (interactions.size == MAX_INTERACTIONS) interactions.removeFirst()
+        interactions.addLast(data)
+      }
+    }
+
+    fun takeInteractions(): List<JSObject> = synchronized(interactions) {
+      interactions.toList().also { interactions.clear() }
+    }
   }
 
   override fun load(webView: WebView) {
@@ -157,6 +263,7 @@ class NotificationPlugin(private val activity: Activity): Plugin(activity) {
     notificationManager = activity.getSystemService(Context.NOTIFICATION_SERVICE) as NotificationManager
 
     val intent = activity.intent
+    launchSource = launchSourceOf(intent)
     intent?.let {
       onIntent(it)
     }
@@ -191,6 +298,7 @@ class NotificationPlugin(private val activity: Activity): Plugin(activity) {
     if (Intent.ACTION_MAIN == intent.action) {
       val dataJson = manager.handleNotificationActionPerformed(intent, notificationStorage)
       if (dataJson != null) {
+        recordInteraction(dataJson)
         trigger("actionPerformed", dataJson)
         triggerNotificationClicked(
           intent.getIntExtra(NOTIFICATION_INTENT_KEY, -1),
@@ -209,11 +317,39 @@ class NotificationPlugin(private val activity: Activity): Plugin(activity) {
     }
   }
 
+  private fun launchSourceOf(intent: Intent?): JSObject {
+    val source = JSObject()
+    val id = intent?.getIntExtra(NOTIFICATION_INTENT_KEY, Int.MIN_VALUE) ?: Int.MIN_VALUE
+    val messageId = intent?.extras?.getString("google.message_id")
+    when {
+      id != Int.MIN_VALUE -> {
+        val actionId = intent?.getStringExtra(ACTION_INTENT_KEY)
+        if (actionId == null || actionId == DEFAULT_PRESS_ACTION) {
+          source.put("type", "notificationTap")
+        } else {
+          source.put("type", "notificationAction")
+          source.put("actionId", actionId)
+        }
+        source.put("id", id)
+      }
+      messageId != null -> {
+        source.put("type", "push")
+        source.put("messageId", messageId)
+      }
+      else -> source.put("type", "normal")
+    }
+    return source
+  }
+
   private fun extractLocalNotificationData(intent: Intent): JSObject? {
     val notificationJson = intent.getStringExtra(NOTIFICATION_OBJ_INTENT_KEY) ?: return null
     return try {
       val notification = JSObject(notificationJson)
-      if (notification.has("extra")) notification.getJSObject("extra") else null
+      when {
+        notification.has("extraJson") -> JSObject(notification.getString("extraJson"))
+        notification.has("extra") -> notification.getJSObject("extra")
+        else -> null
+      }
     } catch (e: Exception) {
       Logger.error(Logger.tags(TAG), "Failed to extract local notification data: ${e.message}", e)
       null
@@ -261,14 +397,54 @@ class NotificationPlugin(private val activity: Activity): Plugin(activity) {
     val notification = invoke.parseArgs(Notification::class.java)
     notification.sourceJson = invoke.getRawArgs()
 
+    if (notification.announceOnly) {
+      announce(notification.getAnnouncementText())
+      invoke.resolveObject(notification.id)
+      return
+    }
+
+    val channelId = notification.channelId
+    if (channelId != null && Build.VERSION.SDK_INT >= Build.VERSION_CODES.O) {
+      val existing = notificationManager.notificationChannels.map { it.id }.toSet()
+      val resolved = resolveChannelId(channelId, notification.channelIdFallback, existing)
+      if (resolved == null) {
+        invoke.reject("channel not found: $channelId", "channelNotFound")
+        return
+      }
+      notification.channelId = resolved
+    }
+
     val id = manager.schedule(notification)
-    if (notification.schedule != null) {
+    // Scheduled and count-down notifications are re-posted from storage
+    if (notification.schedule != null || notification.countDownTo != null) {
       notificationStorage.appendNotifications(listOf(notification))
     }
 
     invoke.resolveObject(id)
   }
 
+  /**
+   * Sends a transient announcement event; TalkBack speaks it without
+   * anything being posted. A no-op while no accessibility service is on.
+   */
+  private fun announce(text: String) {
+    val accessibilityManager =
+      activity.getSystemService(Context.ACCESSIBILITY_SERVICE) as AccessibilityManager
+    if (!accessibilityManager.isEnabled || text.isEmpty()) {
+      return
+    }
+    val event = if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.R) {
+      AccessibilityEvent(AccessibilityEvent.TYPE_ANNOUNCEMENT)
+    } else {
+      @Suppress("DEPRECATION")
+      AccessibilityEvent.obtain(AccessibilityEvent.TYPE_ANNOUNCEMENT)
+    }
+    event.packageName = activity.packageName
+    event.className = javaClass.name
+    event.text.add(text)
+    accessibilityManager.sendAccessibilityEvent(event)
+  }
+
   @Command
   fun batch(invoke: Invoke) {
     val args = invoke.parseArgs(BatchArgs::class.java)
@@ -290,6 +466,48 @@ class NotificationPlugin(private val activity: Activity): Plugin(activity) {
     invoke.resolve()
   }
 
+  @SuppressLint("ObsoleteSdkInt")
+  @Command
+  fun dismiss(invoke: Invoke) {
+    val args = invoke.parseArgs(DismissArgs::class.java)
+    val wasPending = notificationStorage.getSavedNotification(args.id.toString()) != null
+    val wasActive = Build.VERSION.SDK_INT >= Build.VERSION_CODES.M &&
+      notificationManager.activeNotifications.any { it.id == args.id }
+    // Cancels the alarm, removes the visible notification and forgets it.
+    manager.cancel(listOf(args.id))
+
+    val result = JSObject()
+    result.put("wasPending", wasPending)
+    result.put("wasActive", wasActive)
+    invoke.resolve(result)
+  }
+
+  @SuppressLint("ObsoleteSdkInt")
+  @Command
+  fun clearScope(invoke: Invoke) {
+    val args = invoke.parseArgs(ClearScopeArgs::class.java)
+    // Shown notifications first: canceling the pending ones also takes
+    // their visible copies away.
+    var removed = 0
+    if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.M) {
+      for (active in notificationManager.activeNotifications) {
+        if (active.notification.extras.getString(NOTIFICATION_SCOPE_EXTRA) == args.scope) {
+          notificationManager.cancel(active.tag, active.id)
+          removed++
+        }
+      }
+    }
+    val pending = notificationStorage.getSavedNotifications()
+      .filter { it.scope == args.scope }
+      .map { it.id }
+    manager.cancel(pending)
+
+    val result = JSObject()
+    result.put("canceled", pending.size)
+    result.put("removed", removed)
+    invoke.resolve(result)
+  }
+
   @Command
   fun cancelAll(invoke: Invoke) {
     val ids = notificationStorage.getSavedNotificationIds().mapNotNull { it.toIntOrNull() }
@@ -297,6 +515,18 @@ class NotificationPlugin(private val activity: Activity): Plugin(activity) {
     invoke.resolve()
   }
 
+  @Command
+  fun getLaunchSource(invoke: Invoke) {
+    invoke.resolve(launchSource)
+  }
+
+  // Takes no arguments: the body may be empty, so nothing is parsed.
+  @Command
+  fun removeAllActive(invoke: Invoke) {
+    notificationManager.cancelAll()
+    invoke.resolve()
+  }
+
   @Command
   fun removeActive(invoke: Invoke) {
     val args = invoke.parseArgs(RemoveActiveArgs::class.java)
@@ -330,6 +560,13 @@ class NotificationPlugin(private val activity: Activity): Plugin(activity) {
     invoke.resolve()
   }
 
+  @Command
+  fun setNotificationCategories(invoke: Invoke) {
+    val args = invoke.parseArgs(RegisterActionTypesArgs::class.java)
+    notificationStorage.replaceActionGroups(args.types)
+    invoke.resolve()
+  }
+
   @SuppressLint("ObsoleteSdkInt")
   @Command
   fun getActive(invoke: Invoke) {
@@ -346,9 +583,45 @@ class NotificationPlugin(private val activity: Activity): Plugin(activity) {
     channelManager.createChannel(invoke)
   }
 
+  @Command
+  fun createChannels(invoke: Invoke) {
+    channelManager.createChannels(invoke)
+  }
+
   @Command
   fun deleteChannel(invoke: Invoke) {
-    channelManager.deleteChannel(invoke)
+    val args = invoke.parseArgs(DeleteChannelArgs::class.java)
+    if (Build.VERSION.SDK_INT < Build.VERSION_CODES.O) {
+      invoke.reject("channel not available")
+      return
+    }
+
+    val pending = notificationStorage.getSavedNotifications().filter { it.channelId == args.id }
+    val canceled = JSArray()
+    val reassigned = JSArray()
+    val policy = args.onPending
+    when (policy?.policy) {
+      "cancel" -> {
+        manager.cancel(pending.map { it.id })
+        pending.forEach { canceled.put(it.id) }
+      }
+      "reassign" -> {
+        val mapper = jsonMapper()
+        for (notification in pending) {
+          notification.channelId = policy.toChannel
+          notification.sourceJson = mapper.writeValueAsString(notification)
+          reassigned.put(notification.id)
+        }
+        manager.schedule(pending)
+        notificationStorage.appendNotifications(pending)
+      }
+    }
+    channelManager.deleteChannel(args.id)
+
+    val result = JSObject()
+    result.put("canceled", canceled)
+    result.put("reassigned", reassigned)
+    invoke.resolve(result)
   }
 
   @Command
@@ -372,8 +645,19 @@ class NotificationPlugin(private val activity: Activity): Plugin(activity) {
     if (Build.VERSION.SDK_INT < Build.VERSION_CODES.TIRAMISU) {
       permissionState(invoke)
     } else {
-      if (getPermissionState(LOCAL_NOTIFICATIONS) !== PermissionState.GRANTED) {
-        requestPermissionForAlias(LOCAL_NOTIFICATIONS, invoke, "permissionsCallback")
+      val skipRationale = runCatching {
+        invoke.parseArgs(RequestPermissionsArgs::class.java).skipRationale
+      }.getOrDefault(false)
+      when {
+        getPermissionState(LOCAL_NOTIFICATIONS) == PermissionState.GRANTED -> permissionState(invoke)
+        // Denied once before: let the app explain why before asking again.
+        !skipRationale &&
+          activity.shouldShowRequestPermissionRationale(Manifest.permission.POST_NOTIFICATIONS) -> {
+          val permissionsResultJSON = JSObject()
+          permissionsResultJSON.put("permissionState", "prompt-with-rationale")
+          invoke.resolve(permissionsResultJSON)
+        }
+        else -> requestPermissionForAlias(LOCAL_NOTIFICATIONS, invoke, "permissionsCallback")
       }
     }
   }
@@ -416,6 +700,35 @@ class NotificationPlugin(private val activity: Activity): Plugin(activity) {
     getFirebaseToken()
   }
 
+  @Command
+  fun getPushTokenCached(invoke: Invoke) {
+    val result = JSObject()
+    notificationStorage.getPushToken()?.let { result.put("token", it) }
+    invoke.resolve(result)
+  }
+
+  /** Setup checks only the native side can run, reported by `doctor()`. */
+  @Command
+  fun doctor(invoke: Invoke) {
+    val result = JSObject()
+    if (BuildConfig.ENABLE_PUSH_NOTIFICATIONS) {
+      result.put("firebaseConfigured", FirebaseApp.getApps(activity).isNotEmpty())
+    }
+    invoke.resolve(result)
+  }
+
+  @Command
+  fun isDoNotDisturbActive(invoke: Invoke) {
+    val filter = notificationManager.currentInterruptionFilter
+    val result = JSObject()
+    result.put(
+      "active",
+      filter == NotificationManager.INTERRUPTION_FILTER_NONE ||
+        filter == NotificationManager.INTERRUPTION_FILTER_ALARMS
+    )
+    invoke.resolve(result)
+  }
+
   @Command
   fun unregisterForPushNotifications(invoke: Invoke) {
     if (!BuildConfig.ENABLE_PUSH_NOTIFICATIONS) {
@@ -429,6 +742,7 @@ class NotificationPlugin(private val activity: Activity): Plugin(activity) {
         return@addOnCompleteListener
       }
       cachedToken = null
+      notificationStorage.clearPushToken()
       invoke.resolve()
     }
   }
@@ -465,6 +779,7 @@ class NotificationPlugin(private val activity: Activity): Plugin(activity) {
 
       val token = task.result
       cachedToken = token
+      notificationStorage.savePushToken(token)
       val result = JSObject()
       result.put("deviceToken", token)
       pendingTokenInvoke?.resolve(result)
@@ -532,6 +847,83 @@ class NotificationPlugin(private val activity: Activity): Plugin(activity) {
     }
   }
 
+  @Command
+  fun registerChannelBlockedListener(invoke: Invoke) {
+    if (Build.VERSION.SDK_INT < Build.VERSION_CODES.O) {
+      invoke.reject("channel not available")
+      return
+    }
+    if (channelImportances == null) {
+      channelImportances = channelManager.channelImportances()
+    }
+    invoke.resolve()
+  }
+
+  // Channels can only be blocked from the system settings, so a change shows
+  // up once the user comes back to the app.
+  override fun onResume() {
+    super.onResume()
+    val before = channelImportances ?: return
+    val after = channelManager.channelImportances()
+    channelImportances = after
+    for (channelId in blockedChannels(before, after)) {
+      val data = JSObject()
+      data.put("channelId", channelId)
+      data.put("importance", after[channelId])
+      trigger("channelBlocked", data)
+    }
+  }
+
+  // Digests are decided on the Rust side, which has no other way to reach
+  // the JS listeners.
+  @Command
+  fun emitNotificationsCoalesced(invoke: Invoke) {
+    val args = invoke.parseArgs(NotificationsCoalescedArgs::class.java)
+    val data = JSObject()
+    data.put("digestId", args.digestId)
+    data.put("group", args.group)
+    data.put("ids", JSArray(args.ids))
+    trigger("notificationsCoalesced", data)
+    invoke.resolve()
+  }
+
+  // Like digests, suppressed notifications are decided on the Rust side.
+  @Command
+  fun emitNotificationFallback(invoke: Invoke) {
+    trigger("notificationFallback", invoke.getArgs())
+    invoke.resolve()
+  }
+
+  // Ignored fields are looked up on the Rust side as well.
+  @Command
+  fun emitNotificationWarning(invoke: Invoke) {
+    trigger("notificationWarning", invoke.getArgs())
+    invoke.resolve()
+  }
+
+  @Command
+  fun updateScheduledNotification(invoke: Invoke) {
+    val args = invoke.parseArgs(UpdateScheduledNotificationArgs::class.java)
+    val reply = scheduledReplies[args.id]
+    if (reply == null) {
+      invoke.reject("scheduled notification ${args.id} isn't waiting for a replacement")
+      return
+    }
+    val notification = args.notification
+    notification.id = args.id
+    notification.schedule = null
+    notification.sourceJson = jsonMapper().writeValueAsString(notification)
+    reply(notification)
+    invoke.resolve()
+  }
+
+  @Command
+  fun takeInteractions(invoke: Invoke) {
+    val result = JSObject()
+    result.put("interactions", JSArray(Companion.takeInteractions()))
+    invoke.resolve(result)
+  }
+
   @Command
   fun setClickListenerActive(invoke: Invoke) {
     val args = invoke.parseArgs(SetClickListenerActiveArgs::class.java)
@@ -3,6 +3,7 @@ package app.tauri.notification
 import android.annotation.SuppressLint
 import android.text.format.DateUtils
 import com.fasterxml.jackson.annotation.JsonFormat
+import com.fasterxml.jackson.annotation.JsonIgnore
 import com.fasterxml.jackson.annotation.JsonProperty
 import com.fasterxml.jackson.core.JsonGenerator
 import com.fasterxml.jackson.core.JsonParser
@@ -173,7 +174,9 @@ class DateMatch {
   var second: Int? = null
 
   // Unit used to save the last used unit for a trigger.
-  // One of the Calendar constants values
+  // One of the Calendar constants values. Derived from the fields above, so
+  // it's kept out of the JSON: the Rust side rejects unknown interval fields.
+  @get:JsonIgnore
   var unit: Int? = -1
 
   /**
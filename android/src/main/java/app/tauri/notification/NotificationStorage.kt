@@ -10,6 +10,14 @@ private const val STORAGE_TAG = "NotificationStorage"
 private const val NOTIFICATION_STORE_ID = "NOTIFICATION_STORE"
 // Key used to save action types
 private const val ACTION_TYPES_ID = "ACTION_TYPE_STORE"
+// Key for the ids of the saved action types
+private const val ACTION_TYPE_IDS_ID = "ACTION_TYPE_IDS_STORE"
+private const val ACTION_TYPE_IDS_KEY = "ids"
+private const val HIDDEN_PREVIEWS_BODY_KEY = "hiddenPreviewsBodyPlaceholder"
+private const val HIDDEN_PREVIEWS_SHOW_TITLE_KEY = "hiddenPreviewsShowTitle"
+// Key for the last FCM token
+private const val PUSH_TOKEN_STORE_ID = "PUSH_TOKEN_STORE"
+private const val PUSH_TOKEN_KEY = "token"
 
 class NotificationStorage(private val context: Context, private val jsonMapper: ObjectMapper) {
   fun appendNotifications(localNotifications: List<Notification>) {
@@ -18,14 +26,15 @@ class NotificationStorage(private val context: Context, private val jsonMapper:
     val editor = storage.edit()
     var savedCount = 0
     for (request in localNotifications) {
-      if (request.schedule != null) {
+      // Count-down notifications are stored so their alarm can fire
+      if (request.schedule != null || request.countDownTo != null) {
         val key: String = request.id.toString()
         val jsonValue = request.sourceJson
         Logger.debug(Logger.tags(STORAGE_TAG), "Saving notification $key, sourceJson is null: ${request.sourceJson == null}, value: ${jsonValue?.take(100)}")
         editor.putString(key, jsonValue)
         savedCount++
       } else {
-        Logger.debug(Logger.tags(STORAGE_TAG), "Skipping notification ${request.id} - no schedule")
+        Logger.debug(Logger.tags(STORAGE_TAG), "Skipping notification ${request.id} - no schedule or count-down")
       }
     }
     editor.apply()
@@ -82,6 +91,18 @@ class NotificationStorage(private val context: Context, private val jsonMapper:
     editor.apply()
   }
 
+  fun savePushToken(token: String) {
+    getStorage(PUSH_TOKEN_STORE_ID).edit().putString(PUSH_TOKEN_KEY, token).apply()
+  }
+
+  fun getPushToken(): String? {
+    return getStorage(PUSH_TOKEN_STORE_ID).getString(PUSH_TOKEN_KEY, null)
+  }
+
+  fun clearPushToken() {
+    getStorage(PUSH_TOKEN_STORE_ID).edit().remove(PUSH_TOKEN_KEY).apply()
+  }
+
   private fun getStorage(key: String): SharedPreferences {
     return context.getSharedPreferences(key, Context.MODE_PRIVATE)
   }
@@ -96,9 +117,34 @@ class NotificationStorage(private val context: Context, private val jsonMapper:
         editor.putString("title$index", action.title)
         editor.putBoolean("input$index", action.input ?: false)
       }
+      editor.putString(HIDDEN_PREVIEWS_BODY_KEY, type.hiddenPreviewsBodyPlaceholder)
+      editor.putBoolean(HIDDEN_PREVIEWS_SHOW_TITLE_KEY, type.hiddenPreviewsShowTitle ?: false)
       editor.apply()
       Logger.debug(Logger.tags(STORAGE_TAG), "Saved action group ${type.id} with ${type.actions.size} actions")
     }
+    saveActionGroupIds(getActionGroupIds() + actions.map { it.id })
+  }
+
+  /**
+   * Replaces every saved action group with [actions]. Groups saved before
+   * their ids were recorded can't be found, and stay.
+   */
+  fun replaceActionGroups(actions: List<ActionType>) {
+    val kept = actions.map { it.id }.toSet()
+    for (id in getActionGroupIds() - kept) {
+      getStorage(ACTION_TYPES_ID + id).edit().clear().apply()
+      Logger.debug(Logger.tags(STORAGE_TAG), "Removed action group $id")
+    }
+    saveActionGroupIds(emptySet())
+    writeActionGroup(actions)
+  }
+
+  private fun getActionGroupIds(): Set<String> {
+    return getStorage(ACTION_TYPE_IDS_ID).getStringSet(ACTION_TYPE_IDS_KEY, null)?.toSet() ?: emptySet()
+  }
+
+  private fun saveActionGroupIds(ids: Set<String>) {
+    getStorage(ACTION_TYPE_IDS_ID).edit().putStringSet(ACTION_TYPE_IDS_KEY, ids).apply()
   }
 
   fun getActionGroup(forId: String): Array<NotificationAction?> {
@@ -120,4 +166,21 @@ class NotificationStorage(private val context: Context, private val jsonMapper:
     }
     return actions
   }
-}
\ No newline at end of file
+
+  /**
+   * What the lock screen shows for notifications of action type [forId]
+   * while their content is private, or null if the type has no placeholder.
+   */
+  fun getHiddenPreviews(forId: String): HiddenPreviews? {
+    val storage = getStorage(ACTION_TYPES_ID + forId)
+    val placeholder = storage.getString(HIDDEN_PREVIEWS_BODY_KEY, null) ?: return null
+    return HiddenPreviews(placeholder, storage.getBoolean(HIDDEN_PREVIEWS_SHOW_TITLE_KEY, false))
+  }
+}
+
+class HiddenPreviews(private val bodyPlaceholder: String, val showTitle: Boolean) {
+  /** The placeholder with `%u` replaced by [count], as Apple formats it. */
+  fun body(count: Int): String {
+    return bodyPlaceholder.replace("%u", count.toString()).replace("%%", "%")
+  }
+}
@@ -1,6 +1,7 @@
 package app.tauri.notification
 
 import app.tauri.plugin.JSObject
+import com.fasterxml.jackson.databind.ObjectMapper
 import com.google.firebase.messaging.FirebaseMessagingService
 import com.google.firebase.messaging.RemoteMessage
 
@@ -8,6 +9,8 @@ class TauriFirebaseMessagingService : FirebaseMessagingService() {
 
   override fun onNewToken(token: String) {
     super.onNewToken(token)
+    // Persist the token even while the app isn't running, for getPushTokenCached
+    NotificationStorage(this, ObjectMapper()).savePushToken(token)
     // Store the token for later retrieval and trigger push-token event
     NotificationPlugin.instance?.handleNewToken(token)
   }
@@ -15,10 +15,12 @@ import android.media.AudioAttributes
 import android.net.Uri
 import android.os.Build
 import android.os.Build.VERSION.SDK_INT
+import android.os.Bundle
 import android.os.UserManager
 import androidx.core.app.NotificationCompat
 import androidx.core.app.NotificationManagerCompat
 import androidx.core.app.RemoteInput
+import androidx.media.app.NotificationCompat as MediaNotificationCompat
 import app.tauri.Logger
 import app.tauri.plugin.JSObject
 import app.tauri.plugin.PluginManager
@@ -27,6 +29,7 @@ import org.json.JSONException
 import org.json.JSONObject
 import java.text.SimpleDateFormat
 import java.util.Date
+import kotlin.math.roundToInt
 
 // Action constants
 const val NOTIFICATION_INTENT_KEY = "NotificationId"
@@ -34,6 +37,7 @@ const val NOTIFICATION_OBJ_INTENT_KEY = "LocalNotficationObject"
 const val ACTION_INTENT_KEY = "NotificationUserAction"
 const val NOTIFICATION_IS_REMOVABLE_KEY = "NotificationRepeating"
 const val REMOTE_INPUT_KEY = "NotificationRemoteInput"
+const val NOTIFICATION_SCOPE_EXTRA = "app.tauri.notification.scope"
 const val DEFAULT_NOTIFICATION_CHANNEL_ID = "default"
 const val DEFAULT_PRESS_ACTION = "tap"
 const val TAG = "NotificationsPlugin"
@@ -126,6 +130,14 @@ class TauriNotificationManager(
     return trigger(notificationManager, notification)
   }
 
+  /**
+   * Posts [notification] now, without touching the alarm a repeating
+   * schedule of the same id keeps.
+   */
+  fun present(notification: Notification) {
+    buildNotification(NotificationManagerCompat.from(context), notification)
+  }
+
   fun schedule(notifications: List<Notification>): List<Int> {
     val ids = mutableListOf<Int>()
     val notificationManager = NotificationManagerCompat.from(context)
@@ -138,11 +150,9 @@ class TauriNotificationManager(
     return ids
   }
 
-  // TODO Progressbar support
   // TODO System categories (DO_NOT_DISTURB etc.)
   // TODO use NotificationCompat.MessagingStyle for latest API
   // TODO expandable notification NotificationCompat.MessagingStyle
-  // TODO media style notification support NotificationCompat.MediaStyle
   @SuppressLint("MissingPermission")
   private fun buildNotification(
     notificationManager: NotificationManagerCompat,
@@ -154,10 +164,13 @@ class TauriNotificationManager(
     )
       .setContentTitle(notification.title)
       .setContentText(notification.body)
+      .setTicker(notification.ticker)
       .setAutoCancel(notification.isAutoCancel)
       .setOngoing(notification.isOngoing)
       .setPriority(NotificationCompat.PRIORITY_DEFAULT)
       .setGroupSummary(notification.isGroupSummary)
+      .setLocalOnly(notification.localOnly)
+      .setSortKey(notification.sortKey)
     if (notification.largeBody != null) {
       // support multiline text
       mBuilder.setStyle(
@@ -174,6 +187,15 @@ class TauriNotificationManager(
       inboxStyle.setSummaryText(notification.summary)
       mBuilder.setStyle(inboxStyle)
     }
+    val mediaControls = notification.mediaControls
+    if (mediaControls != null) {
+      mBuilder.setCategory(NotificationCompat.CATEGORY_TRANSPORT)
+      mBuilder.setStyle(
+        MediaNotificationCompat.MediaStyle()
+          .setMediaSession(TauriMediaSession.attach(context, notification, mediaControls))
+          .setShowActionsInCompactView(*mediaControls.showInCompact.toIntArray())
+      )
+    }
     val sound = notification.getSound(context, getDefaultSound(context))
     if (sound != null) {
       val soundUri = Uri.parse(sound)
@@ -195,10 +217,21 @@ class TauriNotificationManager(
         mBuilder.setSubText(notification.summary)
       }
     }
+    if (notification.quiet) {
+      mBuilder.setSilent(true)
+      mBuilder.setPriority(NotificationCompat.PRIORITY_LOW)
+    }
+    // Kept on the posted notification so `clearScope` can find it.
+    notification.scope?.let { scope ->
+      mBuilder.addExtras(Bundle().apply { putString(NOTIFICATION_SCOPE_EXTRA, scope) })
+    }
     mBuilder.setVisibility(notification.visibility ?: NotificationCompat.VISIBILITY_PRIVATE)
     mBuilder.setOnlyAlertOnce(true)
     mBuilder.setSmallIcon(notification.getSmallIcon(context, getDefaultSmallIcon(context)))
     mBuilder.setLargeIcon(notification.getLargeIcon(context))
+    notification.actionTypeId?.let { storage.getHiddenPreviews(it) }?.let { hidden ->
+      mBuilder.setPublicVersion(buildPublicVersion(notification, channelId, hidden))
+    }
     val iconColor = notification.getIconColor(config?.iconColor ?: "")
     if (iconColor.isNotEmpty()) {
       try {
@@ -208,6 +241,25 @@ class TauriNotificationManager(
       }
     }
     createActionIntents(notification, mBuilder)
+    val countDownTo = notification.countDownTo
+    if (countDownTo != null && notification.schedule == null) {
+      // Posted under the same id once the count-down ends; built before the
+      // chronometer is added so it shows the plain notification as an alarm
+      val alarm = mBuilder.build().apply {
+        category = android.app.Notification.CATEGORY_ALARM
+        flags = flags and android.app.Notification.FLAG_ONLY_ALERT_ONCE.inv()
+      }
+      scheduleCountDownAlarm(alarm, notification, countDownTo)
+      mBuilder
+        .setUsesChronometer(true)
+        .setChronometerCountDown(true)
+        .setShowWhen(true)
+        .setWhen(countDownTo.time)
+    } else {
+      notification.progress?.let { progress ->
+        mBuilder.setProgress(100, (progress.coerceIn(0f, 1f) * 100).roundToInt(), false)
+      }
+    }
     // notificationId is a unique int for each notification that you must define
     val buildNotification = mBuilder.build()
     if (notification.schedule != null) {
@@ -223,6 +275,59 @@ class TauriNotificationManager(
   }
 
   // Create intents for open/dismiss actions
+  private fun buildAction(
+    notification: Notification,
+    notificationAction: NotificationAction,
+    flags: Int
+  ): NotificationCompat.Action {
+    // TODO Add custom icons to actions
+    val actionIntent = buildIntent(notification, notificationAction.id)
+    val actionPendingIntent = PendingIntent.getActivity(
+      context,
+      (notification.id) + notificationAction.id.hashCode(),
+      actionIntent,
+      flags
+    )
+    val actionBuilder: NotificationCompat.Action.Builder = NotificationCompat.Action.Builder(
+      R.drawable.ic_transparent,
+      notificationAction.title,
+      actionPendingIntent
+    )
+    if (notificationAction.input == true) {
+      val remoteInput = RemoteInput.Builder(REMOTE_INPUT_KEY).setLabel(
+        notificationAction.title
+      ).build()
+      actionBuilder.addRemoteInput(remoteInput)
+    }
+    return actionBuilder.build()
+  }
+
+  /**
+   * The version a private notification shows on a secure lock screen: the
+   * action type's placeholder instead of the body, `%u` counting the
+   * notifications of its group.
+   */
+  private fun buildPublicVersion(
+    notification: Notification,
+    channelId: String,
+    hidden: HiddenPreviews,
+  ): android.app.Notification {
+    val group = notification.group
+    val count = if (group == null) {
+      1
+    } else {
+      context.getSystemService(NotificationManager::class.java).activeNotifications.count {
+        it.notification.group == group && it.id != notification.id &&
+          (it.notification.flags and android.app.Notification.FLAG_GROUP_SUMMARY) == 0
+      } + 1
+    }
+    return NotificationCompat.Builder(context, channelId)
+      .setContentTitle(if (hidden.showTitle) notification.title else null)
+      .setContentText(hidden.body(count))
+      .setSmallIcon(notification.getSmallIcon(context, getDefaultSmallIcon(context)))
+      .build()
+  }
+
   private fun createActionIntents(
     notification: Notification,
     mBuilder: NotificationCompat.Builder
@@ -236,32 +341,26 @@ class TauriNotificationManager(
     val pendingIntent = PendingIntent.getActivity(context, notification.id, intent, flags)
     mBuilder.setContentIntent(pendingIntent)
 
+    // Media buttons come first so `showInCompact` indices line up
+    createMediaActionIntents(notification, mBuilder)
+
     // Build action types
     val actionTypeId = notification.actionTypeId
     if (actionTypeId != null) {
       val actionGroup = storage.getActionGroup(actionTypeId)
       for (notificationAction in actionGroup) {
-        // TODO Add custom icons to actions
-        val actionIntent = buildIntent(notification, notificationAction!!.id)
-        val actionPendingIntent = PendingIntent.getActivity(
-          context,
-          (notification.id) + notificationAction.id.hashCode(),
-          actionIntent,
-          flags
-        )
-        val actionBuilder: NotificationCompat.Action.Builder = NotificationCompat.Action.Builder(
-          R.drawable.ic_transparent,
-          notificationAction.title,
-          actionPendingIntent
-        )
-        if (notificationAction.input == true) {
-          val remoteInput = RemoteInput.Builder(REMOTE_INPUT_KEY).setLabel(
-            notificationAction.title
-          ).build()
-          actionBuilder.addRemoteInput(remoteInput)
-        }
-        mBuilder.addAction(actionBuilder.build())
+        mBuilder.addAction(buildAction(notification, notificationAction!!, flags))
+      }
+    }
+
+    // Watch-only actions
+    val wearableActions = notification.wearableActions
+    if (!wearableActions.isNullOrEmpty()) {
+      val wearableExtender = NotificationCompat.WearableExtender()
+      for (notificationAction in wearableActions) {
+        wearableExtender.addAction(buildAction(notification, notificationAction, flags))
       }
+      mBuilder.extend(wearableExtender)
     }
 
     // Dismiss intent
@@ -286,6 +385,34 @@ class TauriNotificationManager(
     mBuilder.setDeleteIntent(deleteIntent)
   }
 
+  private fun createMediaActionIntents(
+    notification: Notification,
+    mBuilder: NotificationCompat.Builder
+  ) {
+    val mediaControls = notification.mediaControls ?: return
+    val flags = PendingIntent.FLAG_UPDATE_CURRENT or PendingIntent.FLAG_IMMUTABLE
+    for (mediaAction in mediaControls.actions) {
+      val intent = Intent(context, MediaActionReceiver::class.java)
+      intent.putExtra(NOTIFICATION_INTENT_KEY, notification.id)
+      intent.putExtra(ACTION_INTENT_KEY, mediaAction)
+      intent.putExtra(NOTIFICATION_OBJ_INTENT_KEY, notification.sourceJson)
+      val pendingIntent = PendingIntent.getBroadcast(
+        context,
+        notification.id + mediaAction.hashCode(),
+        intent,
+        flags
+      )
+      val (icon, title) = when (mediaAction) {
+        MEDIA_ACTION_PREVIOUS -> android.R.drawable.ic_media_previous to "Previous"
+        MEDIA_ACTION_PLAY -> android.R.drawable.ic_media_play to "Play"
+        MEDIA_ACTION_PAUSE -> android.R.drawable.ic_media_pause to "Pause"
+        MEDIA_ACTION_NEXT -> android.R.drawable.ic_media_next to "Next"
+        else -> R.drawable.ic_transparent to mediaAction
+      }
+      mBuilder.addAction(icon, title, pendingIntent)
+    }
+  }
+
   private fun buildIntent(notification: Notification, action: String?): Intent {
     val intent = if (activity != null) {
       Intent(context, activity.javaClass)
@@ -360,6 +487,30 @@ class TauriNotificationManager(
     }
   }
 
+  /**
+   * Re-posts [alarm] when the count-down of [request] reaches [deadline].
+   * Shares the request code of scheduled notifications, so [cancel] stops it.
+   */
+  private fun scheduleCountDownAlarm(
+    alarm: android.app.Notification,
+    request: Notification,
+    deadline: Date
+  ) {
+    val alarmManager = context.getSystemService(Context.ALARM_SERVICE) as AlarmManager
+    val alarmIntent = Intent(context, TimedNotificationPublisher::class.java)
+    alarmIntent.putExtra(NOTIFICATION_INTENT_KEY, request.id)
+    alarmIntent.putExtra(TimedNotificationPublisher.NOTIFICATION_KEY, alarm)
+    var flags = PendingIntent.FLAG_CANCEL_CURRENT
+    if (SDK_INT >= Build.VERSION_CODES.S) {
+      flags = flags or PendingIntent.FLAG_MUTABLE
+    }
+    val pendingIntent = PendingIntent.getBroadcast(context, request.id, alarmIntent, flags)
+    val schedule = NotificationSchedule.At()
+    schedule.date = deadline
+    schedule.allowWhileIdle = true
+    setExactIfPossible(alarmManager, schedule, deadline.time, pendingIntent)
+  }
+
   @SuppressLint("ObsoleteSdkInt", "MissingPermission")
   private fun setExactIfPossible(
     alarmManager: AlarmManager,
@@ -504,10 +655,20 @@ class TimedNotificationPublisher : BroadcastReceiver() {
       return
     }
 
-    NotificationPlugin.triggerNotification(savedNotification)
-    notificationManager.notify(id, notification)
-    if (!rescheduleNotificationIfNeeded(context, intent, id)) {
-      storage.deleteNotification(id.toString())
+    // Lets the app replace the content before it's posted
+    val pending = goAsync()
+    NotificationPlugin.triggerScheduled(savedNotification) { replacement ->
+      try {
+        if (replacement == null || !NotificationPlugin.presentScheduled(replacement)) {
+          NotificationPlugin.triggerNotification(savedNotification)
+          notificationManager.notify(id, notification)
+        }
+        if (!rescheduleNotificationIfNeeded(context, intent, id)) {
+          storage.deleteNotification(id.toString())
+        }
+      } finally {
+        pending.finish()
+      }
     }
   }
 
@@ -210,12 +210,59 @@ class ChannelManagerTest {
         assertFalse(channel.vibration ?: true)
     }
 
+    @Test
+    fun testCreateChannelsArgs() {
+        val first = Channel()
+        first.id = "downloads"
+        first.name = "Downloads"
+        val second = Channel()
+        second.id = "messages"
+        second.name = "Messages"
+
+        val args = CreateChannelsArgs()
+        args.channels = listOf(first, second)
+
+        assertEquals(listOf("downloads", "messages"), args.channels.map { it.id })
+    }
+
     @Test
     fun testDeleteChannelArgs() {
         val args = DeleteChannelArgs()
         args.id = "channel_to_delete"
 
         assertEquals("channel_to_delete", args.id)
+        assertNull(args.onPending)
+    }
+
+    @Test
+    fun testOnPendingPolicy() {
+        val policy = OnPendingPolicy()
+        assertEquals("leave", policy.policy)
+        assertNull(policy.toChannel)
+
+        policy.policy = "reassign"
+        policy.toChannel = "general"
+        assertEquals("reassign", policy.policy)
+        assertEquals("general", policy.toChannel)
+    }
+
+    @Test
+    fun testBlockedChannels() {
+        val before = mapOf("messages" to 4, "promotions" to 3, "muted" to 0)
+        val after = mapOf("messages" to 4, "promotions" to 0, "muted" to 0, "created" to 0)
+
+        assertEquals(listOf("promotions"), blockedChannels(before, after))
+        assertEquals(emptyList<String>(), blockedChannels(after, after))
+    }
+
+    @Test
+    fun testResolveChannelId() {
+        val existing = setOf("messages", "general")
+
+        assertEquals("messages", resolveChannelId("messages", "general", existing))
+        assertEquals("general", resolveChannelId("deleted", "general", existing))
+        assertNull(resolveChannelId("deleted", "missing", existing))
+        assertNull(resolveChannelId("deleted", null, existing))
     }
 
     @Test
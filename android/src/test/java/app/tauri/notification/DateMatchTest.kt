@@ -1,5 +1,6 @@
 package app.tauri.notification
 
+import com.fasterxml.jackson.databind.ObjectMapper
 import org.junit.Assert.*
 import org.junit.Test
 import java.util.*
@@ -282,4 +283,15 @@ class DateMatchTest {
         assertEquals(original.minute, restored.minute)
         assertEquals(original.second, restored.second)
     }
+
+    @Test
+    fun testDateMatch_serialization_omitsUnit() {
+        val date = DateMatch()
+        date.hour = 9
+        date.nextTrigger(Date())
+
+        val json = ObjectMapper().readTree(ObjectMapper().writeValueAsString(date))
+        assertEquals(9, json.get("hour").asInt())
+        assertFalse(json.has("unit"))
+    }
 }
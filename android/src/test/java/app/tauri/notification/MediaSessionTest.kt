@@ -0,0 +1,45 @@
+package app.tauri.notification
+
+import android.support.v4.media.session.PlaybackStateCompat
+import org.json.JSONObject
+import org.junit.Assert.*
+import org.junit.Test
+import org.junit.runner.RunWith
+import org.robolectric.RobolectricTestRunner
+
+@RunWith(RobolectricTestRunner::class)
+class MediaSessionTest {
+
+    @Test
+    fun testMediaActionPayload_includesNotification() {
+        val payload = mediaActionPayload(MEDIA_ACTION_PAUSE, """{"id":5,"title":"Song"}""")
+
+        assertEquals("media:pause", payload.getString("actionId"))
+        assertTrue(payload.isNull("inputValue"))
+        assertEquals(5, payload.getJSONObject("notification").getInt("id"))
+        assertEquals("Song", payload.getJSONObject("notification").getString("title"))
+    }
+
+    @Test
+    fun testMediaActionPayload_invalidNotificationJson() {
+        val payload = mediaActionPayload(MEDIA_ACTION_NEXT, "not json")
+
+        assertEquals("media:next", payload.getString("actionId"))
+        assertEquals(JSONObject.NULL, payload.get("notification"))
+    }
+
+    @Test
+    fun testPlaybackActions_mapsMediaButtons() {
+        val controls = MediaControls().apply {
+            actions = listOf(MEDIA_ACTION_PREVIOUS, MEDIA_ACTION_PAUSE, MEDIA_ACTION_NEXT, "media:unknown")
+        }
+
+        assertEquals(
+            PlaybackStateCompat.ACTION_SKIP_TO_PREVIOUS or
+                PlaybackStateCompat.ACTION_PAUSE or
+                PlaybackStateCompat.ACTION_SKIP_TO_NEXT,
+            playbackActions(controls)
+        )
+        assertEquals(0L, playbackActions(MediaControls()))
+    }
+}
@@ -30,6 +30,7 @@ class NotificationStorageTest {
         every { mockContext.getSharedPreferences(any(), any()) } returns mockSharedPreferences
         every { mockSharedPreferences.edit() } returns mockEditor
         every { mockEditor.apply() } just Runs
+        every { mockSharedPreferences.getStringSet(any(), any()) } returns null
     }
 
     @Test
@@ -278,4 +279,67 @@ class NotificationStorageTest {
         assertEquals("", result[0]?.id)
         assertNull(result[0]?.title)
     }
+
+    @Test
+    fun testWriteActionGroup_hiddenPreviews() {
+        val actionType = ActionType()
+        actionType.id = "message"
+        actionType.actions = listOf()
+        actionType.hiddenPreviewsBodyPlaceholder = "%u new messages"
+        actionType.hiddenPreviewsShowTitle = true
+
+        notificationStorage.writeActionGroup(listOf(actionType))
+
+        verify { mockEditor.putString("hiddenPreviewsBodyPlaceholder", "%u new messages") }
+        verify { mockEditor.putBoolean("hiddenPreviewsShowTitle", true) }
+    }
+
+    @Test
+    fun testGetHiddenPreviews() {
+        every { mockSharedPreferences.getString("hiddenPreviewsBodyPlaceholder", null) } returns "%u new, 100%% private"
+        every { mockSharedPreferences.getBoolean("hiddenPreviewsShowTitle", false) } returns true
+
+        val hidden = notificationStorage.getHiddenPreviews("message")
+
+        assertNotNull(hidden)
+        assertTrue(hidden!!.showTitle)
+        assertEquals("3 new, 100% private", hidden.body(3))
+    }
+
+    @Test
+    fun testGetHiddenPreviews_none() {
+        every { mockSharedPreferences.getString("hiddenPreviewsBodyPlaceholder", null) } returns null
+
+        assertNull(notificationStorage.getHiddenPreviews("message"))
+    }
+
+    @Test
+    fun testSavePushToken() {
+        every { mockEditor.putString("token", "fcm-token") } returns mockEditor
+
+        notificationStorage.savePushToken("fcm-token")
+
+        verify { mockContext.getSharedPreferences("PUSH_TOKEN_STORE", Context.MODE_PRIVATE) }
+        verify { mockEditor.putString("token", "fcm-token") }
+        verify { mockEditor.apply() }
+    }
+
+    @Test
+    fun testGetPushToken() {
+        every { mockSharedPreferences.getString("token", null) } returns "fcm-token"
+        assertEquals("fcm-token", notificationStorage.getPushToken())
+
+        every { mockSharedPreferences.getString("token", null) } returns null
+        assertNull(notificationStorage.getPushToken())
+    }
+
+    @Test
+    fun testClearPushToken() {
+        every { mockEditor.remove("token") } returns mockEditor
+
+        notificationStorage.clearPushToken()
+
+        verify { mockEditor.remove("token") }
+        verify { mockEditor.apply() }
+    }
 }
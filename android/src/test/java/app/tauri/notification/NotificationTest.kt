@@ -430,4 +430,107 @@ class NotificationTest {
         assertEquals(schedule, pendingNotification.schedule)
         assertEquals(extra, pendingNotification.extra)
     }
+
+    @Test
+    fun testBuildNotificationPendingList_includesChannelId() {
+        val notification = Notification().apply {
+            id = 11
+            channelId = "news"
+            schedule = NotificationSchedule.At()
+        }
+
+        val pending = Notification.buildNotificationPendingList(listOf(notification))
+
+        assertEquals(1, pending.size)
+        assertEquals("news", pending[0].channelId)
+    }
+
+    @Test
+    fun testExtraJson_populatesNestedExtra() {
+        val notification = Notification()
+        notification.extraJson =
+            """{"user":{"id":7,"roles":["admin"]},"big":"9007199254740993","name":"Grüße 👋"}"""
+
+        val extra = notification.extra
+        assertNotNull(extra)
+        assertEquals(7, extra!!.getJSONObject("user").getInt("id"))
+        assertEquals("admin", extra.getJSONObject("user").getJSONArray("roles").getString(0))
+        assertEquals("9007199254740993", extra.getString("big"))
+        assertEquals("Grüße 👋", extra.getString("name"))
+    }
+
+    @Test
+    fun testCountDownTo_parsesJsDate() {
+        val objectMapper = ObjectMapper()
+            .disable(DeserializationFeature.FAIL_ON_UNKNOWN_PROPERTIES)
+            .setVisibility(PropertyAccessor.FIELD, JsonAutoDetect.Visibility.ANY)
+
+        val notification = objectMapper.readValue(
+            """{"id":3,"countDownTo":"2030-03-17T17:46:40.123Z"}""",
+            Notification::class.java
+        )
+
+        assertEquals(1_900_000_000_123L, notification.countDownTo?.time)
+        assertNull(Notification().countDownTo)
+    }
+
+    @Test
+    fun testAccessibilityFields_parse() {
+        val objectMapper = ObjectMapper()
+            .disable(DeserializationFeature.FAIL_ON_UNKNOWN_PROPERTIES)
+            .setVisibility(PropertyAccessor.FIELD, JsonAutoDetect.Visibility.ANY)
+
+        val notification = objectMapper.readValue(
+            """{"id":4,"title":"Upload","ticker":"Upload finished","announceOnly":true}""",
+            Notification::class.java
+        )
+
+        assertEquals("Upload finished", notification.ticker)
+        assertTrue(notification.announceOnly)
+        assertFalse(Notification().announceOnly)
+    }
+
+    @Test
+    fun testScope_parse() {
+        val objectMapper = ObjectMapper()
+            .disable(DeserializationFeature.FAIL_ON_UNKNOWN_PROPERTIES)
+            .setVisibility(PropertyAccessor.FIELD, JsonAutoDetect.Visibility.ANY)
+
+        val notification = objectMapper.readValue(
+            """{"id":5,"title":"Invoice","scope":"alice@example.com"}""",
+            Notification::class.java
+        )
+
+        assertEquals("alice@example.com", notification.scope)
+        assertNull(Notification().scope)
+    }
+
+    @Test
+    fun testQuiet_parse() {
+        val objectMapper = ObjectMapper()
+            .disable(DeserializationFeature.FAIL_ON_UNKNOWN_PROPERTIES)
+            .setVisibility(PropertyAccessor.FIELD, JsonAutoDetect.Visibility.ANY)
+
+        val notification = objectMapper.readValue(
+            """{"id":5,"title":"Digest","quiet":true}""",
+            Notification::class.java
+        )
+
+        assertTrue(notification.quiet)
+        assertFalse(Notification().quiet)
+    }
+
+    @Test
+    fun testGetAnnouncementText() {
+        val notification = Notification().apply {
+            title = "Upload"
+            body = "3 files sent"
+        }
+        assertEquals("Upload. 3 files sent", notification.getAnnouncementText())
+
+        notification.ticker = "Upload finished"
+        assertEquals("Upload finished", notification.getAnnouncementText())
+
+        assertEquals("", Notification().getAnnouncementText())
+    }
 }
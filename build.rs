@@ -27,6 +27,40 @@ const COMMANDS: &[&str] = &[
     "list_distributors",
     "set_distributor",
     "set_token",
+    "get_pending_count_by_channel",
+    "simulate_click",
+    "simulate_action",
+    "get_push_token_cached",
+    "get_channel_defaults",
+    "clear_channel_defaults",
+    "ensure_channels",
+    "get_channel",
+    "get_notification_history",
+    "run_doctor",
+    "register_channel_blocked_listener",
+    "dismiss",
+    "clear_scope",
+    "get_toast_xml",
+    "get_schedule_capabilities",
+    "get_notification_stats",
+    "reset_stats",
+    "get_plugin_info",
+    "is_do_not_disturb_active",
+    "get_active_grouped",
+    "flush_group",
+    "update_scheduled_notification",
+    "batch_detailed",
+    "remove_active_detailed",
+    "cancel_detailed",
+    "cancel_by_group",
+    "remove_all_active",
+    "get_launch_source",
+    "patch_active",
+    "get_ignored_fields",
+    "set_notification_categories",
+    "get_unread_count",
+    "unregister_action_type",
+    "clear_action_types",
 ];
 
 fn main() {
@@ -15,23 +15,62 @@ import {
   Importance,
   Visibility,
   sendNotification,
+  sendNotifications,
+  sendNotificationsDetailed,
   isPermissionGranted,
+  isDoNotDisturbActive,
+  getUnreadCount,
   requestPermission,
   registerForPushNotifications,
+  getPushTokenCached,
   unregisterForPushNotifications,
   registerActionTypes,
+  setNotificationCategories,
+  unregisterActionType,
+  clearActionTypes,
   pending,
+  pendingCountByChannel,
+  simulateClick,
+  simulateAction,
   cancel,
+  cancelDetailed,
+  cancelByGroup,
+  dismiss,
+  clearScope,
   cancelAll,
   active,
+  activeGrouped,
+  flushGroup,
+  notificationHistory,
+  runDoctor,
+  getToastXml,
+  ignoredFields,
+  scheduleCapabilities,
+  pluginInfo,
+  launchSource,
+  notificationStats,
+  resetNotificationStats,
   removeActive,
+  removeActiveDetailed,
   removeAllActive,
+  patchActive,
   createChannel,
+  ensureChannels,
+  getChannel,
   removeChannel,
   channels,
+  getChannelDefaults,
+  clearChannelDefaults,
   onNotificationReceived,
+  onScheduledNotificationTriggered,
   onAction,
   onNotificationClicked,
+  onNotificationFailed,
+  onNotificationQueued,
+  onNotificationsCoalesced,
+  onNotificationFallback,
+  onNotificationWarning,
+  onChannelBlocked,
 } from "./index";
 
 describe("Schedule", () => {
@@ -452,6 +491,32 @@ describe("Notification Functions", () => {
     });
   });
 
+  describe("isDoNotDisturbActive", () => {
+    it("should call invoke with correct plugin command", async () => {
+      mockInvoke.mockResolvedValue(true);
+
+      const result = await isDoNotDisturbActive();
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|is_do_not_disturb_active",
+      );
+      expect(result).toBe(true);
+    });
+  });
+
+  describe("getUnreadCount", () => {
+    it("should return the unread count", async () => {
+      mockInvoke.mockResolvedValue(3);
+
+      const result = await getUnreadCount();
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|get_unread_count",
+      );
+      expect(result).toBe(3);
+    });
+  });
+
   describe("requestPermission", () => {
     it("should call invoke with correct plugin command", async () => {
       mockInvoke.mockResolvedValue("granted");
@@ -464,6 +529,18 @@ describe("Notification Functions", () => {
       expect(result).toBe("granted");
     });
 
+    it("should ask again after the rationale was shown", async () => {
+      mockInvoke.mockResolvedValue("granted");
+
+      const result = await requestPermission({ afterRationale: true });
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|request_permission",
+        { afterRationale: true },
+      );
+      expect(result).toBe("granted");
+    });
+
     it("should return denied when permission denied", async () => {
       mockInvoke.mockResolvedValue("denied");
 
@@ -487,6 +564,25 @@ describe("Notification Functions", () => {
     });
   });
 
+  describe("getPushTokenCached", () => {
+    it("should call invoke and return the cached token", async () => {
+      mockInvoke.mockResolvedValue("abc123token");
+
+      const result = await getPushTokenCached();
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|get_push_token_cached",
+      );
+      expect(result).toBe("abc123token");
+    });
+
+    it("should return null when the app never registered", async () => {
+      mockInvoke.mockResolvedValue(null);
+
+      expect(await getPushTokenCached()).toBeNull();
+    });
+  });
+
   describe("unregisterForPushNotifications", () => {
     it("should call invoke with correct plugin command", async () => {
       mockInvoke.mockResolvedValue("");
@@ -509,13 +605,14 @@ describe("Notification Functions", () => {
 
   describe("sendNotification", () => {
     it("should send notification with string title", async () => {
-      mockInvoke.mockResolvedValue(undefined);
+      mockInvoke.mockResolvedValue(123456);
 
-      await sendNotification("Test Title");
+      const id = await sendNotification("Test Title");
 
       expect(mockInvoke).toHaveBeenCalledWith("plugin:notifications|notify", {
         options: { title: "Test Title" },
       });
+      expect(id).toBe(123456);
     });
 
     it("should send notification with full options object", async () => {
@@ -583,6 +680,66 @@ describe("Notification Functions", () => {
     });
   });
 
+
+  describe("sendNotifications", () => {
+    it("should send all notifications and return their ids", async () => {
+      mockInvoke.mockResolvedValue([1, 2]);
+
+      const options = [
+        { id: 1, title: "First" },
+        { id: 2, title: "Second" },
+      ];
+      const result = await sendNotifications(options);
+
+      expect(mockInvoke).toHaveBeenCalledWith("plugin:notifications|batch", {
+        options,
+      });
+      expect(result).toEqual([1, 2]);
+    });
+  });
+
+  describe("setNotificationCategories", () => {
+    it("should replace action types", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      const types = [
+        { id: "message", actions: [{ id: "reply", title: "Reply" }] },
+      ];
+
+      await setNotificationCategories(types);
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|set_notification_categories",
+        { types },
+      );
+    });
+  });
+
+  describe("unregisterActionType", () => {
+    it("should remove one action type", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await unregisterActionType("message");
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|unregister_action_type",
+        { id: "message" },
+      );
+    });
+  });
+
+  describe("clearActionTypes", () => {
+    it("should remove every action type", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await clearActionTypes();
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|clear_action_types",
+      );
+    });
+  });
+
   describe("registerActionTypes", () => {
     it("should register action types", async () => {
       mockInvoke.mockResolvedValue(undefined);
@@ -638,6 +795,16 @@ describe("Notification Functions", () => {
         { types },
       );
     });
+
+    it("should report unchanged registrations", async () => {
+      mockInvoke.mockResolvedValue("unchanged");
+
+      const result = await registerActionTypes([
+        { id: "message", actions: [{ id: "reply", title: "Reply" }] },
+      ]);
+
+      expect(result).toBe("unchanged");
+    });
   });
 
   describe("pending", () => {
@@ -669,6 +836,276 @@ describe("Notification Functions", () => {
     });
   });
 
+  describe("pendingCountByChannel", () => {
+    it("should retrieve pending counts per channel", async () => {
+      mockInvoke.mockResolvedValue({ messages: 2, default: 1 });
+
+      const result = await pendingCountByChannel();
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|get_pending_count_by_channel",
+      );
+      expect(result).toEqual({ messages: 2, default: 1 });
+    });
+  });
+
+  describe("simulateClick", () => {
+    it("should simulate a click on a notification", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await simulateClick(42);
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|simulate_click",
+        { id: 42 },
+      );
+    });
+  });
+
+  describe("simulateAction", () => {
+    it("should simulate an action with input", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await simulateAction(42, "reply", "On my way");
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|simulate_action",
+        { id: 42, actionId: "reply", inputValue: "On my way" },
+      );
+    });
+  });
+
+  describe("dismiss", () => {
+    it("should dismiss a notification by ID and return where it was", async () => {
+      const outcome = { wasPending: true, wasActive: false };
+      mockInvoke.mockResolvedValue(outcome);
+
+      const result = await dismiss(42);
+
+      expect(mockInvoke).toHaveBeenCalledWith("plugin:notifications|dismiss", {
+        id: 42,
+      });
+      expect(result).toEqual(outcome);
+    });
+  });
+
+  describe("clearScope", () => {
+    it("should clear a scope and return the counts", async () => {
+      const clearance = { canceled: 2, removed: 1 };
+      mockInvoke.mockResolvedValue(clearance);
+
+      const result = await clearScope("alice@example.com");
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|clear_scope",
+        { scope: "alice@example.com" },
+      );
+      expect(result).toEqual(clearance);
+    });
+  });
+
+  describe("ignoredFields", () => {
+    it("should return the options the platform ignores", async () => {
+      mockInvoke.mockResolvedValue(["largeIcon"]);
+
+      const result = await ignoredFields({ title: "Hi", largeIcon: "avatar" });
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|get_ignored_fields",
+        { options: { title: "Hi", largeIcon: "avatar" } },
+      );
+      expect(result).toEqual(["largeIcon"]);
+    });
+  });
+
+  describe("getToastXml", () => {
+    it("should return the toast XML for the options", async () => {
+      mockInvoke.mockResolvedValue("<toast/>");
+
+      const result = await getToastXml({ title: "Test" });
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|get_toast_xml",
+        { options: { title: "Test" } },
+      );
+      expect(result).toBe("<toast/>");
+    });
+  });
+
+  describe("notificationStats", () => {
+    it("should return the counters over a range", async () => {
+      const stats = {
+        delivered: 3,
+        clicked: 1,
+        dismissed: 0,
+        actionCounts: { reply: 2 },
+      };
+      mockInvoke.mockResolvedValue(stats);
+      const since = new Date("2025-03-10T00:00:00Z");
+
+      const result = await notificationStats({ since, channelId: "chat" });
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|get_notification_stats",
+        { range: { since, channelId: "chat" } },
+      );
+      expect(result).toEqual(stats);
+    });
+
+    it("should reset the counters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await resetNotificationStats();
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|reset_stats",
+      );
+    });
+  });
+
+  describe("scheduleCapabilities", () => {
+    it("should return the platform's schedule capabilities", async () => {
+      const capabilities = {
+        scheduling: true,
+        allowWhileIdle: false,
+        deliveredAfterSleep: true,
+      };
+      mockInvoke.mockResolvedValue(capabilities);
+
+      const result = await scheduleCapabilities();
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|get_schedule_capabilities",
+      );
+      expect(result).toEqual(capabilities);
+    });
+  });
+
+  describe("pluginInfo", () => {
+    it("should return the backend and plugin version", async () => {
+      const info = { platform: "windows", version: "0.4.0" };
+      mockInvoke.mockResolvedValue(info);
+
+      const result = await pluginInfo();
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|get_plugin_info",
+      );
+      expect(result).toEqual(info);
+    });
+  });
+
+  describe("launchSource", () => {
+    it("should return how the app was started", async () => {
+      const source = { type: "notificationAction", id: 7, actionId: "reply" };
+      mockInvoke.mockResolvedValue(source);
+
+      const result = await launchSource();
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|get_launch_source",
+      );
+      expect(result).toEqual(source);
+    });
+  });
+
+  describe("activeGrouped", () => {
+    it("should retrieve active notifications by group", async () => {
+      const mockGroups = [
+        {
+          group: "chat",
+          children: [
+            {
+              id: 2,
+              groupSummary: false,
+              data: {},
+              extra: {},
+              attachments: [],
+              sortKey: "a",
+              deliveredAt: 1700000000000,
+            },
+          ],
+        },
+      ];
+      mockInvoke.mockResolvedValue(mockGroups);
+
+      const result = await activeGrouped();
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|get_active_grouped",
+      );
+      expect(result).toEqual(mockGroups);
+    });
+  });
+
+  describe("flushGroup", () => {
+    it("should flush the held summary of a group", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await flushGroup("sync");
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|flush_group",
+        { group: "sync" },
+      );
+    });
+  });
+
+  describe("detailed bulk calls", () => {
+    const bulkResult = { succeeded: [1, 3], failed: [[2, "not found"]] };
+
+    it("should report the outcome of each sent notification", async () => {
+      mockInvoke.mockResolvedValue(bulkResult);
+      const options = [{ id: 1 }, { id: 2 }, { id: 3 }];
+
+      const result = await sendNotificationsDetailed(options);
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|batch_detailed",
+        { options },
+      );
+      expect(result).toEqual(bulkResult);
+    });
+
+    it("should report the outcome of each canceled notification", async () => {
+      mockInvoke.mockResolvedValue(bulkResult);
+
+      const result = await cancelDetailed([1, 2, 3]);
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|cancel_detailed",
+        { notifications: [1, 2, 3] },
+      );
+      expect(result).toEqual(bulkResult);
+    });
+
+    it("should report the outcome of each removed notification", async () => {
+      mockInvoke.mockResolvedValue(bulkResult);
+
+      const result = await removeActiveDetailed([1, 2, 3]);
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|remove_active_detailed",
+        { notifications: [1, 2, 3] },
+      );
+      expect(result).toEqual(bulkResult);
+    });
+  });
+
+  describe("cancelByGroup", () => {
+    it("should return the canceled ids", async () => {
+      mockInvoke.mockResolvedValue([1, 4]);
+
+      const canceled = await cancelByGroup("semester-2024");
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|cancel_by_group",
+        { group: "semester-2024" },
+      );
+      expect(canceled).toEqual([1, 4]);
+    });
+  });
+
   describe("cancel", () => {
     it("should cancel notifications by IDs", async () => {
       mockInvoke.mockResolvedValue(undefined);
@@ -713,6 +1150,56 @@ describe("Notification Functions", () => {
     });
   });
 
+  describe("notificationHistory", () => {
+    it("should retrieve the notification history", async () => {
+      mockInvoke.mockResolvedValue([]);
+
+      const result = await notificationHistory();
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|get_notification_history",
+      );
+      expect(result).toEqual([]);
+    });
+  });
+
+  describe("runDoctor", () => {
+    it("should return the diagnostics", async () => {
+      const diagnostics = [
+        {
+          severity: "error",
+          code: "permissionDenied",
+          message: "Notifications are turned off for this app",
+          fixHint: "Ask the user to allow notifications",
+        },
+      ];
+      mockInvoke.mockResolvedValue(diagnostics);
+
+      const result = await runDoctor();
+
+      expect(mockInvoke).toHaveBeenCalledWith("plugin:notifications|run_doctor");
+      expect(result).toEqual(diagnostics);
+    });
+
+    it("should report a missing capability instead of failing", async () => {
+      mockInvoke.mockRejectedValue(
+        "notifications.run_doctor not allowed. Permissions associated with this command: notifications:allow-run-doctor",
+      );
+
+      const result = await runDoctor();
+
+      expect(result).toHaveLength(1);
+      expect(result[0].code).toBe("missingCapability");
+      expect(result[0].severity).toBe("error");
+    });
+
+    it("should rethrow other errors", async () => {
+      mockInvoke.mockRejectedValue(new Error("boom"));
+
+      await expect(runDoctor()).rejects.toThrow("boom");
+    });
+  });
+
   describe("active", () => {
     it("should retrieve active notifications", async () => {
       const mockActive = [
@@ -793,7 +1280,21 @@ describe("Notification Functions", () => {
       await removeAllActive();
 
       expect(mockInvoke).toHaveBeenCalledWith(
-        "plugin:notifications|remove_all",
+        "plugin:notifications|remove_all_active",
+      );
+    });
+  });
+
+  describe("patchActive", () => {
+    it("should patch a shown notification", async () => {
+      mockInvoke.mockResolvedValue("replacedQuietly");
+
+      const strategy = await patchActive(7, { body: "Done", progress: 1 });
+
+      expect(strategy).toBe("replacedQuietly");
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|patch_active",
+        { id: 7, patch: { body: "Done", progress: 1 } },
       );
     });
   });
@@ -839,9 +1340,60 @@ describe("Notification Functions", () => {
     });
   });
 
+  describe("createChannel result", () => {
+    it("should return what happened to the channel", async () => {
+      mockInvoke.mockResolvedValue({
+        status: "existsWithDifferentSettings",
+        differingFields: ["importance"],
+      });
+
+      const result = await createChannel({ id: "news", name: "News" });
+
+      expect(result).toEqual({
+        status: "existsWithDifferentSettings",
+        differingFields: ["importance"],
+      });
+    });
+  });
+
+  describe("ensureChannels", () => {
+    it("should create channels in one call", async () => {
+      mockInvoke.mockResolvedValue([
+        { status: "created" },
+        { status: "unchanged" },
+      ]);
+
+      const channels = [
+        { id: "messages", name: "Messages" },
+        { id: "downloads", name: "Downloads" },
+      ];
+      const result = await ensureChannels(channels);
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|ensure_channels",
+        { channels },
+      );
+      expect(result).toEqual([{ status: "created" }, { status: "unchanged" }]);
+    });
+  });
+
+  describe("getChannel", () => {
+    it("should retrieve a channel by id", async () => {
+      mockInvoke.mockResolvedValue(null);
+
+      const result = await getChannel("missing");
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|get_channel",
+        { id: "missing" },
+      );
+      expect(result).toBeNull();
+    });
+  });
+
   describe("removeChannel", () => {
     it("should delete notification channel", async () => {
-      mockInvoke.mockResolvedValue(undefined);
+      mockInvoke.mockResolvedValue({ canceled: [], reassigned: [] });
 
       await removeChannel("test-channel");
 
@@ -849,11 +1401,58 @@ describe("Notification Functions", () => {
         "plugin:notifications|delete_channel",
         {
           id: "test-channel",
+          onPending: undefined,
+        },
+      );
+    });
+
+    it("should pass the pending policy and return the summary", async () => {
+      mockInvoke.mockResolvedValue({ canceled: [], reassigned: [1, 2] });
+
+      const result = await removeChannel("promotions", {
+        policy: "reassign",
+        toChannel: "general",
+      });
+
+      expect(result).toEqual({ canceled: [], reassigned: [1, 2] });
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|delete_channel",
+        {
+          id: "promotions",
+          onPending: { policy: "reassign", toChannel: "general" },
         },
       );
     });
   });
 
+  describe("getChannelDefaults", () => {
+    it("should retrieve the defaults bound to a channel", async () => {
+      mockInvoke.mockResolvedValue({ ongoing: true, silent: true });
+
+      const result = await getChannelDefaults("downloads");
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|get_channel_defaults",
+        { channelId: "downloads" },
+      );
+      expect(result).toEqual({ ongoing: true, silent: true });
+    });
+  });
+
+  describe("clearChannelDefaults", () => {
+    it("should clear the defaults bound to a channel", async () => {
+      mockInvoke.mockResolvedValue(true);
+
+      const result = await clearChannelDefaults("downloads");
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|clear_channel_defaults",
+        { channelId: "downloads" },
+      );
+      expect(result).toBe(true);
+    });
+  });
+
   describe("channels", () => {
     it("should retrieve all notification channels", async () => {
       const mockChannels = [
@@ -887,6 +1486,42 @@ describe("Notification Functions", () => {
     });
   });
 
+  describe("onScheduledNotificationTriggered", () => {
+    it("should send the replacement returned by the callback", async () => {
+      let capturedCallback: ((n: any) => Promise<void>) | undefined;
+      mockAddPluginListener.mockImplementation((_plugin, event, cb) => {
+        expect(event).toBe("scheduledNotificationTriggered");
+        capturedCallback = cb;
+        return Promise.resolve(vi.fn());
+      });
+      mockInvoke.mockResolvedValue(undefined);
+
+      await onScheduledNotificationTriggered((notification) => ({
+        ...notification,
+        body: "3 unread",
+      }));
+      await capturedCallback?.({ id: 7, title: "Inbox", body: "stale" });
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|update_scheduled_notification",
+        { id: 7, data: { id: 7, title: "Inbox", body: "3 unread" } },
+      );
+    });
+
+    it("should keep the notification when the callback returns nothing", async () => {
+      let capturedCallback: ((n: any) => Promise<void>) | undefined;
+      mockAddPluginListener.mockImplementation((_plugin, _event, cb) => {
+        capturedCallback = cb;
+        return Promise.resolve(vi.fn());
+      });
+
+      await onScheduledNotificationTriggered(() => undefined);
+      await capturedCallback?.({ id: 7, title: "Inbox" });
+
+      expect(mockInvoke).not.toHaveBeenCalled();
+    });
+  });
+
   describe("onNotificationReceived", () => {
     it("should register notification received listener", async () => {
       const mockUnlisten = vi.fn();
@@ -898,7 +1533,7 @@ describe("Notification Functions", () => {
       expect(mockAddPluginListener).toHaveBeenCalledWith(
         "notifications",
         "notification",
-        callback,
+        expect.any(Function),
       );
       expect(unlisten).toBe(mockUnlisten);
     });
@@ -919,6 +1554,31 @@ describe("Notification Functions", () => {
 
       expect(callback).toHaveBeenCalledWith(mockNotification);
     });
+
+    it("should parse extraJson into a nested extra object", async () => {
+      let capturedCallback: ((notification: any) => void) | undefined;
+
+      mockAddPluginListener.mockImplementation((_plugin, _event, cb) => {
+        capturedCallback = cb;
+        return Promise.resolve(vi.fn());
+      });
+
+      const callback = vi.fn();
+      await onNotificationReceived(callback);
+
+      capturedCallback?.({
+        id: 1,
+        extraJson: '{"order":{"id":"9007199254740993","items":[1,2]},"emoji":"🔔"}',
+      });
+
+      expect(callback).toHaveBeenCalledWith({
+        id: 1,
+        extra: {
+          order: { id: "9007199254740993", items: [1, 2] },
+          emoji: "🔔",
+        },
+      });
+    });
   });
 
   describe("onAction", () => {
@@ -932,7 +1592,7 @@ describe("Notification Functions", () => {
       expect(mockAddPluginListener).toHaveBeenCalledWith(
         "notifications",
         "actionPerformed",
-        callback,
+        expect.any(Function),
       );
       expect(unlisten).toBe(mockUnlisten);
     });
@@ -953,6 +1613,145 @@ describe("Notification Functions", () => {
 
       expect(callback).toHaveBeenCalledWith(mockNotification);
     });
+
+    it("should parse extraJson on the nested notification", async () => {
+      let capturedCallback: ((notification: any) => void) | undefined;
+
+      mockAddPluginListener.mockImplementation((_plugin, _event, cb) => {
+        capturedCallback = cb;
+        return Promise.resolve(vi.fn());
+      });
+
+      const callback = vi.fn();
+      await onAction(callback);
+
+      capturedCallback?.({
+        actionId: "tap",
+        notification: { id: 2, extraJson: '{"tags":["a","b"]}' },
+      });
+
+      expect(callback).toHaveBeenCalledWith({
+        actionId: "tap",
+        notification: { id: 2, extra: { tags: ["a", "b"] } },
+      });
+    });
+  });
+
+  describe("onNotificationFailed", () => {
+    it("should register notification failed listener", async () => {
+      const mockUnlisten = vi.fn();
+      mockAddPluginListener.mockResolvedValue(mockUnlisten);
+
+      const callback = vi.fn();
+      const unlisten = await onNotificationFailed(callback);
+
+      expect(mockAddPluginListener).toHaveBeenCalledWith(
+        "notifications",
+        "notificationFailed",
+        callback,
+      );
+      expect(unlisten).toBe(mockUnlisten);
+    });
+  });
+
+  describe("onNotificationsCoalesced", () => {
+    it("should register notifications coalesced listener", async () => {
+      const mockUnlisten = vi.fn();
+      mockAddPluginListener.mockResolvedValue(mockUnlisten);
+
+      const callback = vi.fn();
+      const unlisten = await onNotificationsCoalesced(callback);
+
+      expect(mockAddPluginListener).toHaveBeenCalledWith(
+        "notifications",
+        "notificationsCoalesced",
+        callback,
+      );
+      expect(unlisten).toBe(mockUnlisten);
+    });
+  });
+
+  describe("onNotificationFallback", () => {
+    it("should register notification fallback listener", async () => {
+      const mockUnlisten = vi.fn();
+      mockAddPluginListener.mockResolvedValue(mockUnlisten);
+
+      const callback = vi.fn();
+      const unlisten = await onNotificationFallback(callback);
+
+      expect(mockAddPluginListener).toHaveBeenCalledWith(
+        "notifications",
+        "notificationFallback",
+        callback,
+      );
+      expect(unlisten).toBe(mockUnlisten);
+    });
+  });
+
+  describe("onNotificationWarning", () => {
+    it("should register notification warning listener", async () => {
+      const mockUnlisten = vi.fn();
+      mockAddPluginListener.mockResolvedValue(mockUnlisten);
+
+      const callback = vi.fn();
+      const unlisten = await onNotificationWarning(callback);
+
+      expect(mockAddPluginListener).toHaveBeenCalledWith(
+        "notifications",
+        "notificationWarning",
+        callback,
+      );
+      expect(unlisten).toBe(mockUnlisten);
+    });
+  });
+
+  describe("onChannelBlocked", () => {
+    it("should register the listener and start watching channels", async () => {
+      const listener = { unregister: vi.fn() };
+      mockAddPluginListener.mockResolvedValue(listener);
+      mockInvoke.mockResolvedValue(undefined);
+
+      const callback = vi.fn();
+      const result = await onChannelBlocked(callback);
+
+      expect(mockAddPluginListener).toHaveBeenCalledWith(
+        "notifications",
+        "channelBlocked",
+        callback,
+      );
+      expect(mockInvoke).toHaveBeenCalledWith(
+        "plugin:notifications|register_channel_blocked_listener",
+      );
+      expect(result).toBe(listener);
+    });
+
+    it("should unregister the listener when channels are unsupported", async () => {
+      const listener = { unregister: vi.fn().mockResolvedValue(undefined) };
+      mockAddPluginListener.mockResolvedValue(listener);
+      mockInvoke.mockRejectedValue("Channels are not supported on iOS");
+
+      await expect(onChannelBlocked(vi.fn())).rejects.toBe(
+        "Channels are not supported on iOS",
+      );
+      expect(listener.unregister).toHaveBeenCalled();
+    });
+  });
+
+  describe("onNotificationQueued", () => {
+    it("should register notification queued listener", async () => {
+      const mockUnlisten = vi.fn();
+      mockAddPluginListener.mockResolvedValue(mockUnlisten);
+
+      const callback = vi.fn();
+      const unlisten = await onNotificationQueued(callback);
+
+      expect(mockAddPluginListener).toHaveBeenCalledWith(
+        "notifications",
+        "notificationQueued",
+        callback,
+      );
+      expect(unlisten).toBe(mockUnlisten);
+    });
   });
 
   describe("onNotificationClicked", () => {
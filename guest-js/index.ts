@@ -24,10 +24,16 @@ interface Options {
   /**
    * Identifier of the {@link Channel} that delivers this notification.
    *
-   * If the channel does not exist, the notification won't fire.
+   * On Android, {@link sendNotification} fails with `channel not found` if
+   * the channel doesn't exist, unless `channelIdFallback` does.
    * Make sure the channel exists with {@link channels} and {@link createChannel}.
    */
   channelId?: string;
+  /**
+   * Channel that delivers the notification on Android when the `channelId`
+   * one doesn't exist.
+   */
+  channelIdFallback?: string;
   /**
    * Notification title.
    */
@@ -47,7 +53,9 @@ interface Options {
    */
   largeBody?: string;
   /**
-   * Detail text for the notification with `largeBody`, `inboxLines` or `groupSummary`.
+   * Secondary line of the notification: the summary text of the `largeBody`,
+   * `inboxLines` or `groupSummary` style on Android, the subtitle on iOS and
+   * macOS, and the attribution line on Windows.
    */
   summary?: string;
   /**
@@ -62,8 +70,19 @@ interface Options {
   group?: string;
   /**
    * Instructs the system that this notification is the summary of a group on Android.
+   * Shown before any child of its group, it is held until the first child has
+   * been shown; see `flushGroup()`.
    */
   groupSummary?: boolean;
+  /**
+   * Orders the notification within its group: notifications with a key come
+   * first, in plain string order, and the rest follow newest first.
+   * Android applies it with `setSortKey`; Windows re-posts the group's
+   * toasts that sort before this one (clicks on re-posted toasts reach only
+   * the COM activator); iOS and macOS always list the newest first.
+   * `activeGrouped()` reports the order on every platform.
+   */
+  sortKey?: string;
   /**
    * The sound resource name. Only available on mobile.
    */
@@ -73,7 +92,9 @@ interface Options {
    * Changes the notification style to inbox.
    * Cannot be used with `largeBody`.
    *
-   * Only supports up to 5 lines.
+   * Only supports up to 5 lines. Empty lines are dropped and the rest are
+   * cut to 5 with a logged warning, or rejected when `strictLimits` is set
+   * in the plugin config. Outside Android the lines are shown in the body.
    */
   inboxLines?: string[];
   /**
@@ -89,7 +110,7 @@ interface Options {
    */
   largeIcon?: string;
   /**
-   * Icon color on Android.
+   * Icon color on Android, as `#RGB`, `#RRGGBB` or `#AARRGGBB`.
    */
   iconColor?: string;
   /**
@@ -100,6 +121,58 @@ interface Options {
    * Extra payload to store in the notification.
    */
   extra?: Record<string, unknown>;
+  /**
+   * Label of the window to show, unminimize and focus when the notification
+   * is clicked (Windows and macOS). Overrides the `focusWindowOnClick`
+   * plugin config.
+   */
+  focusWindow?: string;
+  /**
+   * Emit `notificationFallback` instead of showing the notification while
+   * the window with this label is focused, or any window of the app for
+   * `true`. Not applied to scheduled notifications.
+   */
+  suppressIfFocused?: string | boolean;
+  /**
+   * Playback buttons for a media notification (Android and Windows). Each
+   * button emits `actionPerformed` with its id as `actionId`.
+   */
+  mediaControls?: MediaControls;
+  /**
+   * Shows a live count-down to this date and re-posts the notification as an
+   * alarm once it passes (Android and Windows). Cancel the notification id to
+   * stop both. Cannot be combined with `schedule`.
+   */
+  countDownTo?: Date;
+  /**
+   * Shows a progress bar filled to this fraction, from 0 to 1 (Android and
+   * Windows). Update it with `patchActive`. Ignored with `countDownTo`.
+   */
+  progress?: number;
+  /**
+   * Account or profile the notification belongs to. `clearScope` takes away
+   * every scheduled and shown notification of a scope, e.g. on logout.
+   */
+  scope?: string;
+  /**
+   * How many times an interval schedule repeats after its first delivery;
+   * it repeats until canceled otherwise (Windows only). Windows repeats
+   * while the app runs. Other platforms reject it.
+   */
+  maxRepetitions?: number;
+  /**
+   * Drop a scheduled notification instead of showing it late when the app
+   * starts again after its time (Windows only, where scheduled toasts
+   * outlive the app; ignored elsewhere). Can't be combined with a repeating
+   * schedule.
+   */
+  showOnlyOnce?: boolean;
+  /**
+   * What to do with the notification during the `quietHours` of the plugin
+   * config, instead of their `policy`: `"defer"` it to the end of quiet
+   * hours, deliver it `"silent"`ly, or `"bypass"` quiet hours.
+   */
+  quietHoursPolicy?: QuietHoursPolicy;
   /**
    * If true, the notification cannot be dismissed by the user on Android.
    *
@@ -116,12 +189,51 @@ interface Options {
    * Changes the notification presentation to be silent on iOS (no badge, no sound, not listed).
    */
   silent?: boolean;
+  /**
+   * Keep the notification in the notification list after it times out (Linux).
+   * Shows the toast for longer on Windows. Can't be combined with `transient`.
+   */
+  persistent?: boolean;
+  /**
+   * Keep the notification out of the notification list once it closes (Linux).
+   */
+  transient?: boolean;
+  /**
+   * Keep the notification on screen until the user dismisses it (Linux, Windows).
+   */
+  requireInteraction?: boolean;
+  /**
+   * Text accessibility services read when the notification is posted.
+   * Maps to the ticker on Android; also the text of an `announceOnly` announcement.
+   */
+  ticker?: string;
+  /**
+   * Deliver a screen-reader announcement (TalkBack / VoiceOver) instead of a
+   * visual notification. Android, iOS and macOS only.
+   */
+  announceOnly?: boolean;
+  /**
+   * Keep the notification on this device instead of bridging it to a paired
+   * watch. Android only.
+   */
+  localOnly?: boolean;
+  /**
+   * Actions shown only on a paired watch. Android only.
+   */
+  wearableActions?: Action[];
   /**
    * The source of the notification. Only present in `onNotificationReceived` callbacks.
    * - `"push"` — notification received from a remote push (FCM/APNs).
    * - `"local"` — notification created locally (immediate or scheduled).
    */
   source?: "push" | "local";
+  /**
+   * The app's notification setting, read once Windows accepted the toast.
+   * Only present in `onNotificationReceived` callbacks on Windows. Toasts
+   * Windows accepts while notifications are turned off aren't displayed and
+   * don't reach `onNotificationReceived`.
+   */
+  windowsSetting?: "enabled";
   /**
    * Notification visibility.
    */
@@ -271,6 +383,36 @@ interface Attachment {
   id: string;
   /** Attachment URL. Accepts the `asset` and `file` protocols. */
   url: string;
+  /**
+   * Media type of the attachment (iOS/macOS). Inferred from the file
+   * extension when omitted; ignored on other platforms.
+   */
+  typeHint?: "image" | "video" | "audio";
+}
+
+/**
+ * A media notification button, reported as the `actionId` of the
+ * `actionPerformed` event when pressed.
+ */
+type MediaAction = "media:previous" | "media:play" | "media:pause" | "media:next";
+
+/**
+ * What {@link sendNotification} does with a notification during quiet hours.
+ */
+type QuietHoursPolicy = "defer" | "silent" | "bypass";
+
+/**
+ * Playback buttons of a media notification.
+ *
+ * Android renders them with `MediaStyle` backed by a plugin-managed media
+ * session; Windows shows them as toast buttons. iOS, macOS and the
+ * notify-rust backend reject the notification.
+ */
+interface MediaControls {
+  /** Buttons in display order. */
+  actions: MediaAction[];
+  /** Indices into `actions` of up to three buttons shown while collapsed (Android). */
+  showInCompact?: number[];
 }
 
 /**
@@ -303,15 +445,19 @@ interface ActionType {
   id: string;
   /** The list of associated actions. */
   actions: Action[];
-  /** Placeholder text shown in place of the notification body when previews are hidden (iOS). */
+  /**
+   * Text shown in place of the body when previews are hidden (iOS, macOS),
+   * or on a secure lock screen (Android). `%u` is replaced with the number
+   * of notifications and `%%` with a percent sign. Not supported on Windows.
+   */
   hiddenPreviewsBodyPlaceholder?: string;
   /** Whether to include a custom dismiss action (iOS). */
   customDismissAction?: boolean;
   /** Whether the notification can be displayed in CarPlay (iOS). */
   allowInCarPlay?: boolean;
-  /** Whether to show the title when previews are hidden (iOS). */
+  /** Whether to show the title when previews are hidden (iOS, macOS, Android). */
   hiddenPreviewsShowTitle?: boolean;
-  /** Whether to show the subtitle when previews are hidden (iOS). */
+  /** Whether to show the subtitle when previews are hidden (iOS, macOS). */
   hiddenPreviewsShowSubtitle?: boolean;
 }
 
@@ -327,6 +473,18 @@ interface PendingNotification {
   body?: string;
   /** The schedule configuration for this notification. */
   schedule: Schedule;
+  /** Channel (Android) or thread identifier (iOS/macOS) of the notification. */
+  channelId?: string;
+  /** Tag the notification was scheduled with (Windows). */
+  tag?: string;
+  /** Group the notification belongs to (Windows). */
+  group?: string;
+  /** Extra payload the notification was scheduled with (macOS). */
+  extra?: Record<string, unknown>;
+  /** Action type the notification was scheduled with (macOS). */
+  actionTypeId?: string;
+  /** Sound the notification was scheduled with (macOS). */
+  sound?: string;
 }
 
 /**
@@ -357,6 +515,25 @@ interface ActiveNotification {
   schedule?: Schedule;
   /** The sound resource name. */
   sound?: string;
+  /** The notification's `sortKey`. */
+  sortKey?: string;
+  /**
+   * When the notification was posted, in milliseconds since the Unix epoch.
+   * Not reported on Windows and Linux.
+   */
+  deliveredAt?: number;
+}
+
+/**
+ * The active notifications of one group, see `activeGrouped()`.
+ */
+interface ActiveGroup {
+  /** Group identifier, missing for the notifications without a group. */
+  group?: string;
+  /** The group's summary notification, if one is active. */
+  summary?: ActiveNotification;
+  /** Ordered by `sortKey`, then newest first. */
+  children: ActiveNotification[];
 }
 
 /**
@@ -411,6 +588,162 @@ interface Channel {
   visibility?: Visibility;
 }
 
+/**
+ * What `createChannel` and `ensureChannels` did with a requested channel.
+ * An existing channel is never modified: with different settings, it is
+ * reported with the names of the fields that differ.
+ */
+type ChannelCreation =
+  | { status: "created" }
+  | { status: "unchanged" }
+  | { status: "existsWithDifferentSettings"; differingFields: string[] };
+
+/**
+ * What `removeChannel` does with the pending notifications that target the
+ * removed channel. With `leave`, they stay scheduled but are never delivered.
+ */
+type OnPendingPolicy =
+  | { policy: "leave" }
+  | { policy: "cancel" }
+  | { policy: "reassign"; toChannel: string };
+
+/** Ids of the pending notifications `removeChannel` canceled or reassigned. */
+interface ChannelDeletion {
+  canceled: number[];
+  reassigned: number[];
+}
+
+/**
+ * Outcome of each notification of `sendNotificationsDetailed`,
+ * `cancelDetailed` and `removeActiveDetailed`. Both lists keep the order the
+ * notifications were passed in.
+ */
+interface BulkResult {
+  succeeded: number[];
+  /** The ids that failed, with the error. */
+  failed: Array<[number, string]>;
+}
+
+/**
+ * What a change to the registered action types did:
+ * - `unchanged`: the types already were these; the platform wasn't called.
+ * - `scheduled`: handed to the platform shortly, in one call with the other
+ *   changes made until then (iOS, macOS, Android).
+ * - `applied`: used by the next toast shown (Windows).
+ */
+type ActionTypesUpdate = "unchanged" | "scheduled" | "applied";
+
+/** Changes `patchActive` makes; fields left out keep their value. */
+interface ActivePatch {
+  title?: string;
+  body?: string;
+  /** Filled fraction of the progress bar, from 0 to 1. */
+  progress?: number;
+  /** Merged into the notification's `extra`. */
+  extra?: Record<string, unknown>;
+}
+
+/**
+ * How `patchActive` changed the notification:
+ * - `inPlace`: updated where it is shown (Windows, progress only).
+ * - `replacedQuietly`: shown again under its id without alerting.
+ * - `reshown`: closed and shown again, which may alert (notify-rust).
+ * - `rebuilt`: replaced quietly, but rebuilt from what the platform reports
+ *   about it, as it wasn't shown in this session. Actions and icons are lost.
+ */
+type PatchStrategy = "inPlace" | "replacedQuietly" | "reshown" | "rebuilt";
+
+/** How many notifications `clearScope` took away. */
+interface ScopeClearance {
+  /** Scheduled notifications canceled. */
+  canceled: number;
+  /** Shown notifications removed. */
+  removed: number;
+}
+
+/** Where `dismiss` found the notification. */
+interface DismissOutcome {
+  wasPending: boolean;
+  wasActive: boolean;
+}
+
+/**
+ * Delivery and interaction counters from `notificationStats`. Scheduled
+ * notifications count as delivered when they are scheduled.
+ */
+interface NotificationStats {
+  delivered: number;
+  clicked: number;
+  dismissed: number;
+  /** Uses of each action, by action id. */
+  actionCounts: Record<string, number>;
+}
+
+/**
+ * What `notificationStats` sums up. Counters are kept per UTC day, so
+ * `since` and `until` take in the whole days they fall on.
+ */
+interface StatsRange {
+  since?: Date;
+  until?: Date;
+  /** Only count notifications on this channel. */
+  channelId?: string;
+  /** Only count notifications of this group. */
+  group?: string;
+}
+
+/** Which plugin build is running. */
+interface PluginInfo {
+  /** Backend: `windows`, `macos`, `ios`, `android` or `notify-rust`. */
+  platform: string;
+  /** Version of the plugin crate. */
+  version: string;
+}
+
+/** How the app was started. */
+type LaunchSource =
+  | { type: "normal" }
+  /** `id` is missing when the platform doesn't report it. */
+  | { type: "notificationTap"; id?: number }
+  /** Windows doesn't report the notification's `id` for actions. */
+  | { type: "notificationAction"; id?: number; actionId: string }
+  /** `messageId` is the FCM message id, when there is one. */
+  | { type: "push"; messageId?: string };
+
+/** What scheduled notifications can be relied on for on this platform. */
+interface ScheduleCapabilities {
+  /** Schedules are honored; without a scheduler they show right away. */
+  scheduling: boolean;
+  /** `allowWhileIdle` has an effect (Android only). */
+  allowWhileIdle: boolean;
+  /** Notifications that came due while the device slept show on wake. */
+  deliveredAfterSleep: boolean;
+}
+
+/**
+ * One finding of `runDoctor`. `code` is stable and can be matched in tests
+ * and referenced from support docs.
+ */
+interface Diagnostic {
+  severity: "info" | "warning" | "error";
+  code:
+    | "notBundled"
+    | "toastsUnavailable"
+    | "toastsDisabled"
+    | "notificationServerUnavailable"
+    | "permissionDenied"
+    | "permissionNotRequested"
+    | "permissionCheckFailed"
+    | "missingDefaultChannel"
+    | "missingChannel"
+    | "pushDisabled"
+    | "firebaseNotConfigured"
+    | "missingPushEntitlement"
+    | "missingCapability";
+  message: string;
+  fixHint: string;
+}
+
 /**
  * Checks if the permission to send notifications is granted.
  * @example
@@ -423,21 +756,78 @@ async function isPermissionGranted(): Promise<boolean> {
   return await invoke("plugin:notifications|is_permission_granted");
 }
 
+/**
+ * Checks whether Do Not Disturb (or a Focus) is on, for example to skip
+ * sounds the system would mute anyway.
+ *
+ * - Android: the interruption filter blocks everything or allows only alarms.
+ * - iOS / macOS: a Focus is on. Reading the Focus status needs the
+ *   Communication Notifications capability and the user's consent; without
+ *   them this reports whether alerts are turned off for the app.
+ * - Windows: the user is busy, presenting or running a full-screen app.
+ *   Focus assist rules are not reported.
+ * - Linux: not supported, rejects.
+ *
+ * @example
+ * ```typescript
+ * import { isDoNotDisturbActive } from '@choochmeque/tauri-plugin-notifications-api';
+ * const silent = await isDoNotDisturbActive();
+ * ```
+ */
+async function isDoNotDisturbActive(): Promise<boolean> {
+  return await invoke("plugin:notifications|is_do_not_disturb_active");
+}
+
+/**
+ * Returns how many unread notifications the user has.
+ *
+ * - Android: the app's notifications in the notification shade.
+ * - iOS: the app icon badge number.
+ * - macOS: the number on the Dock badge, `0` without one.
+ * - Windows: the app's toasts in Action Center, including dismissed ones.
+ * - Linux: the notifications shown and not yet closed.
+ *
+ * @example
+ * ```typescript
+ * import { getUnreadCount } from '@choochmeque/tauri-plugin-notifications-api';
+ * const unread = await getUnreadCount();
+ * ```
+ */
+async function getUnreadCount(): Promise<number> {
+  return await invoke("plugin:notifications|get_unread_count");
+}
+
 /**
  * Requests the permission to send notifications.
+ *
+ * On Android, once the user has denied the permission, this resolves with
+ * `prompt-with-rationale` instead of showing the system dialog. Explain why
+ * the app needs notifications, then call it again with
+ * `{ afterRationale: true }` to show the dialog.
+ *
  * @example
  * ```typescript
  * import { isPermissionGranted, requestPermission } from '@choochmeque/tauri-plugin-notifications-api';
  * let permissionGranted = await isPermissionGranted();
  * if (!permissionGranted) {
- *   const permission = await requestPermission();
+ *   let permission = await requestPermission();
+ *   if (permission === 'prompt-with-rationale' && (await explainNotifications())) {
+ *     permission = await requestPermission({ afterRationale: true });
+ *   }
  *   permissionGranted = permission === 'granted';
  * }
  * ```
  *
  * @returns A promise resolving to whether the user granted the permission or not.
  */
-async function requestPermission(): Promise<NotificationPermission> {
+async function requestPermission(options?: {
+  afterRationale?: boolean;
+}): Promise<NotificationPermission | "prompt-with-rationale"> {
+  if (options?.afterRationale) {
+    return await invoke("plugin:notifications|request_permission", {
+      afterRationale: true,
+    });
+  }
   return await invoke("plugin:notifications|request_permission");
 }
 
@@ -471,6 +861,26 @@ async function registerForPushNotifications(): Promise<string> {
   return await invoke("plugin:notifications|register_for_push_notifications");
 }
 
+/**
+ * Returns the push token from the last successful
+ * {@link registerForPushNotifications} call without contacting the push
+ * service, or `null` if the app never registered (or has unregistered since).
+ *
+ * The token is kept in `SharedPreferences` on Android, `UserDefaults` on
+ * iOS/macOS and the app data directory on Windows/Linux.
+ *
+ * @example
+ * ```typescript
+ * import { getPushTokenCached } from '@choochmeque/tauri-plugin-notifications-api';
+ * const token = await getPushTokenCached();
+ * ```
+ *
+ * @returns A promise resolving to the cached push identifier, or `null`.
+ */
+async function getPushTokenCached(): Promise<string | null> {
+  return await invoke("plugin:notifications|get_push_token_cached");
+}
+
 /**
  * Unregisters the app from push notifications.
  *
@@ -603,12 +1013,14 @@ async function setToken(token: string): Promise<void> {
  * }
  * if (permissionGranted) {
  *   sendNotification('Tauri is awesome!');
- *   sendNotification({ title: 'TAURI', body: 'Tauri is awesome!' });
+ *   const id = await sendNotification({ title: 'TAURI', body: 'Tauri is awesome!' });
  * }
  * ```
+ *
+ * @returns The id of the notification, generated when `options` has none.
  */
-async function sendNotification(options: Options | string): Promise<void> {
-  await invoke("plugin:notifications|notify", {
+async function sendNotification(options: Options | string): Promise<number> {
+  return await invoke<number>("plugin:notifications|notify", {
     options:
       typeof options === "string"
         ? {
@@ -618,8 +1030,46 @@ async function sendNotification(options: Options | string): Promise<void> {
   });
 }
 
+/**
+ * Sends several notifications in one call.
+ * @example
+ * ```typescript
+ * import { sendNotifications } from '@choochmeque/tauri-plugin-notifications-api';
+ * const ids = await sendNotifications([
+ *   { title: 'First' },
+ *   { title: 'Second', body: 'Tauri is awesome!' },
+ * ]);
+ * ```
+ *
+ * @returns The ids of the notifications, in order. Rejects if any of them
+ * failed; the others are still shown.
+ */
+async function sendNotifications(options: Options[]): Promise<number[]> {
+  return await invoke<number[]>("plugin:notifications|batch", { options });
+}
+
+/**
+ * Sends several notifications in one call, like `sendNotifications`, and
+ * reports which ones were shown and why the others weren't.
+ *
+ * @example
+ * ```typescript
+ * import { sendNotificationsDetailed } from '@choochmeque/tauri-plugin-notifications-api';
+ * const { failed } = await sendNotificationsDetailed(messages.map(toOptions));
+ * for (const [id, error] of failed) console.warn(`Not shown: ${id}: ${error}`);
+ * ```
+ *
+ * @returns The outcome of each notification.
+ */
+async function sendNotificationsDetailed(
+  options: Options[],
+): Promise<BulkResult> {
+  return await invoke("plugin:notifications|batch_detailed", { options });
+}
+
 /**
  * Register actions that are performed when the user clicks on the notification.
+ * Adds to the registered types, replacing the ones with the same `id`.
  *
  * @example
  * ```typescript
@@ -633,189 +1083,722 @@ async function sendNotification(options: Options | string): Promise<void> {
  * }])
  * ```
  *
- * @returns A promise indicating the success or failure of the operation.
+ * @returns What the call changed; `unchanged` when the platform wasn't called.
  */
-async function registerActionTypes(types: ActionType[]): Promise<void> {
-  await invoke("plugin:notifications|register_action_types", { types });
+async function registerActionTypes(types: ActionType[]): Promise<ActionTypesUpdate> {
+  return await invoke("plugin:notifications|register_action_types", { types });
 }
 
 /**
- * Retrieves the list of pending notifications.
+ * Replaces every registered action type with `types`, removing the ones
+ * missing from it.
  *
  * @example
  * ```typescript
- * import { pending } from '@choochmeque/tauri-plugin-notifications-api';
- * const pendingNotifications = await pending();
+ * import { setNotificationCategories } from '@choochmeque/tauri-plugin-notifications-api';
+ * await setNotificationCategories([{
+ *   id: 'message',
+ *   actions: [{ id: 'reply', title: 'Reply', input: true }]
+ * }])
  * ```
  *
- * @returns A promise resolving to the list of pending notifications.
+ * @returns What the call changed; `unchanged` when the platform wasn't called.
  */
-async function pending(): Promise<PendingNotification[]> {
-  return await invoke("plugin:notifications|get_pending");
+async function setNotificationCategories(types: ActionType[]): Promise<ActionTypesUpdate> {
+  return await invoke("plugin:notifications|set_notification_categories", { types });
 }
 
 /**
- * Cancels the pending notifications with the given list of identifiers.
+ * Removes the registered action type `id`. Does nothing if there is none.
  *
  * @example
  * ```typescript
- * import { cancel } from '@choochmeque/tauri-plugin-notifications-api';
- * await cancel([-34234, 23432, 4311]);
+ * import { unregisterActionType } from '@choochmeque/tauri-plugin-notifications-api';
+ * await unregisterActionType('message');
  * ```
  *
- * @returns A promise indicating the success or failure of the operation.
+ * @returns What the call changed; `unchanged` when the platform wasn't called.
  */
-async function cancel(notifications: number[]): Promise<void> {
-  await invoke("plugin:notifications|cancel", { notifications });
+async function unregisterActionType(id: string): Promise<ActionTypesUpdate> {
+  return await invoke("plugin:notifications|unregister_action_type", { id });
 }
 
 /**
- * Cancels all pending notifications.
+ * Removes every registered action type.
  *
  * @example
  * ```typescript
- * import { cancelAll } from '@choochmeque/tauri-plugin-notifications-api';
- * await cancelAll();
+ * import { clearActionTypes } from '@choochmeque/tauri-plugin-notifications-api';
+ * await clearActionTypes();
  * ```
  *
- * @returns A promise indicating the success or failure of the operation.
+ * @returns What the call changed; `unchanged` when the platform wasn't called.
  */
-async function cancelAll(): Promise<void> {
-  await invoke("plugin:notifications|cancel_all");
+async function clearActionTypes(): Promise<ActionTypesUpdate> {
+  return await invoke("plugin:notifications|clear_action_types");
 }
 
 /**
- * Retrieves the list of active notifications.
+ * Retrieves the list of pending notifications.
  *
  * @example
  * ```typescript
- * import { active } from '@choochmeque/tauri-plugin-notifications-api';
- * const activeNotifications = await active();
+ * import { pending } from '@choochmeque/tauri-plugin-notifications-api';
+ * const pendingNotifications = await pending();
  * ```
  *
- * @returns A promise resolving to the list of active notifications.
+ * @returns A promise resolving to the list of pending notifications.
  */
-async function active(): Promise<ActiveNotification[]> {
-  return await invoke("plugin:notifications|get_active");
+async function pending(): Promise<PendingNotification[]> {
+  return await invoke("plugin:notifications|get_pending");
 }
 
 /**
- * Removes the active notifications with the given list of identifiers.
+ * Counts pending notifications per channel. Notifications without a channel,
+ * and every notification on Windows, are counted under `"default"`.
  *
  * @example
  * ```typescript
- * import { removeActive } from '@choochmeque/tauri-plugin-notifications-api';
- * await removeActive([{ id: 1 }, { id: 2, tag: 'news' }]);
+ * import { pendingCountByChannel } from '@choochmeque/tauri-plugin-notifications-api';
+ * const counts = await pendingCountByChannel();
+ * console.log(counts['messages'] ?? 0);
  * ```
  *
- * @returns A promise indicating the success or failure of the operation.
+ * @returns A promise resolving to a map of channel id to pending count.
  */
-async function removeActive(
-  notifications: Array<{ id: number; tag?: string }>,
-): Promise<void> {
-  await invoke("plugin:notifications|remove_active", { notifications });
+async function pendingCountByChannel(): Promise<Record<string, number>> {
+  return await invoke("plugin:notifications|get_pending_count_by_channel");
 }
 
 /**
- * Removes all active notifications.
+ * Cancels the pending notifications with the given list of identifiers.
  *
  * @example
  * ```typescript
- * import { removeAllActive } from '@choochmeque/tauri-plugin-notifications-api';
- * await removeAllActive()
+ * import { cancel } from '@choochmeque/tauri-plugin-notifications-api';
+ * await cancel([-34234, 23432, 4311]);
  * ```
  *
  * @returns A promise indicating the success or failure of the operation.
  */
-async function removeAllActive(): Promise<void> {
-  await invoke("plugin:notifications|remove_all");
+async function cancel(notifications: number[]): Promise<void> {
+  await invoke("plugin:notifications|cancel", { notifications });
 }
 
 /**
- * Creates a notification channel.
+ * Cancels the pending notifications with the given identifiers, like
+ * `cancel`, and reports the outcome of each one. Only Windows and Linux
+ * report them one by one; elsewhere they succeed or fail together.
  *
  * @example
  * ```typescript
- * import { createChannel, Importance, Visibility } from '@choochmeque/tauri-plugin-notifications-api';
- * await createChannel({
- *   id: 'new-messages',
- *   name: 'New Messages',
- *   lights: true,
- *   vibration: true,
- *   importance: Importance.Default,
- *   visibility: Visibility.Private
- * });
+ * import { cancelDetailed } from '@choochmeque/tauri-plugin-notifications-api';
+ * const { succeeded } = await cancelDetailed([1, 2, 3]);
  * ```
  *
- * @returns A promise indicating the success or failure of the operation.
+ * @returns The outcome of each identifier.
  */
-async function createChannel(channel: Channel): Promise<void> {
-  await invoke("plugin:notifications|create_channel", { channel });
+async function cancelDetailed(notifications: number[]): Promise<BulkResult> {
+  return await invoke("plugin:notifications|cancel_detailed", {
+    notifications,
+  });
 }
 
 /**
- * Removes the channel with the given identifier.
+ * Cancels every pending notification scheduled under `group` and resolves
+ * with their identifiers. Shown notifications of the group are left alone.
+ * Windows only; other platforms reject.
  *
  * @example
  * ```typescript
- * import { removeChannel } from '@choochmeque/tauri-plugin-notifications-api';
- * await removeChannel('new-messages');
+ * import { cancelByGroup } from '@choochmeque/tauri-plugin-notifications-api';
+ * const canceled = await cancelByGroup('semester-2024');
  * ```
- *
- * @returns A promise indicating the success or failure of the operation.
  */
-async function removeChannel(id: string): Promise<void> {
-  await invoke("plugin:notifications|delete_channel", { id });
+async function cancelByGroup(group: string): Promise<number[]> {
+  return await invoke("plugin:notifications|cancel_by_group", { group });
 }
 
 /**
- * Retrieves the list of notification channels.
+ * Cancels the notification with the given identifier if it is pending, and
+ * removes it if it is shown, without knowing beforehand which one it is.
+ * Both flags are `false` when the identifier is unknown.
  *
  * @example
  * ```typescript
- * import { channels } from '@choochmeque/tauri-plugin-notifications-api';
- * const notificationChannels = await channels();
+ * import { dismiss } from '@choochmeque/tauri-plugin-notifications-api';
+ * const { wasPending, wasActive } = await dismiss(42);
  * ```
  *
- * @returns A promise resolving to the list of notification channels.
+ * @returns Where the notification was found.
  */
-async function channels(): Promise<Channel[]> {
-  return await invoke("plugin:notifications|list_channels");
+async function dismiss(id: number): Promise<DismissOutcome> {
+  return await invoke("plugin:notifications|dismiss", { id });
 }
 
 /**
- * Registers a listener for incoming notifications.
+ * Cancels the scheduled and removes the shown notifications sent with the
+ * given `scope`, including ones scheduled in earlier sessions. Not supported
+ * with notify-rust on macOS and Windows.
  *
  * @example
  * ```typescript
- * import { onNotificationReceived } from '@choochmeque/tauri-plugin-notifications-api';
- * const unlisten = await onNotificationReceived((notification) => {
- *   console.log('Notification received:', notification);
- * });
- * // Later, to stop listening:
- * // unlisten();
+ * import { clearScope } from '@choochmeque/tauri-plugin-notifications-api';
+ * // On logout
+ * const { canceled, removed } = await clearScope(account.id);
  * ```
  *
- * @param cb - Callback function to handle received notifications.
- * @returns A promise resolving to a function that removes the listener.
+ * @returns How many notifications were canceled and removed.
  */
-async function onNotificationReceived(
-  cb: (notification: Options) => void,
-): Promise<PluginListener> {
-  return await addPluginListener("notifications", "notification", cb);
+async function clearScope(scope: string): Promise<ScopeClearance> {
+  return await invoke("plugin:notifications|clear_scope", { scope });
 }
 
 /**
- * Registers a listener for notification action events.
+ * Cancels all pending notifications.
  *
  * @example
  * ```typescript
- * import { onAction } from '@choochmeque/tauri-plugin-notifications-api';
- * const unlisten = await onAction((notification) => {
- *   console.log('Action performed on notification:', notification);
- * });
- * // Later, to stop listening:
- * // unlisten();
+ * import { cancelAll } from '@choochmeque/tauri-plugin-notifications-api';
+ * await cancelAll();
+ * ```
+ *
+ * @returns A promise indicating the success or failure of the operation.
+ */
+async function cancelAll(): Promise<void> {
+  await invoke("plugin:notifications|cancel_all");
+}
+
+/**
+ * Retrieves the list of active notifications.
+ *
+ * @example
+ * ```typescript
+ * import { active } from '@choochmeque/tauri-plugin-notifications-api';
+ * const activeNotifications = await active();
+ * ```
+ *
+ * @returns A promise resolving to the list of active notifications.
+ */
+async function active(): Promise<ActiveNotification[]> {
+  return await invoke("plugin:notifications|get_active");
+}
+
+/**
+ * Retrieves the active notifications split by group, ordered by group id
+ * with the ungrouped ones first. Each group's children are ordered by
+ * `sortKey` (keyed ones first, in plain string order), then newest first.
+ *
+ * @example
+ * ```typescript
+ * import { activeGrouped } from '@choochmeque/tauri-plugin-notifications-api';
+ * const groups = await activeGrouped();
+ * const chat = groups.find((g) => g.group === 'chat')?.children ?? [];
+ * ```
+ *
+ * @returns A promise resolving to the active notifications by group.
+ */
+async function activeGrouped(): Promise<ActiveGroup[]> {
+  return await invoke("plugin:notifications|get_active_grouped");
+}
+
+/**
+ * Shows the summary of `group` held back until the group's first child.
+ * A notification with `groupSummary` shown before any child of its group
+ * waits for that child (or a few seconds), since Android may not group
+ * children under a summary posted before them.
+ *
+ * @example
+ * ```typescript
+ * import { flushGroup } from '@choochmeque/tauri-plugin-notifications-api';
+ * // The sync finished without anything to show under its summary
+ * await flushGroup('sync');
+ * ```
+ */
+async function flushGroup(group: string): Promise<void> {
+  await invoke("plugin:notifications|flush_group", { group });
+}
+
+/**
+ * Retrieves every notification of the app still in the platform's
+ * notification list. On Windows this includes notifications the user
+ * already dismissed, which `active()` leaves out; elsewhere it is the same
+ * as `active()`.
+ *
+ * @example
+ * ```typescript
+ * import { notificationHistory } from '@choochmeque/tauri-plugin-notifications-api';
+ * const inbox = await notificationHistory();
+ * ```
+ *
+ * @returns A promise resolving to the list of notifications.
+ */
+async function notificationHistory(): Promise<ActiveNotification[]> {
+  return await invoke("plugin:notifications|get_notification_history");
+}
+
+/**
+ * The options this platform ignores among the ones set, e.g. `sound` with
+ * the notify-rust backend. Channel and config defaults are not applied.
+ *
+ * @example
+ * ```typescript
+ * import { ignoredFields } from '@choochmeque/tauri-plugin-notifications-api';
+ * const ignored = await ignoredFields({ title: 'Hi', largeIcon: 'avatar' });
+ * ```
+ *
+ * @returns The ignored option names.
+ */
+async function ignoredFields(options: Options): Promise<string[]> {
+  return await invoke("plugin:notifications|get_ignored_fields", { options });
+}
+
+/**
+ * The toast XML Windows would be handed for a notification with these
+ * options, to find out why it looks wrong. Only available in debug builds
+ * of Windows apps, and only with the `notifications:allow-get-toast-xml`
+ * permission.
+ *
+ * @example
+ * ```typescript
+ * import { getToastXml } from '@choochmeque/tauri-plugin-notifications-api';
+ * console.log(await getToastXml({ title: 'Build finished', actionTypeId: 'build' }));
+ * ```
+ *
+ * @returns The toast XML.
+ */
+async function getToastXml(options: Options): Promise<string> {
+  return await invoke("plugin:notifications|get_toast_xml", { options });
+}
+
+/**
+ * Runs the plugin's setup checks for the current platform and returns one
+ * diagnostic per problem, most severe first. When the webview's capabilities
+ * don't allow the check itself, a `missingCapability` diagnostic is returned
+ * instead of an error.
+ *
+ * @example
+ * ```typescript
+ * import { runDoctor } from '@choochmeque/tauri-plugin-notifications-api';
+ * const problems = await runDoctor();
+ * ```
+ *
+ * @returns A promise resolving to the diagnostics; empty when nothing was found.
+ */
+async function runDoctor(): Promise<Diagnostic[]> {
+  try {
+    return await invoke("plugin:notifications|run_doctor");
+  } catch (error) {
+    if (String(error).includes("not allowed")) {
+      return [
+        {
+          severity: "error",
+          code: "missingCapability",
+          message: `The notifications plugin commands are not allowed: ${String(error)}`,
+          fixHint:
+            'Add "notifications:default" to the permissions of a capability that covers this window',
+        },
+      ];
+    }
+    throw error;
+  }
+}
+
+/**
+ * Sums up the delivery and interaction counters. Requires `stats` in the
+ * plugin config.
+ *
+ * @example
+ * ```typescript
+ * import { notificationStats } from '@choochmeque/tauri-plugin-notifications-api';
+ * const week = new Date(Date.now() - 7 * 24 * 60 * 60 * 1000);
+ * const { delivered, clicked } = await notificationStats({ since: week });
+ * ```
+ *
+ * @returns A promise resolving to the counters over the range, or over
+ * everything recorded.
+ */
+async function notificationStats(range?: StatsRange): Promise<NotificationStats> {
+  return await invoke("plugin:notifications|get_notification_stats", {
+    range,
+  });
+}
+
+/**
+ * Clears the delivery and interaction counters. Requires `stats` in the
+ * plugin config.
+ *
+ * @example
+ * ```typescript
+ * import { resetNotificationStats } from '@choochmeque/tauri-plugin-notifications-api';
+ * await resetNotificationStats();
+ * ```
+ */
+async function resetNotificationStats(): Promise<void> {
+  await invoke("plugin:notifications|reset_stats");
+}
+
+/**
+ * Reports how scheduled notifications behave on the current platform, e.g.
+ * to tell users whether a reminder fires while their machine sleeps.
+ *
+ * @example
+ * ```typescript
+ * import { scheduleCapabilities } from '@choochmeque/tauri-plugin-notifications-api';
+ * const { deliveredAfterSleep } = await scheduleCapabilities();
+ * ```
+ *
+ * @returns A promise resolving to the platform's schedule capabilities.
+ */
+async function scheduleCapabilities(): Promise<ScheduleCapabilities> {
+  return await invoke("plugin:notifications|get_schedule_capabilities");
+}
+
+/**
+ * Reports the notification backend in use and the plugin version, e.g. for
+ * bug reports.
+ *
+ * @example
+ * ```typescript
+ * import { pluginInfo } from '@choochmeque/tauri-plugin-notifications-api';
+ * const { platform, version } = await pluginInfo();
+ * ```
+ *
+ * @returns A promise resolving to the plugin info.
+ */
+async function pluginInfo(): Promise<PluginInfo> {
+  return await invoke("plugin:notifications|get_plugin_info");
+}
+
+/**
+ * Reports whether the app was started by a notification, e.g. to pick the
+ * first route before any click listener runs. The same for the whole
+ * session, and it doesn't consume the click or action events.
+ *
+ * @example
+ * ```typescript
+ * import { launchSource } from '@choochmeque/tauri-plugin-notifications-api';
+ * const source = await launchSource();
+ * if (source.type === 'notificationTap') {
+ *   // open the notification's screen
+ * }
+ * ```
+ *
+ * @returns A promise resolving to how the app was started.
+ */
+async function launchSource(): Promise<LaunchSource> {
+  return await invoke("plugin:notifications|get_launch_source");
+}
+
+/**
+ * Removes the active notifications with the given list of identifiers.
+ *
+ * @example
+ * ```typescript
+ * import { removeActive } from '@choochmeque/tauri-plugin-notifications-api';
+ * await removeActive([{ id: 1 }, { id: 2, tag: 'news' }]);
+ * ```
+ *
+ * @returns A promise indicating the success or failure of the operation.
+ */
+async function removeActive(
+  notifications: Array<{ id: number; tag?: string }>,
+): Promise<void> {
+  await invoke("plugin:notifications|remove_active", { notifications });
+}
+
+/**
+ * Removes the active notifications with the given identifiers, like
+ * `removeActive`, and reports the outcome of each one. Only Windows and
+ * Linux report them one by one; elsewhere they succeed or fail together.
+ *
+ * @example
+ * ```typescript
+ * import { removeActiveDetailed } from '@choochmeque/tauri-plugin-notifications-api';
+ * const { failed } = await removeActiveDetailed([1, 2]);
+ * ```
+ *
+ * @returns The outcome of each identifier.
+ */
+async function removeActiveDetailed(
+  notifications: number[],
+): Promise<BulkResult> {
+  return await invoke("plugin:notifications|remove_active_detailed", {
+    notifications,
+  });
+}
+
+/**
+ * Removes all active notifications.
+ *
+ * @example
+ * ```typescript
+ * import { removeAllActive } from '@choochmeque/tauri-plugin-notifications-api';
+ * await removeAllActive()
+ * ```
+ *
+ * @returns A promise indicating the success or failure of the operation.
+ */
+async function removeAllActive(): Promise<void> {
+  await invoke("plugin:notifications|remove_all_active");
+}
+
+/**
+ * Changes the title, body, progress or `extra` of the shown notification
+ * `id` without alerting the user again where the platform allows it.
+ * Rejects if the notification isn't shown.
+ *
+ * @example
+ * ```typescript
+ * import { patchActive } from '@choochmeque/tauri-plugin-notifications-api';
+ * const strategy = await patchActive(7, { body: 'Uploaded 3 of 4 files', progress: 0.75 });
+ * ```
+ *
+ * @returns How the notification was changed.
+ */
+async function patchActive(
+  id: number,
+  patch: ActivePatch,
+): Promise<PatchStrategy> {
+  return await invoke("plugin:notifications|patch_active", { id, patch });
+}
+
+/**
+ * Creates a notification channel.
+ *
+ * @example
+ * ```typescript
+ * import { createChannel, Importance, Visibility } from '@choochmeque/tauri-plugin-notifications-api';
+ * await createChannel({
+ *   id: 'new-messages',
+ *   name: 'New Messages',
+ *   lights: true,
+ *   vibration: true,
+ *   importance: Importance.Default,
+ *   visibility: Visibility.Private
+ * });
+ * ```
+ *
+ * Creating a channel that already exists does nothing; the result tells
+ * whether it was created or existed with the same or other settings.
+ *
+ * @returns A promise resolving to what happened to the channel.
+ */
+async function createChannel(channel: Channel): Promise<ChannelCreation> {
+  return await invoke("plugin:notifications|create_channel", { channel });
+}
+
+/**
+ * Creates every channel that doesn't exist yet, in one call to the platform.
+ * Meant to run on every app start.
+ *
+ * @example
+ * ```typescript
+ * import { ensureChannels, Importance } from '@choochmeque/tauri-plugin-notifications-api';
+ * await ensureChannels([
+ *   { id: 'messages', name: 'Messages', importance: Importance.High },
+ *   { id: 'downloads', name: 'Downloads', importance: Importance.Low }
+ * ]);
+ * ```
+ *
+ * @returns A promise resolving to what happened to each channel, in order.
+ */
+async function ensureChannels(
+  channels: Channel[],
+): Promise<ChannelCreation[]> {
+  return await invoke("plugin:notifications|ensure_channels", { channels });
+}
+
+/**
+ * Retrieves the channel with the given identifier.
+ *
+ * @example
+ * ```typescript
+ * import { getChannel } from '@choochmeque/tauri-plugin-notifications-api';
+ * const channel = await getChannel('new-messages');
+ * ```
+ *
+ * @returns A promise resolving to the channel, or `null` if it doesn't exist.
+ */
+async function getChannel(id: string): Promise<Channel | null> {
+  return await invoke("plugin:notifications|get_channel", { id });
+}
+
+/**
+ * Removes the channel with the given identifier.
+ *
+ * @example
+ * ```typescript
+ * import { removeChannel } from '@choochmeque/tauri-plugin-notifications-api';
+ * await removeChannel('new-messages');
+ * await removeChannel('promotions', { policy: 'reassign', toChannel: 'general' });
+ * ```
+ *
+ * @param onPending What to do with the channel's pending notifications. Defaults to `leave`.
+ * @returns A promise resolving to the pending notifications that were canceled or reassigned.
+ */
+async function removeChannel(
+  id: string,
+  onPending?: OnPendingPolicy,
+): Promise<ChannelDeletion> {
+  return await invoke("plugin:notifications|delete_channel", {
+    id,
+    onPending,
+  });
+}
+
+/**
+ * Retrieves the list of notification channels.
+ *
+ * @example
+ * ```typescript
+ * import { channels } from '@choochmeque/tauri-plugin-notifications-api';
+ * const notificationChannels = await channels();
+ * ```
+ *
+ * @returns A promise resolving to the list of notification channels.
+ */
+async function channels(): Promise<Channel[]> {
+  return await invoke("plugin:notifications|list_channels");
+}
+
+/**
+ * Retrieves the defaults bound to a channel with the Rust
+ * `bind_channel_defaults` API, if any.
+ *
+ * @example
+ * ```typescript
+ * import { getChannelDefaults } from '@choochmeque/tauri-plugin-notifications-api';
+ * const defaults = await getChannelDefaults('downloads');
+ * ```
+ *
+ * @returns A promise resolving to the channel's defaults, or `null`.
+ */
+async function getChannelDefaults(
+  channelId: string,
+): Promise<Partial<Options> | null> {
+  return await invoke("plugin:notifications|get_channel_defaults", {
+    channelId,
+  });
+}
+
+/**
+ * Removes the defaults bound to a channel.
+ *
+ * @example
+ * ```typescript
+ * import { clearChannelDefaults } from '@choochmeque/tauri-plugin-notifications-api';
+ * await clearChannelDefaults('downloads');
+ * ```
+ *
+ * @returns A promise resolving to whether any defaults were bound.
+ */
+async function clearChannelDefaults(channelId: string): Promise<boolean> {
+  return await invoke("plugin:notifications|clear_channel_defaults", {
+    channelId,
+  });
+}
+
+/**
+ * Native plugins may report `extra` as the JSON string the Rust side sent
+ * them (`extraJson`). Turns it back into the `extra` object, on the payload
+ * itself and on a nested `notification`.
+ */
+function withParsedExtra<T>(payload: T): T {
+  if (payload === null || typeof payload !== "object") {
+    return payload;
+  }
+  const { extraJson, ...rest } = payload as T & { extraJson?: unknown };
+  const parsed: Record<string, unknown> = rest;
+  if (typeof extraJson === "string" && extraJson.length > 0) {
+    try {
+      parsed.extra = JSON.parse(extraJson);
+    } catch {
+      parsed.extra = {};
+    }
+  }
+  if (parsed.notification && typeof parsed.notification === "object") {
+    parsed.notification = withParsedExtra(parsed.notification);
+  }
+  return parsed as T;
+}
+
+/**
+ * Registers a listener for incoming notifications.
+ *
+ * @example
+ * ```typescript
+ * import { onNotificationReceived } from '@choochmeque/tauri-plugin-notifications-api';
+ * const unlisten = await onNotificationReceived((notification) => {
+ *   console.log('Notification received:', notification);
+ * });
+ * // Later, to stop listening:
+ * // unlisten();
+ * ```
+ *
+ * @param cb - Callback function to handle received notifications.
+ * @returns A promise resolving to a function that removes the listener.
+ */
+async function onNotificationReceived(
+  cb: (notification: Options) => void,
+): Promise<PluginListener> {
+  return await addPluginListener(
+    "notifications",
+    "notification",
+    (notification: Options) => cb(withParsedExtra(notification)),
+  );
+}
+
+/**
+ * Registers a listener called right before a scheduled notification fires.
+ * Returning options (or a promise of them) replaces what the notification
+ * shows this time; `id` and `schedule` are kept. The notification waits
+ * 200 ms for the callback and then shows as scheduled. Only called while the
+ * app is running. Android only; the event never fires elsewhere.
+ *
+ * @example
+ * ```typescript
+ * import { onScheduledNotificationTriggered } from '@choochmeque/tauri-plugin-notifications-api';
+ * const unlisten = await onScheduledNotificationTriggered((notification) => ({
+ *   ...notification,
+ *   body: `${unreadCount()} unread messages`,
+ * }));
+ * ```
+ *
+ * @param cb - Callback returning the replacement, or nothing to keep it.
+ * @returns A promise resolving to a function that removes the listener.
+ */
+async function onScheduledNotificationTriggered(
+  cb: (
+    notification: Options,
+  ) => Options | undefined | Promise<Options | undefined>,
+): Promise<PluginListener> {
+  return await addPluginListener(
+    "notifications",
+    "scheduledNotificationTriggered",
+    async (notification: Options) => {
+      const replacement = await cb(withParsedExtra(notification));
+      if (replacement) {
+        await invoke("plugin:notifications|update_scheduled_notification", {
+          id: notification.id,
+          data: replacement,
+        });
+      }
+    },
+  );
+}
+
+/**
+ * Registers a listener for notification action events.
+ *
+ * @example
+ * ```typescript
+ * import { onAction } from '@choochmeque/tauri-plugin-notifications-api';
+ * const unlisten = await onAction((notification) => {
+ *   console.log('Action performed on notification:', notification);
+ * });
+ * // Later, to stop listening:
+ * // unlisten();
  * ```
  *
  * @param cb - Callback function to handle notification actions.
@@ -824,7 +1807,11 @@ async function onNotificationReceived(
 async function onAction(
   cb: (notification: Options) => void,
 ): Promise<PluginListener> {
-  return await addPluginListener("notifications", "actionPerformed", cb);
+  return await addPluginListener(
+    "notifications",
+    "actionPerformed",
+    (notification: Options) => cb(withParsedExtra(notification)),
+  );
 }
 
 /**
@@ -882,42 +1869,390 @@ async function onNotificationClicked(
   } as PluginListener;
 }
 
+/**
+ * What a failed Windows call ran into, classified from its HRESULT.
+ */
+type WindowsErrorKind =
+  | "platformUnavailable"
+  | "notPosted"
+  | "busy"
+  | "disabled"
+  | "accessDenied"
+  | "notFound"
+  | "tooLarge"
+  | "invalidArgument"
+  | "other";
+
+/**
+ * Data received when a notification failed with a transient error and was
+ * queued for a background retry.
+ */
+interface NotificationQueuedData {
+  /** Notification ID */
+  id: number;
+  /** OS error code of the failed attempt (an HRESULT such as `0x803E0105` on Windows) */
+  code: string;
+  /** Total number of delivery attempts, including the one that failed */
+  attempts: number;
+}
+
+/**
+ * Registers a listener for notifications queued for a background retry.
+ *
+ * On Windows, `sendNotification` still resolves when a toast fails with a
+ * transient error; this fires before it does, so the app can tell the toast
+ * was queued rather than shown. A `notification` or `notificationFailed`
+ * event follows once the retries settle. Other platforms currently never
+ * emit it.
+ *
+ * @example
+ * ```typescript
+ * import { onNotificationQueued } from '@choochmeque/tauri-plugin-notifications-api';
+ * const unlisten = await onNotificationQueued((data) => {
+ *   console.warn(`Notification ${data.id} queued after ${data.code}`);
+ * });
+ * ```
+ *
+ * @param cb - Callback function to handle queued notifications.
+ * @returns A promise resolving to a function that removes the listener.
+ */
+async function onNotificationQueued(
+  cb: (data: NotificationQueuedData) => void,
+): Promise<PluginListener> {
+  return await addPluginListener("notifications", "notificationQueued", cb);
+}
+
+/**
+ * Data received when a notification could not be delivered.
+ */
+interface NotificationFailedData {
+  /** Notification ID */
+  id: number;
+  /** Error message reported by the OS */
+  error: string;
+  /** OS error code (an HRESULT such as `0x803E0105` on Windows) */
+  code?: string;
+  /** The HRESULT as a signed number (Windows) */
+  hresult?: number;
+  /** What the error means (Windows) */
+  kind?: WindowsErrorKind;
+}
+
+/**
+ * Registers a listener for notifications that could not be delivered.
+ *
+ * On Windows, toasts that fail with a transient error (for example while the
+ * notification platform is still starting after login) are retried in the
+ * background according to the `retry` plugin config. This fires once the
+ * last attempt has failed. Other platforms currently never emit it.
+ *
+ * @example
+ * ```typescript
+ * import { onNotificationFailed } from '@choochmeque/tauri-plugin-notifications-api';
+ * const unlisten = await onNotificationFailed((data) => {
+ *   console.error(`Notification ${data.id} failed: ${data.error}`);
+ * });
+ * ```
+ *
+ * @param cb - Callback function to handle delivery failures.
+ * @returns A promise resolving to a function that removes the listener.
+ */
+async function onNotificationFailed(
+  cb: (data: NotificationFailedData) => void,
+): Promise<PluginListener> {
+  return await addPluginListener("notifications", "notificationFailed", cb);
+}
+
+/**
+ * Data received when notifications are coalesced into a digest.
+ */
+interface NotificationsCoalescedData {
+  /** ID of the digest notification shown in their place */
+  digestId: number;
+  /** The group (or channel, with `groupBy: "channel"`) they belong to */
+  group?: string | null;
+  /** Every notification the digest stands for, oldest first */
+  ids: number[];
+}
+
+/**
+ * Registers a listener for notifications coalesced into a digest by the
+ * `digest` plugin config. Fires when a group switches to a digest, which
+ * removes the notifications shown before it, and every time the digest
+ * takes in another one.
+ *
+ * @example
+ * ```typescript
+ * import { onNotificationsCoalesced } from '@choochmeque/tauri-plugin-notifications-api';
+ * const unlisten = await onNotificationsCoalesced(({ digestId, ids }) => {
+ *   console.log(`${ids.length} notifications shown as digest ${digestId}`);
+ * });
+ * ```
+ *
+ * @param cb - Callback function to handle coalesced notifications.
+ * @returns A promise resolving to a function that removes the listener.
+ */
+async function onNotificationsCoalesced(
+  cb: (data: NotificationsCoalescedData) => void,
+): Promise<PluginListener> {
+  return await addPluginListener("notifications", "notificationsCoalesced", cb);
+}
+
+/**
+ * Data received for a notification that wasn't shown because its window
+ * was focused.
+ */
+interface NotificationFallbackData {
+  /** Why it wasn't shown; currently always `windowFocused`. */
+  reason: "windowFocused";
+  /** Label of the focused window. */
+  window: string;
+  /** The notification, as it was passed to `sendNotification`. */
+  notification: Options;
+}
+
+/**
+ * Registers a listener for notifications kept from the OS by
+ * `suppressIfFocused`, to show them in the app instead.
+ *
+ * @example
+ * ```typescript
+ * import { onNotificationFallback } from '@choochmeque/tauri-plugin-notifications-api';
+ * const unlisten = await onNotificationFallback(({ notification }) => {
+ *   showInAppBanner(notification.title, notification.body);
+ * });
+ * ```
+ *
+ * @param cb - Callback function to handle suppressed notifications.
+ * @returns A promise resolving to a function that removes the listener.
+ */
+async function onNotificationFallback(
+  cb: (data: NotificationFallbackData) => void,
+): Promise<PluginListener> {
+  return await addPluginListener("notifications", "notificationFallback", cb);
+}
+
+/**
+ * Data received for a notification shown without some of its options.
+ */
+interface NotificationWarningData {
+  id: number;
+  /** Why the warning was emitted; currently always `ignoredFields`. */
+  reason: "ignoredFields";
+  /** The options this platform ignored. */
+  fields: string[];
+}
+
+/**
+ * Registers a listener for notifications shown without options this
+ * platform ignores. Only emitted with `warnOnIgnoredFields` set in the
+ * plugin config.
+ *
+ * @example
+ * ```typescript
+ * import { onNotificationWarning } from '@choochmeque/tauri-plugin-notifications-api';
+ * const unlisten = await onNotificationWarning(({ id, fields }) => {
+ *   console.warn(`Notification ${id} ignores ${fields.join(', ')}`);
+ * });
+ * ```
+ *
+ * @param cb - Callback function to handle warnings.
+ * @returns A promise resolving to a function that removes the listener.
+ */
+async function onNotificationWarning(
+  cb: (data: NotificationWarningData) => void,
+): Promise<PluginListener> {
+  return await addPluginListener("notifications", "notificationWarning", cb);
+}
+
+/**
+ * Data received when the user blocks a notification channel.
+ */
+interface ChannelBlockedData {
+  /** Identifier of the blocked channel. */
+  channelId: string;
+  /** The channel's new importance, `Importance.None`. */
+  importance: Importance;
+}
+
+/**
+ * Registers a listener for notification channels the user blocks in the
+ * system settings. Android 8+ only; the change is reported when the app
+ * resumes. Rejects on other platforms.
+ *
+ * @example
+ * ```typescript
+ * import { onChannelBlocked } from '@choochmeque/tauri-plugin-notifications-api';
+ * const unlisten = await onChannelBlocked(({ channelId }) => {
+ *   console.warn(`Channel ${channelId} was blocked`);
+ * });
+ * ```
+ *
+ * @param cb - Callback function to handle blocked channels.
+ * @returns A promise resolving to a function that removes the listener.
+ */
+async function onChannelBlocked(
+  cb: (data: ChannelBlockedData) => void,
+): Promise<PluginListener> {
+  const listener = await addPluginListener(
+    "notifications",
+    "channelBlocked",
+    cb,
+  );
+  try {
+    await invoke("plugin:notifications|register_channel_blocked_listener");
+  } catch (error) {
+    await listener.unregister();
+    throw error;
+  }
+  return listener;
+}
+
+/**
+ * Fires `notificationClicked` (and a `"tap"` action) for an active
+ * notification as if the user had clicked it. Desktop only; requires the
+ * plugin's `test-utils` feature and the `notifications:allow-simulate-click`
+ * permission.
+ *
+ * @example
+ * ```typescript
+ * import { simulateClick } from '@choochmeque/tauri-plugin-notifications-api';
+ * await simulateClick(42);
+ * ```
+ *
+ * @returns A promise indicating the success or failure of the operation.
+ */
+async function simulateClick(id: number): Promise<void> {
+  await invoke("plugin:notifications|simulate_click", { id });
+}
+
+/**
+ * Fires `actionPerformed` for an active notification as if the user had
+ * pressed the given action button. Desktop only; requires the plugin's
+ * `test-utils` feature and the `notifications:allow-simulate-action`
+ * permission.
+ *
+ * @example
+ * ```typescript
+ * import { simulateAction } from '@choochmeque/tauri-plugin-notifications-api';
+ * await simulateAction(42, 'reply', 'On my way');
+ * ```
+ *
+ * @returns A promise indicating the success or failure of the operation.
+ */
+async function simulateAction(
+  id: number,
+  actionId: string,
+  inputValue?: string,
+): Promise<void> {
+  await invoke("plugin:notifications|simulate_action", {
+    id,
+    actionId,
+    inputValue,
+  });
+}
+
 export type {
   Attachment,
+  MediaAction,
+  MediaControls,
   Options,
+  QuietHoursPolicy,
   Action,
   ActionType,
+  ActionTypesUpdate,
   PendingNotification,
   ActiveNotification,
+  ActiveGroup,
   Channel,
+  ChannelCreation,
+  OnPendingPolicy,
+  ChannelDeletion,
+  DismissOutcome,
+  ScopeClearance,
+  BulkResult,
+  ActivePatch,
+  PatchStrategy,
+  LaunchSource,
+  Diagnostic,
+  ScheduleCapabilities,
+  PluginInfo,
+  NotificationStats,
+  StatsRange,
   ScheduleInterval,
   NotificationClickedData,
+  NotificationFailedData,
+  NotificationQueuedData,
+  WindowsErrorKind,
+  NotificationsCoalescedData,
+  NotificationFallbackData,
+  NotificationWarningData,
+  ChannelBlockedData,
 };
 
 export {
   Importance,
   Visibility,
   sendNotification,
+  sendNotifications,
+  sendNotificationsDetailed,
   requestPermission,
   isPermissionGranted,
+  isDoNotDisturbActive,
+  getUnreadCount,
   registerForPushNotifications,
+  getPushTokenCached,
   unregisterForPushNotifications,
   listDistributors,
   setDistributor,
   setToken,
   registerActionTypes,
+  setNotificationCategories,
+  unregisterActionType,
+  clearActionTypes,
   pending,
+  pendingCountByChannel,
   cancel,
+  cancelDetailed,
+  cancelByGroup,
+  dismiss,
+  clearScope,
   cancelAll,
   active,
+  activeGrouped,
+  flushGroup,
+  notificationHistory,
+  ignoredFields,
+  runDoctor,
+  scheduleCapabilities,
+  pluginInfo,
+  launchSource,
+  notificationStats,
+  resetNotificationStats,
+  getToastXml,
   removeActive,
+  removeActiveDetailed,
   removeAllActive,
+  patchActive,
   createChannel,
+  ensureChannels,
+  getChannel,
   removeChannel,
   channels,
+  getChannelDefaults,
+  clearChannelDefaults,
   onNotificationReceived,
+  onScheduledNotificationTriggered,
   onAction,
   onNotificationClicked,
+  onNotificationFailed,
+  onNotificationQueued,
+  onNotificationsCoalesced,
+  onNotificationFallback,
+  onNotificationWarning,
+  onChannelBlocked,
+  simulateClick,
+  simulateAction,
   Schedule,
   ScheduleEvery,
 };
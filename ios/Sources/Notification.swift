@@ -1,4 +1,5 @@
 import Tauri
+import UniformTypeIdentifiers
 import UserNotifications
 
 enum NotificationError: LocalizedError {
@@ -24,6 +25,14 @@ enum NotificationError: LocalizedError {
   }
 }
 
+/// `userInfo` key holding the notification's scope. Kept out of the `data`
+/// reported to JS.
+let scopeUserInfoKey = "tauri.scope"
+/// `userInfo` key holding the sort key, reported by `getActive`.
+let sortKeyUserInfoKey = "tauri.sortKey"
+/// Keys the plugin sets for itself, never reported as `data`.
+let reservedUserInfoKeys: Set<String> = [scopeUserInfoKey, sortKeyUserInfoKey]
+
 func makeNotificationContent(_ notification: Notification) throws -> UNNotificationContent {
   let content = UNMutableNotificationContent()
   content.title = NSString.localizedUserNotificationString(
@@ -44,6 +53,22 @@ func makeNotificationContent(_ notification: Notification) throws -> UNNotificat
       userInfo[key] = value
     }
   }
+  if let extraJson = notification.extraJson,
+    let data = extraJson.data(using: .utf8),
+    let extra = try? JSONSerialization.jsonObject(with: data) as? [String: Any]
+  {
+    // `userInfo` must stay property-list compatible, which rules out NSNull.
+    for (key, value) in extra where !(value is NSNull) {
+      userInfo[key] = value
+    }
+  }
+
+  if let scope = notification.scope {
+    userInfo[scopeUserInfoKey] = scope
+  }
+  if let sortKey = notification.sortKey {
+    userInfo[sortKeyUserInfoKey] = sortKey
+  }
 
   content.userInfo = userInfo
 
@@ -55,11 +80,15 @@ func makeNotificationContent(_ notification: Notification) throws -> UNNotificat
     content.threadIdentifier = threadIdentifier
   }
 
-  if let summaryArgument = notification.summary {
-    content.summaryArgument = summaryArgument
+  // Shown as the subtitle, and in the summary of a collapsed group.
+  if let summary = notification.summary {
+    content.subtitle = summary
+    content.summaryArgument = summary
   }
 
-  if let sound = notification.sound {
+  if notification.quiet ?? false {
+    content.interruptionLevel = .passive
+  } else if let sound = notification.sound {
     content.sound = UNNotificationSound(named: UNNotificationSoundName(sound))
   }
 
@@ -79,7 +108,14 @@ func makeAttachments(_ attachments: [NotificationAttachment]) throws -> [UNNotif
       throw NotificationError.attachmentFileNotFound(path: attachment.url)
     }
 
-    let options = attachment.options != nil ? makeAttachmentOptions(attachment.options!) : nil
+    var options = attachment.options != nil ? makeAttachmentOptions(attachment.options!) : nil
+    // An explicit `iosUNNotificationAttachmentOptionsTypeHintKey` wins.
+    if let hint = attachment.typeHint, let identifier = attachmentTypeIdentifier(hint),
+      options?[UNNotificationAttachmentOptionsTypeHintKey] == nil
+    {
+      options = options ?? [:]
+      options?[UNNotificationAttachmentOptionsTypeHintKey] = identifier
+    }
 
     do {
       let newAttachment = try UNNotificationAttachment(
@@ -93,6 +129,21 @@ func makeAttachments(_ attachments: [NotificationAttachment]) throws -> [UNNotif
   return createdAttachments
 }
 
+/// Maps an `Attachment.typeHint` to the UTI expected by
+/// `UNNotificationAttachmentOptionsTypeHintKey`.
+func attachmentTypeIdentifier(_ hint: String) -> String? {
+  switch hint {
+  case "image":
+    return UTType.image.identifier
+  case "video":
+    return UTType.video.identifier
+  case "audio":
+    return UTType.audio.identifier
+  default:
+    return nil
+  }
+}
+
 func makeAttachmentUrl(_ path: String) -> URL? {
   return URL(string: path)
 }
@@ -9,10 +9,61 @@ public class NotificationHandler: NSObject, NotificationHandlerProtocol {
   private var hasClickedListener = false
   private var pendingNotificationClick: NotificationClickedData? = nil
 
+  // Interactions kept for the Rust interaction stats, which can't listen to
+  // them from the start of a cold launch. Recorded for every response, as
+  // `notificationsMap` doesn't survive the app being killed.
+  private var interactions: [Interaction] = []
+  private let interactionsLock = NSLock()
+  private let maxInteractions = 256
+
+  // The response that launched the app arrives before it first becomes
+  // active; responses after that don't change the launch source.
+  private(set) var launchSource = LaunchSource(type: "normal")
+  private var launchSettled = false
+
   internal func saveNotification(_ key: String, _ notification: Notification) {
     notificationsMap.updateValue(notification, forKey: key)
   }
 
+  func takeInteractions() -> [Interaction] {
+    interactionsLock.lock()
+    defer { interactionsLock.unlock() }
+    let taken = interactions
+    interactions = []
+    return taken
+  }
+
+  private func recordInteraction(_ interaction: Interaction) {
+    interactionsLock.lock()
+    defer { interactionsLock.unlock() }
+    if interactions.count == maxInteractions {
+      interactions.removeFirst()
+    }
+    interactions.append(interaction)
+  }
+
+  func settleLaunch() {
+    launchSettled = true
+  }
+
+  private func recordLaunch(_ response: UNNotificationResponse, actionId: String) {
+    // Dismissing a notification doesn't bring the app up.
+    if launchSettled || actionId == "dismiss" {
+      return
+    }
+    launchSettled = true
+    let request = response.notification.request
+    if request.trigger?.isKind(of: UNPushNotificationTrigger.self) == true {
+      launchSource = LaunchSource(
+        type: "push", messageId: request.content.userInfo["gcm.message_id"] as? String)
+    } else if actionId == "tap" {
+      launchSource = LaunchSource(type: "notificationTap", id: Int(request.identifier))
+    } else {
+      launchSource = LaunchSource(
+        type: "notificationAction", id: Int(request.identifier), actionId: actionId)
+    }
+  }
+
   func setClickListenerActive(_ active: Bool) {
     hasClickedListener = active
 
@@ -59,6 +110,9 @@ public class NotificationHandler: NSObject, NotificationHandlerProtocol {
       if options.silent ?? false {
         return UNNotificationPresentationOptions.init(rawValue: 0)
       }
+      if options.quiet ?? false {
+        return [.badge, .list]
+      }
     }
 
     return [
@@ -76,7 +130,7 @@ public class NotificationHandler: NSObject, NotificationHandlerProtocol {
     if !content.userInfo.isEmpty {
       extra = [:]
       for (key, value) in content.userInfo {
-        if let keyStr = key as? String, let valStr = value as? String {
+        if let keyStr = key as? String, !reservedUserInfoKeys.contains(keyStr), let valStr = value as? String {
           extra?[keyStr] = valStr
         }
       }
@@ -107,12 +161,20 @@ public class NotificationHandler: NSObject, NotificationHandlerProtocol {
       actionIdValue = actionId
     }
 
+    recordLaunch(response, actionId: actionIdValue)
+
     var inputValue: String? = nil
     // If the type of action was for an input type, get the value
     if let inputType = response as? UNTextInputNotificationResponse {
       inputValue = inputType.userText
     }
 
+    recordInteraction(
+      Interaction(
+        actionId: actionIdValue,
+        notification: InteractionNotification(
+          id: Int(originalNotificationRequest.identifier) ?? -1)))
+
     // Only trigger actionPerformed for local notifications (those in our map)
     if let activeNotification = toActiveNotification(originalNotificationRequest) {
       try? self.plugin?.trigger(
@@ -131,7 +193,7 @@ public class NotificationHandler: NSObject, NotificationHandlerProtocol {
     if !userInfo.isEmpty {
       dataDict = [:]
       for (key, value) in userInfo {
-        if let keyStr = key as? String, let valStr = value as? String {
+        if let keyStr = key as? String, !reservedUserInfoKeys.contains(keyStr), let valStr = value as? String {
           dataDict?[keyStr] = valStr
         }
       }
@@ -161,7 +223,8 @@ public class NotificationHandler: NSObject, NotificationHandlerProtocol {
       body: request.content.body,
       sound: notificationRequest.sound ?? "",
       actionTypeId: request.content.categoryIdentifier,
-      attachments: notificationRequest.attachments
+      attachments: notificationRequest.attachments,
+      extraJson: notificationRequest.extraJson
     )
   }
 
@@ -174,7 +237,9 @@ public class NotificationHandler: NSObject, NotificationHandlerProtocol {
       id: Int(request.identifier) ?? -1,
       title: request.content.title,
       body: request.content.body,
-      schedule: schedule
+      schedule: schedule,
+      channelId: request.content.threadIdentifier.isEmpty
+        ? nil : request.content.threadIdentifier
     )
   }
 }
@@ -184,6 +249,7 @@ struct PendingNotification: Encodable {
   let title: String
   let body: String
   let schedule: NotificationSchedule
+  var channelId: String? = nil
 }
 
 struct ActiveNotification: Encodable {
@@ -194,6 +260,10 @@ struct ActiveNotification: Encodable {
   let actionTypeId: String
   let attachments: [NotificationAttachment]?
   var source: String = "local"
+  var extraJson: String? = nil
+  var sortKey: String? = nil
+  /// When the notification was delivered, in milliseconds since the epoch.
+  var deliveredAt: Int64? = nil
 }
 
 struct ReceivedNotification: Encodable {
@@ -202,6 +272,27 @@ struct ReceivedNotification: Encodable {
   let notification: ActiveNotification
 }
 
+/// `actionPerformed` payload with just what the interaction stats need.
+struct Interaction: Encodable {
+  let actionId: String
+  let notification: InteractionNotification
+}
+
+struct InteractionNotification: Encodable {
+  let id: Int
+}
+
+struct LaunchSource: Encodable {
+  let type: String
+  var id: Int? = nil
+  var actionId: String? = nil
+  var messageId: String? = nil
+}
+
+struct Interactions: Encodable {
+  let interactions: [Interaction]
+}
+
 struct NotificationClickedData: Encodable {
   let id: Int
   let data: [String: String]?
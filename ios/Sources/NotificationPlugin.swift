@@ -1,3 +1,4 @@
+import Intents
 import SwiftRs
 import Tauri
 import UIKit
@@ -56,6 +57,8 @@ struct NotificationAttachment: Codable {
   let id: String
   let url: String
   let options: NotificationAttachmentOptions?
+  /// `"image"`, `"video"` or `"audio"`; see `attachmentTypeIdentifier`.
+  var typeHint: String? = nil
 }
 
 struct Notification: Decodable {
@@ -70,6 +73,24 @@ struct Notification: Decodable {
   var actionTypeId: String?
   var summary: String?
   var silent: Bool?
+  /// `extra` encoded as one JSON string by the Rust side, so nested values
+  /// survive (`extra` above only holds flat string pairs).
+  var extraJson: String? = nil
+  /// Account or profile the notification belongs to, see `clearScope`.
+  var scope: String?
+  /// Reported back by `getActive`; the system still lists the newest first.
+  var sortKey: String?
+  /// Deliver without sound or banner, set during quiet hours.
+  var quiet: Bool?
+  /// Text VoiceOver reads for an `announceOnly` notification.
+  var ticker: String?
+  /// Post a VoiceOver announcement instead of delivering a notification.
+  var announceOnly: Bool?
+
+  /// The announced text: the ticker, or the title followed by the body.
+  var announcementText: String {
+    ticker ?? [title, body ?? ""].filter { !$0.isEmpty }.joined(separator: ". ")
+  }
 }
 
 struct RemoveActiveNotification: Decodable {
@@ -119,6 +140,20 @@ struct CancelArgs: Decodable {
   let notifications: [Int]
 }
 
+struct DismissArgs: Decodable {
+  let id: Int
+}
+
+struct ClearScopeArgs: Decodable {
+  let scope: String
+}
+
+struct NotificationsCoalesced: Codable {
+  let digestId: Int
+  let group: String?
+  let ids: [Int]
+}
+
 struct Action: Decodable {
   let id: String
   let title: String
@@ -153,6 +188,21 @@ struct SetClickListenerActiveArgs: Decodable {
   let active: Bool
 }
 
+/// Whether a Focus is on. Reading the Focus status needs the Communication
+/// Notifications capability and the user's consent; without them this falls
+/// back to whether alerts are turned off for the app.
+func doNotDisturbActive() async -> Bool {
+  let focus = INFocusStatusCenter.default
+  if focus.authorizationStatus == .authorized, let isFocused = focus.focusStatus.isFocused {
+    return isFocused
+  }
+  let settings = await UNUserNotificationCenter.current().notificationSettings()
+  return settings.alertSetting == .disabled
+}
+
+/// `UserDefaults` key of the last device token, read by `getPushTokenCached`.
+let pushTokenDefaultsKey = "tauri-plugin-notifications.pushToken"
+
 class NotificationPlugin: Plugin {
   let notificationHandler = NotificationHandler()
   let notificationManager = NotificationManager()
@@ -173,6 +223,13 @@ class NotificationPlugin: Plugin {
   public override func load(webview: WKWebView) {
     super.load(webview: webview)
 
+    // Let a launching response delivered alongside the activation land first.
+    NotificationCenter.default.addObserver(
+      forName: UIApplication.didBecomeActiveNotification, object: nil, queue: .main
+    ) { [weak self] _ in
+      DispatchQueue.main.async { self?.notificationHandler.settleLaunch() }
+    }
+
     #if ENABLE_PUSH_NOTIFICATIONS
       // Store reference to this plugin for event triggering
       AppDelegateSwizzler.plugin = self
@@ -185,6 +242,15 @@ class NotificationPlugin: Plugin {
   @objc public func show(_ invoke: Invoke) throws {
     let notification = try invoke.parseArgs(Notification.self)
 
+    if notification.announceOnly == true {
+      let text = notification.announcementText
+      DispatchQueue.main.async {
+        UIAccessibility.post(notification: .announcement, argument: text)
+      }
+      invoke.resolve(notification.id)
+      return
+    }
+
     let request = try showNotification(invoke: invoke, notification: notification)
     notificationHandler.saveNotification(request.identifier, notification)
     invoke.resolve(Int(request.identifier) ?? -1)
@@ -238,10 +304,36 @@ class NotificationPlugin: Plugin {
     #endif
   }
 
+  @objc public func getPushTokenCached(_ invoke: Invoke) {
+    if let token = UserDefaults.standard.string(forKey: pushTokenDefaultsKey) {
+      invoke.resolve(["token": token])
+    } else {
+      invoke.resolve([:])
+    }
+  }
+
+  /// Setup checks only the native side can run, reported by `doctor()`.
+  @objc public func doctor(_ invoke: Invoke) {
+    var checks: [String: Bool] = [:]
+    #if ENABLE_PUSH_NOTIFICATIONS
+      // Entitlements aren't readable at runtime on iOS; the provisioning
+      // profile embedded at signing time lists them. Simulator builds have
+      // none, so the check is skipped there.
+      if let path = Bundle.main.path(forResource: "embedded", ofType: "mobileprovision"),
+        let data = FileManager.default.contents(atPath: path),
+        let profile = String(data: data, encoding: .isoLatin1)
+      {
+        checks["pushEntitlement"] = profile.contains("<key>aps-environment</key>")
+      }
+    #endif
+    invoke.resolve(checks)
+  }
+
   @objc public func unregisterForPushNotifications(_ invoke: Invoke) {
     #if ENABLE_PUSH_NOTIFICATIONS
       DispatchQueue.main.async {
         UIApplication.shared.unregisterForRemoteNotifications()
+        UserDefaults.standard.removeObject(forKey: pushTokenDefaultsKey)
         invoke.resolve()
       }
     #else
@@ -287,6 +379,7 @@ class NotificationPlugin: Plugin {
     func handlePushTokenReceived(_ token: String) {
       pushTokenTimer?.invalidate()
       pushTokenTimer = nil
+      UserDefaults.standard.set(token, forKey: pushTokenDefaultsKey)
 
       if let completion = pushTokenCompletion {
         pushTokenCompletion = nil
@@ -325,6 +418,18 @@ class NotificationPlugin: Plugin {
     }
   }
 
+  @objc public func isDoNotDisturbActive(_ invoke: Invoke) {
+    Task {
+      invoke.resolve(["active": await doNotDisturbActive()])
+    }
+  }
+
+  @objc public func getUnreadCount(_ invoke: Invoke) {
+    DispatchQueue.main.async {
+      invoke.resolve(["count": max(UIApplication.shared.applicationIconBadgeNumber, 0)])
+    }
+  }
+
   @objc func cancel(_ invoke: Invoke) throws {
     let args = try invoke.parseArgs(CancelArgs.self)
 
@@ -334,6 +439,42 @@ class NotificationPlugin: Plugin {
     invoke.resolve()
   }
 
+  @objc func dismiss(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(DismissArgs.self)
+    let identifier = String(args.id)
+    let center = UNUserNotificationCenter.current()
+
+    center.getPendingNotificationRequests { requests in
+      let wasPending = requests.contains { $0.identifier == identifier }
+      center.getDeliveredNotifications { delivered in
+        let wasActive = delivered.contains { $0.request.identifier == identifier }
+        center.removePendingNotificationRequests(withIdentifiers: [identifier])
+        center.removeDeliveredNotifications(withIdentifiers: [identifier])
+        invoke.resolve(["wasPending": wasPending, "wasActive": wasActive])
+      }
+    }
+  }
+
+  @objc func clearScope(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(ClearScopeArgs.self)
+    let center = UNUserNotificationCenter.current()
+    let inScope = { (content: UNNotificationContent) in
+      content.userInfo[scopeUserInfoKey] as? String == args.scope
+    }
+
+    center.getPendingNotificationRequests { requests in
+      let pending = requests.filter { inScope($0.content) }.map { $0.identifier }
+      center.getDeliveredNotifications { notifications in
+        let delivered = notifications
+          .filter { inScope($0.request.content) }
+          .map { $0.request.identifier }
+        center.removePendingNotificationRequests(withIdentifiers: pending)
+        center.removeDeliveredNotifications(withIdentifiers: delivered)
+        invoke.resolve(["canceled": pending.count, "removed": delivered.count])
+      }
+    }
+  }
+
   @objc func cancelAll(_ invoke: Invoke) {
     UNUserNotificationCenter.current().removeAllPendingNotificationRequests()
     invoke.resolve()
@@ -356,6 +497,23 @@ class NotificationPlugin: Plugin {
     invoke.resolve()
   }
 
+  @objc func setNotificationCategories(_ invoke: Invoke) throws {
+    // `makeCategories` already replaces the whole category set.
+    try registerActionTypes(invoke)
+  }
+
+  @objc func getLaunchSource(_ invoke: Invoke) {
+    invoke.resolve(notificationHandler.launchSource)
+  }
+
+  @objc func removeAllActive(_ invoke: Invoke) {
+    UNUserNotificationCenter.current().removeAllDeliveredNotifications()
+    DispatchQueue.main.async(execute: {
+      UIApplication.shared.applicationIconBadgeNumber = 0
+    })
+    invoke.resolve()
+  }
+
   @objc func removeActive(_ invoke: Invoke) {
     let args = try? invoke.parseArgs(RemoveActiveArgs.self)
     if let args, !args.notifications.isEmpty {
@@ -374,13 +532,41 @@ class NotificationPlugin: Plugin {
     UNUserNotificationCenter.current().getDeliveredNotifications(completionHandler: {
       (notifications) in
       let ret = notifications.compactMap({ (notification) -> ActiveNotification? in
-        return self.notificationHandler.toActiveNotification(
-          notification.request)
+        guard var active = self.notificationHandler.toActiveNotification(notification.request) else {
+          return nil
+        }
+        active.sortKey = notification.request.content.userInfo[sortKeyUserInfoKey] as? String
+        active.deliveredAt = Int64(notification.date.timeIntervalSince1970 * 1000)
+        return active
       })
       invoke.resolve(ret)
     })
   }
 
+  // Digests are decided on the Rust side, which has no other way to reach
+  // the JS listeners.
+  @objc func emitNotificationsCoalesced(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(NotificationsCoalesced.self)
+    try trigger("notificationsCoalesced", data: args)
+    invoke.resolve()
+  }
+
+  // Like digests, suppressed notifications are decided on the Rust side.
+  @objc func emitNotificationFallback(_ invoke: Invoke) throws {
+    trigger("notificationFallback", data: try invoke.getArgs())
+    invoke.resolve()
+  }
+
+  // Ignored fields are looked up on the Rust side as well.
+  @objc func emitNotificationWarning(_ invoke: Invoke) throws {
+    trigger("notificationWarning", data: try invoke.getArgs())
+    invoke.resolve()
+  }
+
+  @objc func takeInteractions(_ invoke: Invoke) {
+    invoke.resolve(Interactions(interactions: notificationHandler.takeInteractions()))
+  }
+
   @objc func setClickListenerActive(_ invoke: Invoke) {
     do {
       let args = try invoke.parseArgs(SetClickListenerActiveArgs.self)
@@ -49,6 +49,30 @@ final class NotificationTests: XCTestCase {
         XCTAssertEqual(content.userInfo["key2"] as? String, "value2")
     }
 
+    func testMakeNotificationContentWithNestedExtraJson() throws {
+        let notification = Notification(
+            id: 1,
+            title: "Test",
+            body: "Body",
+            extra: nil,
+            schedule: nil,
+            attachments: nil,
+            sound: nil,
+            group: nil,
+            actionTypeId: nil,
+            summary: nil,
+            silent: nil,
+            extraJson: #"{"order":{"id":"9007199254740993","items":[1,2]},"skip":null}"#
+        )
+
+        let content = try makeNotificationContent(notification)
+
+        let order = content.userInfo["order"] as? [String: Any]
+        XCTAssertEqual(order?["id"] as? String, "9007199254740993")
+        XCTAssertEqual(order?["items"] as? [Int], [1, 2])
+        XCTAssertNil(content.userInfo["skip"])
+    }
+
     func testMakeNotificationContentWithActionTypeId() throws {
         let notification = Notification(
             id: 1,
@@ -411,6 +435,24 @@ final class NotificationTests: XCTestCase {
         XCTAssertEqual(options, .hiddenPreviewsShowTitle)
     }
 
+    func testMakeCategoryOptionsCombinesFlags() {
+        let actionType = ActionType(
+            id: "test",
+            actions: [],
+            hiddenPreviewsBodyPlaceholder: "%u new messages",
+            customDismissAction: true,
+            allowInCarPlay: nil,
+            hiddenPreviewsShowTitle: true,
+            hiddenPreviewsShowSubtitle: true,
+            hiddenBodyPlaceholder: nil
+        )
+
+        let options = makeCategoryOptions(actionType)
+
+        XCTAssertEqual(
+            options, [.customDismissAction, .hiddenPreviewsShowTitle, .hiddenPreviewsShowSubtitle])
+    }
+
     func testMakeActionsCreatesBasicAction() {
         let actions = [
             Action(
@@ -629,6 +671,33 @@ final class NotificationTests: XCTestCase {
         XCTAssertEqual(pending?.body, "Test Body")
     }
 
+    func testToPendingNotificationReportsThreadIdentifierAsChannel() {
+        let handler = NotificationHandler()
+        let notification = Notification(
+            id: 124,
+            title: "Threaded",
+            body: "Body",
+            extra: nil,
+            schedule: NotificationSchedule.every(interval: .day, count: 1),
+            attachments: nil,
+            sound: nil,
+            group: "news",
+            actionTypeId: nil,
+            summary: nil,
+            silent: nil
+        )
+        handler.saveNotification("124", notification)
+
+        let content = UNMutableNotificationContent()
+        content.threadIdentifier = "news"
+        let request = UNNotificationRequest(identifier: "124", content: content, trigger: nil)
+        XCTAssertEqual(handler.toPendingNotification(request)?.channelId, "news")
+
+        let unthreaded = UNNotificationRequest(
+            identifier: "124", content: UNMutableNotificationContent(), trigger: nil)
+        XCTAssertNil(handler.toPendingNotification(unthreaded)?.channelId)
+    }
+
     // MARK: - makeAttachments Tests
 
     func testMakeAttachmentsWithInvalidUrl() throws {
@@ -647,6 +716,13 @@ final class NotificationTests: XCTestCase {
         }
     }
 
+    func testAttachmentTypeIdentifier() {
+        XCTAssertEqual(attachmentTypeIdentifier("image"), "public.image")
+        XCTAssertEqual(attachmentTypeIdentifier("video"), "public.video")
+        XCTAssertEqual(attachmentTypeIdentifier("audio"), "public.audio")
+        XCTAssertNil(attachmentTypeIdentifier("document"))
+    }
+
     // MARK: - Additional Schedule Tests
 
     func testHandleScheduledNotificationWithAtDate() throws {
@@ -886,6 +962,41 @@ final class NotificationTests: XCTestCase {
         XCTAssertEqual(options.rawValue, 0)
     }
 
+    // MARK: - Hidden Previews Placeholder Tests
+
+    /// Exact payload produced by the Rust `register_action_types` bridge (see
+    /// `APPLE_ACTION_TYPES_PAYLOAD` in `src/models.rs`).
+    private let rustActionTypesPayload = """
+    {"types":[{"id":"secret","actions":[],"hiddenPreviewsBodyPlaceholder":"Hidden message","customDismissAction":false,"allowInCarPlay":false,"hiddenPreviewsShowTitle":false,"hiddenPreviewsShowSubtitle":false}]}
+    """
+
+    func testHiddenPreviewsBodyPlaceholderSurvivesRustPayload() throws {
+        let args = try JSONDecoder().decode(
+            RegisterActionTypesArgs.self, from: rustActionTypesPayload.data(using: .utf8)!)
+
+        XCTAssertEqual(args.types.count, 1)
+        XCTAssertEqual(args.types[0].hiddenPreviewsBodyPlaceholder, "Hidden message")
+
+        let category = makeCategory(args.types[0])
+        XCTAssertEqual(category.identifier, "secret")
+        XCTAssertEqual(category.hiddenPreviewsBodyPlaceholder, "Hidden message")
+    }
+
+    func testHiddenPreviewsBodyPlaceholderFallsBackToLegacyKey() {
+        let actionType = ActionType(
+            id: "legacy",
+            actions: [],
+            hiddenPreviewsBodyPlaceholder: nil,
+            customDismissAction: nil,
+            allowInCarPlay: nil,
+            hiddenPreviewsShowTitle: nil,
+            hiddenPreviewsShowSubtitle: nil,
+            hiddenBodyPlaceholder: "Legacy"
+        )
+
+        XCTAssertEqual(makeCategory(actionType).hiddenPreviewsBodyPlaceholder, "Legacy")
+    }
+
     // MARK: - NotificationSchedule Decoding Tests
 
     func testNotificationScheduleDecodingAtDate() throws {
@@ -11,25 +11,26 @@ internal func makeCategories(_ actionTypes: [ActionType]) {
 
   createdCategories.append(generalCategory)
   for type in actionTypes {
-    let newActions = makeActions(type.actions)
-
-    // Create the custom actions for the TIMER_EXPIRED category.
-    var newCategory: UNNotificationCategory?
-
-    newCategory = UNNotificationCategory(
-      identifier: type.id,
-      actions: newActions,
-      intentIdentifiers: [],
-      hiddenPreviewsBodyPlaceholder: type.hiddenBodyPlaceholder ?? "",
-      options: makeCategoryOptions(type))
-
-    createdCategories.append(newCategory!)
+    createdCategories.append(makeCategory(type))
   }
 
   let center = UNUserNotificationCenter.current()
   center.setNotificationCategories(Set(createdCategories))
 }
 
+func makeCategory(_ type: ActionType) -> UNNotificationCategory {
+  // `hiddenPreviewsBodyPlaceholder` is the key the Rust and JS layers send;
+  // `hiddenBodyPlaceholder` is kept as a fallback for older payloads. The
+  // system replaces a `%u` in it with the number of hidden notifications.
+  return UNNotificationCategory(
+    identifier: type.id,
+    actions: makeActions(type.actions),
+    intentIdentifiers: [],
+    hiddenPreviewsBodyPlaceholder: type.hiddenPreviewsBodyPlaceholder
+      ?? type.hiddenBodyPlaceholder ?? "",
+    options: makeCategoryOptions(type))
+}
+
 func makeActions(_ actions: [Action]) -> [UNNotificationAction] {
   var createdActions = [UNNotificationAction]()
 
@@ -74,15 +75,15 @@ func makeActionOptions(_ action: Action) -> UNNotificationActionOptions {
 }
 
 func makeCategoryOptions(_ type: ActionType) -> UNNotificationCategoryOptions {
+  var options = UNNotificationCategoryOptions()
   if type.customDismissAction ?? false {
-    return .customDismissAction
+    options.insert(.customDismissAction)
   }
   if type.hiddenPreviewsShowTitle ?? false {
-    return .hiddenPreviewsShowTitle
+    options.insert(.hiddenPreviewsShowTitle)
   }
   if type.hiddenPreviewsShowSubtitle ?? false {
-    return .hiddenPreviewsShowSubtitle
+    options.insert(.hiddenPreviewsShowSubtitle)
   }
-
-  return UNNotificationCategoryOptions(rawValue: 0)
+  return options
 }
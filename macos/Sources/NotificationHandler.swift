@@ -54,6 +54,9 @@ public class NotificationHandler: NSObject, NotificationHandlerProtocol {
       if options.silent ?? false {
         return UNNotificationPresentationOptions.init(rawValue: 0)
       }
+      if options.quiet ?? false {
+        return [.badge, .list]
+      }
     }
 
     return [
@@ -71,7 +74,9 @@ public class NotificationHandler: NSObject, NotificationHandlerProtocol {
     if !content.userInfo.isEmpty {
       extra = [:]
       for (key, value) in content.userInfo {
-        if let keyStr = key as? String, let valStr = value as? String {
+        if let keyStr = key as? String, !reservedUserInfoKeys.contains(keyStr),
+          let valStr = value as? String
+        {
           extra?[keyStr] = valStr
         }
       }
@@ -126,7 +131,9 @@ public class NotificationHandler: NSObject, NotificationHandlerProtocol {
     if !userInfo.isEmpty {
       dataDict = [:]
       for (key, value) in userInfo {
-        if let keyStr = key as? String, let valStr = value as? String {
+        if let keyStr = key as? String, !reservedUserInfoKeys.contains(keyStr),
+          let valStr = value as? String
+        {
           dataDict?[keyStr] = valStr
         }
       }
@@ -156,7 +163,8 @@ public class NotificationHandler: NSObject, NotificationHandlerProtocol {
       body: request.content.body,
       sound: notificationRequest.sound ?? "",
       actionTypeId: request.content.categoryIdentifier,
-      attachments: notificationRequest.attachments
+      attachments: notificationRequest.attachments,
+      extraJson: notificationRequest.extraJson
     )
   }
 
@@ -168,7 +176,13 @@ public class NotificationHandler: NSObject, NotificationHandlerProtocol {
       id: Int(request.identifier) ?? -1,
       title: request.content.title,
       body: request.content.body,
-      schedule: notification.schedule!
+      schedule: notification.schedule!,
+      channelId: request.content.threadIdentifier.isEmpty
+        ? nil : request.content.threadIdentifier,
+      actionTypeId: request.content.categoryIdentifier.isEmpty
+        ? nil : request.content.categoryIdentifier,
+      sound: request.content.userInfo[soundUserInfoKey] as? String,
+      extraJson: request.content.userInfo[extraJsonUserInfoKey] as? String
     )
   }
 }
@@ -178,6 +192,10 @@ struct PendingNotification: Encodable {
   let title: String
   let body: String
   let schedule: NotificationSchedule
+  var channelId: String? = nil
+  var actionTypeId: String? = nil
+  var sound: String? = nil
+  var extraJson: String? = nil
 }
 
 struct ActiveNotification: Encodable {
@@ -188,6 +206,10 @@ struct ActiveNotification: Encodable {
   let actionTypeId: String
   let attachments: [NotificationAttachment]?
   var source: String = "local"
+  var extraJson: String? = nil
+  var sortKey: String? = nil
+  /// When the notification was delivered, in milliseconds since the epoch.
+  var deliveredAt: Int64? = nil
 }
 
 struct ReceivedNotification: Encodable {
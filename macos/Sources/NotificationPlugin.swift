@@ -1,8 +1,65 @@
 import AppKit
+import Intents
+import Security
 import UserNotifications
 
 extension FFIResult: Error {}
 
+/// Errors reach Rust as a JSON `{"code", "message"}` object in
+/// `FFIResult.Err`, so callers can tell failures apart by `code`.
+extension FFIResult {
+  static func rejected(_ code: String, _ message: String) -> FFIResult {
+    guard let data = try? JSONEncoder().encode(["code": code, "message": message]),
+      let json = String(data: data, encoding: .utf8)
+    else {
+      return .Err(RustString(message))
+    }
+    return .Err(RustString(json))
+  }
+
+  static func rejected(_ error: Error) -> FFIResult {
+    if let error = error as? FFIResult {
+      return error
+    }
+    return .rejected(errorCode(error), error.localizedDescription)
+  }
+}
+
+/// Stable code of an error thrown by `UserNotifications` or while building a
+/// notification.
+func errorCode(_ error: Error) -> String {
+  if let error = error as? NotificationError {
+    switch error {
+    case .triggerRepeatIntervalTooShort:
+      return "repeatIntervalTooShort"
+    case .attachmentFileNotFound:
+      return "attachmentNotFound"
+    case .attachmentUnableToCreate:
+      return "attachmentInvalid"
+    case .pastScheduledTime:
+      return "pastScheduledTime"
+    case .invalidDate:
+      return "invalidDate"
+    }
+  }
+  if let error = error as? UNError {
+    switch error.code {
+    case .notificationsNotAllowed:
+      return "permissionDenied"
+    case .attachmentInvalidURL, .attachmentUnrecognizedType, .attachmentInvalidFileSize,
+      .attachmentNotInDataStore, .attachmentMoveIntoDataStoreFailed, .attachmentCorrupt:
+      return "attachmentInvalid"
+    case .notificationInvalidNoDate:
+      return "invalidDate"
+    case .notificationInvalidNoContent:
+      return "invalidContent"
+    default:
+      return "platformError"
+    }
+  }
+  return "platformError"
+}
+
 typealias JsonObject = [String: Any]
 
 enum ScheduleEveryKind: String, Codable {
@@ -43,6 +100,8 @@ struct NotificationAttachment: Codable {
   let id: String
   let url: String
   let options: NotificationAttachmentOptions?
+  /// `"image"`, `"video"` or `"audio"`; see `attachmentTypeIdentifier`.
+  var typeHint: String? = nil
 }
 
 struct Notification: Decodable {
@@ -57,12 +116,48 @@ struct Notification: Decodable {
   var actionTypeId: String?
   var summary: String?
   var silent: Bool?
+  /// `extra` encoded as one JSON string by the Rust side, so nested values
+  /// survive (`extra` above only holds flat string pairs).
+  var extraJson: String? = nil
+  /// Account or profile the notification belongs to, see `clearScope`.
+  var scope: String?
+  /// Reported back by `getActive`; the system still lists the newest first.
+  var sortKey: String?
+  /// Deliver without sound or banner, set during quiet hours.
+  var quiet: Bool?
+  /// Text VoiceOver reads for an `announceOnly` notification.
+  var ticker: String?
+  /// Post a VoiceOver announcement instead of delivering a notification.
+  var announceOnly: Bool?
+
+  /// The announced text: the ticker, or the title followed by the body.
+  var announcementText: String {
+    ticker ?? [title, body ?? ""].filter { !$0.isEmpty }.joined(separator: ". ")
+  }
 }
 
 struct CancelArgs: Decodable {
   let notifications: [Int]
 }
 
+struct DismissArgs: Decodable {
+  let id: Int
+}
+
+struct ClearScopeArgs: Decodable {
+  let scope: String
+}
+
+struct DismissOutcome: Encodable {
+  let wasPending: Bool
+  let wasActive: Bool
+}
+
+struct ScopeClearance: Encodable {
+  let canceled: Int
+  let removed: Int
+}
+
 struct Action: Decodable {
   let id: String
   let title: String
@@ -104,12 +199,13 @@ struct RemoveActiveArgs: Decodable {
 extension RustString {
   func decode<T: Decodable>(_ type: T.Type) throws(FFIResult) -> T {
     guard let data = self.toString().data(using: .utf8) else {
-      throw FFIResult.Err(RustString("Invalid UTF-8 string"))
+      throw FFIResult.rejected("invalidArgument", "Invalid UTF-8 string")
     }
     do {
       return try JSONDecoder().decode(type, from: data)
     } catch {
-      throw FFIResult.Err(RustString("Failed to decode JSON: \(error.localizedDescription)"))
+      throw FFIResult.rejected(
+        "invalidArgument", "Failed to decode JSON: \(error.localizedDescription)")
     }
   }
 }
@@ -119,13 +215,14 @@ extension Encodable {
     do {
       let jsonData = try JSONEncoder().encode(self)
       guard let jsonString = String(data: jsonData, encoding: .utf8) else {
-        throw FFIResult.Err(RustString("Failed to encode to JSON string"))
+        throw FFIResult.rejected("serializationFailed", "Failed to encode to JSON string")
       }
       return jsonString
     } catch let error as FFIResult {
       throw error
     } catch {
-      throw FFIResult.Err(RustString("Failed to encode to JSON: \(error.localizedDescription)"))
+      throw FFIResult.rejected(
+        "serializationFailed", "Failed to encode to JSON: \(error.localizedDescription)")
     }
   }
 }
@@ -135,7 +232,7 @@ func showNotification(notification: Notification) async throws(FFIResult) -> UNN
   do {
     content = try makeNotificationContent(notification)
   } catch {
-    throw FFIResult.Err(RustString(error.localizedDescription))
+    throw FFIResult.rejected(error)
   }
 
   var trigger: UNNotificationTrigger?
@@ -145,7 +242,7 @@ func showNotification(notification: Notification) async throws(FFIResult) -> UNN
       try trigger = handleScheduledNotification(schedule)
     }
   } catch {
-    throw FFIResult.Err(RustString(error.localizedDescription))
+    throw FFIResult.rejected(error)
   }
 
   // Schedule the request.
@@ -157,12 +254,27 @@ func showNotification(notification: Notification) async throws(FFIResult) -> UNN
   do {
     try await center.add(request)
   } catch {
-    throw FFIResult.Err(RustString(error.localizedDescription))
+    throw FFIResult.rejected(error)
   }
 
   return request
 }
 
+/// Whether a Focus is on. Reading the Focus status needs the Communication
+/// Notifications capability and the user's consent; without them this falls
+/// back to whether alerts are turned off for the app.
+func doNotDisturbActive() async -> Bool {
+  let focus = INFocusStatusCenter.default
+  if focus.authorizationStatus == .authorized, let isFocused = focus.focusStatus.isFocused {
+    return isFocused
+  }
+  let settings = await UNUserNotificationCenter.current().notificationSettings()
+  return settings.alertSetting == .disabled
+}
+
+/// `UserDefaults` key of the last device token, read by `getPushTokenCached`.
+let pushTokenDefaultsKey = "tauri-plugin-notifications.pushToken"
+
 class NotificationPlugin {
   let notificationHandler = NotificationHandler()
   let notificationManager = NotificationManager()
@@ -190,6 +302,22 @@ class NotificationPlugin {
   public func show(args: RustString) async throws(FFIResult) -> Int32 {
     let notification = try args.decode(Notification.self)
 
+    if notification.announceOnly == true {
+      let text = notification.announcementText
+      await MainActor.run {
+        let element: NSObject = NSApp.mainWindow ?? NSApplication.shared
+        NSAccessibility.post(
+          element: element,
+          notification: .announcementRequested,
+          userInfo: [
+            .announcement: text,
+            .priority: NSAccessibilityPriorityLevel.high.rawValue,
+          ]
+        )
+      }
+      return Int32(notification.id)
+    }
+
     let request = try await showNotification(notification: notification)
     notificationHandler.saveNotification(request.identifier, notification)
     return Int32(request.identifier) ?? -1
@@ -201,7 +329,7 @@ class NotificationPlugin {
       let permissionState = granted ? "granted" : "denied"
       return "{\"permissionState\":\"\(permissionState)\"}"
     } catch {
-      throw FFIResult.Err(RustString(error.localizedDescription))
+      throw FFIResult.rejected(error)
     }
   }
 
@@ -212,11 +340,13 @@ class NotificationPlugin {
       do {
         granted = try await notificationHandler.requestPermissions()
       } catch {
-        throw FFIResult.Err(RustString("Failed to request notification permissions: \(error.localizedDescription)"))
+        throw FFIResult.rejected(
+          errorCode(error),
+          "Failed to request notification permissions: \(error.localizedDescription)")
       }
 
       guard granted else {
-        throw FFIResult.Err(RustString("Notification permissions not granted"))
+        throw FFIResult.rejected("permissionDenied", "Notification permissions not granted")
       }
 
       // Register and wait for token
@@ -228,20 +358,31 @@ class NotificationPlugin {
         }
         return "{\"deviceToken\":\"\(token)\"}"
       } catch {
-        throw FFIResult.Err(RustString(error.localizedDescription))
+        throw FFIResult.rejected(error)
       }
     #else
-      throw FFIResult.Err(RustString("Push notifications are disabled in this build"))
+      throw FFIResult.rejected("unsupported", "Push notifications are disabled in this build")
     #endif
   }
 
+  public func getPushTokenCached() async throws(FFIResult) -> String {
+    let token = UserDefaults.standard.string(forKey: pushTokenDefaultsKey)
+    guard let data = try? JSONEncoder().encode(["token": token]),
+      let json = String(data: data, encoding: .utf8)
+    else {
+      throw FFIResult.rejected("serializationFailed", "Failed to encode the cached push token")
+    }
+    return json
+  }
+
   public func unregisterForPushNotifications() throws(FFIResult) {
     #if ENABLE_PUSH_NOTIFICATIONS
       DispatchQueue.main.async {
         NSApplication.shared.unregisterForRemoteNotifications()
       }
+      UserDefaults.standard.removeObject(forKey: pushTokenDefaultsKey)
     #else
-      throw FFIResult.Err(RustString("Push notifications are disabled in this build"))
+      throw FFIResult.rejected("unsupported", "Push notifications are disabled in this build")
     #endif
   }
 
@@ -263,6 +404,17 @@ class NotificationPlugin {
     return "{\"permissionState\":\"\(permission)\"}"
   }
 
+  public func isDoNotDisturbActive() async throws(FFIResult) -> String {
+    let active = await doNotDisturbActive()
+    return "{\"active\":\(active)}"
+  }
+
+  public func getUnreadCount() async throws(FFIResult) -> String {
+    let label = await MainActor.run { NSApp.dockTile.badgeLabel }
+    let count = label.flatMap { UInt32($0.trimmingCharacters(in: .whitespaces)) } ?? 0
+    return "{\"count\":\(count)}"
+  }
+
   public func cancel(args: RustString) throws(FFIResult) {
     let args = try args.decode(CancelArgs.self)
 
@@ -271,6 +423,42 @@ class NotificationPlugin {
     )
   }
 
+  public func dismiss(args: RustString) async throws(FFIResult) -> String {
+    let args = try args.decode(DismissArgs.self)
+    let identifier = String(args.id)
+    let center = UNUserNotificationCenter.current()
+
+    let wasPending = await center.pendingNotificationRequests().contains {
+      $0.identifier == identifier
+    }
+    let wasActive = await center.deliveredNotifications().contains {
+      $0.request.identifier == identifier
+    }
+    center.removePendingNotificationRequests(withIdentifiers: [identifier])
+    center.removeDeliveredNotifications(withIdentifiers: [identifier])
+
+    return try DismissOutcome(wasPending: wasPending, wasActive: wasActive).toJSONString()
+  }
+
+  public func clearScope(args: RustString) async throws(FFIResult) -> String {
+    let args = try args.decode(ClearScopeArgs.self)
+    let center = UNUserNotificationCenter.current()
+    let inScope = { (content: UNNotificationContent) in
+      content.userInfo[scopeUserInfoKey] as? String == args.scope
+    }
+
+    let pending = await center.pendingNotificationRequests()
+      .filter { inScope($0.content) }
+      .map { $0.identifier }
+    let delivered = await center.deliveredNotifications()
+      .filter { inScope($0.request.content) }
+      .map { $0.request.identifier }
+    center.removePendingNotificationRequests(withIdentifiers: pending)
+    center.removeDeliveredNotifications(withIdentifiers: delivered)
+
+    return try ScopeClearance(canceled: pending.count, removed: delivered.count).toJSONString()
+  }
+
   public func cancelAll() throws(FFIResult) {
     UNUserNotificationCenter.current().removeAllPendingNotificationRequests()
   }
@@ -311,7 +499,12 @@ class NotificationPlugin {
     let notifications = await UNUserNotificationCenter.current().deliveredNotifications()
 
     let ret = notifications.compactMap({ (notification) -> ActiveNotification? in
-      return self.notificationHandler.toActiveNotification(notification.request)
+      guard var active = self.notificationHandler.toActiveNotification(notification.request) else {
+        return nil
+      }
+      active.sortKey = notification.request.content.userInfo[sortKeyUserInfoKey] as? String
+      active.deliveredAt = Int64(notification.date.timeIntervalSince1970 * 1000)
+      return active
     })
 
     return try ret.toJSONString()
@@ -322,6 +515,19 @@ class NotificationPlugin {
     notificationHandler.setClickListenerActive(args.active)
   }
 
+  /// Setup checks only the native side can run, reported by `doctor()`.
+  public func doctor() throws(FFIResult) -> String {
+    var checks: [String: Bool] = [:]
+    #if ENABLE_PUSH_NOTIFICATIONS
+      if let task = SecTaskCreateFromSelf(nil) {
+        checks["pushEntitlement"] =
+          SecTaskCopyValueForEntitlement(
+            task, "com.apple.developer.aps-environment" as CFString, nil) != nil
+      }
+    #endif
+    return try checks.toJSONString()
+  }
+
   #if ENABLE_PUSH_NOTIFICATIONS
     private func registerForPushNotificationsWithCompletion(_ completion: @escaping (Result<String, Error>) -> Void)
     {
@@ -360,6 +566,7 @@ class NotificationPlugin {
     func handlePushTokenReceived(_ token: String) {
       pushTokenTimer?.invalidate()
       pushTokenTimer = nil
+      UserDefaults.standard.set(token, forKey: pushTokenDefaultsKey)
 
       if let completion = pushTokenCompletion {
         pushTokenCompletion = nil
@@ -376,6 +376,7 @@ final class ActionTypeTests: XCTestCase {
 
         XCTAssertEqual(actionType.customDismissAction, true)
         XCTAssertEqual(actionType.hiddenPreviewsShowTitle, true)
+        XCTAssertEqual(makeCategoryOptions(actionType), [.customDismissAction, .hiddenPreviewsShowTitle])
     }
 }
 
@@ -703,6 +704,41 @@ final class HelperFunctionTests: XCTestCase {
         XCTAssertEqual(options.rawValue, 0)
     }
 
+    // MARK: - Hidden Previews Placeholder Tests
+
+    /// Exact payload produced by the Rust `register_action_types` bridge (see
+    /// `APPLE_ACTION_TYPES_PAYLOAD` in `src/models.rs`).
+    private let rustActionTypesPayload = """
+    {"types":[{"id":"secret","actions":[],"hiddenPreviewsBodyPlaceholder":"Hidden message","customDismissAction":false,"allowInCarPlay":false,"hiddenPreviewsShowTitle":false,"hiddenPreviewsShowSubtitle":false}]}
+    """
+
+    func testHiddenPreviewsBodyPlaceholderSurvivesRustPayload() throws {
+        let args = try JSONDecoder().decode(
+            RegisterActionTypesArgs.self, from: rustActionTypesPayload.data(using: .utf8)!)
+
+        XCTAssertEqual(args.types.count, 1)
+        XCTAssertEqual(args.types[0].hiddenPreviewsBodyPlaceholder, "Hidden message")
+
+        let category = makeCategory(args.types[0])
+        XCTAssertEqual(category.identifier, "secret")
+        XCTAssertEqual(category.hiddenPreviewsBodyPlaceholder, "Hidden message")
+    }
+
+    func testHiddenPreviewsBodyPlaceholderFallsBackToLegacyKey() {
+        let actionType = ActionType(
+            id: "legacy",
+            actions: [],
+            hiddenPreviewsBodyPlaceholder: nil,
+            customDismissAction: nil,
+            allowInCarPlay: nil,
+            hiddenPreviewsShowTitle: nil,
+            hiddenPreviewsShowSubtitle: nil,
+            hiddenBodyPlaceholder: "Legacy"
+        )
+
+        XCTAssertEqual(makeCategory(actionType).hiddenPreviewsBodyPlaceholder, "Legacy")
+    }
+
     // MARK: - makeActions Tests
 
     func testMakeActionsBasic() {
@@ -797,6 +833,13 @@ final class HelperFunctionTests: XCTestCase {
         XCTAssertEqual(result[UNNotificationAttachmentOptionsTypeHintKey] as? String, "public.png")
     }
 
+    func testAttachmentTypeIdentifier() {
+        XCTAssertEqual(attachmentTypeIdentifier("image"), "public.image")
+        XCTAssertEqual(attachmentTypeIdentifier("video"), "public.video")
+        XCTAssertEqual(attachmentTypeIdentifier("audio"), "public.audio")
+        XCTAssertNil(attachmentTypeIdentifier("document"))
+    }
+
     func testMakeAttachmentOptionsEmpty() {
         let options = NotificationAttachmentOptions(
             iosUNNotificationAttachmentOptionsTypeHintKey: nil,
@@ -834,6 +877,58 @@ final class NotificationContentTests: XCTestCase {
         XCTAssertEqual(content.userInfo["key2"] as? String, "value2")
     }
 
+    func testMakeNotificationContentWithNestedExtraJson() throws {
+        var notification = makeTestNotification()
+        notification.extraJson = #"{"order":{"id":"9007199254740993","items":[1,2]},"skip":null}"#
+
+        let content = try makeNotificationContent(notification)
+
+        let order = content.userInfo["order"] as? [String: Any]
+        XCTAssertEqual(order?["id"] as? String, "9007199254740993")
+        XCTAssertEqual(order?["items"] as? [Int], [1, 2])
+        XCTAssertNil(content.userInfo["skip"])
+    }
+
+    func testMakeNotificationContentKeepsExtraJsonAndSound() throws {
+        var notification = makeTestNotification(sound: "chime.aiff")
+        notification.extraJson = #"{"orderId":12}"#
+
+        let content = try makeNotificationContent(notification)
+
+        XCTAssertEqual(content.userInfo[extraJsonUserInfoKey] as? String, #"{"orderId":12}"#)
+        XCTAssertEqual(content.userInfo[soundUserInfoKey] as? String, "chime.aiff")
+        XCTAssertEqual(content.userInfo["orderId"] as? Int, 12)
+    }
+
+    func testMakeNotificationContentWithScope() throws {
+        var notification = makeTestNotification()
+        notification.scope = "alice@example.com"
+
+        let content = try makeNotificationContent(notification)
+
+        XCTAssertEqual(content.userInfo[scopeUserInfoKey] as? String, "alice@example.com")
+    }
+
+    func testMakeNotificationContentWithSortKey() throws {
+        var notification = makeTestNotification()
+        notification.sortKey = "0001"
+
+        let content = try makeNotificationContent(notification)
+
+        XCTAssertEqual(content.userInfo[sortKeyUserInfoKey] as? String, "0001")
+        XCTAssertTrue(reservedUserInfoKeys.contains(sortKeyUserInfoKey))
+    }
+
+    func testMakeNotificationContentQuiet() throws {
+        var notification = makeTestNotification(sound: "chime.aiff")
+        notification.quiet = true
+
+        let content = try makeNotificationContent(notification)
+
+        XCTAssertNil(content.sound)
+        XCTAssertEqual(content.interruptionLevel, .passive)
+    }
+
     func testMakeNotificationContentWithActionType() throws {
         let notification = makeTestNotification(actionTypeId: "message_actions")
 
@@ -1336,6 +1431,21 @@ final class NotificationHandlerTests: XCTestCase {
         XCTAssertEqual(pendingNotification?.title, "Scheduled")
     }
 
+    func testToPendingNotificationReportsThreadIdentifierAsChannel() {
+        let schedule = NotificationSchedule.every(interval: .hour, count: 1)
+        let notification = makeTestNotification(id: 457, title: "Threaded", schedule: schedule)
+        handler.saveNotification("457", notification)
+
+        let content = UNMutableNotificationContent()
+        content.threadIdentifier = "news"
+        let request = UNNotificationRequest(identifier: "457", content: content, trigger: nil)
+        XCTAssertEqual(handler.toPendingNotification(request)?.channelId, "news")
+
+        let unthreaded = UNNotificationRequest(
+            identifier: "457", content: UNMutableNotificationContent(), trigger: nil)
+        XCTAssertNil(handler.toPendingNotification(unthreaded)?.channelId)
+    }
+
     func testSetClickListenerActive() {
         // Initially false
         handler.setClickListenerActive(true)
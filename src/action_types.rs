@@ -0,0 +1,372 @@
+//! The action types registered with the plugin, the same on every platform.
+//!
+//! Registering merges into this set: a type replaces the one with its id
+//! and the others stay. A change hands the whole set to the platform, which
+//! replaces what it had, so a platform never holds a type the set doesn't.
+//! The set lives for the process, so apps register their types again on
+//! every launch.
+//!
+//! Setting categories is slow on iOS and can race with a notification
+//! being delivered, so calls that leave the set as it was skip the platform
+//! and report [`ActionTypesUpdate::Unchanged`]. The hash of the last set
+//! handed over is kept in `<app data dir>/action-types.hash`, which makes
+//! registering the same types on the next launch a no-op too.
+//!
+//! One worker thread hands the set over, always the latest one, so changes
+//! made within [`PUSH_DELAY`] of each other reach the platform in one call
+//! and an older set never lands after a newer one. If the platform rejects
+//! the latest set, the registered types go back to the last set it took.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::{ActionType, ActionTypesUpdate, telemetry};
+
+const FILE_NAME: &str = "action-types.hash";
+
+/// How long a change waits for more changes before the set is handed to
+/// the platform.
+pub const PUSH_DELAY: Duration = Duration::from_millis(100);
+
+/// `None` until first used.
+static REGISTRY: Mutex<Option<Registry>> = Mutex::new(None);
+
+/// Hands a set of action types to the platform.
+type Push = Arc<dyn Fn(&[ActionType]) -> crate::Result<()> + Send + Sync>;
+
+#[derive(Default)]
+struct Registry {
+    types: Vec<ActionType>,
+    /// Hash of the types the platform has, or gets with the pending push.
+    target: Option<u64>,
+    /// Counts the changes to `types`.
+    generation: u64,
+    /// The last generation handed over, or given up on.
+    pushed_generation: u64,
+    /// The types the platform took last in this process.
+    pushed: Vec<ActionType>,
+    /// Set by the latest [`schedule`].
+    push: Option<Push>,
+    path: Option<PathBuf>,
+    worker_running: bool,
+}
+
+impl Registry {
+    /// Applies `change` to the types, returning whether they now differ
+    /// from what the platform has or is about to get.
+    fn apply(&mut self, change: impl FnOnce(&mut Vec<ActionType>)) -> bool {
+        change(&mut self.types);
+        let hash = hash(&self.types);
+        if self.target == Some(hash) {
+            return false;
+        }
+        self.target = Some(hash);
+        self.generation += 1;
+        true
+    }
+
+    /// The set to hand over next and its generation, unless the platform
+    /// has the latest one.
+    fn next_push(&self) -> Option<(Vec<ActionType>, u64)> {
+        (self.pushed_generation != self.generation).then(|| (self.types.clone(), self.generation))
+    }
+
+    /// Records how handing over `types`, the set of `generation`, went. A
+    /// failure rolls the types back unless they changed since, in which
+    /// case the newer set is handed over next.
+    fn finish_push(&mut self, types: Vec<ActionType>, generation: u64, succeeded: bool) {
+        if succeeded {
+            self.pushed = types;
+            self.pushed_generation = generation;
+        } else if generation == self.generation {
+            self.types = self.pushed.clone();
+            // The platform may hold another set since the last launch.
+            self.target = None;
+            self.pushed_generation = generation;
+        }
+    }
+}
+
+/// 64-bit FNV-1a of the serialized types. Stable across Rust releases,
+/// unlike `DefaultHasher`, so the stored hash still matches after an app
+/// update.
+fn hash(types: &[ActionType]) -> u64 {
+    let json = serde_json::to_string(types).unwrap_or_default();
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in json.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+fn path<R: Runtime>(app: &AppHandle<R>) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join(FILE_NAME))
+}
+
+fn read_hash(path: Option<&PathBuf>) -> Option<u64> {
+    let text = std::fs::read_to_string(path?).ok()?;
+    u64::from_str_radix(text.trim(), 16).ok()
+}
+
+/// Stores `hash` as the one of the set the platform has, or forgets it.
+fn write_hash(path: Option<&PathBuf>, hash: Option<u64>) {
+    let Some(path) = path else {
+        return;
+    };
+    let result = hash.map_or_else(
+        || {
+            std::fs::remove_file(path).or_else(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    Ok(())
+                } else {
+                    Err(e)
+                }
+            })
+        },
+        |hash| {
+            path.parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|()| std::fs::write(path, format!("{hash:016x}")))
+        },
+    );
+    if let Err(e) = result {
+        telemetry::warning!("Failed to store the action types hash: {e}");
+    }
+}
+
+fn registry() -> MutexGuard<'static, Option<Registry>> {
+    REGISTRY.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The registered action types, in registration order.
+pub fn registered() -> Vec<ActionType> {
+    registry()
+        .as_ref()
+        .map(|registry| registry.types.clone())
+        .unwrap_or_default()
+}
+
+/// The registered action types by id, for looking up a notification's
+/// `actionTypeId`.
+pub fn by_id() -> HashMap<String, ActionType> {
+    registered()
+        .into_iter()
+        .map(|action_type| (action_type.id().to_string(), action_type))
+        .collect()
+}
+
+/// Applies `change` for a platform that reads the registered types when a
+/// notification is shown, and so needs nothing handed over.
+pub fn apply(change: impl FnOnce(&mut Vec<ActionType>)) -> ActionTypesUpdate {
+    let mut registry = registry();
+    if registry.get_or_insert_with(Registry::default).apply(change) {
+        ActionTypesUpdate::Applied
+    } else {
+        ActionTypesUpdate::Unchanged
+    }
+}
+
+/// Applies `change` and, if the set changed, has the push worker hand the
+/// latest set to the platform with `push` after [`PUSH_DELAY`]. A failed
+/// push is logged and rolls the types back, see [`Registry::finish_push`].
+/// Fails, with the change undone, if the worker can't be started.
+pub fn schedule<R: Runtime>(
+    app: &AppHandle<R>,
+    change: impl FnOnce(&mut Vec<ActionType>),
+    push: impl Fn(&[ActionType]) -> crate::Result<()> + Send + Sync + 'static,
+) -> crate::Result<ActionTypesUpdate> {
+    let mut guard = registry();
+    let registry = guard.get_or_insert_with(|| {
+        let path = path(app);
+        Registry {
+            target: read_hash(path.as_ref()),
+            path,
+            ..Registry::default()
+        }
+    });
+    let previous = registry.types.clone();
+    let target = registry.target;
+    if !registry.apply(change) {
+        return Ok(ActionTypesUpdate::Unchanged);
+    }
+    registry.push = Some(Arc::new(push));
+    if registry.worker_running {
+        return Ok(ActionTypesUpdate::Scheduled);
+    }
+
+    let spawned = std::thread::Builder::new()
+        .name("action-types".to_string())
+        .spawn(run_pushes);
+    if let Err(e) = spawned {
+        registry.types = previous;
+        registry.target = target;
+        registry.pushed_generation = registry.generation;
+        return Err(e.into());
+    }
+    registry.worker_running = true;
+    drop(guard);
+    Ok(ActionTypesUpdate::Scheduled)
+}
+
+/// Hands the latest set to the platform until it has it, then stops.
+fn run_pushes() {
+    loop {
+        std::thread::sleep(PUSH_DELAY);
+        let (types, generation, push, path) = {
+            let mut guard = registry();
+            let Some(registry) = guard.as_mut() else {
+                return;
+            };
+            let Some((types, generation)) = registry.next_push() else {
+                registry.worker_running = false;
+                return;
+            };
+            let next = (
+                types,
+                generation,
+                registry.push.clone(),
+                registry.path.clone(),
+            );
+            drop(guard);
+            next
+        };
+        let result = push.map_or(Ok(()), |push| push(&types));
+        if let Err(e) = &result {
+            telemetry::warning!("Failed to register action types: {e}");
+        }
+        let hash = result.is_ok().then(|| hash(&types));
+        if let Some(registry) = registry().as_mut() {
+            registry.finish_push(types, generation, result.is_ok());
+        }
+        write_hash(path.as_ref(), hash);
+    }
+}
+
+/// Adds `types` to `registered`, replacing the ones with the same id in
+/// place.
+pub fn merge(registered: &mut Vec<ActionType>, types: Vec<ActionType>) {
+    for action_type in types {
+        match registered.iter_mut().find(|t| t.id() == action_type.id()) {
+            Some(existing) => *existing = action_type,
+            None => registered.push(action_type),
+        }
+    }
+}
+
+/// Removes the type `id` from `registered`, if there is one.
+pub fn unregister(registered: &mut Vec<ActionType>, id: &str) {
+    registered.retain(|action_type| action_type.id() != id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Action;
+
+    fn action_type(id: &str, action: &str) -> ActionType {
+        ActionType::new(id, vec![Action::new(action, action, false)])
+    }
+
+    fn ids(registered: &[ActionType]) -> Vec<&str> {
+        registered.iter().map(ActionType::id).collect()
+    }
+
+    #[test]
+    fn test_merge_overwrites_and_keeps() {
+        let mut registered = Vec::new();
+        merge(
+            &mut registered,
+            vec![
+                action_type("message", "reply"),
+                action_type("call", "answer"),
+            ],
+        );
+        merge(
+            &mut registered,
+            vec![
+                action_type("message", "read"),
+                action_type("invite", "accept"),
+            ],
+        );
+
+        assert_eq!(ids(&registered), ["message", "call", "invite"]);
+        assert_eq!(registered[0].actions()[0].id(), "read");
+
+        unregister(&mut registered, "call");
+        assert_eq!(ids(&registered), ["message", "invite"]);
+        unregister(&mut registered, "missing");
+        assert_eq!(registered.len(), 2);
+    }
+
+    #[test]
+    fn test_registry_skips_unchanged_sets() {
+        let types = || {
+            vec![
+                action_type("message", "reply"),
+                action_type("call", "answer"),
+            ]
+        };
+
+        let mut registry = Registry::default();
+        assert!(registry.apply(|registered| merge(registered, types())));
+        assert!(!registry.apply(|registered| merge(registered, types())));
+        assert!(
+            registry.apply(|registered| merge(registered, vec![action_type("call", "decline")]))
+        );
+        assert!(registry.apply(Vec::clear));
+        assert!(!registry.apply(Vec::clear));
+
+        // Next launch: the platform still has the set of the last one.
+        let mut relaunched = Registry {
+            target: Some(hash(&types())),
+            ..Registry::default()
+        };
+        assert!(!relaunched.apply(|registered| merge(registered, types())));
+        assert_eq!(ids(&relaunched.types), ["message", "call"]);
+    }
+
+    #[test]
+    fn test_failed_push_rolls_back_to_the_pushed_set() {
+        let mut registry = Registry::default();
+        registry.apply(|registered| merge(registered, vec![action_type("message", "reply")]));
+        let (types, generation) = registry.next_push().expect("nothing to push");
+        registry.finish_push(types, generation, true);
+        assert!(registry.next_push().is_none());
+
+        registry.apply(|registered| merge(registered, vec![action_type("call", "answer")]));
+        let (types, generation) = registry.next_push().expect("nothing to push");
+        registry.finish_push(types, generation, false);
+        assert_eq!(ids(&registry.types), ["message"]);
+        assert!(registry.next_push().is_none());
+        // The platform's set is unknown, so the same change is pushed again.
+        assert!(
+            registry.apply(|registered| merge(registered, vec![action_type("call", "answer")]))
+        );
+    }
+
+    #[test]
+    fn test_failed_push_keeps_newer_changes() {
+        let mut registry = Registry::default();
+        registry.apply(|registered| merge(registered, vec![action_type("message", "reply")]));
+        let (types, generation) = registry.next_push().expect("nothing to push");
+        registry.apply(|registered| merge(registered, vec![action_type("call", "answer")]));
+        registry.finish_push(types, generation, false);
+
+        assert_eq!(ids(&registry.types), ["message", "call"]);
+        let (types, _) = registry.next_push().expect("newer set not pushed");
+        assert_eq!(ids(&types), ["message", "call"]);
+    }
+
+    #[test]
+    fn test_hash_is_stable() {
+        assert_eq!(hash(&[]), 0x0961_2b07_b5ec_b5a5);
+    }
+}
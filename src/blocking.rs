@@ -0,0 +1,151 @@
+//! Synchronous facade over [`Notifications`] for callers that can't `.await`,
+//! such as menu and tray event handlers.
+//!
+//! Each call runs the async API on Tauri's async runtime and parks the
+//! calling thread until it completes. Calling it from a task already running
+//! on that runtime would stall one of its workers (or, on a current-thread
+//! runtime, deadlock), so those calls fail with
+//! [`Error::BlockingInRuntime`](crate::Error::BlockingInRuntime) instead.
+
+use std::future::Future;
+
+use tauri::{Runtime, plugin::PermissionState};
+
+use crate::{ActiveNotification, Notifications, NotificationsBuilder, PendingNotification};
+
+impl<R: Runtime> Notifications<R> {
+    /// Blocking versions of the notification APIs. Must not be used from
+    /// async code, see [`BlockingNotifications`].
+    #[must_use]
+    pub const fn blocking(&self) -> BlockingNotifications<'_, R> {
+        BlockingNotifications { inner: self }
+    }
+}
+
+/// Returned by [`Notifications::blocking`].
+///
+/// The methods wrapping async APIs block the current thread until the
+/// platform answers, and return
+/// [`Error::BlockingInRuntime`](crate::Error::BlockingInRuntime) when called
+/// from within an async runtime; use the async methods there instead.
+pub struct BlockingNotifications<'a, R: Runtime> {
+    inner: &'a Notifications<R>,
+}
+
+/// Fails if the current thread is driving an async runtime, where parking it
+/// could deadlock.
+fn ensure_outside_runtime() -> crate::Result<()> {
+    if tokio::runtime::Handle::try_current().is_ok() {
+        return Err(crate::Error::BlockingInRuntime);
+    }
+    Ok(())
+}
+
+fn block_on<F: Future>(future: F) -> crate::Result<F::Output> {
+    ensure_outside_runtime()?;
+    Ok(tauri::async_runtime::block_on(future))
+}
+
+impl<R: Runtime> BlockingNotifications<'_, R> {
+    /// Shows the notification built by `builder`, see
+    /// [`NotificationsBuilder::show`].
+    pub fn show(&self, builder: NotificationsBuilder<R>) -> crate::Result<()> {
+        block_on(builder.show())?
+    }
+
+    pub fn request_permission(&self) -> crate::Result<PermissionState> {
+        block_on(self.inner.request_permission())?
+    }
+
+    pub fn request_permission_after_rationale(&self) -> crate::Result<PermissionState> {
+        block_on(self.inner.request_permission_after_rationale())?
+    }
+
+    pub fn permission_state(&self) -> crate::Result<PermissionState> {
+        block_on(self.inner.permission_state())?
+    }
+
+    pub fn permission_state_fresh(&self) -> crate::Result<PermissionState> {
+        block_on(self.inner.permission_state_fresh())?
+    }
+
+    pub fn is_do_not_disturb_active(&self) -> crate::Result<bool> {
+        block_on(self.inner.is_do_not_disturb_active())?
+    }
+
+    pub fn get_unread_count(&self) -> crate::Result<u32> {
+        block_on(self.inner.get_unread_count())?
+    }
+
+    pub fn active(&self) -> crate::Result<Vec<ActiveNotification>> {
+        block_on(self.inner.active())?
+    }
+
+    pub fn active_grouped(&self) -> crate::Result<Vec<crate::ActiveGroup>> {
+        block_on(self.inner.active_grouped())?
+    }
+
+    pub fn flush_group(&self, group: &str) -> crate::Result<()> {
+        block_on(self.inner.flush_group(group))
+    }
+
+    pub fn notification_history(&self) -> crate::Result<Vec<ActiveNotification>> {
+        block_on(self.inner.notification_history())?
+    }
+
+    pub fn doctor(&self) -> crate::Result<Vec<crate::Diagnostic>> {
+        block_on(self.inner.doctor())
+    }
+
+    pub fn pending(&self) -> crate::Result<Vec<PendingNotification>> {
+        block_on(self.inner.pending())?
+    }
+
+    pub fn cancel(&self, notifications: Vec<i32>) -> crate::Result<()> {
+        self.inner.cancel(notifications)
+    }
+
+    pub fn dismiss(&self, id: i32) -> crate::Result<crate::DismissOutcome> {
+        block_on(self.inner.dismiss(id))?
+    }
+
+    pub fn clear_scope(&self, scope: &str) -> crate::Result<crate::ScopeClearance> {
+        block_on(self.inner.clear_scope(scope))?
+    }
+
+    pub fn cancel_all(&self) -> crate::Result<()> {
+        self.inner.cancel_all()
+    }
+
+    pub fn remove_active(&self, notifications: Vec<i32>) -> crate::Result<()> {
+        self.inner.remove_active(notifications)
+    }
+
+    pub fn remove_all_active(&self) -> crate::Result<()> {
+        self.inner.remove_all_active()
+    }
+
+    pub fn patch_active(
+        &self,
+        id: i32,
+        patch: crate::ActivePatch,
+    ) -> crate::Result<crate::PatchStrategy> {
+        block_on(self.inner.patch_active(id, patch))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_on_outside_runtime() {
+        assert_eq!(block_on(async { 7 }).expect("Failed to block on"), 7);
+    }
+
+    #[test]
+    fn test_block_on_inside_runtime_fails_instead_of_deadlocking() {
+        let result = tauri::async_runtime::block_on(async { block_on(async { 7 }) });
+        assert!(matches!(result, Err(crate::Error::BlockingInRuntime)));
+    }
+}
@@ -0,0 +1,375 @@
+//! Presentation defaults bound to a channel with
+//! [`Notifications::bind_channel_defaults`], kept in
+//! `<app data dir>/channel-defaults.json`.
+//!
+//! Every backend's `show` fills the fields a notification leaves unset from
+//! the defaults bound to its channel, then from
+//! [`PluginConfig::notification_defaults`](crate::PluginConfig::notification_defaults).
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::{NotificationData, Notifications, telemetry};
+
+const FILE_NAME: &str = "channel-defaults.json";
+
+/// Managed state holding the bound channel defaults and the config defaults.
+pub struct ChannelDefaults {
+    path: Option<PathBuf>,
+    config: Option<NotificationData>,
+    channels: RwLock<HashMap<String, NotificationData>>,
+}
+
+impl ChannelDefaults {
+    /// Loads the defaults stored in the app data dir. A missing or unreadable
+    /// file starts out empty.
+    pub fn load<R: Runtime>(app: &AppHandle<R>, config: Option<NotificationData>) -> Self {
+        let path = app
+            .path()
+            .app_data_dir()
+            .ok()
+            .map(|dir| dir.join(FILE_NAME));
+        let channels = path.as_deref().map(read).unwrap_or_default();
+        Self {
+            path,
+            config,
+            channels: RwLock::new(channels),
+        }
+    }
+
+    fn channels(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, NotificationData>> {
+        self.channels
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Runs `update` on the bound defaults and persists the result.
+    fn update<T>(
+        &self,
+        update: impl FnOnce(&mut HashMap<String, NotificationData>) -> T,
+    ) -> crate::Result<T> {
+        let mut channels = self
+            .channels
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let result = update(&mut channels);
+        // Written under the lock so concurrent updates land in order.
+        if let Some(path) = &self.path {
+            write(path, &channels)?;
+        }
+        drop(channels);
+        Ok(result)
+    }
+
+    /// Fills the fields `data` leaves unset, see [`apply`].
+    pub fn apply(&self, data: &mut NotificationData) {
+        let channels = self.channels();
+        apply(data, &channels, self.config.as_ref());
+    }
+
+    /// The channels the defaults refer to, sorted: the config's
+    /// `channel_id` and every channel with bound defaults.
+    #[cfg(target_os = "android")]
+    pub fn referenced_channels(&self) -> Vec<String> {
+        let channels = self.channels();
+        let referenced: std::collections::BTreeSet<&String> = self
+            .config
+            .as_ref()
+            .and_then(|config| config.channel_id.as_ref())
+            .into_iter()
+            .chain(channels.keys())
+            .collect();
+        referenced.into_iter().cloned().collect()
+    }
+}
+
+/// Fills the fields `data` leaves unset from the defaults of its channel,
+/// then from `config`. A channel set only by `config` still picks up that
+/// channel's defaults.
+fn apply(
+    data: &mut NotificationData,
+    channels: &HashMap<String, NotificationData>,
+    config: Option<&NotificationData>,
+) {
+    if data.channel_id.is_none() {
+        data.channel_id = config.and_then(|config| config.channel_id.clone());
+    }
+    if let Some(defaults) = data
+        .channel_id
+        .as_ref()
+        .and_then(|channel| channels.get(channel))
+    {
+        merge(data, defaults);
+    }
+    if let Some(config) = config {
+        merge(data, config);
+    }
+}
+
+/// Copies every field of `defaults` that `data` leaves unset: `None`
+/// options, empty lists and `false` flags. `extra` keys missing from `data`
+/// are added. `id`, `schedule` and `count_down_to` are never copied.
+fn merge(data: &mut NotificationData, defaults: &NotificationData) {
+    fn fill<T: Clone>(value: &mut Option<T>, default: Option<&T>) {
+        if value.is_none() {
+            *value = default.cloned();
+        }
+    }
+    fn fill_list<T: Clone>(value: &mut Vec<T>, default: &[T]) {
+        if value.is_empty() {
+            value.extend_from_slice(default);
+        }
+    }
+
+    fill(&mut data.channel_id, defaults.channel_id.as_ref());
+    fill(
+        &mut data.channel_id_fallback,
+        defaults.channel_id_fallback.as_ref(),
+    );
+    fill(&mut data.title, defaults.title.as_ref());
+    fill(&mut data.body, defaults.body.as_ref());
+    fill(&mut data.large_body, defaults.large_body.as_ref());
+    fill(&mut data.summary, defaults.summary.as_ref());
+    fill(&mut data.action_type_id, defaults.action_type_id.as_ref());
+    fill(&mut data.group, defaults.group.as_ref());
+    fill(&mut data.sound, defaults.sound.as_ref());
+    fill(&mut data.icon, defaults.icon.as_ref());
+    fill(&mut data.large_icon, defaults.large_icon.as_ref());
+    fill(&mut data.icon_color, defaults.icon_color.as_ref());
+    fill(
+        &mut data.foreground_color,
+        defaults.foreground_color.as_ref(),
+    );
+    fill(&mut data.focus_window, defaults.focus_window.as_ref());
+    fill(&mut data.media_controls, defaults.media_controls.as_ref());
+    fill(&mut data.ticker, defaults.ticker.as_ref());
+    fill_list(&mut data.inbox_lines, &defaults.inbox_lines);
+    fill_list(&mut data.attachments, &defaults.attachments);
+    fill_list(&mut data.wearable_actions, &defaults.wearable_actions);
+    for (key, value) in &defaults.extra {
+        data.extra
+            .entry(key.clone())
+            .or_insert_with(|| value.clone());
+    }
+    data.group_summary |= defaults.group_summary;
+    data.announce_only |= defaults.announce_only;
+    data.local_only |= defaults.local_only;
+    data.ongoing |= defaults.ongoing;
+    data.auto_cancel |= defaults.auto_cancel;
+    data.silent |= defaults.silent;
+    data.persistent |= defaults.persistent;
+    data.transient |= defaults.transient;
+    data.require_interaction |= defaults.require_interaction;
+}
+
+/// `defaults` as JSON that deserializes back into the same
+/// [`NotificationData`]: unlike its `Serialize` impl (the native plugins'
+/// wire format), this keeps the desktop-only fields and `extra` as an object,
+/// and drops the meaningless `id`.
+pub fn to_json(defaults: &NotificationData) -> serde_json::Value {
+    let mut value = serde_json::to_value(defaults).unwrap_or_default();
+    if let Some(object) = value.as_object_mut() {
+        object.remove("id");
+        object.remove("extraJson");
+        if !defaults.extra.is_empty() {
+            object.insert(
+                "extra".into(),
+                serde_json::Value::Object(defaults.extra.clone().into_iter().collect()),
+            );
+        }
+        let desktop_only = [
+            (
+                "foregroundColor",
+                serde_json::json!(defaults.foreground_color),
+            ),
+            ("focusWindow", serde_json::json!(defaults.focus_window)),
+            ("persistent", defaults.persistent.into()),
+            ("transient", defaults.transient.into()),
+            ("requireInteraction", defaults.require_interaction.into()),
+        ];
+        object.extend(desktop_only.map(|(key, value)| (key.to_string(), value)));
+    }
+    value
+}
+
+fn write(path: &Path, channels: &HashMap<String, NotificationData>) -> crate::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let stored: serde_json::Map<String, serde_json::Value> = channels
+        .iter()
+        .map(|(channel, defaults)| (channel.clone(), to_json(defaults)))
+        .collect();
+    let json = serde_json::to_vec_pretty(&stored).map_err(std::io::Error::other)?;
+    std::fs::write(path, json)?;
+    Ok(())
+}
+
+fn read(path: &Path) -> HashMap<String, NotificationData> {
+    let Ok(json) = std::fs::read(path) else {
+        return HashMap::new();
+    };
+    serde_json::from_slice(&json).unwrap_or_else(|e| {
+        telemetry::warning!("Ignoring unreadable channel defaults in {path:?}: {e}");
+        HashMap::new()
+    })
+}
+
+impl<R: Runtime> Notifications<R> {
+    /// Binds presentation defaults to `channel_id`: every notification shown
+    /// on that channel takes the fields it leaves unset from `defaults`
+    /// before falling back to
+    /// [`PluginConfig::notification_defaults`](crate::PluginConfig::notification_defaults).
+    /// Replaces any defaults already bound to the channel and persists them
+    /// across restarts.
+    ///
+    /// Flags can only be turned on by defaults: a notification can't opt out
+    /// of `silent` on a channel whose defaults set it. `id`, `schedule` and
+    /// `count_down_to` are ignored.
+    ///
+    /// ```no_run
+    /// use tauri_plugin_notifications::{NotificationData, NotificationsExt};
+    ///
+    /// fn bind_downloads<R: tauri::Runtime>(
+    ///     app: &tauri::AppHandle<R>,
+    /// ) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let defaults: NotificationData =
+    ///         serde_json::from_value(serde_json::json!({ "ongoing": true, "silent": true }))?;
+    ///     app.notifications().bind_channel_defaults("downloads", defaults)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn bind_channel_defaults(
+        &self,
+        channel_id: impl Into<String>,
+        defaults: NotificationData,
+    ) -> crate::Result<()> {
+        let channel_id = channel_id.into();
+        self.with_channel_defaults(|state| {
+            state.update(|channels| channels.insert(channel_id, defaults))
+        })
+        .map(|_| ())
+    }
+
+    /// The defaults bound to `channel_id`, if any.
+    #[must_use]
+    pub fn get_channel_defaults(&self, channel_id: &str) -> Option<NotificationData> {
+        self.with_channel_defaults(|state| state.channels().get(channel_id).cloned())
+    }
+
+    /// Unbinds the defaults of `channel_id`. Returns whether any were bound.
+    pub fn clear_channel_defaults(&self, channel_id: &str) -> crate::Result<bool> {
+        self.with_channel_defaults(|state| {
+            state.update(|channels| channels.remove(channel_id).is_some())
+        })
+    }
+
+    fn with_channel_defaults<T>(&self, f: impl FnOnce(&ChannelDefaults) -> T) -> T {
+        let builder = self.builder();
+        f(&builder.app_handle().state::<ChannelDefaults>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(json: serde_json::Value) -> NotificationData {
+        serde_json::from_value(json).expect("Failed to deserialize notification data")
+    }
+
+    #[test]
+    fn test_merge_precedence() {
+        let channels = HashMap::from([(
+            "downloads".to_string(),
+            data(serde_json::json!({
+                "ongoing": true,
+                "silent": true,
+                "icon": "download",
+                "sound": "channel.wav",
+                "extra": { "kind": "download", "source": "channel" },
+            })),
+        )]);
+        let config = data(serde_json::json!({
+            "icon": "app",
+            "sound": "config.wav",
+            "summary": "From config",
+            "autoCancel": true,
+        }));
+
+        let mut explicit = data(serde_json::json!({
+            "id": 7,
+            "channelId": "downloads",
+            "icon": "explicit",
+            "extra": { "source": "explicit" },
+        }));
+        apply(&mut explicit, &channels, Some(&config));
+
+        assert_eq!(explicit.id, 7);
+        // Explicit values win over both.
+        assert_eq!(explicit.icon.as_deref(), Some("explicit"));
+        assert_eq!(explicit.extra["source"], "explicit");
+        // Channel defaults win over config defaults.
+        assert_eq!(explicit.sound.as_deref(), Some("channel.wav"));
+        assert_eq!(explicit.extra["kind"], "download");
+        assert!(explicit.ongoing && explicit.silent);
+        // Config defaults fill whatever is left.
+        assert_eq!(explicit.summary.as_deref(), Some("From config"));
+        assert!(explicit.auto_cancel);
+    }
+
+    #[test]
+    fn test_merge_without_channel_defaults() {
+        let config = data(serde_json::json!({ "channelId": "downloads", "icon": "app" }));
+        let channels = HashMap::from([(
+            "downloads".to_string(),
+            data(serde_json::json!({ "icon": "download" })),
+        )]);
+
+        let mut other = data(serde_json::json!({ "channelId": "chat" }));
+        apply(&mut other, &channels, Some(&config));
+        assert_eq!(other.icon.as_deref(), Some("app"));
+        assert!(!other.ongoing);
+
+        // A channel set by the config defaults picks up its channel defaults.
+        let mut unset = data(serde_json::json!({}));
+        apply(&mut unset, &channels, Some(&config));
+        assert_eq!(unset.channel_id.as_deref(), Some("downloads"));
+        assert_eq!(unset.icon.as_deref(), Some("download"));
+
+        let mut untouched = data(serde_json::json!({ "title": "Hi" }));
+        apply(&mut untouched, &channels, None);
+        assert_eq!(untouched.title.as_deref(), Some("Hi"));
+        assert_eq!(untouched.icon, None);
+    }
+
+    #[test]
+    fn test_write_then_read_keeps_desktop_fields() {
+        let dir =
+            std::env::temp_dir().join(format!("channel-defaults-test-{}", std::process::id()));
+        let path = dir.join(FILE_NAME);
+        assert!(read(&path).is_empty());
+
+        let defaults = data(serde_json::json!({
+            "ongoing": true,
+            "requireInteraction": true,
+            "focusWindow": "main",
+            "foregroundColor": [1, 2, 3],
+            "extra": { "kind": "download" },
+        }));
+        write(&path, &HashMap::from([("downloads".to_string(), defaults)]))
+            .expect("Failed to write channel defaults");
+
+        let read = read(&path);
+        let defaults = &read["downloads"];
+        assert!(defaults.ongoing && defaults.require_interaction);
+        assert_eq!(defaults.focus_window.as_deref(), Some("main"));
+        assert_eq!(defaults.foreground_color, Some((1, 2, 3)));
+        assert_eq!(defaults.extra["kind"], "download");
+
+        std::fs::remove_dir_all(&dir).expect("Failed to remove test dir");
+    }
+}
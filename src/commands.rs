@@ -28,12 +28,33 @@ pub async fn is_permission_granted<R: Runtime>(
     }
 }
 
+#[command]
+pub async fn is_do_not_disturb_active<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+) -> Result<bool> {
+    notification.is_do_not_disturb_active().await
+}
+
+#[command]
+pub async fn get_unread_count<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+) -> Result<u32> {
+    notification.get_unread_count().await
+}
+
 #[command]
 pub async fn request_permission<R: Runtime>(
     _app: AppHandle<R>,
     notification: State<'_, Notifications<R>>,
+    after_rationale: Option<bool>,
 ) -> Result<PermissionState> {
-    notification.request_permission().await
+    if after_rationale.unwrap_or(false) {
+        notification.request_permission_after_rationale().await
+    } else {
+        notification.request_permission().await
+    }
 }
 
 #[command]
@@ -44,6 +65,14 @@ pub async fn register_for_push_notifications<R: Runtime>(
     notification.register_for_push_notifications().await
 }
 
+#[command]
+pub async fn get_push_token_cached<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+) -> Result<Option<String>> {
+    Ok(notification.get_push_token_cached().await)
+}
+
 #[command]
 pub async fn unregister_for_push_notifications<R: Runtime>(
     _app: AppHandle<R>,
@@ -93,10 +122,26 @@ pub async fn notify<R: Runtime>(
     _app: AppHandle<R>,
     notification: State<'_, Notifications<R>>,
     options: NotificationData,
-) -> Result<()> {
-    let mut builder = notification.builder();
-    builder.data = options;
-    builder.show().await
+) -> Result<i32> {
+    notification.show_notification(options).await
+}
+
+#[command]
+pub async fn batch<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    options: Vec<NotificationData>,
+) -> Result<Vec<i32>> {
+    notification.show_batch(options).await
+}
+
+#[command]
+pub async fn batch_detailed<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    options: Vec<NotificationData>,
+) -> Result<crate::BulkResult> {
+    Ok(notification.show_batch_detailed(options).await)
 }
 
 #[command]
@@ -104,10 +149,38 @@ pub async fn register_action_types<R: Runtime>(
     _app: AppHandle<R>,
     notification: State<'_, Notifications<R>>,
     types: Vec<crate::ActionType>,
-) -> Result<()> {
+) -> Result<crate::ActionTypesUpdate> {
+    crate::validate_action_types(&types)?;
     notification.register_action_types(types)
 }
 
+#[command]
+pub async fn set_notification_categories<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    types: Vec<crate::ActionType>,
+) -> Result<crate::ActionTypesUpdate> {
+    crate::validate_action_types(&types)?;
+    notification.set_notification_categories(types)
+}
+
+#[command]
+pub async fn unregister_action_type<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    id: String,
+) -> Result<crate::ActionTypesUpdate> {
+    notification.unregister_action_type(&id)
+}
+
+#[command]
+pub async fn clear_action_types<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+) -> Result<crate::ActionTypesUpdate> {
+    notification.clear_action_types()
+}
+
 #[command]
 pub async fn get_pending<R: Runtime>(
     _app: AppHandle<R>,
@@ -116,6 +189,14 @@ pub async fn get_pending<R: Runtime>(
     notification.pending().await
 }
 
+#[command]
+pub async fn get_pending_count_by_channel<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+) -> Result<std::collections::HashMap<String, usize>> {
+    notification.pending_count_by_channel().await
+}
+
 #[command]
 pub async fn get_active<R: Runtime>(
     _app: AppHandle<R>,
@@ -124,6 +205,99 @@ pub async fn get_active<R: Runtime>(
     notification.active().await
 }
 
+#[command]
+pub async fn get_active_grouped<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+) -> Result<Vec<crate::ActiveGroup>> {
+    notification.active_grouped().await
+}
+
+#[command]
+pub fn update_scheduled_notification<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    id: i32,
+    data: NotificationData,
+) -> Result<()> {
+    notification.update_scheduled_notification(id, &data)
+}
+
+#[command]
+pub async fn flush_group<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    group: String,
+) -> Result<()> {
+    notification.flush_group(&group).await;
+    Ok(())
+}
+
+#[command]
+pub async fn get_notification_history<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+) -> Result<Vec<crate::ActiveNotification>> {
+    notification.notification_history().await
+}
+
+#[command]
+pub async fn run_doctor<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+) -> Result<Vec<crate::Diagnostic>> {
+    Ok(notification.doctor().await)
+}
+
+#[command]
+pub fn get_notification_stats<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    range: Option<crate::StatsRange>,
+) -> Result<crate::NotificationStats> {
+    notification.stats(&range.unwrap_or_default())
+}
+
+#[command]
+pub fn reset_stats<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+) -> Result<()> {
+    notification.reset_stats()
+}
+
+#[command]
+pub fn get_schedule_capabilities<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+) -> crate::ScheduleCapabilities {
+    notification.schedule_capabilities()
+}
+
+#[command]
+pub fn get_plugin_info<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+) -> crate::PluginInfo {
+    notification.plugin_info()
+}
+
+#[command]
+pub fn get_launch_source<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+) -> crate::LaunchSource {
+    notification.launch_source()
+}
+
+#[command]
+pub fn register_channel_blocked_listener<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+) -> Result<()> {
+    notification.register_channel_blocked_listener()
+}
+
 #[command]
 pub fn set_click_listener_active<R: Runtime>(
     _app: AppHandle<R>,
@@ -143,6 +317,16 @@ pub fn remove_active<R: Runtime>(
     notification.remove_active(ids)
 }
 
+#[command]
+pub fn remove_active_detailed<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    notifications: Vec<i32>,
+) -> Result<crate::BulkResult> {
+    notification.remove_active_detailed(notifications)
+}
+
+/// Same as [`remove_all_active`], kept for callers invoking it directly.
 #[command]
 pub fn remove_all<R: Runtime>(
     _app: AppHandle<R>,
@@ -151,6 +335,14 @@ pub fn remove_all<R: Runtime>(
     notification.remove_all_active()
 }
 
+#[command]
+pub fn remove_all_active<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+) -> Result<()> {
+    notification.remove_all_active()
+}
+
 #[command]
 pub fn cancel<R: Runtime>(
     _app: AppHandle<R>,
@@ -160,6 +352,52 @@ pub fn cancel<R: Runtime>(
     notification.cancel(notifications)
 }
 
+#[command]
+pub fn cancel_detailed<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    notifications: Vec<i32>,
+) -> Result<crate::BulkResult> {
+    notification.cancel_detailed(notifications)
+}
+
+#[command]
+pub fn cancel_by_group<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    group: String,
+) -> Result<Vec<i32>> {
+    notification.cancel_by_group(&group)
+}
+
+#[command]
+pub async fn patch_active<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    id: i32,
+    patch: crate::ActivePatch,
+) -> Result<crate::PatchStrategy> {
+    notification.patch_active(id, patch).await
+}
+
+#[command]
+pub async fn dismiss<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    id: i32,
+) -> Result<crate::DismissOutcome> {
+    notification.dismiss(id).await
+}
+
+#[command]
+pub async fn clear_scope<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    scope: String,
+) -> Result<crate::ScopeClearance> {
+    notification.clear_scope(&scope).await
+}
+
 #[command]
 pub fn cancel_all<R: Runtime>(
     _app: AppHandle<R>,
@@ -173,17 +411,27 @@ pub fn create_channel<R: Runtime>(
     _app: AppHandle<R>,
     notification: State<'_, Notifications<R>>,
     channel: crate::Channel,
-) -> Result<()> {
+) -> Result<crate::ChannelCreation> {
     notification.create_channel(channel)
 }
 
+#[command]
+pub fn ensure_channels<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    channels: Vec<crate::Channel>,
+) -> Result<Vec<crate::ChannelCreation>> {
+    notification.ensure_channels(channels)
+}
+
 #[command]
 pub fn delete_channel<R: Runtime>(
     _app: AppHandle<R>,
     notification: State<'_, Notifications<R>>,
     id: String,
-) -> Result<()> {
-    notification.delete_channel(id)
+    on_pending: Option<crate::OnPendingPolicy>,
+) -> Result<crate::ChannelDeletion> {
+    notification.delete_channel(id, on_pending.unwrap_or_default())
 }
 
 #[command]
@@ -193,3 +441,72 @@ pub fn list_channels<R: Runtime>(
 ) -> Result<Vec<crate::Channel>> {
     notification.list_channels()
 }
+
+#[command]
+pub fn get_channel<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    id: String,
+) -> Result<Option<crate::Channel>> {
+    notification.get_channel(&id)
+}
+
+#[command]
+pub fn get_channel_defaults<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    channel_id: String,
+) -> Option<serde_json::Value> {
+    notification
+        .get_channel_defaults(&channel_id)
+        .as_ref()
+        .map(crate::channel_defaults::to_json)
+}
+
+#[command]
+pub fn clear_channel_defaults<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    channel_id: String,
+) -> Result<bool> {
+    notification.clear_channel_defaults(&channel_id)
+}
+
+#[command]
+pub fn get_ignored_fields(options: NotificationData) -> Vec<&'static str> {
+    crate::field_support::ignored_fields(&options, crate::Backend::current())
+}
+
+#[cfg(all(debug_assertions, target_os = "windows", not(feature = "notify-rust")))]
+#[command]
+pub fn get_toast_xml<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    options: NotificationData,
+) -> Result<String> {
+    notification.toast_notification_xml(&options)
+}
+
+#[cfg(all(desktop, feature = "test-utils"))]
+#[command]
+pub async fn simulate_click<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    id: i32,
+) -> Result<()> {
+    notification.simulate_click(id).await
+}
+
+#[cfg(all(desktop, feature = "test-utils"))]
+#[command]
+pub async fn simulate_action<R: Runtime>(
+    _app: AppHandle<R>,
+    notification: State<'_, Notifications<R>>,
+    id: i32,
+    action_id: String,
+    input_value: Option<String>,
+) -> Result<()> {
+    notification
+        .simulate_action(id, &action_id, input_value)
+        .await
+}
@@ -23,6 +23,7 @@ struct ActiveEntry {
     handle: notify_rust::NotificationHandle,
     title: Option<String>,
     body: Option<String>,
+    scope: Option<String>,
 }
 
 // Signature must match the iOS/Android `init` so the cfg-gated call sites in `lib.rs::init` compile uniformly.
@@ -39,6 +40,10 @@ pub fn init<R: Runtime, C: DeserializeOwned>(
         active_counter: std::sync::atomic::AtomicU64::new(0),
         #[cfg(all(target_os = "linux", feature = "push-notifications"))]
         unifiedpush: tokio::sync::OnceCell::new(),
+        #[cfg(all(not(target_os = "linux"), feature = "notify-rust-active-cache"))]
+        active_cache: ActiveCache::default(),
+        #[cfg(windows)]
+        app_id: std::sync::RwLock::new(None),
     })
 }
 
@@ -60,6 +65,87 @@ pub struct Notifications<R: Runtime> {
     active_counter: std::sync::atomic::AtomicU64,
     #[cfg(all(target_os = "linux", feature = "push-notifications"))]
     unifiedpush: tokio::sync::OnceCell<std::sync::Arc<crate::unifiedpush::UnifiedPushState>>,
+    #[cfg(all(not(target_os = "linux"), feature = "notify-rust-active-cache"))]
+    active_cache: ActiveCache,
+    /// AUMID override from [`Notifications::set_app_id`].
+    #[cfg(windows)]
+    app_id: std::sync::RwLock<Option<String>>,
+}
+
+#[cfg(windows)]
+impl<R: Runtime> Notifications<R> {
+    /// Shows later notifications under `app_id` instead of the bundle
+    /// identifier, e.g. the AUMID of the installer's Start Menu shortcut.
+    /// Set from [`PluginConfig::windows_app_id`](crate::PluginConfig::windows_app_id)
+    /// during plugin setup.
+    pub fn set_app_id(&self, app_id: impl Into<String>) -> crate::Result<()> {
+        let app_id = app_id.into();
+        crate::validate_windows_app_id(&app_id)?;
+        *self
+            .app_id
+            .write()
+            .map_err(|_| crate::Error::Io(std::io::Error::other("Lock poisoned")))? = Some(app_id);
+        Ok(())
+    }
+}
+
+/// AUMID passed to notify-rust: the [`Notifications::set_app_id`] override on
+/// Windows, otherwise the bundle identifier.
+fn toast_app_id<R: Runtime>(app: &AppHandle<R>) -> String {
+    #[cfg(windows)]
+    {
+        use tauri::Manager;
+        let app_id = app
+            .try_state::<Notifications<R>>()
+            .and_then(|state| state.app_id.read().ok()?.clone());
+        if let Some(app_id) = app_id {
+            return app_id;
+        }
+    }
+    app.config().identifier.clone()
+}
+
+/// How long the `notify-rust-active-cache` feature reports a notification as
+/// active on macOS / Windows. notify-rust has no close callback there, so
+/// this stands in for the notification leaving the screen and the list.
+#[cfg(all(not(target_os = "linux"), feature = "notify-rust-active-cache"))]
+const ACTIVE_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+/// Notifications shown on macOS / Windows by id, each with the time it stops
+/// being reported by `active()`.
+#[cfg(all(not(target_os = "linux"), feature = "notify-rust-active-cache"))]
+type ActiveCacheMap =
+    std::collections::HashMap<i32, (crate::ActiveNotification, std::time::Instant)>;
+
+#[cfg(all(not(target_os = "linux"), feature = "notify-rust-active-cache"))]
+#[derive(Default)]
+struct ActiveCache(std::sync::RwLock<ActiveCacheMap>);
+
+#[cfg(all(not(target_os = "linux"), feature = "notify-rust-active-cache"))]
+impl ActiveCache {
+    fn entries(&self) -> std::sync::RwLockWriteGuard<'_, ActiveCacheMap> {
+        match self.0.write() {
+            Ok(entries) => entries,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn insert(&self, notification: crate::ActiveNotification, now: std::time::Instant) {
+        self.entries()
+            .insert(notification.id(), (notification, now + ACTIVE_CACHE_TTL));
+    }
+
+    /// Drops the expired entries and returns the others, ordered by id.
+    fn list(&self, now: std::time::Instant) -> Vec<crate::ActiveNotification> {
+        let mut entries = self.entries();
+        entries.retain(|_, (_, expires_at)| *expires_at > now);
+        let mut active: Vec<_> = entries
+            .values()
+            .map(|(notification, _)| notification.clone())
+            .collect();
+        active.sort_by_key(crate::ActiveNotification::id);
+        active
+    }
 }
 
 #[cfg(target_os = "linux")]
@@ -73,8 +159,12 @@ fn active_lock_err(e: impl std::fmt::Display) -> crate::Error {
 impl<R: Runtime> Notifications<R> {
     /// Finds every tracked notification whose caller id is in `caller_ids`,
     /// removes them from the active map, and dispatches `handle.close()` on
-    /// the blocking pool so the command call returns quickly.
-    fn close_by_caller_ids(&self, caller_ids: &[i32]) -> crate::Result<()> {
+    /// the blocking pool so the command call returns quickly. Returns the
+    /// caller ids that had a tracked notification.
+    fn close_by_caller_ids(
+        &self,
+        caller_ids: &[i32],
+    ) -> crate::Result<std::collections::HashSet<i32>> {
         let mut to_close: Vec<ActiveEntry> = Vec::new();
         {
             let mut active = self.active.lock().map_err(active_lock_err)?;
@@ -96,10 +186,34 @@ impl<R: Runtime> Notifications<R> {
                 .collect();
             *active = kept;
         }
+        let closed = to_close.iter().map(|entry| entry.caller_id).collect();
         for entry in to_close {
             tauri::async_runtime::spawn_blocking(move || entry.handle.close());
         }
-        Ok(())
+        Ok(closed)
+    }
+
+    /// [`close_by_caller_ids`](Self::close_by_caller_ids) with the outcome of
+    /// each id. Ids with no tracked notification fail with
+    /// [`Error::NotFound`](crate::Error::NotFound).
+    fn close_by_caller_ids_detailed(
+        &self,
+        caller_ids: Vec<i32>,
+    ) -> crate::Result<crate::BulkResult> {
+        let closed = self.close_by_caller_ids(&caller_ids)?;
+        Ok(caller_ids
+            .into_iter()
+            .map(|id| {
+                let outcome = if closed.contains(&id) {
+                    Ok(())
+                } else {
+                    Err(crate::Error::NotFound(format!(
+                        "no active notification with id {id}"
+                    )))
+                };
+                (id, outcome)
+            })
+            .collect())
     }
 }
 
@@ -138,7 +252,7 @@ impl<R: Runtime> Notifications<R> {
                 ) {
                     Ok(n) => n,
                     Err(e) => {
-                        log::warn!("Failed to build push notification: {e}");
+                        crate::telemetry::warning!("Failed to build push notification: {e}");
                         return;
                     }
                 };
@@ -153,6 +267,7 @@ impl<R: Runtime> Notifications<R> {
                             handle,
                             title,
                             body,
+                            scope: None,
                         };
                         let lock = state.active.lock();
                         match lock {
@@ -160,12 +275,16 @@ impl<R: Runtime> Notifications<R> {
                                 active.insert(entry_id, entry);
                             }
                             Err(poisoned) => {
-                                log::warn!("active notifications mutex was poisoned; recovering");
+                                crate::telemetry::warning!(
+                                    "active notifications mutex was poisoned; recovering"
+                                );
                                 poisoned.into_inner().insert(entry_id, entry);
                             }
                         }
                     }
-                    Err(e) => log::warn!("Failed to show push notification toast: {e}"),
+                    Err(e) => {
+                        crate::telemetry::warning!("Failed to show push notification toast: {e}");
+                    }
                 }
             });
         })
@@ -174,37 +293,69 @@ impl<R: Runtime> Notifications<R> {
 
 // `async` and `Result` mirror the mobile/macOS plugin API so callers can `.await` and `?` uniformly.
 impl<R: Runtime> crate::NotificationsBuilder<R> {
-    pub async fn show(self) -> crate::Result<()> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            err,
+            fields(
+                platform = crate::telemetry::BACKEND,
+                id = self.data.id,
+                channel = self.data.channel_id.as_deref(),
+                scheduled = self.data.schedule.is_some()
+            )
+        )
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn show(mut self) -> crate::Result<()> {
+        self.apply_defaults()?;
+        self.apply_quiet_hours();
+        self.check_extra_size()?;
+        self.check_platform_options()?;
+        self.resolve_flag_conflicts()?;
+        self.apply_inbox_lines()?;
+        self.report_ignored_fields();
+        if self.suppress_if_window_focused() {
+            return Ok(());
+        }
+        if let Some(result) = self.coalesce().await {
+            return result;
+        }
+        let shown = self.data.clone();
+        // Fetched before the dispatcher so a slow host only holds up this
+        // notification. `shown` keeps the original URL.
+        #[cfg(feature = "media-cache")]
+        if let Some(icon) = self.data.icon.take() {
+            let local = crate::media_cache::localize_all(&self.app, [icon.as_str()])
+                .await
+                .remove(&icon);
+            self.data.icon = Some(local.map_or(icon, |path| path.display().to_string()));
+        }
+        crate::dispatch::deliver_in_group(self.dispatcher(), shown, self.deliver()).await
+    }
+
+    async fn deliver(self) -> crate::Result<()> {
         let caller_id = self.data.id;
-        let title = self
-            .data
-            .title
-            .or_else(|| self.app.config().product_name.clone());
+        let persistence = imp::Persistence::of(&self.data);
+        let title = self.data.title;
         let body = self.data.body;
+        #[cfg(target_os = "linux")]
+        let scope = self.data.scope;
         let icon = self.data.icon;
-        let identifier = self.app.config().identifier.clone();
+        let identifier = toast_app_id(&self.app);
         let app = self.app.clone();
 
-        let notification = imp::build_notification(
+        let mut notification = imp::build_notification(
             title.as_deref(),
             body.as_deref(),
             icon.as_deref(),
             &identifier,
         )?;
+        persistence.apply(&mut notification);
 
-        // `notify_rust::Notification::show()` is sync and runs an internal
-        // blocking D-Bus call (via zbus's `block_on`). Calling it inside
-        // `async_runtime::spawn` panics with "Cannot start a runtime from
-        // within a runtime"; `spawn_blocking` parks it on a blocking thread.
-        // We `.await` the join so we can capture the handle for tracking and
-        // surface any error to the caller.
-        let join_result = tauri::async_runtime::spawn_blocking(move || notification.show())
-            .await
-            .map_err(|e| {
-                crate::Error::Io(std::io::Error::other(format!(
-                    "notification spawn_blocking join error: {e}"
-                )))
-            })?;
+        // Awaited so the handle can be tracked and any error reaches the
+        // caller.
+        let join_result = imp::deliver(move || notification.show()).await?;
 
         match join_result {
             #[cfg(target_os = "linux")]
@@ -218,6 +369,7 @@ impl<R: Runtime> crate::NotificationsBuilder<R> {
                     handle,
                     title,
                     body,
+                    scope,
                 };
                 // Take the lock into a binding so its `MutexGuard` temporary
                 // doesn't outlive `state` in the `match` arms.
@@ -227,7 +379,9 @@ impl<R: Runtime> crate::NotificationsBuilder<R> {
                         active.insert(entry_id, entry);
                     }
                     Err(poisoned) => {
-                        log::warn!("active notifications mutex was poisoned; recovering");
+                        crate::telemetry::warning!(
+                            "active notifications mutex was poisoned; recovering"
+                        );
                         poisoned.into_inner().insert(entry_id, entry);
                     }
                 }
@@ -237,6 +391,15 @@ impl<R: Runtime> crate::NotificationsBuilder<R> {
             // there's nothing to keep alive.
             #[cfg(any(target_os = "macos", target_os = "windows"))]
             Ok(_) => {
+                #[cfg(feature = "notify-rust-active-cache")]
+                {
+                    use tauri::Manager;
+                    app.state::<Notifications<R>>().active_cache.insert(
+                        crate::ActiveNotification::new(caller_id, title, body),
+                        std::time::Instant::now(),
+                    );
+                }
+                #[cfg(not(feature = "notify-rust-active-cache"))]
                 let _ = (caller_id, title, body, app);
             }
             // Propagate the underlying `notify-rust` failure (missing
@@ -261,6 +424,7 @@ impl<R: Runtime> Notifications<R> {
         NotificationsBuilder::new(self.app.clone())
     }
 
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn request_permission(&self) -> crate::Result<PermissionState> {
         Ok(PermissionState::Granted)
     }
@@ -269,11 +433,18 @@ impl<R: Runtime> Notifications<R> {
     /// selected (or first available) `UnifiedPush` distributor and returns the
     /// endpoint URL. Apps that need endpoint stability across launches should
     /// call [`set_token`](Self::set_token) before this with a persisted token.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn register_for_push_notifications(&self) -> crate::Result<String> {
         #[cfg(all(target_os = "linux", feature = "push-notifications"))]
         {
             let state = self.unifiedpush_state().await?;
-            state.register().await
+            let endpoint = state.register().await?;
+            crate::push_token::store(&self.app, &endpoint);
+            Ok(endpoint)
         }
         #[cfg(not(all(target_os = "linux", feature = "push-notifications")))]
         {
@@ -283,9 +454,29 @@ impl<R: Runtime> Notifications<R> {
         }
     }
 
+    /// The endpoint from the last successful
+    /// [`register_for_push_notifications`](Self::register_for_push_notifications),
+    /// read from disk without contacting the distributor. Always `None`
+    /// without `UnifiedPush` support.
+    #[must_use]
+    pub async fn get_push_token_cached(&self) -> Option<String> {
+        #[cfg(all(target_os = "linux", feature = "push-notifications"))]
+        {
+            crate::push_token::load(&self.app)
+        }
+        #[cfg(not(all(target_os = "linux", feature = "push-notifications")))]
+        {
+            None
+        }
+    }
+
     /// Sync signature preserved for source compatibility — callers that need
     /// the Linux `UnifiedPush` unregister path should use
     /// [`unregister_for_push_notifications_async`] instead.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
     pub fn unregister_for_push_notifications(&self) -> crate::Result<()> {
         Err(crate::Error::Io(std::io::Error::other(
             "Push notifications are not supported on desktop platforms",
@@ -296,12 +487,18 @@ impl<R: Runtime> Notifications<R> {
     /// `push-notifications` feature this calls
     /// `org.unifiedpush.Distributor1.Unregister` and clears the in-memory
     /// active registration.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn unregister_for_push_notifications_async(&self) -> crate::Result<()> {
         #[cfg(all(target_os = "linux", feature = "push-notifications"))]
         {
             if let Some(state) = self.unifiedpush.get() {
                 state.unregister().await?;
             }
+            crate::push_token::clear(&self.app);
             Ok(())
         }
         #[cfg(not(all(target_os = "linux", feature = "push-notifications")))]
@@ -314,6 +511,7 @@ impl<R: Runtime> Notifications<R> {
 
     /// Lists currently running `UnifiedPush` distributors. Linux-only.
     #[cfg(all(target_os = "linux", feature = "push-notifications"))]
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn list_distributors(&self) -> crate::Result<Vec<String>> {
         let state = self.unifiedpush_state().await?;
         state.list_distributors().await
@@ -321,6 +519,7 @@ impl<R: Runtime> Notifications<R> {
 
     /// Pins the chosen `UnifiedPush` distributor for this process. Linux-only.
     #[cfg(all(target_os = "linux", feature = "push-notifications"))]
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn set_distributor(&self, name: String) -> crate::Result<()> {
         let state = self.unifiedpush_state().await?;
         state.set_distributor(name).await
@@ -330,28 +529,108 @@ impl<R: Runtime> Notifications<R> {
     /// Pass the same token across launches to keep the endpoint URL stable.
     /// Linux-only.
     #[cfg(all(target_os = "linux", feature = "push-notifications"))]
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn set_token(&self, token: String) -> crate::Result<()> {
         let state = self.unifiedpush_state().await?;
         state.set_token(token).await
     }
 
-    pub async fn permission_state(&self) -> crate::Result<PermissionState> {
+    /// Linux: whether a notification server answers on the session bus.
+    pub(crate) async fn platform_diagnostics(&self) -> Vec<crate::Diagnostic> {
+        #[cfg(target_os = "linux")]
+        {
+            let server = tauri::async_runtime::spawn_blocking(notify_rust::get_server_information)
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|server| server.map_err(|e| e.to_string()));
+            if let Err(e) = server {
+                return vec![crate::Diagnostic::new(
+                    crate::Severity::Error,
+                    crate::DiagnosticCode::NotificationServerUnavailable,
+                    format!("No notification server answers on the session bus: {e}"),
+                    "Run a notification daemon (the desktop environment's, or dunst/mako) in the user session",
+                )];
+            }
+        }
+        Vec::new()
+    }
+
+    /// Reads the permission state from the platform, bypassing the cache of
+    /// [`permission_state`](Self::permission_state).
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn permission_state_fresh(&self) -> crate::Result<PermissionState> {
         Ok(PermissionState::Granted)
     }
 
+    /// Not supported with notify-rust: the notification servers don't expose
+    /// their Do Not Disturb state.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn is_do_not_disturb_active(&self) -> crate::Result<bool> {
+        Err(crate::Error::Unsupported(
+            "Do Not Disturb state is not available with notify-rust".to_string(),
+        ))
+    }
+
+    /// Number of notifications the user hasn't dismissed yet, the count of
+    /// [`active`](Self::active). Fails where `active` is unsupported.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn get_unread_count(&self) -> crate::Result<u32> {
+        let active = self.active().await?;
+        Ok(u32::try_from(active.len()).unwrap_or(u32::MAX))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn pending(&self) -> crate::Result<Vec<crate::PendingNotification>> {
         Err(crate::Error::Io(std::io::Error::other(
             "Pending notifications are not supported with notify-rust",
         )))
     }
 
+    /// Number of pending notifications per channel. Not supported with
+    /// notify-rust, see [`Self::pending`].
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn pending_count_by_channel(
+        &self,
+    ) -> crate::Result<std::collections::HashMap<String, usize>> {
+        let pending = self.pending().await?;
+        Ok(crate::count_pending_by_channel(&pending))
+    }
+
+    /// Deletes every file in the remote media cache.
+    #[cfg(feature = "media-cache")]
+    pub fn clear_media_cache(&self) -> crate::Result<()> {
+        crate::media_cache::clear(&self.app)
+    }
+
+    /// Size and hit/miss counters of the remote media cache.
+    #[cfg(feature = "media-cache")]
+    pub fn media_cache_stats(&self) -> crate::Result<crate::MediaCacheStats> {
+        crate::media_cache::stats(&self.app)
+    }
+
     /// Linux: returns the currently-tracked notifications. The list is
     /// populated by [`NotificationsBuilder::show`] and pruned by
     /// `cancel`/`cancel_all`/`remove_active`. Entries dismissed by the user
     /// or expired by the OS may linger until the next explicit cancel call,
     /// since notify-rust doesn't expose a non-consuming "closed" callback.
     ///
-    /// macOS / Windows: still unsupported.
+    /// macOS / Windows: unsupported, unless the `notify-rust-active-cache`
+    /// feature is enabled. Then the notifications shown in the last ten
+    /// minutes are returned, one per id, whether or not the user has
+    /// dismissed them since.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn active(&self) -> crate::Result<Vec<crate::ActiveNotification>> {
         #[cfg(target_os = "linux")]
         {
@@ -367,7 +646,11 @@ impl<R: Runtime> Notifications<R> {
                 })
                 .collect())
         }
-        #[cfg(not(target_os = "linux"))]
+        #[cfg(all(not(target_os = "linux"), feature = "notify-rust-active-cache"))]
+        {
+            Ok(self.active_cache.list(std::time::Instant::now()))
+        }
+        #[cfg(all(not(target_os = "linux"), not(feature = "notify-rust-active-cache")))]
         {
             Err(crate::Error::Io(std::io::Error::other(
                 "Active notifications are not supported with notify-rust",
@@ -375,6 +658,17 @@ impl<R: Runtime> Notifications<R> {
         }
     }
 
+    /// Same as [`active`](Self::active): dismissed notifications aren't
+    /// tracked.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn notification_history(&self) -> crate::Result<Vec<crate::ActiveNotification>> {
+        self.active().await
+    }
+
     pub fn set_click_listener_active(&self, _active: bool) -> crate::Result<()> {
         Err(crate::Error::Io(std::io::Error::other(
             "Click listeners are not supported with notify-rust",
@@ -386,10 +680,15 @@ impl<R: Runtime> Notifications<R> {
     /// macOS / Windows: unsupported.
     // Existing public signature; switching to `&[i32]` would be breaking.
     #[allow(clippy::needless_pass_by_value)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND, ids = ?ids))
+    )]
     pub fn remove_active(&self, ids: Vec<i32>) -> crate::Result<()> {
         #[cfg(target_os = "linux")]
         {
-            self.close_by_caller_ids(&ids)
+            self.close_by_caller_ids(&ids)?;
+            Ok(())
         }
         #[cfg(not(target_os = "linux"))]
         {
@@ -400,6 +699,18 @@ impl<R: Runtime> Notifications<R> {
         }
     }
 
+    /// [`remove_active`](Self::remove_active) with the outcome of each id.
+    /// Ids with no active notification fail with
+    /// [`Error::NotFound`](crate::Error::NotFound).
+    #[cfg(target_os = "linux")]
+    pub fn remove_active_detailed(&self, ids: Vec<i32>) -> crate::Result<crate::BulkResult> {
+        self.close_by_caller_ids_detailed(ids)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
     pub fn remove_all_active(&self) -> crate::Result<()> {
         Err(crate::Error::Io(std::io::Error::other(
             "Removing active notifications is not supported with notify-rust",
@@ -410,10 +721,22 @@ impl<R: Runtime> Notifications<R> {
     /// macOS / Windows: unsupported.
     // Existing public signature; switching to `&[i32]` would be breaking.
     #[allow(clippy::needless_pass_by_value)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            err,
+            fields(
+                platform = crate::telemetry::BACKEND,
+                ids = ?notifications
+            )
+        )
+    )]
     pub fn cancel(&self, notifications: Vec<i32>) -> crate::Result<()> {
         #[cfg(target_os = "linux")]
         {
-            self.close_by_caller_ids(&notifications)
+            self.close_by_caller_ids(&notifications)?;
+            Ok(())
         }
         #[cfg(not(target_os = "linux"))]
         {
@@ -424,8 +747,91 @@ impl<R: Runtime> Notifications<R> {
         }
     }
 
+    /// [`cancel`](Self::cancel) with the outcome of each id. Ids with no
+    /// active notification fail with [`Error::NotFound`](crate::Error::NotFound).
+    #[cfg(target_os = "linux")]
+    pub fn cancel_detailed(&self, notifications: Vec<i32>) -> crate::Result<crate::BulkResult> {
+        self.close_by_caller_ids_detailed(notifications)
+    }
+
+    /// Linux: closes the tracked notification `id`, if any. Nothing is ever
+    /// pending with notify-rust, so `was_pending` is always `false`.
+    /// macOS / Windows: unsupported.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND, id))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn dismiss(&self, id: i32) -> crate::Result<crate::DismissOutcome> {
+        #[cfg(target_os = "linux")]
+        {
+            let was_active = self
+                .active
+                .lock()
+                .map_err(active_lock_err)?
+                .values()
+                .any(|entry| entry.caller_id == id);
+            if was_active {
+                self.close_by_caller_ids(&[id])?;
+            }
+            Ok(crate::DismissOutcome {
+                was_pending: false,
+                was_active,
+            })
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = id;
+            Err(crate::Error::Io(std::io::Error::other(
+                "Dismissing notifications is not supported with notify-rust",
+            )))
+        }
+    }
+
+    /// Linux: closes every tracked notification tagged with `scope`. Nothing
+    /// is ever pending with notify-rust, so `canceled` is always 0.
+    /// macOS / Windows: unsupported.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND, scope))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn clear_scope(&self, scope: &str) -> crate::Result<crate::ScopeClearance> {
+        #[cfg(target_os = "linux")]
+        {
+            let cleared: Vec<ActiveEntry> = {
+                let mut active = self.active.lock().map_err(active_lock_err)?;
+                let (cleared, kept) = std::mem::take(&mut *active)
+                    .into_iter()
+                    .partition(|(_, entry)| entry.scope.as_deref() == Some(scope));
+                *active = kept;
+                drop(active);
+                cleared.into_values().collect()
+            };
+            let removed = cleared.len();
+            for entry in cleared {
+                tauri::async_runtime::spawn_blocking(move || entry.handle.close());
+            }
+            Ok(crate::ScopeClearance {
+                canceled: 0,
+                removed,
+            })
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = scope;
+            Err(crate::Error::Io(std::io::Error::other(
+                "Clearing notification scopes is not supported with notify-rust",
+            )))
+        }
+    }
+
     /// Linux: closes every tracked notification.
     /// macOS / Windows: unsupported.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
     pub fn cancel_all(&self) -> crate::Result<()> {
         #[cfg(target_os = "linux")]
         {
@@ -448,24 +854,96 @@ impl<R: Runtime> Notifications<R> {
         }
     }
 
-    pub fn register_action_types(&self, _types: Vec<crate::ActionType>) -> crate::Result<()> {
+    pub fn register_action_types(
+        &self,
+        _types: Vec<crate::ActionType>,
+    ) -> crate::Result<crate::ActionTypesUpdate> {
         Err(crate::Error::Io(std::io::Error::other(
             "Action types are not supported with notify-rust",
         )))
     }
 
-    pub fn create_channel(&self, _channel: crate::Channel) -> crate::Result<()> {
+    pub fn set_notification_categories(
+        &self,
+        _types: Vec<crate::ActionType>,
+    ) -> crate::Result<crate::ActionTypesUpdate> {
+        Err(crate::Error::Io(std::io::Error::other(
+            "Action types are not supported with notify-rust",
+        )))
+    }
+
+    pub fn unregister_action_type(&self, _id: &str) -> crate::Result<crate::ActionTypesUpdate> {
+        Err(crate::Error::Io(std::io::Error::other(
+            "Action types are not supported with notify-rust",
+        )))
+    }
+
+    pub fn clear_action_types(&self) -> crate::Result<crate::ActionTypesUpdate> {
+        Err(crate::Error::Io(std::io::Error::other(
+            "Action types are not supported with notify-rust",
+        )))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    pub fn create_channel(
+        &self,
+        _channel: crate::Channel,
+    ) -> crate::Result<crate::ChannelCreation> {
         Err(crate::Error::Io(std::io::Error::other(
             "Notification channels are not supported with notify-rust",
         )))
     }
 
-    pub fn delete_channel(&self, _id: impl Into<String>) -> crate::Result<()> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    pub fn ensure_channels(
+        &self,
+        _channels: Vec<crate::Channel>,
+    ) -> crate::Result<Vec<crate::ChannelCreation>> {
         Err(crate::Error::Io(std::io::Error::other(
             "Notification channels are not supported with notify-rust",
         )))
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    pub fn update_channel(&self, _channel: crate::Channel) -> crate::Result<()> {
+        Err(crate::Error::Io(std::io::Error::other(
+            "Notification channels are not supported with notify-rust",
+        )))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    pub fn delete_channel(
+        &self,
+        _id: impl Into<String>,
+        _on_pending: crate::OnPendingPolicy,
+    ) -> crate::Result<crate::ChannelDeletion> {
+        Err(crate::Error::Io(std::io::Error::other(
+            "Notification channels are not supported with notify-rust",
+        )))
+    }
+
+    pub fn register_channel_blocked_listener(&self) -> crate::Result<()> {
+        Err(crate::Error::Io(std::io::Error::other(
+            "Notification channels are not supported with notify-rust",
+        )))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
     pub fn list_channels(&self) -> crate::Result<Vec<crate::Channel>> {
         Err(crate::Error::Io(std::io::Error::other(
             "Notification channels are not supported with notify-rust",
@@ -475,10 +953,59 @@ impl<R: Runtime> Notifications<R> {
 
 mod imp {
     //! Helpers for assembling the cross-platform `notify_rust::Notification`
-    //! before handing it off to a blocking thread for delivery.
+    //! and handing it off to the delivery thread.
 
+    use std::panic::AssertUnwindSafe;
     #[cfg(windows)]
     use std::path::MAIN_SEPARATOR as SEP;
+    use std::sync::mpsc;
+    use std::sync::{Mutex, PoisonError};
+
+    type Job = Box<dyn FnOnce() + Send>;
+
+    /// Sender to the delivery thread, `None` until the first notification.
+    static DELIVERY: Mutex<Option<mpsc::Sender<Job>>> = Mutex::new(None);
+
+    /// Runs `job` on the delivery thread and waits for its result.
+    ///
+    /// `notify_rust::Notification::show()` blocks, on Linux in a D-Bus call
+    /// through zbus's `block_on`, which panics inside the async runtime.
+    /// One long-lived thread shows every notification in turn, so a burst
+    /// of them doesn't take a blocking-pool thread each.
+    pub async fn deliver<T: Send + 'static>(
+        job: impl FnOnce() -> T + Send + 'static,
+    ) -> crate::Result<T> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        submit(Box::new(move || {
+            let _ = tx.send(job());
+        }))?;
+        rx.await
+            .map_err(|_| crate::Error::Io(std::io::Error::other("notification delivery panicked")))
+    }
+
+    /// Queues `job`, starting the delivery thread on first use.
+    fn submit(job: Job) -> crate::Result<()> {
+        let mut delivery = DELIVERY.lock().unwrap_or_else(PoisonError::into_inner);
+        let sender = if let Some(sender) = delivery.as_ref() {
+            sender
+        } else {
+            let (sender, receiver) = mpsc::channel::<Job>();
+            std::thread::Builder::new()
+                .name("notify-rust".to_string())
+                .spawn(move || {
+                    for job in receiver {
+                        // A panic only fails the delivery that raised it.
+                        let _ = std::panic::catch_unwind(AssertUnwindSafe(job));
+                    }
+                })?;
+            delivery.insert(sender)
+        };
+        sender.send(job).map_err(|_| {
+            crate::Error::Io(std::io::Error::other(
+                "notification delivery thread stopped",
+            ))
+        })
+    }
 
     /// Builds a fully-configured `notify_rust::Notification` from the parts
     /// the cross-platform builder produced. Returns an error only on Windows
@@ -532,4 +1059,105 @@ mod imp {
 
         Ok(notification)
     }
+
+    /// Whether the notification list keeps the notification after its popup
+    /// closes. `persistent` and `transient` can't be combined.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub enum Listing {
+        /// The server's default.
+        Default,
+        /// Kept after it times out (`resident`).
+        Resident,
+        /// Dropped once the popup closes (`transient`).
+        Transient,
+    }
+
+    /// How long the notification stays on screen and in the notification
+    /// list, taken from the builder flags.
+    #[derive(Clone, Copy)]
+    pub struct Persistence {
+        listing: Listing,
+        never_expires: bool,
+        quiet: bool,
+    }
+
+    impl Persistence {
+        /// `ongoing` implies resident and no timeout, unless the
+        /// notification is transient.
+        pub const fn of(data: &crate::NotificationData) -> Self {
+            let listing = if data.transient {
+                Listing::Transient
+            } else if data.persistent || data.ongoing {
+                Listing::Resident
+            } else {
+                Listing::Default
+            };
+            Self {
+                listing,
+                never_expires: data.ongoing || data.require_interaction,
+                quiet: data.quiet,
+            }
+        }
+
+        /// Sets the timeout and, on Linux, the `resident` / `transient`
+        /// hints, and low urgency without sound for quiet notifications.
+        /// notify-rust turns a `Never` timeout into a long toast on Windows
+        /// and ignores it on macOS.
+        pub fn apply(self, notification: &mut notify_rust::Notification) {
+            if self.never_expires {
+                notification.timeout(notify_rust::Timeout::Never);
+            }
+            #[cfg(target_os = "linux")]
+            {
+                match self.listing {
+                    Listing::Default => {}
+                    Listing::Resident => {
+                        notification.hint(notify_rust::Hint::Resident(true));
+                    }
+                    Listing::Transient => {
+                        notification.hint(notify_rust::Hint::Transient(true));
+                    }
+                }
+                if self.quiet {
+                    notification.urgency(notify_rust::Urgency::Low);
+                    notification.hint(notify_rust::Hint::SuppressSound(true));
+                }
+            }
+            #[cfg(not(target_os = "linux"))]
+            let _ = (self.listing, self.quiet);
+        }
+    }
+}
+
+#[cfg(all(test, not(target_os = "linux"), feature = "notify-rust-active-cache"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_cache_expires_entries() {
+        let cache = ActiveCache::default();
+        let start = std::time::Instant::now();
+        cache.insert(
+            crate::ActiveNotification::new(2, Some("Second".into()), None),
+            start,
+        );
+        cache.insert(
+            crate::ActiveNotification::new(1, Some("First".into()), None),
+            start + std::time::Duration::from_secs(60),
+        );
+
+        let ids = |active: Vec<crate::ActiveNotification>| {
+            active
+                .iter()
+                .map(crate::ActiveNotification::id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(cache.list(start)), vec![1, 2]);
+        assert_eq!(ids(cache.list(start + ACTIVE_CACHE_TTL)), vec![1]);
+        assert!(
+            cache
+                .list(start + ACTIVE_CACHE_TTL + std::time::Duration::from_secs(60))
+                .is_empty()
+        );
+    }
 }
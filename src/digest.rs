@@ -0,0 +1,392 @@
+//! Digest mode: once a group shows more than
+//! [`DigestPolicy::threshold`] notifications within
+//! [`DigestPolicy::window`], the ones shown in the window and every later one
+//! are replaced by a single digest notification.
+//!
+//! The group stays in digest mode until it has been quiet for a whole window;
+//! the next notification after that is shown on its own again. Every switch
+//! to or update of a digest emits `notificationsCoalesced` with the ids it
+//! stands for, so the frontend can reconcile.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Deserializer};
+use tauri::{AppHandle, Runtime};
+
+use crate::{NotificationData, NotificationsExt, telemetry};
+
+/// Event emitted whenever a digest is shown or updated.
+pub const COALESCED_EVENT: &str = "notificationsCoalesced";
+
+/// `extra` key of the digest notification listing the coalesced ids.
+pub const COALESCED_IDS_KEY: &str = "coalescedIds";
+
+/// Which notifications count towards the same digest.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DigestGroupBy {
+    /// The notification's `group`.
+    #[default]
+    Group,
+    /// The notification's channel.
+    Channel,
+}
+
+/// Plugin config of digest mode, see [`PluginConfig::digest`](crate::PluginConfig::digest).
+///
+/// ```json
+/// { "threshold": 5, "window": 60, "titleTemplate": "{count} items updated" }
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestPolicy {
+    /// Notifications of a group shown on their own within `window`; the
+    /// next one switches the group to a digest.
+    pub threshold: usize,
+    /// Sliding window the threshold applies to, in seconds.
+    #[serde(deserialize_with = "deserialize_seconds")]
+    pub window: Duration,
+    /// Title of the digest. `{count}` is replaced by the number of
+    /// notifications it stands for, `{group}` by the group's key.
+    #[serde(default = "default_title_template")]
+    pub title_template: String,
+    /// What groups notifications; those without one share a digest.
+    #[serde(default)]
+    pub group_by: DigestGroupBy,
+}
+
+fn default_title_template() -> String {
+    "{count} new notifications".to_string()
+}
+
+fn deserialize_seconds<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    u64::deserialize(deserializer).map(Duration::from_secs)
+}
+
+impl DigestPolicy {
+    fn key(&self, data: &NotificationData) -> Option<String> {
+        match self.group_by {
+            DigestGroupBy::Group => data.group.clone(),
+            DigestGroupBy::Channel => data.channel_id.clone(),
+        }
+    }
+
+    // `{count}` and `{group}` are the placeholders of `title_template`.
+    #[allow(clippy::literal_string_with_formatting_args)]
+    fn title(&self, count: usize, key: Option<&str>) -> String {
+        self.title_template
+            .replace("{count}", &count.to_string())
+            .replace("{group}", key.unwrap_or_default())
+    }
+}
+
+/// A group switched to, or already is in, digest mode: the notification
+/// being shown is replaced by the digest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coalesced {
+    pub digest_id: i32,
+    pub key: Option<String>,
+    /// Notifications shown on their own that the digest replaces. Empty
+    /// when an existing digest is updated.
+    pub remove: Vec<i32>,
+    /// Every notification the digest stands for, oldest first.
+    pub ids: Vec<i32>,
+}
+
+#[derive(Debug)]
+struct ActiveDigest {
+    id: i32,
+    ids: Vec<i32>,
+}
+
+#[derive(Debug, Default)]
+struct Bucket {
+    /// Notifications shown on their own within the window, oldest first.
+    shown: VecDeque<(Instant, i32)>,
+    digest: Option<ActiveDigest>,
+    last: Option<Instant>,
+}
+
+/// Managed state tracking every group's recent notifications.
+#[derive(Debug)]
+pub struct Digest {
+    policy: DigestPolicy,
+    groups: Mutex<HashMap<Option<String>, Bucket>>,
+}
+
+impl Digest {
+    pub fn new(policy: DigestPolicy) -> Self {
+        Self {
+            policy,
+            groups: Mutex::default(),
+        }
+    }
+
+    /// Records `data` being shown at `now`. `None` lets it through;
+    /// otherwise it is coalesced into the returned digest.
+    pub fn admit(&self, data: &NotificationData, now: Instant) -> Option<Coalesced> {
+        let window = self.policy.window;
+        let key = self.policy.key(data);
+        let mut groups = self
+            .groups
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let bucket = groups.entry(key.clone()).or_default();
+
+        if bucket
+            .last
+            .is_some_and(|last| now.duration_since(last) >= window)
+        {
+            *bucket = Bucket::default();
+        }
+        bucket.last = Some(now);
+
+        if let Some(digest) = &mut bucket.digest {
+            digest.ids.push(data.id);
+            return Some(Coalesced {
+                digest_id: digest.id,
+                key,
+                remove: Vec::new(),
+                ids: digest.ids.clone(),
+            });
+        }
+
+        while bucket
+            .shown
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) >= window)
+        {
+            bucket.shown.pop_front();
+        }
+        if bucket.shown.len() < self.policy.threshold {
+            bucket.shown.push_back((now, data.id));
+            return None;
+        }
+
+        let remove: Vec<i32> = bucket.shown.drain(..).map(|(_, id)| id).collect();
+        let mut ids = remove.clone();
+        ids.push(data.id);
+        let digest_id = rand::random();
+        bucket.digest = Some(ActiveDigest {
+            id: digest_id,
+            ids: ids.clone(),
+        });
+        drop(groups);
+        Some(Coalesced {
+            digest_id,
+            key,
+            remove,
+            ids,
+        })
+    }
+
+    /// The digest notification for `coalesced`. Takes channel, group and
+    /// scope from `data`, the notification that is coalesced.
+    pub fn notification(&self, coalesced: &Coalesced, data: &NotificationData) -> NotificationData {
+        NotificationData {
+            id: coalesced.digest_id,
+            channel_id: data.channel_id.clone(),
+            channel_id_fallback: data.channel_id_fallback.clone(),
+            group: data.group.clone(),
+            scope: data.scope.clone(),
+            title: Some(
+                self.policy
+                    .title(coalesced.ids.len(), coalesced.key.as_deref()),
+            ),
+            extra: HashMap::from([(
+                COALESCED_IDS_KEY.to_string(),
+                serde_json::json!(coalesced.ids),
+            )]),
+            ..NotificationData::default()
+        }
+    }
+}
+
+impl Coalesced {
+    /// Takes away the notifications the digest replaces, shows the digest
+    /// and emits [`COALESCED_EVENT`].
+    pub async fn show<R: Runtime>(
+        self,
+        app: &AppHandle<R>,
+        digest: NotificationData,
+        dispatcher: Option<crate::dispatch::Dispatcher>,
+    ) -> crate::Result<()> {
+        let mut remove = self.remove.clone();
+        // notify-rust can't replace a shown notification, so the previous
+        // digest is closed first.
+        if cfg!(all(
+            desktop,
+            any(target_os = "linux", feature = "notify-rust")
+        )) {
+            remove.push(self.digest_id);
+        }
+        if !remove.is_empty() {
+            // Queued behind the deliveries of the notifications it removes.
+            let handle = app.clone();
+            let removed = crate::dispatch::deliver(dispatcher, async move {
+                handle.notifications().remove_active(remove)
+            })
+            .await;
+            if let Err(e) = removed {
+                telemetry::warning!("Failed to remove coalesced notifications: {e}");
+            }
+        }
+
+        let mut builder = app.notifications().builder();
+        builder.data = digest;
+        builder.is_digest = true;
+        // `show()` is what called us, so its future has to be boxed.
+        Box::pin(builder.show()).await?;
+
+        let payload = serde_json::json!({
+            "digestId": self.digest_id,
+            "group": self.key,
+            "ids": self.ids,
+        });
+        if let Err(e) = crate::listeners::trigger(COALESCED_EVENT, payload.to_string()) {
+            telemetry::warning!("Failed to trigger {COALESCED_EVENT}: {e}");
+        }
+        #[cfg(mobile)]
+        if let Err(e) = app.notifications().emit_coalesced(&payload) {
+            telemetry::warning!("Failed to forward {COALESCED_EVENT}: {e}");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(threshold: usize) -> DigestPolicy {
+        serde_json::from_value(serde_json::json!({
+            "threshold": threshold,
+            "window": 60,
+            "titleTemplate": "{count} items updated in {group}",
+        }))
+        .expect("valid digest policy")
+    }
+
+    fn notification(id: i32, group: &str) -> NotificationData {
+        NotificationData {
+            id,
+            group: Some(group.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Mock backend: applies admissions the way `show()` does and records
+    /// what ends up on screen.
+    #[derive(Default)]
+    struct MockBackend {
+        shown: Vec<i32>,
+        digests: HashMap<i32, NotificationData>,
+        events: Vec<Coalesced>,
+    }
+
+    impl MockBackend {
+        fn show(&mut self, digest: &Digest, data: &NotificationData, now: Instant) {
+            match digest.admit(data, now) {
+                None => self.shown.push(data.id),
+                Some(coalesced) => {
+                    self.shown.retain(|id| !coalesced.remove.contains(id));
+                    self.digests
+                        .insert(coalesced.digest_id, digest.notification(&coalesced, data));
+                    self.events.push(coalesced);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_below_threshold_passes_through() {
+        let digest = Digest::new(policy(3));
+        let mut backend = MockBackend::default();
+        let start = Instant::now();
+        for id in 1..=3 {
+            backend.show(&digest, &notification(id, "sync"), start);
+        }
+        assert_eq!(backend.shown, [1, 2, 3]);
+        assert!(backend.events.is_empty());
+    }
+
+    #[test]
+    fn test_exceeding_threshold_replaces_shown_with_digest() {
+        let digest = Digest::new(policy(3));
+        let mut backend = MockBackend::default();
+        let start = Instant::now();
+        for id in 1..=5 {
+            backend.show(&digest, &notification(id, "sync"), start);
+        }
+
+        assert!(backend.shown.is_empty());
+        assert_eq!(backend.digests.len(), 1);
+        assert_eq!(backend.events[0].remove, [1, 2, 3]);
+        assert_eq!(backend.events[0].ids, [1, 2, 3, 4]);
+        assert!(backend.events[1].remove.is_empty());
+        assert_eq!(backend.events[1].ids, [1, 2, 3, 4, 5]);
+
+        let shown = &backend.digests[&backend.events[0].digest_id];
+        assert_eq!(shown.title.as_deref(), Some("5 items updated in sync"));
+        assert_eq!(shown.group.as_deref(), Some("sync"));
+        assert_eq!(
+            shown.extra[COALESCED_IDS_KEY],
+            serde_json::json!([1, 2, 3, 4, 5])
+        );
+    }
+
+    #[test]
+    fn test_groups_are_counted_separately() {
+        let digest = Digest::new(policy(1));
+        let mut backend = MockBackend::default();
+        let start = Instant::now();
+        backend.show(&digest, &notification(1, "mail"), start);
+        backend.show(&digest, &notification(2, "sync"), start);
+        assert_eq!(backend.shown, [1, 2]);
+
+        backend.show(&digest, &notification(3, "sync"), start);
+        assert_eq!(backend.shown, [1]);
+        assert_eq!(backend.events[0].key.as_deref(), Some("sync"));
+    }
+
+    #[test]
+    fn test_window_slides_and_digest_ends_after_a_quiet_window() {
+        let digest = Digest::new(policy(2));
+        let mut backend = MockBackend::default();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        backend.show(&digest, &notification(1, "sync"), at(0));
+        backend.show(&digest, &notification(2, "sync"), at(30));
+        // 1 left the window, so this is only the second one within it.
+        backend.show(&digest, &notification(3, "sync"), at(61));
+        assert_eq!(backend.shown, [1, 2, 3]);
+
+        backend.show(&digest, &notification(4, "sync"), at(62));
+        assert_eq!(backend.events[0].remove, [2, 3]);
+        backend.show(&digest, &notification(5, "sync"), at(100));
+        assert_eq!(backend.events.len(), 2);
+
+        // Quiet for a whole window: shown on its own again.
+        backend.show(&digest, &notification(6, "sync"), at(160));
+        assert_eq!(backend.shown, [1, 6]);
+        assert_eq!(backend.events.len(), 2);
+    }
+
+    #[test]
+    fn test_policy_deserialization() {
+        let policy: DigestPolicy = serde_json::from_value(serde_json::json!({
+            "threshold": 10,
+            "window": 30,
+            "groupBy": "channel",
+        }))
+        .expect("valid digest policy");
+        assert_eq!(policy.window, Duration::from_secs(30));
+        assert_eq!(policy.group_by, DigestGroupBy::Channel);
+        assert_eq!(policy.title(12, None), "12 new notifications");
+    }
+}
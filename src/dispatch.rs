@@ -0,0 +1,567 @@
+//! Ordered delivery of `show()` calls.
+//!
+//! Backends hand the OS call for each notification to a [`Dispatcher`], whose
+//! single consumer task runs them one at a time in the order they were
+//! queued. Without it, notifications shown in a tight loop race each other
+//! (notify-rust runs every `show` on its own blocking thread, Windows mixes
+//! `Show` and `AddToSchedule`) and can appear out of order.
+//!
+//! A group summary shown before any child of its group is held back until
+//! the first child has been delivered: some Android skins don't group
+//! children under a summary that was posted first.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+use tauri::async_runtime::{Sender, channel};
+use tokio::time::Instant;
+
+use crate::telemetry;
+
+type Job = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Queued jobs before `run` starts waiting for a free slot. Waiters are
+/// served in FIFO order, so a full queue only delays, never reorders.
+const QUEUE_CAPACITY: usize = 64;
+
+/// How long a held summary waits for the first child of its group before
+/// it is shown anyway.
+const SUMMARY_HOLD: Duration = Duration::from_secs(5);
+
+/// Groups remembered as started. Beyond that the oldest is forgotten, and
+/// its next summary is held again until a child shows or the hold ends.
+const STARTED_LIMIT: usize = 256;
+
+/// Where a notification stands in its group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupRole {
+    /// Not in a group, or scheduled: the OS decides when those show.
+    Ungrouped,
+    Child(String),
+    Summary(String),
+}
+
+impl GroupRole {
+    pub fn of(data: &crate::NotificationData) -> Self {
+        match &data.group {
+            Some(group) if data.schedule.is_none() => {
+                if data.group_summary {
+                    Self::Summary(group.clone())
+                } else {
+                    Self::Child(group.clone())
+                }
+            }
+            _ => Self::Ungrouped,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Groups {
+    /// Groups with a child queued since the plugin started, the most recent
+    /// [`STARTED_LIMIT`] of them, oldest first.
+    started: VecDeque<String>,
+    /// Summaries waiting for the first child of their group, by group, each
+    /// with the end of its hold.
+    held: HashMap<String, Vec<(Instant, Job)>>,
+}
+
+impl Groups {
+    fn start(&mut self, group: &str) {
+        if self.started.iter().any(|started| started == group) {
+            return;
+        }
+        if self.started.len() == STARTED_LIMIT {
+            self.started.pop_front();
+        }
+        self.started.push_back(group.to_string());
+    }
+
+    /// Takes the summaries of `group` whose hold ended by `now`, leaving the
+    /// ones held later.
+    fn take_expired(&mut self, group: &str, now: Instant) -> Vec<Job> {
+        let Some(held) = self.held.get_mut(group) else {
+            return Vec::new();
+        };
+        let (expired, waiting): (Vec<_>, Vec<_>) = std::mem::take(held)
+            .into_iter()
+            .partition(|(deadline, _)| *deadline <= now);
+        *held = waiting;
+        if held.is_empty() {
+            self.held.remove(group);
+        }
+        expired.into_iter().map(|(_, job)| job).collect()
+    }
+
+    /// Takes every summary held for `group`.
+    fn take_all(&mut self, group: &str) -> Vec<Job> {
+        self.held
+            .remove(group)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, job)| job)
+            .collect()
+    }
+}
+
+fn closed() -> crate::Error {
+    crate::Error::Io(std::io::Error::other(
+        "notification dispatch queue closed before delivery completed",
+    ))
+}
+
+/// Handle to a per-plugin delivery queue. Cheap to clone.
+#[derive(Clone)]
+pub struct Dispatcher {
+    jobs: Sender<Job>,
+    groups: Arc<Mutex<Groups>>,
+}
+
+impl Dispatcher {
+    /// Spawns the consumer task on Tauri's async runtime.
+    pub fn new() -> Self {
+        let (jobs, mut queue) = channel::<Job>(QUEUE_CAPACITY);
+        tauri::async_runtime::spawn(async move {
+            while let Some(job) = queue.recv().await {
+                // A separate task keeps the queue alive if a delivery panics.
+                let _ = tauri::async_runtime::spawn(job).await;
+            }
+        });
+        Self {
+            jobs,
+            groups: Arc::default(),
+        }
+    }
+
+    fn groups(&self) -> MutexGuard<'_, Groups> {
+        self.groups.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Queues `deliver` behind every earlier call and resolves with its
+    /// result once it has run. The job is queued on the first poll, so
+    /// futures polled in call order are delivered in call order.
+    pub async fn run<F, T>(&self, deliver: F) -> crate::Result<T>
+    where
+        F: Future<Output = crate::Result<T>> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (done, mut result) = channel(1);
+        let job: Job = Box::pin(async move {
+            let _ = done.send(deliver.await).await;
+        });
+        self.jobs.send(job).await.map_err(|_| closed())?;
+        result.recv().await.ok_or_else(closed)?
+    }
+
+    /// Like [`run`](Self::run), but a summary shown before any child of its
+    /// group is held and resolves right away. It is delivered right after
+    /// the group's first child, on [`flush_group`](Self::flush_group) or
+    /// [`SUMMARY_HOLD`] after it was held, whichever comes first; its errors
+    /// are logged.
+    pub async fn run_in_group<F>(&self, role: GroupRole, deliver: F) -> crate::Result<()>
+    where
+        F: Future<Output = crate::Result<()>> + Send + 'static,
+    {
+        match role {
+            GroupRole::Ungrouped => self.run(deliver).await,
+            GroupRole::Summary(group) => {
+                {
+                    let mut groups = self.groups();
+                    if !groups.started.contains(&group) {
+                        telemetry::debug!(
+                            "Holding the summary of group '{group}' until its first child is shown"
+                        );
+                        let deadline = Instant::now() + SUMMARY_HOLD;
+                        groups
+                            .held
+                            .entry(group.clone())
+                            .or_default()
+                            .push((deadline, logged(deliver)));
+                        drop(groups);
+                        let dispatcher = self.clone();
+                        tauri::async_runtime::spawn(async move {
+                            tokio::time::sleep_until(deadline).await;
+                            let expired = dispatcher.groups().take_expired(&group, Instant::now());
+                            dispatcher.show_held(&group, expired).await;
+                        });
+                        return Ok(());
+                    }
+                }
+                self.run(deliver).await
+            }
+            GroupRole::Child(group) => {
+                self.groups().start(&group);
+                let groups = self.groups.clone();
+                self.run(async move {
+                    let result = deliver.await;
+                    let held = groups
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .take_all(&group);
+                    if !held.is_empty() {
+                        telemetry::debug!(
+                            "Showing the summary of group '{group}' after its first child"
+                        );
+                    }
+                    for summary in held {
+                        summary.await;
+                    }
+                    result
+                })
+                .await
+            }
+        }
+    }
+
+    /// Queues the summaries of `group` held by
+    /// [`run_in_group`](Self::run_in_group) behind everything already
+    /// queued, and waits for them.
+    pub async fn flush_group(&self, group: &str) {
+        let held = self.groups().take_all(group);
+        self.show_held(group, held).await;
+    }
+
+    /// Queues `held`, summaries of `group`, behind everything already queued
+    /// and waits for them.
+    async fn show_held(&self, group: &str, held: Vec<Job>) {
+        if held.is_empty() {
+            return;
+        }
+        telemetry::debug!("Showing the held summary of group '{group}'");
+        let _ = self
+            .run(async move {
+                for summary in held {
+                    summary.await;
+                }
+                Ok(())
+            })
+            .await;
+    }
+}
+
+/// A held summary's delivery, with its error logged since nobody is
+/// waiting for it anymore.
+fn logged<F>(deliver: F) -> Job
+where
+    F: Future<Output = crate::Result<()>> + Send + 'static,
+{
+    Box::pin(async move {
+        if let Err(e) = deliver.await {
+            telemetry::warning!("Failed to show a held group summary: {e}");
+        }
+    })
+}
+
+/// Runs `deliver` through `dispatcher`, or right away when there is none
+/// (the builder's `unordered()`). The delivery stays in the caller's span.
+pub async fn deliver<F, T>(dispatcher: Option<Dispatcher>, deliver: F) -> crate::Result<T>
+where
+    F: Future<Output = crate::Result<T>> + Send + 'static,
+    T: Send + 'static,
+{
+    #[cfg(feature = "tracing")]
+    let deliver = tracing::Instrument::in_current_span(deliver);
+    match dispatcher {
+        Some(dispatcher) => dispatcher.run(deliver).await,
+        None => deliver.await,
+    }
+}
+
+/// [`deliver`] for the `show()` of `shown`, keeping a group summary
+/// behind the first child of its group (see
+/// [`Dispatcher::run_in_group`]). Without a dispatcher nothing is held.
+///
+/// `shown` counts as delivered, in the stats and for `patch_active`, once
+/// `deliver` succeeds: for a held summary that is after `show()` returned.
+pub async fn deliver_in_group<F>(
+    dispatcher: Option<Dispatcher>,
+    shown: crate::NotificationData,
+    deliver: F,
+) -> crate::Result<()>
+where
+    F: Future<Output = crate::Result<()>> + Send + 'static,
+{
+    let role = GroupRole::of(&shown);
+    let deliver = async move {
+        deliver.await?;
+        crate::stats::Delivery::of(&shown).record();
+        crate::patch::remember(shown);
+        Ok(())
+    };
+    #[cfg(feature = "tracing")]
+    let deliver = tracing::Instrument::in_current_span(deliver);
+    match dispatcher {
+        Some(dispatcher) => dispatcher.run_in_group(role, deliver).await,
+        None => deliver.await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        task::Poll,
+        time::Duration,
+    };
+
+    use super::*;
+
+    type Delivery = Pin<Box<dyn Future<Output = crate::Result<usize>> + Send>>;
+
+    /// Polls every future on each wake, like `futures::future::join_all`.
+    async fn join_all(futures: Vec<Delivery>) -> Vec<crate::Result<usize>> {
+        let mut futures: Vec<Option<Delivery>> = futures.into_iter().map(Some).collect();
+        let mut results: Vec<Option<crate::Result<usize>>> = futures.iter().map(|_| None).collect();
+        std::future::poll_fn(|cx| {
+            for (slot, result) in futures.iter_mut().zip(results.iter_mut()) {
+                if let Some(future) = slot
+                    && let Poll::Ready(value) = future.as_mut().poll(cx)
+                {
+                    *result = Some(value);
+                    *slot = None;
+                }
+            }
+            if futures.iter().all(Option::is_none) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+        results.into_iter().flatten().collect()
+    }
+
+    /// Mock OS call: earlier notifications take longer, so any parallelism
+    /// would record them out of order.
+    fn mock_delivery(dispatcher: &Dispatcher, id: usize, log: &Arc<Mutex<Vec<usize>>>) -> Delivery {
+        let dispatcher = dispatcher.clone();
+        let log = log.clone();
+        Box::pin(async move {
+            dispatcher
+                .run(async move {
+                    let delay = Duration::from_millis(2 * (10 - id as u64));
+                    tauri::async_runtime::spawn_blocking(move || std::thread::sleep(delay))
+                        .await
+                        .map_err(|e| crate::Error::Io(std::io::Error::other(e.to_string())))?;
+                    log.lock().expect("log mutex poisoned").push(id);
+                    Ok(id)
+                })
+                .await
+        })
+    }
+
+    #[test]
+    fn test_concurrent_shows_are_delivered_in_call_order() {
+        tauri::async_runtime::block_on(async {
+            let dispatcher = Dispatcher::new();
+            let log = Arc::new(Mutex::new(Vec::new()));
+            let deliveries = (0..10)
+                .map(|id| mock_delivery(&dispatcher, id, &log))
+                .collect();
+
+            let results = join_all(deliveries).await;
+
+            let expected: Vec<usize> = (0..10).collect();
+            assert_eq!(*log.lock().expect("log mutex poisoned"), expected);
+            let ids: Vec<usize> = results
+                .into_iter()
+                .map(|r| r.expect("delivery failed"))
+                .collect();
+            assert_eq!(ids, expected);
+        });
+    }
+
+    #[test]
+    fn test_delivery_error_reaches_its_caller_only() {
+        tauri::async_runtime::block_on(async {
+            let dispatcher = Dispatcher::new();
+            let failed: crate::Result<()> = dispatcher
+                .run(async { Err(crate::Error::Io(std::io::Error::other("no daemon"))) })
+                .await;
+            assert!(failed.is_err_and(|e| e.to_string() == "no daemon"));
+
+            let next = dispatcher.run(async { Ok(7) }).await;
+            assert_eq!(next.expect("queue should survive a failed delivery"), 7);
+        });
+    }
+
+    #[test]
+    fn test_queue_survives_a_panicking_delivery() {
+        tauri::async_runtime::block_on(async {
+            let dispatcher = Dispatcher::new();
+            let panicked: crate::Result<()> =
+                dispatcher.run(async { panic!("delivery panicked") }).await;
+            assert!(panicked.is_err());
+
+            let next = dispatcher.run(async { Ok(1) }).await;
+            assert_eq!(next.expect("queue should survive a panic"), 1);
+        });
+    }
+
+    /// Records `name` once delivered.
+    fn record(
+        log: &Arc<Mutex<Vec<&'static str>>>,
+        name: &'static str,
+    ) -> impl Future<Output = crate::Result<()>> + Send + 'static {
+        let log = log.clone();
+        async move {
+            log.lock().expect("log mutex poisoned").push(name);
+            Ok(())
+        }
+    }
+
+    fn child(group: &str) -> GroupRole {
+        GroupRole::Child(group.to_string())
+    }
+
+    fn summary(group: &str) -> GroupRole {
+        GroupRole::Summary(group.to_string())
+    }
+
+    #[test]
+    fn test_summary_shown_first_waits_for_a_child() {
+        tauri::async_runtime::block_on(async {
+            let dispatcher = Dispatcher::new();
+            let log = Arc::new(Mutex::new(Vec::new()));
+
+            // Sequential shows: the summary resolves without being delivered.
+            dispatcher
+                .run_in_group(summary("chat"), record(&log, "summary"))
+                .await
+                .expect("summary failed");
+            dispatcher
+                .run_in_group(GroupRole::Ungrouped, record(&log, "other"))
+                .await
+                .expect("ungrouped failed");
+            dispatcher
+                .run_in_group(child("mail"), record(&log, "mail"))
+                .await
+                .expect("child of another group failed");
+            dispatcher
+                .run_in_group(child("chat"), record(&log, "child"))
+                .await
+                .expect("child failed");
+
+            assert_eq!(
+                *log.lock().expect("log mutex poisoned"),
+                ["other", "mail", "child", "summary"]
+            );
+        });
+    }
+
+    #[test]
+    fn test_interleaved_group_shows_keep_children_first() {
+        tauri::async_runtime::block_on(async {
+            let dispatcher = Dispatcher::new();
+            let log = Arc::new(Mutex::new(Vec::new()));
+            let show = |role: GroupRole, name: &'static str| -> Delivery {
+                let dispatcher = dispatcher.clone();
+                let deliver = record(&log, name);
+                Box::pin(async move { dispatcher.run_in_group(role, deliver).await.map(|()| 0) })
+            };
+
+            let results = join_all(vec![
+                show(summary("chat"), "summary 1"),
+                show(child("chat"), "child 1"),
+                show(summary("chat"), "summary 2"),
+                show(child("chat"), "child 2"),
+            ])
+            .await;
+
+            assert!(results.iter().all(Result::is_ok));
+            // The held summary follows the first child; once the group has a
+            // child, summaries keep their place in the queue.
+            assert_eq!(
+                *log.lock().expect("log mutex poisoned"),
+                ["child 1", "summary 1", "summary 2", "child 2"]
+            );
+        });
+    }
+
+    #[test]
+    fn test_flush_group_shows_held_summary() {
+        tauri::async_runtime::block_on(async {
+            let dispatcher = Dispatcher::new();
+            let log = Arc::new(Mutex::new(Vec::new()));
+
+            dispatcher
+                .run_in_group(summary("chat"), record(&log, "summary"))
+                .await
+                .expect("summary failed");
+            assert!(log.lock().expect("log mutex poisoned").is_empty());
+
+            dispatcher.flush_group("other").await;
+            assert!(log.lock().expect("log mutex poisoned").is_empty());
+
+            dispatcher.flush_group("chat").await;
+            assert_eq!(*log.lock().expect("log mutex poisoned"), ["summary"]);
+
+            // Flushed once: a later child doesn't show it again.
+            dispatcher
+                .run_in_group(child("chat"), record(&log, "child"))
+                .await
+                .expect("child failed");
+            assert_eq!(
+                *log.lock().expect("log mutex poisoned"),
+                ["summary", "child"]
+            );
+        });
+    }
+
+    #[test]
+    fn test_hold_ends_per_summary() {
+        let start = Instant::now();
+        let mut groups = Groups::default();
+        let held = groups.held.entry("chat".to_string()).or_default();
+        held.push((start, Box::pin(async {})));
+        held.push((start + SUMMARY_HOLD, Box::pin(async {})));
+
+        assert!(groups.take_expired("other", start).is_empty());
+        assert_eq!(groups.take_expired("chat", start).len(), 1);
+        // The later summary is still held.
+        assert_eq!(groups.held.get("chat").map(Vec::len), Some(1));
+        assert_eq!(groups.take_expired("chat", start + SUMMARY_HOLD).len(), 1);
+        assert!(!groups.held.contains_key("chat"));
+    }
+
+    #[test]
+    fn test_group_role_of() {
+        let mut data = crate::NotificationData {
+            group: Some("chat".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(GroupRole::of(&data), child("chat"));
+        data.group_summary = true;
+        assert_eq!(GroupRole::of(&data), summary("chat"));
+        data.schedule = Some(crate::Schedule::Every {
+            interval: crate::ScheduleEvery::Day,
+            count: 1,
+            allow_while_idle: false,
+        });
+        assert_eq!(GroupRole::of(&data), GroupRole::Ungrouped);
+        data.schedule = None;
+        data.group = None;
+        assert_eq!(GroupRole::of(&data), GroupRole::Ungrouped);
+    }
+
+    #[test]
+    fn test_started_groups_are_capped() {
+        let mut groups = Groups::default();
+        for index in 0..=STARTED_LIMIT {
+            groups.start(&index.to_string());
+        }
+        groups.start("1");
+        assert_eq!(groups.started.len(), STARTED_LIMIT);
+        assert!(!groups.started.contains(&"0".to_string()));
+        assert_eq!(groups.started.front().map(String::as_str), Some("1"));
+        assert_eq!(
+            groups.started.back().map(String::as_str),
+            Some(STARTED_LIMIT.to_string().as_str())
+        );
+    }
+}
@@ -0,0 +1,314 @@
+//! Runtime setup checks behind [`Notifications::doctor`].
+//!
+//! Every backend contributes its own checks through `platform_diagnostics`
+//! (bundle, toast settings, notification server, channels, push setup);
+//! the permission and feature checks below are shared.
+
+use serde::{Deserialize, Serialize};
+use tauri::Runtime;
+
+use crate::{Channel, DEFAULT_CHANNEL_ID, Notifications, PermissionState};
+
+/// How much a [`Diagnostic`] gets in the way of delivering notifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    /// Worth knowing; notifications still work.
+    Info,
+    /// Some notifications won't be delivered.
+    Warning,
+    /// No notification will be delivered.
+    Error,
+}
+
+/// Stable identifier of a [`Diagnostic`], serialized in camelCase
+/// (`notBundled`, `permissionDenied`, ...). Codes are never renamed, so
+/// they can be matched in tests and referenced from support docs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum DiagnosticCode {
+    /// macOS: the app isn't running from a `.app` bundle.
+    NotBundled,
+    /// Windows: no toast notifier is available for the app's `AppUserModelID`.
+    ToastsUnavailable,
+    /// Windows: toasts are turned off for the app or the user, by group
+    /// policy or by the app manifest.
+    ToastsDisabled,
+    /// Linux: no notification server answers on the session bus.
+    NotificationServerUnavailable,
+    /// The user denied notifications.
+    PermissionDenied,
+    /// Notifications were never requested; `show` won't prompt by itself.
+    PermissionNotRequested,
+    /// The permission state could not be read.
+    PermissionCheckFailed,
+    /// Android: the `default` channel, used by notifications without a
+    /// channel id, was deleted.
+    MissingDefaultChannel,
+    /// Android: a channel named by `notificationDefaults` or by bound
+    /// channel defaults doesn't exist.
+    MissingChannel,
+    /// The `push-notifications` feature is off.
+    PushDisabled,
+    /// Android: Firebase isn't initialized, usually a missing
+    /// `google-services.json`.
+    FirebaseNotConfigured,
+    /// iOS/macOS: the app is signed without the push (`aps-environment`)
+    /// entitlement.
+    MissingPushEntitlement,
+    /// Reported by the JS `runDoctor` when the webview's capabilities don't
+    /// grant the plugin's commands.
+    MissingCapability,
+}
+
+/// One finding of [`Notifications::doctor`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: DiagnosticCode,
+    /// What is wrong, for the user or developer.
+    pub message: String,
+    /// How to fix it.
+    pub fix_hint: String,
+}
+
+impl Diagnostic {
+    #[must_use]
+    pub fn new(
+        severity: Severity,
+        code: DiagnosticCode,
+        message: impl Into<String>,
+        fix_hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            severity,
+            code,
+            message: message.into(),
+            fix_hint: fix_hint.into(),
+        }
+    }
+}
+
+/// Response of the native `doctor` command. Each check is missing when it
+/// doesn't apply to the platform or build.
+#[cfg(any(mobile, all(target_os = "macos", not(feature = "notify-rust"))))]
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NativeDiagnostics {
+    #[serde(default)]
+    pub firebase_configured: Option<bool>,
+    #[serde(default)]
+    pub push_entitlement: Option<bool>,
+}
+
+#[cfg(any(mobile, all(target_os = "macos", not(feature = "notify-rust"))))]
+impl NativeDiagnostics {
+    pub(crate) fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if self.firebase_configured == Some(false) {
+            diagnostics.push(Diagnostic::new(
+                Severity::Warning,
+                DiagnosticCode::FirebaseNotConfigured,
+                "Firebase is not initialized, so push notifications can't be registered",
+                "Add google-services.json to the Android app module and apply the com.google.gms.google-services Gradle plugin",
+            ));
+        }
+        if self.push_entitlement == Some(false) {
+            diagnostics.push(Diagnostic::new(
+                Severity::Warning,
+                DiagnosticCode::MissingPushEntitlement,
+                "The app is signed without the aps-environment entitlement, so APNs registration fails",
+                "Enable the Push Notifications capability for the app identifier and add aps-environment to the entitlements file",
+            ));
+        }
+        diagnostics
+    }
+}
+
+/// Diagnoses the permission state, or the failure to read it.
+pub fn permission_diagnostic(state: crate::Result<PermissionState>) -> Option<Diagnostic> {
+    match state {
+        Ok(PermissionState::Granted) => None,
+        Ok(PermissionState::Denied) => Some(Diagnostic::new(
+            Severity::Error,
+            DiagnosticCode::PermissionDenied,
+            "Notifications are turned off for this app",
+            "Ask the user to allow notifications for the app in the system settings",
+        )),
+        Ok(_) => Some(Diagnostic::new(
+            Severity::Warning,
+            DiagnosticCode::PermissionNotRequested,
+            "Notification permission has not been requested yet",
+            "Call requestPermission() before showing notifications",
+        )),
+        Err(e) => Some(Diagnostic::new(
+            Severity::Error,
+            DiagnosticCode::PermissionCheckFailed,
+            format!("Failed to read the notification permission: {e}"),
+            "Check the logs for the underlying error",
+        )),
+    }
+}
+
+/// Diagnoses the channels `referenced` by the notification defaults that
+/// are not in `existing`, plus a deleted `default` channel. Channels only
+/// exist on Android.
+#[cfg_attr(not(target_os = "android"), allow(dead_code))]
+pub fn channel_diagnostics(referenced: &[String], existing: &[Channel]) -> Vec<Diagnostic> {
+    let exists = |id: &str| existing.iter().any(|channel| channel.id() == id);
+    let mut diagnostics = Vec::new();
+    if !exists(DEFAULT_CHANNEL_ID) {
+        diagnostics.push(Diagnostic::new(
+            Severity::Warning,
+            DiagnosticCode::MissingDefaultChannel,
+            "The default channel was deleted; notifications without a channel id are not delivered",
+            "Give every notification a channel id, or restart the app to recreate the default channel",
+        ));
+    }
+    for id in referenced {
+        if id != DEFAULT_CHANNEL_ID && !exists(id) {
+            diagnostics.push(Diagnostic::new(
+                Severity::Warning,
+                DiagnosticCode::MissingChannel,
+                format!("Channel '{id}' is used by the notification defaults but doesn't exist"),
+                format!(
+                    "Create channel '{id}' with createChannel() or ensureChannels() at startup"
+                ),
+            ));
+        }
+    }
+    diagnostics
+}
+
+/// The `push-notifications` feature is off on a backend that supports push.
+fn push_disabled() -> Option<Diagnostic> {
+    let supported = cfg!(any(
+        mobile,
+        target_os = "linux",
+        all(target_os = "macos", not(feature = "notify-rust"))
+    ));
+    (supported && !cfg!(feature = "push-notifications")).then(|| {
+        Diagnostic::new(
+            Severity::Info,
+            DiagnosticCode::PushDisabled,
+            "Push notifications are not compiled in",
+            "Enable the push-notifications feature of the plugin to register for push notifications",
+        )
+    })
+}
+
+impl<R: Runtime> Notifications<R> {
+    /// Runs the runtime setup checks for this platform: the `.app` bundle on
+    /// macOS, the toast notifier and its setting on Windows, the notification
+    /// server on Linux, the channels the defaults rely on on Android, the
+    /// notification permission, and the push setup (Firebase on Android, the
+    /// push entitlement on iOS/macOS). Returns one [`Diagnostic`] per problem,
+    /// most severe first; an empty list means nothing was found.
+    ///
+    /// ```no_run
+    /// use tauri_plugin_notifications::{DiagnosticCode, NotificationsExt};
+    ///
+    /// async fn check<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    ///     for diagnostic in app.notifications().doctor().await {
+    ///         if diagnostic.code == DiagnosticCode::PermissionDenied {
+    ///             eprintln!("{}: {}", diagnostic.message, diagnostic.fix_hint);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub async fn doctor(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = self.platform_diagnostics().await;
+        // Without a bundle or a notifier the permission can't be read, and
+        // the platform diagnostic already says why.
+        if !diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+        {
+            diagnostics.extend(permission_diagnostic(self.permission_state().await));
+        }
+        diagnostics.extend(push_disabled());
+        diagnostics.sort_by_key(|diagnostic| std::cmp::Reverse(diagnostic.severity));
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(id: &str) -> Channel {
+        Channel::builder(id, id).build()
+    }
+
+    #[test]
+    fn test_permission_diagnostic() {
+        assert!(permission_diagnostic(Ok(PermissionState::Granted)).is_none());
+        let codes: Vec<DiagnosticCode> = [
+            Ok(PermissionState::Denied),
+            Ok(PermissionState::Prompt),
+            Err(crate::Error::Io(std::io::Error::other("boom"))),
+        ]
+        .into_iter()
+        .filter_map(permission_diagnostic)
+        .map(|diagnostic| diagnostic.code)
+        .collect();
+        assert_eq!(
+            codes,
+            [
+                DiagnosticCode::PermissionDenied,
+                DiagnosticCode::PermissionNotRequested,
+                DiagnosticCode::PermissionCheckFailed,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_channel_diagnostics() {
+        let referenced = vec!["downloads".to_string(), "messages".to_string()];
+        assert!(
+            channel_diagnostics(
+                &referenced,
+                &[
+                    channel("default"),
+                    channel("downloads"),
+                    channel("messages")
+                ]
+            )
+            .is_empty()
+        );
+
+        let diagnostics = channel_diagnostics(&referenced, &[channel("downloads")]);
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.code)
+                .collect::<Vec<_>>(),
+            [
+                DiagnosticCode::MissingDefaultChannel,
+                DiagnosticCode::MissingChannel,
+            ]
+        );
+        assert!(diagnostics[1].message.contains("'messages'"));
+    }
+
+    #[test]
+    fn test_diagnostic_serialization() {
+        let diagnostic = Diagnostic::new(
+            Severity::Error,
+            DiagnosticCode::NotBundled,
+            "message",
+            "hint",
+        );
+        assert_eq!(
+            serde_json::to_value(&diagnostic).expect("Failed to serialize"),
+            serde_json::json!({
+                "severity": "error",
+                "code": "notBundled",
+                "message": "message",
+                "fixHint": "hint",
+            })
+        );
+    }
+}
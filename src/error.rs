@@ -56,6 +56,119 @@ pub enum Error {
     #[cfg(desktop)]
     #[error(transparent)]
     PluginInvoke(#[from] crate::error::PluginInvokeError),
+    /// The notification's `extra` payload is over `PluginConfig::max_extra_bytes`.
+    #[error("notification `extra` is {size} bytes when encoded, over the {limit} byte limit")]
+    ExtraTooLarge { size: usize, limit: usize },
+    /// An argument failed validation before reaching the platform.
+    #[error("{0}")]
+    InvalidArgument(String),
+    /// Something the call refers to doesn't exist, e.g. the notification's
+    /// Android channel and its fallback.
+    #[error("{0}")]
+    NotFound(String),
+    /// The platform won't show notifications for this app, e.g. because its
+    /// Windows app id isn't registered.
+    #[error("notifications are unavailable: {0}")]
+    PermissionDenied(String),
+    /// The platform doesn't let the app do this, e.g. raise the importance of
+    /// an existing Android channel.
+    #[error("{0}")]
+    Unsupported(String),
+    /// `show_with_timeout` gave up waiting for the platform.
+    #[error("timed out waiting for the platform to show the notification")]
+    Timeout,
+    /// A `Notifications::blocking` method was called from within an async
+    /// runtime, where waiting for the result could deadlock.
+    #[error("blocking notification API called from within an async runtime; use the async API")]
+    BlockingInRuntime,
+    /// Some notifications of a `show_batch` failed. `failed` holds the index
+    /// of each one in the batch and its error.
+    #[error(
+        "{} of {} notifications failed: {}",
+        .failed.len(),
+        .succeeded.len() + .failed.len(),
+        describe_failures(.failed)
+    )]
+    PartialBatch {
+        succeeded: Vec<i32>,
+        failed: Vec<(usize, Self)>,
+    },
+    /// A Windows API call failed. `code` is `hresult` as `0x803E0105`.
+    #[cfg(all(target_os = "windows", not(feature = "notify-rust")))]
+    #[error("[{code}] - {message}")]
+    Windows {
+        code: String,
+        hresult: i32,
+        kind: WindowsErrorKind,
+        message: String,
+    },
+}
+
+/// What a failed Windows call ran into, from the HRESULTs the notification
+/// APIs return. Serialized in camelCase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum WindowsErrorKind {
+    /// The notification platform isn't running yet, typically right after
+    /// login (`WPN_E_PLATFORM_UNAVAILABLE`).
+    PlatformUnavailable,
+    /// The notification wasn't posted, e.g. while the platform restarts
+    /// (`WPN_E_NOTIFICATION_NOT_POSTED`).
+    NotPosted,
+    /// The call was rejected by a busy COM server (`RPC_E_CALL_REJECTED`,
+    /// `RPC_E_SERVERCALL_RETRYLATER`).
+    Busy,
+    /// Notifications are turned off for the app or the user
+    /// (`WPN_E_NOTIFICATION_DISABLED`, `WPN_E_NOTIFICATION_TYPE_DISABLED`).
+    Disabled,
+    /// `E_ACCESSDENIED`, `WPN_E_ACCESS_DENIED`.
+    AccessDenied,
+    /// The notification, file or registry key isn't there
+    /// (`E_ELEMENTNOTFOUND`, `ERROR_FILE_NOT_FOUND`).
+    NotFound,
+    /// The toast or its tag is too large (`WPN_E_NOTIFICATION_SIZE`,
+    /// `WPN_E_TAG_SIZE`).
+    TooLarge,
+    /// `E_INVALIDARG`.
+    InvalidArgument,
+    /// Any other HRESULT.
+    Other,
+}
+
+impl WindowsErrorKind {
+    /// The kind of `hresult`.
+    #[must_use]
+    pub const fn from_hresult(hresult: i32) -> Self {
+        match hresult.cast_unsigned() {
+            0x803E_0105 => Self::PlatformUnavailable,
+            0x803E_0108 => Self::NotPosted,
+            0x8001_0001 | 0x8001_010A => Self::Busy,
+            0x803E_0111 | 0x803E_0114 => Self::Disabled,
+            0x8007_0005 | 0x803E_0117 => Self::AccessDenied,
+            0x8007_0490 | 0x8007_0002 => Self::NotFound,
+            0x803E_0115 | 0x803E_0116 => Self::TooLarge,
+            0x8007_0057 => Self::InvalidArgument,
+            _ => Self::Other,
+        }
+    }
+
+    /// Whether the same call may succeed if retried shortly.
+    #[must_use]
+    pub const fn is_transient(self) -> bool {
+        matches!(
+            self,
+            Self::PlatformUnavailable | Self::NotPosted | Self::Busy
+        )
+    }
+}
+
+fn describe_failures(failed: &[(usize, Error)]) -> String {
+    failed
+        .iter()
+        .map(|(index, e)| format!("#{index}: {e}"))
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 impl Serialize for Error {
@@ -105,6 +218,30 @@ mod tests {
         assert!(matches!(err, Error::PluginInvoke(_)));
     }
 
+    #[test]
+    fn test_timeout_error_serialization() {
+        let json = serde_json::to_string(&Error::Timeout).expect("Failed to serialize error");
+        assert_eq!(
+            json,
+            "\"timed out waiting for the platform to show the notification\""
+        );
+    }
+
+    #[test]
+    fn test_partial_batch_display() {
+        let err = Error::PartialBatch {
+            succeeded: vec![1, 3],
+            failed: vec![
+                (1, Error::Timeout),
+                (3, Error::InvalidArgument("bad".to_string())),
+            ],
+        };
+        assert_eq!(
+            err.to_string(),
+            "2 of 4 notifications failed: #1: timed out waiting for the platform to show the notification; #3: bad"
+        );
+    }
+
     #[test]
     fn test_result_type_err() {
         let io_err = io::Error::other("test");
@@ -0,0 +1,364 @@
+//! Typed stream of notification events for Rust code on desktop.
+//!
+//! Every payload [`crate::listeners::trigger`] dispatches is also converted
+//! into a [`NotificationEvent`] and published on one broadcast channel.
+//! Each [`NotificationEventStream`] is a subscriber of that channel. A
+//! subscriber that falls more than [`PluginConfig::event_buffer`](crate::PluginConfig::event_buffer)
+//! events behind loses the oldest ones rather than holding up the others.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::OnceLock;
+use std::task::{Context, Poll};
+
+use tauri::{Runtime, plugin::PermissionState};
+use tokio::sync::broadcast::{self, Receiver, Sender, error::RecvError};
+
+use crate::telemetry;
+
+/// Default for [`PluginConfig::event_buffer`](crate::PluginConfig::event_buffer).
+pub const DEFAULT_EVENT_BUFFER: usize = 64;
+
+static EVENTS: OnceLock<Sender<NotificationEvent>> = OnceLock::new();
+
+/// A notification event, as seen by the JS listeners of the same name.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum NotificationEvent {
+    /// A notification was shown (`notification`). Carries the notification
+    /// as the backend reported it.
+    Delivered(serde_json::Value),
+    /// The user clicked a notification (`notificationClicked`). `id` is `-1`
+    /// when the click carried none.
+    Clicked {
+        id: i32,
+        data: HashMap<String, serde_json::Value>,
+    },
+    /// The user pressed an action button or clicked the notification
+    /// (`actionPerformed`).
+    ActionPerformed {
+        action_id: String,
+        input_value: Option<String>,
+        notification: serde_json::Value,
+    },
+    /// The user dismissed a notification. Windows only.
+    Dismissed { id: i32 },
+    /// A push message arrived while the app was running (macOS, and
+    /// `UnifiedPush` on Linux).
+    PushReceived(serde_json::Value),
+    /// `request_permission` changed the notification permission. macOS only;
+    /// the other desktop platforms have no permission prompt.
+    PermissionChanged(PermissionState),
+    /// Notifications were coalesced into the digest `digest_id`
+    /// (`notificationsCoalesced`); `ids` are every notification it stands
+    /// for. See [`PluginConfig::digest`](crate::PluginConfig::digest).
+    Coalesced { digest_id: i32, ids: Vec<i32> },
+    /// A notification wasn't shown because its window was focused
+    /// (`notificationFallback`). See
+    /// [`NotificationsBuilder::suppress_if_focused`](crate::NotificationsBuilder::suppress_if_focused).
+    Fallback {
+        window: String,
+        notification: serde_json::Value,
+    },
+    /// A notification was shown without `fields`, which the platform
+    /// ignores (`notificationWarning`). Only emitted with
+    /// [`PluginConfig::warn_on_ignored_fields`](crate::PluginConfig::warn_on_ignored_fields).
+    Warning { id: i32, fields: Vec<String> },
+}
+
+impl NotificationEvent {
+    /// Converts a triggered `event` payload. `None` for events that have no
+    /// variant (yet).
+    fn from_trigger(event: &str, payload: &serde_json::Value) -> Option<Self> {
+        let event = match event {
+            "notification"
+                if payload.get("source").and_then(serde_json::Value::as_str) == Some("push") =>
+            {
+                Self::PushReceived(payload.clone())
+            }
+            "notification" => Self::Delivered(payload.clone()),
+            "push-message" => Self::PushReceived(payload.clone()),
+            "notificationClicked" => {
+                let (id, data) = crate::listeners::parse_click(payload);
+                Self::Clicked { id, data }
+            }
+            "actionPerformed" => Self::ActionPerformed {
+                action_id: payload.get("actionId")?.as_str()?.to_string(),
+                input_value: payload
+                    .get("inputValue")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::to_string),
+                notification: payload.get("notification").cloned().unwrap_or_default(),
+            },
+            "notificationDismissed" => Self::Dismissed {
+                id: i32::try_from(payload.get("id")?.as_i64()?).ok()?,
+            },
+            "permissionChanged" => Self::PermissionChanged(
+                serde_json::from_value(payload.get("permissionState")?.clone()).ok()?,
+            ),
+            "notificationsCoalesced" => Self::Coalesced {
+                digest_id: i32::try_from(payload.get("digestId")?.as_i64()?).ok()?,
+                ids: serde_json::from_value(payload.get("ids")?.clone()).ok()?,
+            },
+            "notificationFallback" => Self::Fallback {
+                window: payload.get("window")?.as_str()?.to_string(),
+                notification: payload.get("notification").cloned().unwrap_or_default(),
+            },
+            "notificationWarning" => Self::Warning {
+                id: i32::try_from(payload.get("id")?.as_i64()?).ok()?,
+                fields: serde_json::from_value(payload.get("fields")?.clone()).ok()?,
+            },
+            _ => return None,
+        };
+        Some(event)
+    }
+}
+
+/// Creates the channel with room for `capacity` events per subscriber. Only
+/// the first call has an effect.
+pub fn init(capacity: usize) {
+    let _ = EVENTS.get_or_init(|| broadcast::channel(capacity.max(1)).0);
+}
+
+fn sender() -> &'static Sender<NotificationEvent> {
+    EVENTS.get_or_init(|| broadcast::channel(DEFAULT_EVENT_BUFFER).0)
+}
+
+/// Whether any [`NotificationEventStream`] is alive.
+pub fn has_subscribers() -> bool {
+    EVENTS
+        .get()
+        .is_some_and(|events| events.receiver_count() > 0)
+}
+
+/// Publishes the triggered `event` to every live stream.
+pub fn publish(event: &str, payload: &serde_json::Value) {
+    if !has_subscribers() {
+        return;
+    }
+    if let Some(event) = NotificationEvent::from_trigger(event, payload) {
+        // Only fails when the last stream was dropped in the meantime.
+        let _ = sender().send(event);
+    }
+}
+
+type Received = (
+    Result<NotificationEvent, RecvError>,
+    Receiver<NotificationEvent>,
+);
+type Recv = Pin<Box<dyn Future<Output = Received> + Send>>;
+
+/// Waits for the next event and hands the receiver back, so the stream can
+/// keep the pending `recv` in a `'static` future.
+async fn recv(mut receiver: Receiver<NotificationEvent>) -> Received {
+    let result = receiver.recv().await;
+    (result, receiver)
+}
+
+/// Stream of [`NotificationEvent`]s returned by
+/// [`Notifications::event_stream`](crate::Notifications::event_stream).
+/// Dropping it unsubscribes.
+pub struct NotificationEventStream {
+    recv: Recv,
+}
+
+impl std::fmt::Debug for NotificationEventStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NotificationEventStream")
+            .finish_non_exhaustive()
+    }
+}
+
+impl NotificationEventStream {
+    fn new() -> Self {
+        Self {
+            recv: Box::pin(recv(sender().subscribe())),
+        }
+    }
+
+    /// The next event. Never returns `None` while the app is running.
+    pub async fn next(&mut self) -> Option<NotificationEvent> {
+        std::future::poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<NotificationEvent>> {
+        loop {
+            let (result, receiver) = std::task::ready!(self.recv.as_mut().poll(cx));
+            self.recv = Box::pin(recv(receiver));
+            match result {
+                Ok(event) => return Poll::Ready(Some(event)),
+                Err(RecvError::Lagged(skipped)) => {
+                    telemetry::warning!(
+                        "Notification event stream lagged, dropped {skipped} events"
+                    );
+                }
+                Err(RecvError::Closed) => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl futures_core::Stream for NotificationEventStream {
+    type Item = NotificationEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_recv(cx)
+    }
+}
+
+impl<R: Runtime> crate::Notifications<R> {
+    /// Subscribes to all notification events, alongside the JS listeners
+    /// and [`on_notification_clicked`](Self::on_notification_clicked)
+    /// callbacks. Each stream receives every event published after it was
+    /// created; dropping it unsubscribes.
+    ///
+    /// ```no_run
+    /// use tauri_plugin_notifications::{NotificationEvent, NotificationsExt};
+    ///
+    /// fn forward_events<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    ///     let mut events = app.notifications().event_stream();
+    ///     tauri::async_runtime::spawn(async move {
+    ///         while let Some(event) = events.next().await {
+    ///             match event {
+    ///                 NotificationEvent::Clicked { id, .. } => println!("clicked {id}"),
+    ///                 other => println!("{other:?}"),
+    ///             }
+    ///         }
+    ///     });
+    /// }
+    /// ```
+    #[must_use]
+    pub fn event_stream(&self) -> NotificationEventStream {
+        let stream = NotificationEventStream::new();
+        // Clicks that arrived before any listener existed are buffered by the
+        // native backends; flush them to the new stream.
+        #[cfg(all(target_os = "windows", not(feature = "notify-rust")))]
+        self.drain_pending_clicks();
+        #[cfg(all(target_os = "macos", not(feature = "notify-rust")))]
+        if let Err(e) = self.set_click_listener_active(true) {
+            telemetry::warning!("Failed to mark the click listener active: {e}");
+        }
+        stream
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_from_trigger() {
+        let clicked = NotificationEvent::from_trigger(
+            "notificationClicked",
+            &serde_json::json!({ "id": 3, "data": { "route": "/inbox" } }),
+        );
+        assert!(
+            matches!(clicked, Some(NotificationEvent::Clicked { id: 3, ref data }) if data["route"] == "/inbox")
+        );
+
+        let action = NotificationEvent::from_trigger(
+            "actionPerformed",
+            &serde_json::json!({ "actionId": "reply", "inputValue": "hi", "notification": { "id": 3 } }),
+        );
+        assert!(matches!(
+            action,
+            Some(NotificationEvent::ActionPerformed { ref action_id, input_value: Some(ref input), .. })
+                if action_id == "reply" && input == "hi"
+        ));
+
+        assert!(matches!(
+            NotificationEvent::from_trigger(
+                "notification",
+                &serde_json::json!({ "source": "push" })
+            ),
+            Some(NotificationEvent::PushReceived(_))
+        ));
+        assert!(matches!(
+            NotificationEvent::from_trigger("notification", &serde_json::json!({ "id": 1 })),
+            Some(NotificationEvent::Delivered(_))
+        ));
+        assert!(matches!(
+            NotificationEvent::from_trigger(
+                "notificationDismissed",
+                &serde_json::json!({ "id": 5 })
+            ),
+            Some(NotificationEvent::Dismissed { id: 5 })
+        ));
+        assert!(matches!(
+            NotificationEvent::from_trigger(
+                "permissionChanged",
+                &serde_json::json!({ "permissionState": "granted" })
+            ),
+            Some(NotificationEvent::PermissionChanged(
+                PermissionState::Granted
+            ))
+        ));
+        assert!(matches!(
+            NotificationEvent::from_trigger(
+                "notificationsCoalesced",
+                &serde_json::json!({ "digestId": 3, "group": null, "ids": [1, 2] })
+            ),
+            Some(NotificationEvent::Coalesced { digest_id: 3, ids }) if ids == [1, 2]
+        ));
+        assert!(matches!(
+            NotificationEvent::from_trigger(
+                "notificationFallback",
+                &serde_json::json!({ "reason": "windowFocused", "window": "chat", "notification": { "id": 4 } })
+            ),
+            Some(NotificationEvent::Fallback { ref window, ref notification })
+                if window == "chat" && notification["id"] == 4
+        ));
+        assert!(matches!(
+            NotificationEvent::from_trigger(
+                "notificationWarning",
+                &serde_json::json!({ "id": 5, "reason": "ignoredFields", "fields": ["sound"] })
+            ),
+            Some(NotificationEvent::Warning { id: 5, ref fields }) if fields == &["sound"]
+        ));
+        assert!(
+            NotificationEvent::from_trigger("notificationFailed", &serde_json::json!({})).is_none()
+        );
+    }
+
+    #[test]
+    fn test_stream_receives_and_unsubscribes_on_drop() {
+        tauri::async_runtime::block_on(async {
+            let before = sender().receiver_count();
+            let mut stream = NotificationEventStream::new();
+            assert_eq!(sender().receiver_count(), before + 1);
+
+            publish("notificationDismissed", &serde_json::json!({ "id": 9 }));
+            assert!(matches!(
+                stream.next().await,
+                Some(NotificationEvent::Dismissed { id: 9 })
+            ));
+
+            drop(stream);
+            assert_eq!(sender().receiver_count(), before);
+        });
+    }
+
+    #[test]
+    fn test_lagging_stream_drops_oldest() {
+        tauri::async_runtime::block_on(async {
+            let (events, receiver) = broadcast::channel(2);
+            let mut stream = NotificationEventStream {
+                recv: Box::pin(recv(receiver)),
+            };
+            for id in 0..5 {
+                events
+                    .send(NotificationEvent::Dismissed { id })
+                    .expect("Stream should be subscribed");
+            }
+            assert!(matches!(
+                stream.next().await,
+                Some(NotificationEvent::Dismissed { id: 3 })
+            ));
+            assert!(matches!(
+                stream.next().await,
+                Some(NotificationEvent::Dismissed { id: 4 })
+            ));
+        });
+    }
+}
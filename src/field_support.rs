@@ -0,0 +1,247 @@
+//! Which [`NotificationData`] fields each backend renders.
+//!
+//! Backends skip the fields they have no equivalent for. Every backend's
+//! `show` looks the populated fields up in [`FIELD_SUPPORT`] and logs the
+//! ones it skips at debug level; with
+//! [`PluginConfig::warn_on_ignored_fields`](crate::PluginConfig::warn_on_ignored_fields)
+//! it also emits [`WARNING_EVENT`].
+
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+
+#[cfg(mobile)]
+use crate::NotificationsExt;
+use crate::{NotificationData, Schedule, telemetry};
+
+/// Event emitted for a notification shown without some of its fields.
+pub const WARNING_EVENT: &str = "notificationWarning";
+
+/// A notification backend. Which one a build uses depends on the target and
+/// the `notify-rust` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Backend {
+    Android,
+    Ios,
+    Macos,
+    Windows,
+    /// notify-rust on Linux, the only backend there.
+    Linux,
+    /// notify-rust on macOS or Windows, with the `notify-rust` feature.
+    NotifyRust,
+}
+
+#[cfg(target_os = "android")]
+const CURRENT: Backend = Backend::Android;
+#[cfg(target_os = "ios")]
+const CURRENT: Backend = Backend::Ios;
+#[cfg(all(target_os = "macos", not(feature = "notify-rust")))]
+const CURRENT: Backend = Backend::Macos;
+#[cfg(all(target_os = "windows", not(feature = "notify-rust")))]
+const CURRENT: Backend = Backend::Windows;
+#[cfg(target_os = "linux")]
+const CURRENT: Backend = Backend::Linux;
+#[cfg(all(
+    any(target_os = "macos", target_os = "windows"),
+    feature = "notify-rust"
+))]
+const CURRENT: Backend = Backend::NotifyRust;
+
+impl Backend {
+    /// The backend of this build.
+    #[must_use]
+    pub const fn current() -> Self {
+        CURRENT
+    }
+}
+
+/// A notification field and the backends that render it.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldSupport {
+    /// Name of the field in the JS `Options`.
+    pub field: &'static str,
+    pub backends: &'static [Backend],
+    populated: fn(&NotificationData) -> bool,
+}
+
+impl FieldSupport {
+    #[must_use]
+    pub fn is_supported_on(&self, backend: Backend) -> bool {
+        self.backends.contains(&backend)
+    }
+}
+
+use Backend::{Android, Ios, Linux, Macos, NotifyRust, Windows};
+
+const ALL: &[Backend] = &[Android, Ios, Macos, Windows, Linux, NotifyRust];
+const NATIVE: &[Backend] = &[Android, Ios, Macos, Windows];
+
+const fn field(
+    field: &'static str,
+    backends: &'static [Backend],
+    populated: fn(&NotificationData) -> bool,
+) -> FieldSupport {
+    FieldSupport {
+        field,
+        backends,
+        populated,
+    }
+}
+
+/// Support of the optional fields a notification is shown with. Inbox lines
+/// are folded into the body where there is no inbox style, so they count
+/// as supported everywhere.
+pub const FIELD_SUPPORT: &[FieldSupport] = &[
+    field("title", ALL, |d| d.title.is_some()),
+    field("body", ALL, |d| d.body.is_some()),
+    field("inboxLines", ALL, |d| !d.inbox_lines.is_empty()),
+    field("largeBody", &[Android, Windows], |d| d.large_body.is_some()),
+    field("summary", NATIVE, |d| d.summary.is_some()),
+    field("schedule", NATIVE, |d| d.schedule.is_some()),
+    field("schedule.allowWhileIdle", &[Android], |d| {
+        matches!(
+            d.schedule,
+            Some(
+                Schedule::At {
+                    allow_while_idle: true,
+                    ..
+                } | Schedule::Interval {
+                    allow_while_idle: true,
+                    ..
+                } | Schedule::Every {
+                    allow_while_idle: true,
+                    ..
+                }
+            )
+        )
+    }),
+    field("channelId", &[Android], |d| d.channel_id.is_some()),
+    field("actionTypeId", NATIVE, |d| d.action_type_id.is_some()),
+    field("group", NATIVE, |d| d.group.is_some()),
+    field("groupSummary", &[Android, Windows], |d| d.group_summary),
+    field("sound", NATIVE, |d| d.sound.is_some()),
+    field("icon", &[Android, Windows, Linux, NotifyRust], |d| {
+        d.icon.is_some()
+    }),
+    field("largeIcon", &[Android], |d| d.large_icon.is_some()),
+    field("iconColor", &[Android], |d| d.icon_color.is_some()),
+    field("attachments", &[Ios, Macos, Windows], |d| {
+        !d.attachments.is_empty()
+    }),
+    field("focusWindow", &[Macos, Windows], |d| {
+        d.focus_window.is_some()
+    }),
+    field("progress", &[Android, Windows], |d| d.progress.is_some()),
+    field("ticker", &[Android, Ios, Macos], |d| d.ticker.is_some()),
+    field("localOnly", &[Android], |d| d.local_only),
+    field("wearableActions", &[Android], |d| {
+        !d.wearable_actions.is_empty()
+    }),
+    field("ongoing", &[Android, Linux], |d| d.ongoing),
+    field("autoCancel", &[Android], |d| d.auto_cancel),
+    field("silent", &[Ios, Macos, Windows], |d| d.silent),
+    field("persistent", &[Windows, Linux], |d| d.persistent),
+    field("transient", &[Linux], |d| d.transient),
+    field("requireInteraction", &[Windows, Linux], |d| {
+        d.require_interaction
+    }),
+    // Set on the action type the notification names, so never reported as
+    // ignored; listed to complete the table.
+    field(
+        "actionType.hiddenPreviewsBodyPlaceholder",
+        &[Android, Ios, Macos],
+        |_| false,
+    ),
+];
+
+/// The fields of `data` that `backend` doesn't render.
+pub fn ignored_fields(data: &NotificationData, backend: Backend) -> Vec<&'static str> {
+    FIELD_SUPPORT
+        .iter()
+        .filter(|support| (support.populated)(data) && !support.is_supported_on(backend))
+        .map(|support| support.field)
+        .collect()
+}
+
+/// Logs the fields of `data` the current backend skips and, if `warn`,
+/// emits [`WARNING_EVENT`] for them.
+pub fn report<R: Runtime>(app: &AppHandle<R>, data: &NotificationData, warn: bool) {
+    let fields = ignored_fields(data, Backend::current());
+    if fields.is_empty() {
+        return;
+    }
+    telemetry::debug!(
+        "Notification {}: {} ignores {}",
+        data.id,
+        telemetry::BACKEND,
+        fields.join(", ")
+    );
+    if !warn {
+        return;
+    }
+    let payload = serde_json::json!({
+        "id": data.id,
+        "reason": "ignoredFields",
+        "fields": fields,
+    });
+    if let Err(e) = crate::listeners::trigger(WARNING_EVENT, payload.to_string()) {
+        telemetry::warning!("Failed to emit {WARNING_EVENT}: {e}");
+    }
+    #[cfg(mobile)]
+    if let Err(e) = app.notifications().emit_warning(&payload) {
+        telemetry::warning!("Failed to forward {WARNING_EVENT}: {e}");
+    }
+    #[cfg(not(mobile))]
+    let _ = app;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScheduleCapabilities;
+
+    fn support(field: &str) -> &'static FieldSupport {
+        FIELD_SUPPORT
+            .iter()
+            .find(|support| support.field == field)
+            .expect("field missing from the table")
+    }
+
+    #[test]
+    fn test_table_matches_schedule_capabilities() {
+        let backend = Backend::current();
+        let capabilities = ScheduleCapabilities::current();
+        assert_eq!(
+            support("schedule").is_supported_on(backend),
+            capabilities.scheduling
+        );
+        assert_eq!(
+            support("schedule.allowWhileIdle").is_supported_on(backend),
+            capabilities.allow_while_idle
+        );
+
+        for (index, support) in FIELD_SUPPORT.iter().enumerate() {
+            assert!(
+                FIELD_SUPPORT[..index]
+                    .iter()
+                    .all(|other| other.field != support.field),
+                "{} listed twice",
+                support.field
+            );
+        }
+    }
+
+    #[test]
+    fn test_ignored_fields() {
+        let data = NotificationData {
+            title: Some("Title".to_string()),
+            sound: Some("ping".to_string()),
+            large_icon: Some("avatar".to_string()),
+            transient: true,
+            ..NotificationData::default()
+        };
+        assert_eq!(ignored_fields(&data, Linux), ["sound", "largeIcon"]);
+        assert_eq!(ignored_fields(&data, Android), ["transient"]);
+        assert!(ignored_fields(&NotificationData::default(), NotifyRust).is_empty());
+    }
+}
@@ -0,0 +1,120 @@
+//! Brings a window to the front when a notification is clicked.
+//!
+//! Android and iOS bring the app forward on their own when a notification is
+//! tapped; on desktop a click only emits events, so a minimized window stays
+//! minimized. The click paths of the native desktop backends call
+//! [`on_click`] before emitting `notificationClicked`, which shows,
+//! unminimizes and focuses the window named by the notification's
+//! `focus_window` override or, failing that,
+//! [`PluginConfig::focus_window_on_click`](crate::PluginConfig::focus_window_on_click).
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use tauri::{AppHandle, Manager, Runtime};
+
+type FocusFn = Box<dyn Fn(&str) + Send + Sync>;
+
+struct FocusOnClick {
+    default: Option<String>,
+    /// Per-notification `focus_window` labels, removed once clicked.
+    overrides: Mutex<HashMap<i32, String>>,
+    focus: FocusFn,
+}
+
+impl FocusOnClick {
+    fn label_for(&self, id: i32) -> Option<String> {
+        let overridden = match self.overrides.lock() {
+            Ok(mut overrides) => overrides.remove(&id),
+            Err(poisoned) => poisoned.into_inner().remove(&id),
+        };
+        overridden.or_else(|| self.default.clone())
+    }
+}
+
+static FOCUS: OnceLock<FocusOnClick> = OnceLock::new();
+
+/// Called once from plugin setup with the configured default window label.
+pub fn init<R: Runtime>(app: AppHandle<R>, default: Option<String>) {
+    let _ = FOCUS.set(FocusOnClick {
+        default,
+        overrides: Mutex::new(HashMap::new()),
+        focus: Box::new(move |label| focus_window(&app, label)),
+    });
+}
+
+/// Remembers the window to focus when notification `id` is clicked.
+pub fn remember(id: i32, label: String) {
+    if let Some(state) = FOCUS.get() {
+        match state.overrides.lock() {
+            Ok(mut overrides) => overrides.insert(id, label),
+            Err(poisoned) => poisoned.into_inner().insert(id, label),
+        };
+    }
+}
+
+/// Whether [`PluginConfig::focus_window_on_click`](crate::PluginConfig::focus_window_on_click)
+/// is set.
+pub fn has_default() -> bool {
+    FOCUS.get().is_some_and(|state| state.default.is_some())
+}
+
+/// Focuses the window for a click on notification `id` (`-1` when the click
+/// carried no id), if one is configured.
+pub fn on_click(id: i32) {
+    let Some(state) = FOCUS.get() else {
+        return;
+    };
+    if let Some(label) = state.label_for(id) {
+        (state.focus)(&label);
+    }
+}
+
+fn focus_window<R: Runtime>(app: &AppHandle<R>, label: &str) {
+    // The window may have been closed since the notification was shown.
+    let Some(window) = app.get_webview_window(label) else {
+        crate::telemetry::warning!("Window '{label}' to focus on notification click not found");
+        return;
+    };
+    if let Err(e) = window
+        .show()
+        .and_then(|()| window.unminimize())
+        .and_then(|()| window.set_focus())
+    {
+        crate::telemetry::warning!("Failed to focus window '{label}': {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(default: Option<&str>) -> FocusOnClick {
+        FocusOnClick {
+            default: default.map(str::to_string),
+            overrides: Mutex::new(HashMap::new()),
+            focus: Box::new(|_| {}),
+        }
+    }
+
+    #[test]
+    fn test_override_wins_once_then_default() {
+        let state = state(Some("main"));
+        state
+            .overrides
+            .lock()
+            .expect("overrides mutex poisoned")
+            .insert(7, "chat".to_string());
+
+        assert_eq!(state.label_for(7).as_deref(), Some("chat"));
+        // The override is consumed by the click.
+        assert_eq!(state.label_for(7).as_deref(), Some("main"));
+        assert_eq!(state.label_for(-1).as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn test_no_label_without_config_or_override() {
+        let state = state(None);
+        assert_eq!(state.label_for(1), None);
+    }
+}
@@ -1,13 +1,12 @@
 //! Send message notifications (brief auto-expiring OS window element) to your user. Can also be used with the Notification Web API.
 
 use serde::{Deserialize, Serialize};
-#[cfg(desktop)]
 use tauri::AppHandle;
 #[cfg(mobile)]
 use tauri::plugin::PluginHandle;
 use tauri::{
-    Manager, Runtime,
-    plugin::{Builder, TauriPlugin},
+    Manager, RunEvent, Runtime, WindowEvent,
+    plugin::{Builder, PluginApi, TauriPlugin},
 };
 
 /// Top-level plugin config deserialized from the `plugins.notifications` block
@@ -18,6 +17,61 @@ use tauri::{
 pub struct PluginConfig {
     #[cfg(target_os = "windows")]
     pub windows: WindowsConfig,
+    #[cfg(all(
+        feature = "media-cache",
+        desktop,
+        any(feature = "notify-rust", target_os = "linux", target_os = "windows")
+    ))]
+    pub media_cache: MediaCacheConfig,
+    /// Largest accepted `extra` payload in bytes, measured as the JSON string
+    /// sent to the native plugins. Defaults to
+    /// [`DEFAULT_MAX_EXTRA_BYTES`] (64 KiB).
+    pub max_extra_bytes: Option<usize>,
+    /// Label of the window to show, unminimize and focus when a notification
+    /// is clicked (Windows and macOS). Per notification, see
+    /// [`NotificationsBuilder::focus_window`].
+    pub focus_window_on_click: Option<String>,
+    /// AppUserModelID that toasts are shown and looked up under on
+    /// unpackaged Windows apps, instead of the bundle identifier. Set it to
+    /// the AUMID of the installer's Start Menu shortcut so clicks reach the
+    /// app and the toast uses its icon. Packaged apps always use their
+    /// package's AUMID.
+    #[cfg(target_os = "windows")]
+    pub windows_app_id: Option<String>,
+    /// Events each [`Notifications::event_stream`] can fall behind by before
+    /// it loses the oldest ones. Defaults to [`DEFAULT_EVENT_BUFFER`].
+    /// Desktop only.
+    pub event_buffer: Option<usize>,
+    /// Fields applied to every notification that leaves them unset, after
+    /// the defaults bound to its channel with
+    /// [`Notifications::bind_channel_defaults`]. Same shape as the JS
+    /// `Options`; `id`, `schedule` and `countDownTo` are ignored.
+    pub notification_defaults: Option<NotificationData>,
+    /// Daily window in which `show()` defers notifications or delivers them
+    /// quietly. Per notification, see
+    /// [`NotificationsBuilder::quiet_hours_policy`].
+    pub quiet_hours: Option<QuietHours>,
+    /// Coalesces bursts of notifications into one digest notification once
+    /// a group exceeds a threshold within a time window.
+    pub digest: Option<DigestPolicy>,
+    /// Count deliveries, clicks, dismissals and actions per day, channel and
+    /// group, see [`Notifications::stats`].
+    pub stats: bool,
+    /// Reject notifications over a platform limit, such as more than
+    /// [`MAX_INBOX_LINES`] inbox lines, or with conflicting flags, such as
+    /// `ongoing` with `autoCancel`, instead of trimming or resolving them
+    /// with a logged warning. Notifications without title, body or inbox
+    /// lines are rejected too, instead of being shown with the product name
+    /// alone.
+    pub strict_limits: bool,
+    /// Emit `notificationWarning` for notifications with fields the platform
+    /// ignores, see [`FIELD_SUPPORT`]. They are logged at debug level either
+    /// way.
+    pub warn_on_ignored_fields: bool,
+    /// Seconds [`Notifications::permission_state`] reuses the state it read
+    /// from the platform; `0` turns caching off. Defaults to
+    /// [`DEFAULT_PERMISSION_CACHE_TTL`] (30 s).
+    pub permission_cache_ttl: Option<u64>,
 }
 
 /// Windows-only plugin config.
@@ -35,6 +89,93 @@ pub struct WindowsConfig {
     /// and `<com:Class Id>` entries. Accepts the `xxxxxxxx-xxxx-...` form
     /// with or without surrounding braces.
     pub toast_activator_clsid: Option<String>,
+    /// Retry policy for toasts that fail to show or schedule with a transient
+    /// error (e.g. the notification platform not being up yet right after
+    /// login).
+    pub retry: RetryPolicy,
+    /// Register the app's display name (`productName`) and
+    /// [`app_identity_icon`](Self::app_identity_icon) for its toasts during
+    /// plugin setup, see `Notifications::register_app_identity`. Unpackaged
+    /// apps only; failures are logged.
+    pub register_app_identity: bool,
+    /// `.png` or `.ico` shown on toasts when
+    /// [`register_app_identity`](Self::register_app_identity) is set. Absolute,
+    /// or relative to the app's resource directory.
+    pub app_identity_icon: Option<String>,
+    /// Disable toasts on unpackaged apps whose AUMID has no
+    /// `AppUserModelId` registry key (see
+    /// [`register_app_identity`](Self::register_app_identity)) instead of
+    /// showing toasts Windows may silently drop. Showing, scheduling and
+    /// cancelling then fail with [`Error::PermissionDenied`] and
+    /// `permission_state` reports `Denied`. Leave it off when the AUMID is
+    /// only registered through a Start Menu shortcut, which isn't detected.
+    pub require_registered_app_id: bool,
+    /// What a repeating schedule does with the occurrences it missed while
+    /// the machine slept or the app was suspended or closed. Windows delivers
+    /// the occurrence that was already scheduled once it wakes up either way.
+    pub missed_fire_policy: MissedFirePolicy,
+    /// How often, in seconds, repeating schedules re-read the wall clock to
+    /// notice that the machine slept past an occurrence. Defaults to 60.
+    /// Resuming from sleep triggers a check as well.
+    pub clock_check_interval_secs: Option<u64>,
+}
+
+/// Backoff applied to `ToastNotifier::Show` / `AddToSchedule` when they fail
+/// with a known-transient HRESULT. The delay doubles after every attempt,
+/// starting at `base_delay_ms` and capped at `max_delay_ms`.
+#[cfg(target_os = "windows")]
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RetryPolicy {
+    /// Total number of delivery attempts, including the first one. `1`
+    /// disables retrying.
+    pub attempts: u32,
+    /// Delay before the first retry, in milliseconds.
+    pub base_delay_ms: u64,
+    /// Upper bound for any single delay, in milliseconds.
+    pub max_delay_ms: u64,
+}
+
+#[cfg(target_os = "windows")]
+impl Default for RetryPolicy {
+    /// Three attempts spread over roughly five seconds (1.5s, then 3s).
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            base_delay_ms: 1500,
+            max_delay_ms: 4000,
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl RetryPolicy {
+    /// Delay before retry number `retry` (1-based).
+    #[must_use]
+    pub fn delay(&self, retry: u32) -> std::time::Duration {
+        let factor = 1u64
+            .checked_shl(retry.saturating_sub(1))
+            .unwrap_or(u64::MAX);
+        std::time::Duration::from_millis(
+            self.base_delay_ms
+                .saturating_mul(factor)
+                .min(self.max_delay_ms),
+        )
+    }
+}
+
+/// What the plugin does with an interval schedule when it notices that
+/// more than one occurrence came due, see
+/// [`WindowsConfig::missed_fire_policy`].
+#[cfg(target_os = "windows")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MissedFirePolicy {
+    /// Show one catch-up toast for all the missed occurrences.
+    #[default]
+    FireOnce,
+    /// Drop the missed occurrences and carry on with the next one due.
+    Skip,
 }
 
 pub use models::*;
@@ -51,13 +192,72 @@ mod unifiedpush;
 #[cfg(all(target_os = "windows", not(feature = "notify-rust")))]
 mod windows;
 
+#[cfg(any(
+    mobile,
+    all(
+        any(target_os = "macos", target_os = "windows"),
+        not(feature = "notify-rust")
+    )
+))]
+mod action_types;
+mod blocking;
+mod channel_defaults;
 mod commands;
+mod digest;
+mod dispatch;
+mod doctor;
 mod error;
 #[cfg(desktop)]
+mod events;
+mod field_support;
+#[cfg(all(
+    any(target_os = "macos", target_os = "windows"),
+    not(feature = "notify-rust")
+))]
+mod focus;
 mod listeners;
+#[cfg(all(
+    feature = "media-cache",
+    desktop,
+    any(feature = "notify-rust", target_os = "linux", target_os = "windows")
+))]
+mod media_cache;
 mod models;
+mod patch;
+mod permission_cache;
+#[cfg(any(
+    all(target_os = "windows", not(feature = "notify-rust")),
+    all(desktop, target_os = "linux", feature = "push-notifications")
+))]
+mod push_token;
+mod quiet_hours;
+#[cfg(all(target_os = "windows", not(feature = "notify-rust")))]
+mod show_once;
+#[cfg(all(desktop, feature = "test-utils"))]
+mod simulate;
+mod stats;
+mod suppress;
+mod telemetry;
+#[cfg(all(target_os = "windows", not(feature = "notify-rust")))]
+mod timers;
 
-pub use error::{Error, Result};
+pub use blocking::BlockingNotifications;
+pub use digest::{DigestGroupBy, DigestPolicy};
+pub use doctor::{Diagnostic, DiagnosticCode, Severity};
+pub use error::{Error, Result, WindowsErrorKind};
+#[cfg(desktop)]
+pub use events::{DEFAULT_EVENT_BUFFER, NotificationEvent, NotificationEventStream};
+pub use field_support::{Backend, FIELD_SUPPORT, FieldSupport};
+pub use listeners::ListenerHandle;
+#[cfg(all(
+    feature = "media-cache",
+    desktop,
+    any(feature = "notify-rust", target_os = "linux", target_os = "windows")
+))]
+pub use media_cache::{MediaCacheConfig, MediaCacheStats};
+pub use permission_cache::DEFAULT_PERMISSION_CACHE_TTL;
+pub use quiet_hours::{QuietHours, QuietHoursPolicy};
+pub use stats::{NotificationStats, StatsRange};
 
 #[cfg(all(desktop, any(feature = "notify-rust", target_os = "linux")))]
 pub use desktop::Notifications;
@@ -66,7 +266,21 @@ pub use macos::Notifications;
 #[cfg(mobile)]
 pub use mobile::Notifications;
 #[cfg(all(target_os = "windows", not(feature = "notify-rust")))]
-pub use windows::Notifications;
+pub use windows::{NotificationSetting, Notifications};
+
+/// Managed state holding the resolved [`PluginConfig::max_extra_bytes`].
+struct ExtraLimit(usize);
+
+/// Managed state holding [`PluginConfig::strict_limits`].
+struct StrictLimits(bool);
+
+/// Whether [`PluginConfig::strict_limits`] is set.
+fn is_strict<R: Runtime>(app: &tauri::AppHandle<R>) -> bool {
+    app.try_state::<StrictLimits>().is_some_and(|s| s.0)
+}
+
+/// Managed state holding [`PluginConfig::warn_on_ignored_fields`].
+struct WarnOnIgnoredFields(bool);
 
 /// The notification builder.
 #[derive(Debug)]
@@ -81,6 +295,12 @@ pub struct NotificationsBuilder<R: Runtime> {
     #[cfg(mobile)]
     handle: PluginHandle<R>,
     pub(crate) data: NotificationData,
+    unordered: bool,
+    /// Set on digest notifications, which are never coalesced themselves.
+    is_digest: bool,
+    /// Set when showing a patched notification again, which is never
+    /// suppressed or coalesced.
+    is_patch: bool,
 }
 
 impl<R: Runtime> NotificationsBuilder<R> {
@@ -89,6 +309,9 @@ impl<R: Runtime> NotificationsBuilder<R> {
         Self {
             app,
             data: NotificationData::default(),
+            unordered: false,
+            is_digest: false,
+            is_patch: false,
         }
     }
 
@@ -98,6 +321,9 @@ impl<R: Runtime> NotificationsBuilder<R> {
             app,
             plugin,
             data: NotificationData::default(),
+            unordered: false,
+            is_digest: false,
+            is_patch: false,
         }
     }
 
@@ -107,6 +333,9 @@ impl<R: Runtime> NotificationsBuilder<R> {
             app,
             plugin,
             data: Default::default(),
+            unordered: false,
+            is_digest: false,
+            is_patch: false,
         }
     }
 
@@ -115,9 +344,287 @@ impl<R: Runtime> NotificationsBuilder<R> {
         Self {
             handle,
             data: NotificationData::default(),
+            unordered: false,
+            is_digest: false,
+            is_patch: false,
+        }
+    }
+
+    #[cfg(desktop)]
+    const fn app_handle(&self) -> &AppHandle<R> {
+        &self.app
+    }
+
+    #[cfg(mobile)]
+    fn app_handle(&self) -> &tauri::AppHandle<R> {
+        self.handle.app()
+    }
+
+    /// Called first by every backend's `show`: fills the fields left unset
+    /// from the channel's bound defaults, then from
+    /// [`PluginConfig::notification_defaults`], and a title still missing
+    /// with the app's product name, so every platform shows the same title.
+    fn apply_defaults(&mut self) -> crate::Result<()> {
+        #[cfg(desktop)]
+        let app = &self.app;
+        #[cfg(mobile)]
+        let app = self.handle.app();
+        if let Some(defaults) = app.try_state::<channel_defaults::ChannelDefaults>() {
+            defaults.apply(&mut self.data);
+        }
+        self.data
+            .fill_title(app.config().product_name.as_deref(), is_strict(app))
+    }
+
+    /// Called by every backend's `show` right after
+    /// [`apply_defaults`](Self::apply_defaults): defers or quiets the
+    /// notification during [`PluginConfig::quiet_hours`].
+    fn apply_quiet_hours(&mut self) {
+        #[cfg(desktop)]
+        let app = &self.app;
+        #[cfg(mobile)]
+        let app = self.handle.app();
+        if let Some(quiet_hours) = app.try_state::<QuietHours>() {
+            let local = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
+            quiet_hours.apply(&mut self.data, time::OffsetDateTime::now_utc(), local);
         }
     }
 
+    /// Called by every backend's `show` before handing the notification to
+    /// the OS.
+    fn check_extra_size(&self) -> crate::Result<()> {
+        #[cfg(desktop)]
+        let app = &self.app;
+        #[cfg(mobile)]
+        let app = self.handle.app();
+        let limit = app
+            .try_state::<ExtraLimit>()
+            .map_or(DEFAULT_MAX_EXTRA_BYTES, |limit| limit.0);
+        self.data.check_extra_size(limit)
+    }
+
+    /// Called by every backend's `show` alongside
+    /// [`check_extra_size`](Self::check_extra_size). Rejects options only
+    /// some backends can render ([`media_controls`](Self::media_controls),
+    /// [`count_down_to`](Self::count_down_to),
+    /// [`max_repetitions`](Self::max_repetitions),
+    /// [`announce_only`](Self::announce_only)) elsewhere, and validates them.
+    fn check_platform_options(&self) -> crate::Result<()> {
+        let unsupported = |option: &str| {
+            crate::Error::Io(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                format!(
+                    "{option} are not supported on {}",
+                    crate::telemetry::BACKEND
+                ),
+            ))
+        };
+        let supported = cfg!(any(
+            target_os = "android",
+            all(target_os = "windows", not(feature = "notify-rust"))
+        ));
+        if let Some(controls) = &self.data.media_controls {
+            if !supported {
+                return Err(unsupported("Media controls"));
+            }
+            controls.validate()?;
+        }
+        if let Some(deadline) = self.data.count_down_to {
+            if !supported {
+                return Err(unsupported("Count-down notifications"));
+            }
+            if self.data.schedule.is_some() {
+                return Err(crate::Error::InvalidArgument(
+                    "a count-down notification can't also be scheduled".to_string(),
+                ));
+            }
+            if deadline <= time::OffsetDateTime::now_utc() {
+                return Err(crate::Error::InvalidArgument(
+                    "count-down deadline must be in the future".to_string(),
+                ));
+            }
+        }
+        if self.data.max_repetitions.is_some() {
+            if !cfg!(all(target_os = "windows", not(feature = "notify-rust"))) {
+                return Err(unsupported("Repetition limits"));
+            }
+            if !matches!(self.data.schedule, Some(Schedule::Interval { .. })) {
+                return Err(crate::Error::InvalidArgument(
+                    "max_repetitions requires an interval schedule".to_string(),
+                ));
+            }
+        }
+        if self.data.persistent && self.data.transient {
+            return Err(crate::Error::InvalidArgument(
+                "a notification can't be both persistent and transient".to_string(),
+            ));
+        }
+        if self.data.announce_only {
+            if !cfg!(any(
+                mobile,
+                all(target_os = "macos", not(feature = "notify-rust"))
+            )) {
+                return Err(unsupported("Accessibility announcements"));
+            }
+            if self.data.schedule.is_some() {
+                return Err(crate::Error::InvalidArgument(
+                    "an announcement can't be scheduled".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Called by every backend's `show` after
+    /// [`check_platform_options`](Self::check_platform_options): drops
+    /// `auto_cancel` from an ongoing notification, or rejects it if strict.
+    fn resolve_flag_conflicts(&mut self) -> crate::Result<()> {
+        #[cfg(desktop)]
+        let app = &self.app;
+        #[cfg(mobile)]
+        let app = self.handle.app();
+        if self.data.resolve_ongoing_auto_cancel(is_strict(app))? {
+            telemetry::warning!(
+                "Notification {}: autoCancel ignored, the notification is ongoing",
+                self.data.id
+            );
+        }
+        Ok(())
+    }
+
+    /// Called by every backend's `show` after
+    /// [`resolve_flag_conflicts`](Self::resolve_flag_conflicts): enforces
+    /// [`MAX_INBOX_LINES`] and, where there is no inbox style, moves the lines
+    /// into the body.
+    fn apply_inbox_lines(&mut self) -> crate::Result<()> {
+        #[cfg(desktop)]
+        let app = &self.app;
+        #[cfg(mobile)]
+        let app = self.handle.app();
+        let dropped = self.data.limit_inbox_lines(is_strict(app))?;
+        if dropped > 0 {
+            telemetry::warning!(
+                "Notification {}: dropped {dropped} inbox lines over the limit of {MAX_INBOX_LINES}",
+                self.data.id
+            );
+        }
+        #[cfg(not(target_os = "android"))]
+        self.data.fold_inbox_lines();
+        Ok(())
+    }
+
+    /// Called by every backend's `show` once the notification is complete:
+    /// logs the fields this platform ignores, see [`FIELD_SUPPORT`].
+    fn report_ignored_fields(&self) {
+        if self.is_patch {
+            return;
+        }
+        let app = self.app_handle();
+        let warn = app.try_state::<WarnOnIgnoredFields>().is_some_and(|w| w.0);
+        field_support::report(app, &self.data, warn);
+    }
+
+    /// The fields set on this notification that this platform ignores, as
+    /// named in the JS `Options`. Channel and config defaults are not
+    /// applied.
+    #[must_use]
+    pub fn ignored_fields(&self) -> Vec<&'static str> {
+        field_support::ignored_fields(&self.data, Backend::current())
+    }
+
+    /// Records the [`focus_window`](Self::focus_window) override for the
+    /// click handler. Called by the native desktop backends' `show`.
+    #[cfg(all(
+        any(target_os = "macos", target_os = "windows"),
+        not(feature = "notify-rust")
+    ))]
+    fn remember_focus_window(&self) {
+        if let Some(label) = &self.data.focus_window {
+            focus::remember(self.data.id, label.clone());
+        }
+    }
+
+    /// The plugin's dispatch queue, or `None` when [`unordered`](Self::unordered)
+    /// was set. Backends run the OS call of `show` through it.
+    fn dispatcher(&self) -> Option<dispatch::Dispatcher> {
+        if self.unordered {
+            return None;
+        }
+        #[cfg(desktop)]
+        let app = &self.app;
+        #[cfg(mobile)]
+        let app = self.handle.app();
+        app.try_state::<dispatch::Dispatcher>()
+            .map(|dispatcher| dispatcher.inner().clone())
+    }
+
+    /// Called by every backend's `show` right before delivery: once the
+    /// notification's group is past the [`PluginConfig::digest`] threshold,
+    /// shows the group's digest instead and returns its result.
+    async fn coalesce(&self) -> Option<crate::Result<()>> {
+        if self.is_digest
+            || self.is_patch
+            || self.data.schedule.is_some()
+            || self.data.count_down_to.is_some()
+            || self.data.announce_only
+        {
+            return None;
+        }
+        let app = self.app_handle();
+        let (coalesced, notification) = {
+            let digest = app.try_state::<digest::Digest>()?;
+            let coalesced = digest.admit(&self.data, std::time::Instant::now())?;
+            let notification = digest.notification(&coalesced, &self.data);
+            (coalesced, notification)
+        };
+        Some(coalesced.show(app, notification, self.dispatcher()).await)
+    }
+
+    /// Called by every backend's `show` before [`coalesce`](Self::coalesce):
+    /// emits `notificationFallback` instead of showing the notification
+    /// while the window picked by [`suppress_if_focused`](Self::suppress_if_focused)
+    /// is focused, and returns whether it did.
+    fn suppress_if_window_focused(&self) -> bool {
+        let Some(target) = &self.data.suppress_if_focused else {
+            return false;
+        };
+        if self.is_patch || self.data.schedule.is_some() {
+            return false;
+        }
+        let Some(window) = suppress::focused_window(self.app_handle(), target) else {
+            return false;
+        };
+        telemetry::debug!(
+            "Notification {} not shown: window '{window}' is focused",
+            self.data.id
+        );
+        suppress::emit_fallback(self.app_handle(), &self.data, &window);
+        true
+    }
+
+    /// Skips the plugin's dispatch queue. By default `show()` calls reach the
+    /// OS one at a time, in the order they were made; unordered notifications
+    /// are delivered as soon as `show()` runs, possibly in parallel with
+    /// others.
+    #[must_use]
+    pub const fn unordered(mut self) -> Self {
+        self.unordered = true;
+        self
+    }
+
+    /// [`show`](Self::show), failing with [`Error::Timeout`] if the platform
+    /// hasn't confirmed the notification within `timeout` (for example a
+    /// hung iOS simulator).
+    ///
+    /// The timeout only stops the wait: a notification already handed to the
+    /// dispatch queue or the OS may still appear afterwards.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn show_with_timeout(self, timeout: std::time::Duration) -> crate::Result<()> {
+        tokio::time::timeout(timeout, self.show())
+            .await
+            .map_err(|_| crate::Error::Timeout)?
+    }
+
     /// Sets the notification identifier.
     #[must_use]
     pub const fn id(mut self, id: i32) -> Self {
@@ -127,14 +634,25 @@ impl<R: Runtime> NotificationsBuilder<R> {
 
     /// Identifier of the {@link Channel} that delivers this notification.
     ///
-    /// If the channel does not exist, the notification won't fire.
-    /// Make sure the channel exists with {@link listChannels} and {@link createChannel}.
+    /// On Android, `show()` fails with [`Error::NotFound`] if the channel
+    /// doesn't exist, unless [`channel_id_fallback`](Self::channel_id_fallback)
+    /// does. Make sure the channel exists with {@link listChannels} and
+    /// {@link createChannel}.
     #[must_use]
     pub fn channel_id(mut self, id: impl Into<String>) -> Self {
         self.data.channel_id.replace(id.into());
         self
     }
 
+    /// Channel that delivers the notification on Android when the
+    /// [`channel_id`](Self::channel_id) one doesn't exist, e.g. because the
+    /// user deleted it or it is created later. Ignored elsewhere.
+    #[must_use]
+    pub fn channel_id_fallback(mut self, id: impl Into<String>) -> Self {
+        self.data.channel_id_fallback.replace(id.into());
+        self
+    }
+
     /// Sets the notification title.
     #[must_use]
     pub fn title(mut self, title: impl Into<String>) -> Self {
@@ -156,6 +674,54 @@ impl<R: Runtime> NotificationsBuilder<R> {
         self
     }
 
+    /// Tags the notification with an account or profile, so
+    /// [`Notifications::clear_scope`] can take away that scope's scheduled
+    /// and shown notifications at once, e.g. on logout. The scope is
+    /// persisted with the notification, so it also matches notifications
+    /// scheduled in earlier sessions.
+    #[must_use]
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.data.scope.replace(scope.into());
+        self
+    }
+
+    /// Caps how many times an [`Interval`](Schedule::Interval) schedule
+    /// repeats after its first delivery; without it, it repeats until
+    /// canceled.
+    ///
+    /// Windows only: scheduled toasts fire once, so the plugin schedules
+    /// each next occurrence after the previous one fired. The schedule and
+    /// the repetitions left are stored in the app data dir, so a restarted
+    /// app carries on where the last run stopped. Other platforms fail `show()` with
+    /// [`std::io::ErrorKind::Unsupported`].
+    #[must_use]
+    pub const fn max_repetitions(mut self, max_repetitions: u32) -> Self {
+        self.data.max_repetitions = Some(max_repetitions);
+        self
+    }
+
+    /// Shows a scheduled notification at most once, and only while it's
+    /// relevant: if the app starts again after the scheduled time, the toast
+    /// is pulled from the schedule instead of showing late.
+    ///
+    /// Windows only, where scheduled toasts outlive the app; ignored
+    /// elsewhere. Can't be combined with a repeating schedule.
+    #[must_use]
+    pub const fn show_only_once(mut self) -> Self {
+        self.data.show_only_once = true;
+        self
+    }
+
+    /// What to do with this notification during
+    /// [`PluginConfig::quiet_hours`], instead of the configured policy:
+    /// defer it to the end of quiet hours, deliver it quietly, or deliver it
+    /// as usual.
+    #[must_use]
+    pub const fn quiet_hours_policy(mut self, policy: QuietHoursPolicy) -> Self {
+        self.data.quiet_hours_policy = Some(policy);
+        self
+    }
+
     /// Multiline text.
     /// Changes the notification style to big text.
     /// Cannot be used with `inboxLines`.
@@ -165,7 +731,13 @@ impl<R: Runtime> NotificationsBuilder<R> {
         self
     }
 
-    /// Detail text for the notification with `largeBody`, `inboxLines` or `groupSummary`.
+    /// Secondary line of the notification.
+    ///
+    /// - **Android**: summary text of the `largeBody`, `inboxLines` or
+    ///   `groupSummary` style.
+    /// - **iOS/macOS**: the subtitle, also used in the summary of a collapsed
+    ///   group.
+    /// - **Windows**: the attribution line, in small print below the text.
     #[must_use]
     pub fn summary(mut self, summary: impl Into<String>) -> Self {
         self.data.summary.replace(summary.into());
@@ -189,9 +761,42 @@ impl<R: Runtime> NotificationsBuilder<R> {
     }
 
     /// Instructs the system that this notification is the summary of a group on Android.
+    ///
+    /// On Windows, `group_summary` combined with [`group`](Self::group) is the
+    /// equivalent of Android's summary notification: the toast is tagged with
+    /// the group and rendered with `scenario="reminder"`, so it stays on screen
+    /// until dismissed.
+    ///
+    /// A summary shown before any child of its group is held back until the
+    /// first child has been shown, since Android may not group children under
+    /// a summary posted before them. `show()` resolves right away then; see
+    /// [`Notifications::flush_group`] to show it without a child.
+    #[must_use]
+    pub const fn set_group_summary(mut self, group_summary: bool) -> Self {
+        self.data.group_summary = group_summary;
+        self
+    }
+
+    /// Marks the notification as the summary of its group.
+    #[deprecated(note = "use `set_group_summary(true)`")]
+    #[must_use]
+    pub const fn group_summary(self) -> Self {
+        self.set_group_summary(true)
+    }
+
+    /// Orders the notification within its [`group`](Self::group): keyed
+    /// notifications come first, in plain string order, and the rest follow
+    /// newest first.
+    ///
+    /// Maps to `setSortKey` on Android. Windows re-posts the group's toasts
+    /// that sort before this one so Action Center lists them in order;
+    /// re-posted toasts are delivered to the COM activator only when clicked.
+    /// iOS and macOS always list notifications newest first. Everywhere the
+    /// order is reported by
+    /// [`Notifications::active_grouped`](crate::Notifications::active_grouped).
     #[must_use]
-    pub const fn group_summary(mut self) -> Self {
-        self.data.group_summary = true;
+    pub fn sort_key(mut self, sort_key: impl Into<String>) -> Self {
+        self.data.sort_key.replace(sort_key.into());
         self
     }
 
@@ -206,13 +811,26 @@ impl<R: Runtime> NotificationsBuilder<R> {
     /// Changes the notification style to inbox.
     /// Cannot be used with `largeBody`.
     ///
-    /// Only supports up to 5 lines.
+    /// Only supports up to [`MAX_INBOX_LINES`] lines; see
+    /// [`inbox_lines`](Self::inbox_lines).
     #[must_use]
     pub fn inbox_line(mut self, line: impl Into<String>) -> Self {
         self.data.inbox_lines.push(line.into());
         self
     }
 
+    /// Replaces the inbox lines of the notification.
+    ///
+    /// `show()` drops empty lines and keeps the first [`MAX_INBOX_LINES`],
+    /// logging a warning, or fails with [`Error::InvalidArgument`] under
+    /// [`PluginConfig::strict_limits`]. Without an inbox style (everywhere
+    /// but Android) the lines are shown in the body, below its text.
+    #[must_use]
+    pub fn inbox_lines(mut self, lines: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.data.inbox_lines = lines.into_iter().map(Into::into).collect();
+        self
+    }
+
     /// Notification icon.
     ///
     /// On Android the icon must be placed in the app's `res/drawable` folder.
@@ -231,10 +849,124 @@ impl<R: Runtime> NotificationsBuilder<R> {
         self
     }
 
-    /// Icon color on Android.
+    /// Icon color on Android, as `#RGB`, `#RRGGBB` or `#AARRGGBB`.
+    ///
+    /// Fails with [`Error::InvalidArgument`] for any other format.
+    pub fn icon_color(mut self, icon_color: impl Into<String>) -> Result<Self> {
+        let icon_color = models::normalize_icon_color(&icon_color.into())?;
+        self.data.icon_color.replace(icon_color);
+        Ok(self)
+    }
+
+    /// Foreground (text) color of the toast on Windows. Ignored on other
+    /// platforms.
+    #[must_use]
+    pub const fn foreground_color(mut self, r: u8, g: u8, b: u8) -> Self {
+        self.data.foreground_color.replace((r, g, b));
+        self
+    }
+
+    /// Label of the window to show, unminimize and focus when this
+    /// notification is clicked, overriding
+    /// [`PluginConfig::focus_window_on_click`]. Windows and macOS; Android and
+    /// iOS bring the app to the front on their own.
+    #[must_use]
+    pub fn focus_window(mut self, label: impl Into<String>) -> Self {
+        self.data.focus_window.replace(label.into());
+        self
+    }
+
+    /// Emits `notificationFallback` with the notification instead of showing
+    /// it while the window labeled `window_label` is focused, or any window
+    /// of the app for `None`; e.g. for a chat the user is looking at.
+    /// `show()` still succeeds. The focus is checked when `show()` runs, so
+    /// scheduled notifications are always shown.
+    #[must_use]
+    pub fn suppress_if_focused(mut self, window_label: Option<String>) -> Self {
+        self.data.suppress_if_focused = Some(window_label.map_or(
+            suppress::FocusTarget::AnyWindow,
+            suppress::FocusTarget::Window,
+        ));
+        self
+    }
+
+    /// Turns the notification into a media notification with
+    /// playback buttons. Pressing one emits `actionPerformed` with the
+    /// button's [`MediaAction::action_id`] (`media:play`, `media:pause`, ...).
+    ///
+    /// Android uses `MediaStyle` backed by a media session the plugin
+    /// manages; Windows shows the buttons on the toast. Other platforms fail
+    /// `show()` with [`std::io::ErrorKind::Unsupported`].
+    #[must_use]
+    pub fn media_controls(mut self, controls: MediaControls) -> Self {
+        self.data.media_controls.replace(controls);
+        self
+    }
+
+    /// Shows a live count-down to `deadline` ("4:59 remaining") and re-posts
+    /// the notification as an alarm once it passes. [`cancel`] with the
+    /// notification id stops both the count-down and the alarm.
+    ///
+    /// Android uses a count-down chronometer and posts the alarm with the
+    /// alarm category and the notification's sound. Windows shows a progress
+    /// bar the plugin updates every second and schedules an alarm-scenario
+    /// toast for the deadline. Other platforms fail `show()` with
+    /// [`std::io::ErrorKind::Unsupported`]. Can't be combined with
+    /// [`schedule`](Self::schedule).
+    ///
+    /// [`cancel`]: Notifications::cancel
+    #[must_use]
+    pub const fn count_down_to(mut self, deadline: time::OffsetDateTime) -> Self {
+        self.data.count_down_to.replace(deadline);
+        self
+    }
+
+    /// Shows a progress bar filled to `fraction`, clamped to 0..=1. Update
+    /// it with [`Notifications::patch_active`]. Android and Windows only;
+    /// ignored with [`count_down_to`](Self::count_down_to).
+    #[must_use]
+    pub const fn progress(mut self, fraction: f32) -> Self {
+        self.data.progress = Some(fraction.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Text accessibility services read when the notification is posted,
+    /// instead of just the title. Maps to `setTicker` on Android; also used
+    /// as the text of an [`announce_only`](Self::announce_only) announcement.
+    #[must_use]
+    pub fn ticker(mut self, ticker: impl Into<String>) -> Self {
+        self.data.ticker.replace(ticker.into());
+        self
+    }
+
+    /// Delivers a screen-reader announcement instead of a visual
+    /// notification, for minor updates that shouldn't clutter the
+    /// notification shade. The announced text is the
+    /// [`ticker`](Self::ticker), or the title and body.
+    ///
+    /// Supported on Android, iOS and macOS (without `notify-rust`); other
+    /// platforms fail `show()` with [`std::io::ErrorKind::Unsupported`].
+    /// Can't be combined with [`schedule`](Self::schedule).
+    #[must_use]
+    pub const fn announce_only(mut self, announce_only: bool) -> Self {
+        self.data.announce_only = announce_only;
+        self
+    }
+
+    /// Keeps the notification on this device instead of bridging it to a
+    /// paired watch. Android only; ignored elsewhere.
+    #[must_use]
+    pub const fn local_only(mut self, local_only: bool) -> Self {
+        self.data.local_only = local_only;
+        self
+    }
+
+    /// Actions shown only on a paired watch, through a `WearableExtender`.
+    /// They're delivered like the notification's other actions. Android
+    /// only; ignored elsewhere.
     #[must_use]
-    pub fn icon_color(mut self, icon_color: impl Into<String>) -> Self {
-        self.data.icon_color.replace(icon_color.into());
+    pub fn wearable_actions(mut self, actions: Vec<Action>) -> Self {
+        self.data.wearable_actions = actions;
         self
     }
 
@@ -246,6 +978,12 @@ impl<R: Runtime> NotificationsBuilder<R> {
     }
 
     /// Adds an extra payload to store in the notification.
+    ///
+    /// Values may be nested objects and arrays. Integers outside ±2^53 are
+    /// delivered back as strings, since the native platforms and JavaScript
+    /// can't represent them exactly as numbers. The encoded payload is limited
+    /// to [`PluginConfig::max_extra_bytes`]; `show` fails with
+    /// [`Error::ExtraTooLarge`] above it.
     #[must_use]
     pub fn extra(mut self, key: impl Into<String>, value: impl Serialize) -> Self {
         if let Ok(value) = serde_json::to_value(value) {
@@ -255,27 +993,83 @@ impl<R: Runtime> NotificationsBuilder<R> {
     }
 
     /// If true, the notification cannot be dismissed by the user on Android.
+    /// On Linux it never times out and stays in the notification list.
     ///
     /// An application service must manage the dismissal of the notification.
     /// It is typically used to indicate a background task that is pending (e.g. a file download)
     /// or the user is engaged with (e.g. playing music).
+    ///
+    /// Takes precedence over [`set_auto_cancel`](Self::set_auto_cancel),
+    /// which is dropped with a warning, or rejected with
+    /// [`PluginConfig::strict_limits`].
+    #[must_use]
+    pub const fn set_ongoing(mut self, ongoing: bool) -> Self {
+        self.data.ongoing = ongoing;
+        self
+    }
+
+    /// Makes the notification ongoing.
+    #[deprecated(note = "use `set_ongoing(true)`")]
+    #[must_use]
+    pub const fn ongoing(self) -> Self {
+        self.set_ongoing(true)
+    }
+
+    /// If true, the notification is cancelled when the user clicks on it.
+    /// Can't be combined with [`set_ongoing`](Self::set_ongoing).
     #[must_use]
-    pub const fn ongoing(mut self) -> Self {
-        self.data.ongoing = true;
+    pub const fn set_auto_cancel(mut self, auto_cancel: bool) -> Self {
+        self.data.auto_cancel = auto_cancel;
         self
     }
 
     /// Automatically cancel the notification when the user clicks on it.
+    #[deprecated(note = "use `set_auto_cancel(true)`")]
     #[must_use]
-    pub const fn auto_cancel(mut self) -> Self {
-        self.data.auto_cancel = true;
+    pub const fn auto_cancel(self) -> Self {
+        self.set_auto_cancel(true)
+    }
+
+    /// If true, changes the notification presentation to be silent on iOS
+    /// (no badge, no sound, not listed).
+    #[must_use]
+    pub const fn set_silent(mut self, silent: bool) -> Self {
+        self.data.silent = silent;
         self
     }
 
     /// Changes the notification presentation to be silent on iOS (no badge, no sound, not listed).
+    #[deprecated(note = "use `set_silent(true)`")]
+    #[must_use]
+    pub const fn silent(self) -> Self {
+        self.set_silent(true)
+    }
+
+    /// Keeps the notification in the notification list after it times out
+    /// (the `resident` hint on Linux). On Windows the toast stays on screen
+    /// longer; Action Center keeps it either way. Ignored on other platforms.
+    /// Can't be combined with [`transient`](Self::transient).
+    #[must_use]
+    pub const fn persistent(mut self, persistent: bool) -> Self {
+        self.data.persistent = persistent;
+        self
+    }
+
+    /// Keeps the notification out of the notification list once its popup
+    /// closes (the `transient` hint on Linux). Ignored on other platforms.
+    /// Can't be combined with [`persistent`](Self::persistent).
     #[must_use]
-    pub const fn silent(mut self) -> Self {
-        self.data.silent = true;
+    pub const fn transient(mut self, transient: bool) -> Self {
+        self.data.transient = transient;
+        self
+    }
+
+    /// Keeps the notification on screen until the user dismisses it. Linux
+    /// disables the timeout; Windows uses the reminder scenario with a
+    /// dismiss button. Ignored on other platforms.
+    #[must_use]
+    pub const fn require_interaction(mut self, require_interaction: bool) -> Self {
+        self.data.require_interaction = require_interaction;
         self
     }
 }
@@ -291,31 +1085,447 @@ impl<R: Runtime, T: Manager<R>> crate::NotificationsExt<R> for T {
     }
 }
 
+/// Windows limits AppUserModelIDs to 128 characters without spaces.
+#[cfg(target_os = "windows")]
+fn validate_windows_app_id(app_id: &str) -> Result<()> {
+    if app_id.is_empty() || app_id.len() > 128 || app_id.contains(char::is_whitespace) {
+        return Err(Error::InvalidArgument(format!(
+            "invalid AppUserModelID {app_id:?}: expected 1-128 characters without spaces"
+        )));
+    }
+    Ok(())
+}
+
+/// One copy of `data` per window label for
+/// [`Notifications::broadcast_notification`]. The first copy keeps `data.id`,
+/// the others get fresh ids, and each focuses its own window when clicked.
+fn broadcast_copies(data: &NotificationData, labels: Vec<String>) -> Vec<NotificationData> {
+    let mut ids = std::collections::HashSet::new();
+    labels
+        .into_iter()
+        .map(|label| {
+            let mut copy = data.clone();
+            while !ids.insert(copy.id) {
+                copy.id = rand::random();
+            }
+            copy.focus_window = Some(label);
+            copy
+        })
+        .collect()
+}
+
+impl<R: Runtime> Notifications<R> {
+    /// What scheduled notifications can be relied on for on this platform,
+    /// e.g. to tell users whether a reminder fires while the machine sleeps.
+    #[must_use]
+    pub const fn schedule_capabilities(&self) -> ScheduleCapabilities {
+        ScheduleCapabilities::current()
+    }
+
+    /// Backend compiled into this build: `"windows"`, `"macos"`, `"ios"`,
+    /// `"android"` or `"notify-rust"`.
+    #[must_use]
+    pub const fn platform_name(&self) -> &'static str {
+        telemetry::BACKEND
+    }
+
+    /// Version of this plugin crate.
+    #[must_use]
+    pub const fn plugin_version(&self) -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    /// [`platform_name`](Self::platform_name) and
+    /// [`plugin_version`](Self::plugin_version) together, e.g. for bug
+    /// reports.
+    #[must_use]
+    pub const fn plugin_info(&self) -> PluginInfo {
+        PluginInfo {
+            platform: self.platform_name(),
+            version: self.plugin_version(),
+        }
+    }
+
+    /// How the app was started: normally, or by tapping a notification, one
+    /// of its actions or a push. Doesn't consume the click or action events.
+    ///
+    /// Read from the platform on the first call and the same for the rest
+    /// of the session. Android reads the launch intent and Windows the
+    /// activation that started the process. iOS only learns about a tap
+    /// once the app has started, so call this after plugin setup rather
+    /// than from it. macOS and notify-rust always report
+    /// [`LaunchSource::Normal`].
+    pub fn launch_source(&self) -> LaunchSource {
+        static LAUNCH_SOURCE: std::sync::OnceLock<LaunchSource> = std::sync::OnceLock::new();
+        LAUNCH_SOURCE
+            .get_or_init(|| self.read_launch_source())
+            .clone()
+    }
+
+    // Same signature as the mobile and Windows versions, which ask the
+    // platform.
+    #[cfg(not(any(mobile, all(target_os = "windows", not(feature = "notify-rust")))))]
+    #[allow(clippy::unused_self)]
+    const fn read_launch_source(&self) -> LaunchSource {
+        LaunchSource::Normal
+    }
+
+    /// The channel with identifier `id`, if it exists. Android only, like
+    /// [`list_channels`](Self::list_channels).
+    pub fn get_channel(&self, id: &str) -> Result<Option<Channel>> {
+        Ok(self
+            .list_channels()?
+            .into_iter()
+            .find(|channel| channel.id() == id))
+    }
+
+    /// Changes the importance of the existing channel `id` to
+    /// `new_importance`. Android lets apps only lower a channel's importance;
+    /// raising it fails with [`Error::Unsupported`] since only the user can
+    /// do that, in the system settings. Android only, like
+    /// [`list_channels`](Self::list_channels).
+    pub fn upgrade_channel(&self, id: &str, new_importance: Importance) -> Result<()> {
+        let channel = self
+            .get_channel(id)?
+            .ok_or_else(|| Error::NotFound(format!("no notification channel `{id}`")))?;
+        if new_importance > channel.importance() {
+            return Err(Error::Unsupported(
+                "channel importance can only be upgraded by the user in Android settings"
+                    .to_string(),
+            ));
+        }
+        self.update_channel(
+            ChannelBuilder::from(channel)
+                .importance(new_importance)
+                .build(),
+        )
+    }
+
+    /// Replaces what scheduled notification `id` shows as it fires, in reply
+    /// to the `scheduledNotificationTriggered` event for it. The notification waits
+    /// 200 ms for the reply and then shows as scheduled; a repeating
+    /// schedule keeps the original content for later firings. `data`'s `id`
+    /// and `schedule` are ignored.
+    ///
+    /// Android only: iOS offers no hook before a local notification is
+    /// presented.
+    pub fn update_scheduled_notification(&self, id: i32, data: &NotificationData) -> Result<()> {
+        #[cfg(target_os = "android")]
+        return self.replace_scheduled(id, data);
+        #[cfg(not(target_os = "android"))]
+        {
+            let _ = (id, data);
+            Err(Error::Unsupported(
+                "scheduled notifications can only be replaced as they fire on Android".to_string(),
+            ))
+        }
+    }
+
+    /// Shows the system permission dialog even where
+    /// [`request_permission`](Self::request_permission) returned
+    /// [`PermissionState::PromptWithRationale`]: call it once the app has
+    /// explained why it needs the permission. Only Android asks for a
+    /// rationale; elsewhere this is `request_permission`.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn request_permission_after_rationale(&self) -> Result<PermissionState> {
+        #[cfg(mobile)]
+        return self.request_permission_with(true).await;
+        #[cfg(not(mobile))]
+        self.request_permission().await
+    }
+
+    /// The notification permission state. Reused for
+    /// [`PluginConfig::permission_cache_ttl`] after it has been read from the
+    /// platform, see [`permission_state_fresh`](Self::permission_state_fresh)
+    /// for an uncached read.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn permission_state(&self) -> Result<PermissionState> {
+        permission_cache::get_or_read(self.permission_state_fresh()).await
+    }
+
+    /// The [`active`](Self::active) notifications split by group, with each
+    /// group's children ordered by
+    /// [`ActiveNotification::sort_key`] and then newest first.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn active_grouped(&self) -> Result<Vec<ActiveGroup>> {
+        Ok(ActiveGroup::collect(self.active().await?))
+    }
+
+    /// Shows the summaries of `group` held back until the group's first
+    /// child, see [`NotificationsBuilder::set_group_summary`]. Use it when a
+    /// group ends up with no children to show. Does nothing if none is held.
+    pub async fn flush_group(&self, group: &str) {
+        if let Some(dispatcher) = self.builder().dispatcher() {
+            dispatcher.flush_group(group).await;
+        }
+    }
+
+    /// Shows a notification with `title` and `body` right away and returns
+    /// its id. Shorthand for `builder().title(title).body(body).show()`.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn send_immediate(&self, title: &str, body: &str) -> Result<i32> {
+        let builder = self.builder().title(title).body(body);
+        let id = builder.data.id;
+        builder.show().await?;
+        Ok(id)
+    }
+
+    /// Like [`send_immediate`](Self::send_immediate), with the notification
+    /// marked [`silent`](NotificationsBuilder::set_silent).
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn send_silent(&self, title: &str, body: &str) -> Result<i32> {
+        let builder = self.builder().title(title).body(body).set_silent(true);
+        let id = builder.data.id;
+        builder.show().await?;
+        Ok(id)
+    }
+
+    /// Posts `data` once per open webview window (sorted by label) and
+    /// returns the ids used, one per window. Each copy focuses its own window
+    /// when clicked (Windows and macOS). Without any window, and on mobile,
+    /// this is a plain `show()` of `data`. A copy that fails doesn't stop the
+    /// others; the failures come back as [`Error::PartialBatch`], indexed by
+    /// window in label order.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn broadcast_notification(&self, data: NotificationData) -> Result<Vec<i32>> {
+        #[cfg(desktop)]
+        let mut labels: Vec<String> = self.builder().app.webview_windows().into_keys().collect();
+        #[cfg(mobile)]
+        let mut labels: Vec<String> = Vec::new();
+        labels.sort();
+
+        if labels.is_empty() {
+            return Ok(vec![self.show_notification(data).await?]);
+        }
+
+        let mut succeeded = Vec::with_capacity(labels.len());
+        let mut failed = Vec::new();
+        for (index, copy) in broadcast_copies(&data, labels).into_iter().enumerate() {
+            let id = copy.id;
+            match self.builder_with(copy).show().await {
+                Ok(()) => succeeded.push(id),
+                Err(e) => failed.push((index, e)),
+            }
+        }
+        if failed.is_empty() {
+            Ok(succeeded)
+        } else {
+            Err(Error::PartialBatch { succeeded, failed })
+        }
+    }
+
+    /// Shows `data`, e.g. options deserialized from JS, and returns its id.
+    /// Fields it leaves unset get the same channel and config defaults as a
+    /// notification built with [`builder`](Self::builder).
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn show_notification(&self, data: NotificationData) -> Result<i32> {
+        let id = data.id;
+        self.builder_with(data).show().await?;
+        Ok(id)
+    }
+
+    /// A builder for `data` as it is, e.g. to show an existing
+    /// [`NotificationData`].
+    fn builder_with(&self, data: NotificationData) -> NotificationsBuilder<R> {
+        let mut builder = self.builder();
+        builder.data = data;
+        builder
+    }
+
+    /// Shows each notification in order and returns their ids. A failing
+    /// notification doesn't stop the rest: if any fail, the result is
+    /// [`Error::PartialBatch`] with the ids that were shown and the index and
+    /// error of each one that wasn't.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn show_batch(&self, batch: Vec<NotificationData>) -> Result<Vec<i32>> {
+        let mut succeeded = Vec::with_capacity(batch.len());
+        let mut failed = Vec::new();
+        for (index, data) in batch.into_iter().enumerate() {
+            let id = data.id;
+            match self.builder_with(data).show().await {
+                Ok(()) => succeeded.push(id),
+                Err(e) => failed.push((index, e)),
+            }
+        }
+        if failed.is_empty() {
+            Ok(succeeded)
+        } else {
+            Err(Error::PartialBatch { succeeded, failed })
+        }
+    }
+
+    /// Shows each notification in order, like
+    /// [`show_batch`](Self::show_batch), and reports the outcome of each one
+    /// by id.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn show_batch_detailed(&self, batch: Vec<NotificationData>) -> BulkResult {
+        let mut result = BulkResult::default();
+        for data in batch {
+            let id = data.id;
+            result.push(id, self.builder_with(data).show().await);
+        }
+        result
+    }
+
+    /// [`cancel`](Self::cancel) with the outcome of each id. The native
+    /// plugins cancel them all in one call, so they succeed or fail together.
+    #[cfg(not(any(
+        target_os = "linux",
+        all(target_os = "windows", not(feature = "notify-rust"))
+    )))]
+    pub fn cancel_detailed(&self, notifications: Vec<i32>) -> Result<BulkResult> {
+        let result = self.cancel(notifications.clone());
+        Ok(BulkResult::all(notifications, &result))
+    }
+
+    /// [`remove_active`](Self::remove_active) with the outcome of each id.
+    /// The native plugins remove them all in one call, so they succeed or
+    /// fail together.
+    #[cfg(not(any(
+        target_os = "linux",
+        all(target_os = "windows", not(feature = "notify-rust"))
+    )))]
+    pub fn remove_active_detailed(&self, notifications: Vec<i32>) -> Result<BulkResult> {
+        let result = self.remove_active(notifications.clone());
+        Ok(BulkResult::all(notifications, &result))
+    }
+
+    /// Cancels every notification scheduled under `group` and returns their
+    /// ids. Only Windows reports the group of a scheduled notification.
+    #[cfg(not(all(target_os = "windows", not(feature = "notify-rust"))))]
+    pub fn cancel_by_group(&self, group: &str) -> Result<Vec<i32>> {
+        let _ = group;
+        Err(Error::Unsupported(
+            "canceling by group is only supported on Windows".to_string(),
+        ))
+    }
+
+    /// Changes the shown notification `id`, e.g. a download's progress, and
+    /// returns how it was done. Fails with [`Error::NotFound`] if it isn't
+    /// [`active`](Self::active).
+    ///
+    /// Windows moves the progress bar of a notification shown with
+    /// [`progress`](NotificationsBuilder::progress) in place. Otherwise the
+    /// notification is shown again under its id: Android re-posts it, which
+    /// alerts only once; Windows, iOS and macOS show it quietly, straight to
+    /// the notification list; notify-rust closes it and shows it again, which
+    /// may alert. One shown in an earlier session is rebuilt from what
+    /// [`active`](Self::active) reports about it.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn patch_active(&self, id: i32, patch: ActivePatch) -> Result<PatchStrategy> {
+        let Some(active) = self.active().await?.into_iter().find(|n| n.id == id) else {
+            return Err(Error::NotFound(format!("notification {id} is not shown")));
+        };
+        let (mut data, strategy) = crate::patch::shown(id).map_or_else(
+            || (crate::patch::rebuild(active), PatchStrategy::Rebuilt),
+            |data| (data, PatchStrategy::ReplacedQuietly),
+        );
+
+        #[cfg(all(target_os = "windows", not(feature = "notify-rust")))]
+        if let Some(fraction) = patch.progress.filter(|_| patch.is_progress_only())
+            && data.progress.is_some()
+            && data.count_down_to.is_none()
+        {
+            let fraction = fraction.clamp(0.0, 1.0);
+            if self.update_progress(id, data.group.as_deref(), fraction)? {
+                data.progress = Some(fraction);
+                crate::patch::remember(data);
+                return Ok(PatchStrategy::InPlace);
+            }
+        }
+
+        patch.apply(&mut data);
+        // Already delivered: show it now, not at its next occurrence.
+        data.schedule = None;
+        #[cfg(not(target_os = "android"))]
+        {
+            data.quiet = true;
+        }
+        #[cfg(any(
+            target_os = "ios",
+            all(target_os = "macos", not(feature = "notify-rust"))
+        ))]
+        self.remove_active(vec![id])?;
+        #[cfg(all(desktop, any(feature = "notify-rust", target_os = "linux")))]
+        let strategy = {
+            if let Err(e) = self.remove_active(vec![id]) {
+                telemetry::warning!("Failed to close notification {id} before patching it: {e}");
+            }
+            // Rebuilt or not, showing it again may alert.
+            let _ = strategy;
+            PatchStrategy::Reshown
+        };
+
+        let mut builder = self.builder_with(data);
+        builder.is_patch = true;
+        builder.show().await?;
+        Ok(strategy)
+    }
+}
+
 /// Initializes the plugin.
 #[must_use]
 pub fn init<R: Runtime>() -> TauriPlugin<R, Option<PluginConfig>> {
     Builder::<R, Option<PluginConfig>>::new("notifications")
         .invoke_handler(tauri::generate_handler![
             commands::notify,
+            commands::batch,
+            commands::batch_detailed,
             commands::request_permission,
             commands::register_for_push_notifications,
             commands::unregister_for_push_notifications,
+            commands::get_push_token_cached,
             commands::is_permission_granted,
+            commands::is_do_not_disturb_active,
+            commands::get_unread_count,
             commands::register_action_types,
+            commands::set_notification_categories,
+            commands::unregister_action_type,
+            commands::clear_action_types,
             commands::get_pending,
+            commands::get_pending_count_by_channel,
             commands::get_active,
+            commands::get_active_grouped,
+            commands::flush_group,
+            commands::update_scheduled_notification,
+            commands::get_notification_history,
+            commands::register_channel_blocked_listener,
+            commands::run_doctor,
+            commands::get_schedule_capabilities,
+            commands::get_plugin_info,
+            commands::get_launch_source,
+            commands::get_notification_stats,
+            commands::reset_stats,
             commands::set_click_listener_active,
             commands::remove_active,
+            commands::remove_active_detailed,
             commands::remove_all,
+            commands::remove_all_active,
             commands::cancel,
+            commands::cancel_detailed,
+            commands::cancel_by_group,
+            commands::patch_active,
+            commands::dismiss,
+            commands::clear_scope,
             commands::cancel_all,
             commands::create_channel,
+            commands::ensure_channels,
+            commands::get_channel,
             commands::delete_channel,
             commands::list_channels,
+            commands::get_channel_defaults,
+            commands::clear_channel_defaults,
             #[cfg(desktop)]
             listeners::register_listener,
             #[cfg(desktop)]
             listeners::remove_listener,
+            commands::get_ignored_fields,
+            #[cfg(all(debug_assertions, target_os = "windows", not(feature = "notify-rust")))]
+            commands::get_toast_xml,
+            #[cfg(all(desktop, feature = "test-utils"))]
+            commands::simulate_click,
+            #[cfg(all(desktop, feature = "test-utils"))]
+            commands::simulate_action,
             #[cfg(all(desktop, target_os = "linux", feature = "push-notifications"))]
             commands::list_distributors,
             #[cfg(all(desktop, target_os = "linux", feature = "push-notifications"))]
@@ -324,28 +1534,124 @@ pub fn init<R: Runtime>() -> TauriPlugin<R, Option<PluginConfig>> {
             commands::set_token,
         ])
         .setup(|app, api| {
-            #[cfg(desktop)]
             listeners::init();
-            #[cfg(all(target_os = "windows", not(feature = "notify-rust")))]
-            let windows_config = api
-                .config()
-                .as_ref()
-                .map(|c| c.windows.clone())
-                .unwrap_or_default();
-            #[cfg(mobile)]
-            let notification = mobile::init(app, api)?;
-            #[cfg(all(desktop, any(feature = "notify-rust", target_os = "linux")))]
-            let notification = desktop::init(app, api)?;
-            #[cfg(all(target_os = "macos", not(feature = "notify-rust")))]
-            let notification = macos::init(app, api)?;
-            #[cfg(all(target_os = "windows", not(feature = "notify-rust")))]
-            let notification = windows::init(app, api, windows_config)?;
-            app.manage(notification);
-            Ok(())
+            manage_state(app, api.config().as_ref());
+            #[cfg(all(
+                feature = "media-cache",
+                desktop,
+                any(feature = "notify-rust", target_os = "linux", target_os = "windows")
+            ))]
+            manage_media_cache(app, api.config().as_ref())?;
+            manage_backend(app, api)
+        })
+        .on_event(|_app, event| {
+            // The user may be back from changing the permission in the
+            // system settings.
+            if matches!(
+                event,
+                RunEvent::Resumed
+                    | RunEvent::WindowEvent {
+                        event: WindowEvent::Focused(true),
+                        ..
+                    }
+            ) {
+                permission_cache::invalidate();
+            }
         })
         .build()
 }
 
+/// Sets up the state shared by every backend from the plugin config.
+fn manage_state<R: Runtime>(app: &AppHandle<R>, config: Option<&PluginConfig>) {
+    #[cfg(desktop)]
+    events::init(
+        config
+            .and_then(|c| c.event_buffer)
+            .unwrap_or(DEFAULT_EVENT_BUFFER),
+    );
+    app.manage(ExtraLimit(
+        config
+            .and_then(|c| c.max_extra_bytes)
+            .unwrap_or(DEFAULT_MAX_EXTRA_BYTES),
+    ));
+    app.manage(StrictLimits(config.is_some_and(|c| c.strict_limits)));
+    app.manage(WarnOnIgnoredFields(
+        config.is_some_and(|c| c.warn_on_ignored_fields),
+    ));
+    app.manage(dispatch::Dispatcher::new());
+    permission_cache::init(
+        config
+            .and_then(|c| c.permission_cache_ttl)
+            .map_or(DEFAULT_PERMISSION_CACHE_TTL, std::time::Duration::from_secs),
+    );
+    app.manage(channel_defaults::ChannelDefaults::load(
+        app,
+        config.and_then(|c| c.notification_defaults.clone()),
+    ));
+    if let Some(quiet_hours) = config.and_then(|c| c.quiet_hours.clone()) {
+        app.manage(quiet_hours);
+    }
+    if let Some(policy) = config.and_then(|c| c.digest.clone()) {
+        app.manage(digest::Digest::new(policy));
+    }
+    if config.is_some_and(|c| c.stats) {
+        stats::init(app);
+    }
+    #[cfg(all(
+        any(target_os = "macos", target_os = "windows"),
+        not(feature = "notify-rust")
+    ))]
+    focus::init(
+        app.clone(),
+        config.and_then(|c| c.focus_window_on_click.clone()),
+    );
+}
+
+/// Opens the cache of downloaded notification media.
+#[cfg(all(
+    feature = "media-cache",
+    desktop,
+    any(feature = "notify-rust", target_os = "linux", target_os = "windows")
+))]
+fn manage_media_cache<R: Runtime>(
+    app: &AppHandle<R>,
+    config: Option<&PluginConfig>,
+) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let config = config.map(|c| c.media_cache).unwrap_or_default();
+    let dir = app.path().app_cache_dir()?.join("notification-media");
+    app.manage(media_cache::MediaCache::open(dir, config)?);
+    Ok(())
+}
+
+/// Starts the platform backend and manages its [`Notifications`].
+fn manage_backend<R: Runtime>(
+    app: &AppHandle<R>,
+    api: PluginApi<R, Option<PluginConfig>>,
+) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    #[cfg(all(target_os = "windows", not(feature = "notify-rust")))]
+    let windows_config = api
+        .config()
+        .as_ref()
+        .map(|c| c.windows.clone())
+        .unwrap_or_default();
+    #[cfg(target_os = "windows")]
+    let windows_app_id = api.config().as_ref().and_then(|c| c.windows_app_id.clone());
+    #[cfg(mobile)]
+    let notification = mobile::init(app, api)?;
+    #[cfg(all(desktop, any(feature = "notify-rust", target_os = "linux")))]
+    let notification = desktop::init(app, api)?;
+    #[cfg(all(target_os = "windows", feature = "notify-rust"))]
+    if let Some(app_id) = windows_app_id {
+        notification.set_app_id(app_id)?;
+    }
+    #[cfg(all(target_os = "macos", not(feature = "notify-rust")))]
+    let notification = macos::init(app, api)?;
+    #[cfg(all(target_os = "windows", not(feature = "notify-rust")))]
+    let notification = windows::init(app, api, windows_config, windows_app_id)?;
+    app.manage(notification);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -361,6 +1667,15 @@ mod tests {
         NotificationData::default()
     }
 
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn test_validate_windows_app_id() {
+        assert!(validate_windows_app_id("Company.App.Desktop").is_ok());
+        assert!(validate_windows_app_id("").is_err());
+        assert!(validate_windows_app_id("My App").is_err());
+        assert!(validate_windows_app_id(&"a".repeat(129)).is_err());
+    }
+
     #[test]
     fn test_notification_data_id() {
         let mut data = create_test_data();
@@ -462,6 +1777,42 @@ mod tests {
         assert_eq!(data.icon_color, Some("#FF0000".to_string()));
     }
 
+    #[test]
+    fn test_notification_data_foreground_color_not_serialized() {
+        let mut data = create_test_data();
+        data.foreground_color = Some((255, 0, 0));
+        let json = serde_json::to_value(&data).expect("Failed to serialize");
+        assert!(json.get("foregroundColor").is_none());
+    }
+
+    #[test]
+    fn test_broadcast_copies_one_per_window() {
+        let data = NotificationData {
+            id: 10,
+            title: Some("Hello".to_string()),
+            ..Default::default()
+        };
+
+        let copies = broadcast_copies(&data, vec!["main".to_string(), "settings".to_string()]);
+
+        assert_eq!(copies.len(), 2);
+        assert_eq!(copies[0].id, 10);
+        assert_ne!(copies[1].id, 10);
+        assert_eq!(copies[0].focus_window.as_deref(), Some("main"));
+        assert_eq!(copies[1].focus_window.as_deref(), Some("settings"));
+        assert!(copies.iter().all(|c| c.title.as_deref() == Some("Hello")));
+    }
+
+    #[test]
+    fn test_notification_data_focus_window_not_serialized() {
+        let mut data = create_test_data();
+        data.focus_window = Some("main".to_string());
+        data.suppress_if_focused = Some(suppress::FocusTarget::AnyWindow);
+        let json = serde_json::to_value(&data).expect("Failed to serialize");
+        assert!(json.get("focusWindow").is_none());
+        assert!(json.get("suppressIfFocused").is_none());
+    }
+
     #[test]
     fn test_notification_data_attachments() {
         let mut data = create_test_data();
@@ -515,4 +1866,57 @@ mod tests {
         assert!(data.schedule.is_some());
         assert!(matches!(data.schedule, Some(Schedule::Every { .. })));
     }
+
+    /// Commands implemented only by the Android/iOS plugin classes. They are
+    /// reachable from JS on mobile without a Rust handler.
+    const NATIVE_ONLY_COMMANDS: &[&str] = &["show", "check_permissions", "permission_state"];
+
+    fn build_commands() -> Vec<&'static str> {
+        let src = include_str!("../build.rs");
+        let start = src
+            .find("const COMMANDS")
+            .expect("COMMANDS not found in build.rs");
+        let end = start + src[start..].find("];").expect("unterminated COMMANDS");
+        src[start..end].split('"').skip(1).step_by(2).collect()
+    }
+
+    fn handler_commands() -> Vec<&'static str> {
+        let src = include_str!("lib.rs");
+        let start = src
+            .find("generate_handler![")
+            .expect("generate_handler! not found in lib.rs");
+        let end = start
+            + src[start..]
+                .find("])")
+                .expect("unterminated generate_handler!");
+        src[start..end]
+            .lines()
+            .map(str::trim)
+            .filter_map(|line| {
+                line.strip_prefix("commands::")
+                    .or_else(|| line.strip_prefix("listeners::"))
+            })
+            .map(|name| name.trim_end_matches(','))
+            .collect()
+    }
+
+    #[test]
+    fn test_build_commands_match_handler() {
+        let commands = build_commands();
+        let handlers = handler_commands();
+        assert!(!handlers.is_empty());
+
+        for name in &handlers {
+            assert!(
+                commands.contains(name),
+                "`{name}` is in generate_handler! but missing from build.rs COMMANDS"
+            );
+        }
+        for name in &commands {
+            assert!(
+                handlers.contains(name) || NATIVE_ONLY_COMMANDS.contains(name),
+                "`{name}` is in build.rs COMMANDS but has no Rust command handler"
+            );
+        }
+    }
 }
@@ -5,21 +5,173 @@
 //! for plugin listeners, this module can be removed.
 //!
 //! Provides channel-based event delivery for notification events such as
-//! notification received, action performed, and notification clicked.
+//! notification received, action performed, and notification clicked, and
+//! feeds the same events to Rust callbacks and [`crate::events`] streams.
+//!
+//! On mobile only the Rust callbacks are used: JS listeners go through
+//! Tauri's native plugin listeners, and the events Rust subscribes to (such
+//! as `channelBlocked` on Android) are forwarded here from the native plugin.
 
 use std::collections::HashMap;
-use std::sync::{OnceLock, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
 
+#[cfg(desktop)]
+use tauri::AppHandle;
 #[cfg(all(target_os = "windows", not(feature = "notify-rust")))]
 use tauri::Manager;
-use tauri::{AppHandle, Runtime};
+use tauri::Runtime;
 
 use crate::error::{ErrorResponse, PluginInvokeError};
 
 type ChannelMap = HashMap<u32, tauri::ipc::Channel<serde_json::Value>>;
 type ListenerMap = HashMap<String, ChannelMap>;
 
+type RustCallback = Arc<dyn Fn(&serde_json::Value) + Send + Sync>;
+type RustListenerMap = HashMap<String, HashMap<u64, RustCallback>>;
+
 static LISTENERS: OnceLock<RwLock<ListenerMap>> = OnceLock::new();
+/// Closures registered from Rust (e.g. [`crate::Notifications::on_notification_clicked`]),
+/// keyed by event name and [`ListenerHandle`] id.
+static RUST_LISTENERS: OnceLock<RwLock<RustListenerMap>> = OnceLock::new();
+static NEXT_RUST_LISTENER_ID: AtomicU64 = AtomicU64::new(0);
+
+fn rust_listeners() -> &'static RwLock<RustListenerMap> {
+    RUST_LISTENERS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Keeps a Rust listener registered; dropping it unregisters the callback.
+#[must_use = "the listener is removed as soon as the handle is dropped"]
+#[derive(Debug)]
+pub struct ListenerHandle {
+    event: String,
+    id: u64,
+}
+
+impl Drop for ListenerHandle {
+    fn drop(&mut self) {
+        let mut guard = match rust_listeners().write() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if let Some(callbacks) = guard.get_mut(&self.event) {
+            callbacks.remove(&self.id);
+        }
+    }
+}
+
+/// Registers `callback` for every payload triggered for `event`.
+fn register_rust_listener(
+    event: &str,
+    callback: impl Fn(&serde_json::Value) + Send + Sync + 'static,
+) -> ListenerHandle {
+    let id = NEXT_RUST_LISTENER_ID.fetch_add(1, Ordering::Relaxed);
+    match rust_listeners().write() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+    .entry(event.to_string())
+    .or_default()
+    .insert(id, Arc::new(callback));
+    ListenerHandle {
+        event: event.to_string(),
+        id,
+    }
+}
+
+fn rust_callbacks(event: &str) -> Vec<RustCallback> {
+    let guard = match rust_listeners().read() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    guard
+        .get(event)
+        .map(|c| c.values().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Splits a `channelBlocked` payload into the channel id and its new
+/// importance.
+fn parse_channel_blocked(payload: &serde_json::Value) -> Option<(String, crate::Importance)> {
+    let channel_id = payload.get("channelId")?.as_str()?.to_string();
+    let importance = serde_json::from_value(payload.get("importance")?.clone()).ok()?;
+    Some((channel_id, importance))
+}
+
+/// Splits a `notificationClicked` payload into the notification id (`-1`
+/// when the click carried none) and its data.
+#[cfg(desktop)]
+pub fn parse_click(payload: &serde_json::Value) -> (i32, HashMap<String, serde_json::Value>) {
+    let id = payload
+        .get("id")
+        .and_then(serde_json::Value::as_i64)
+        .and_then(|id| i32::try_from(id).ok())
+        .unwrap_or(-1);
+    let data = payload
+        .get("data")
+        .and_then(serde_json::Value::as_object)
+        .map(|data| data.clone().into_iter().collect())
+        .unwrap_or_default();
+    (id, data)
+}
+
+impl<R: Runtime> crate::Notifications<R> {
+    /// Calls `callback` with the channel id and its new importance whenever
+    /// the user blocks a notification channel in the system settings,
+    /// alongside any JS `onChannelBlocked` listeners. Android 8+ only; the
+    /// change is detected when the app resumes.
+    ///
+    /// The callback stays registered until the returned handle is dropped.
+    pub fn on_channel_blocked<F>(&self, callback: F) -> crate::Result<ListenerHandle>
+    where
+        F: Fn(String, crate::Importance) + Send + 'static,
+    {
+        let callback = Mutex::new(callback);
+        let handle = register_rust_listener("channelBlocked", move |payload| {
+            let Some((channel_id, importance)) = parse_channel_blocked(payload) else {
+                crate::telemetry::warning!("Ignoring malformed channelBlocked payload: {payload}");
+                return;
+            };
+            let callback = match callback.lock() {
+                Ok(callback) => callback,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            callback(channel_id, importance);
+        });
+        self.register_channel_blocked_listener()?;
+        Ok(handle)
+    }
+
+    /// Calls `callback` with the notification id and data whenever a
+    /// notification is clicked, alongside any JS `onNotificationClicked`
+    /// listeners. The callback stays registered until the returned handle is
+    /// dropped.
+    #[cfg(desktop)]
+    pub fn on_notification_clicked<F>(&self, callback: F) -> ListenerHandle
+    where
+        F: Fn(i32, HashMap<String, serde_json::Value>) + Send + 'static,
+    {
+        // `Mutex` makes the `Send`-only closure shareable across threads.
+        let callback = Mutex::new(callback);
+        let handle = register_rust_listener("notificationClicked", move |payload| {
+            let (id, data) = parse_click(payload);
+            let callback = match callback.lock() {
+                Ok(callback) => callback,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            callback(id, data);
+        });
+        // Clicks that arrived before any listener existed are buffered by the
+        // native backends; flush them to the new listener.
+        #[cfg(all(target_os = "windows", not(feature = "notify-rust")))]
+        self.drain_pending_clicks();
+        #[cfg(all(target_os = "macos", not(feature = "notify-rust")))]
+        if let Err(e) = self.set_click_listener_active(true) {
+            crate::telemetry::warning!("Failed to mark the click listener active: {e}");
+        }
+        handle
+    }
+}
 
 /// Initialize the listeners registry. Call this during plugin init.
 pub fn init() {
@@ -39,6 +191,8 @@ pub fn has_listeners(event: &str) -> bool {
         return false;
     };
     guard.get(event).is_some_and(|c| !c.is_empty())
+        || !rust_callbacks(event).is_empty()
+        || crate::events::has_subscribers()
 }
 
 /// Trigger an event to all registered listeners for the given event name.
@@ -47,6 +201,8 @@ pub fn has_listeners(event: &str) -> bool {
 // Owned `payload` is taken from the FFI bridge in `macos.rs`.
 #[allow(dead_code, clippy::needless_pass_by_value)]
 pub fn trigger(event: &str, payload: String) -> crate::Result<()> {
+    crate::stats::observe(event, &payload);
+    crate::permission_cache::observe(event, &payload);
     let listeners = LISTENERS.get().ok_or_else(|| {
         crate::Error::from(PluginInvokeError::InvokeRejected(ErrorResponse {
             code: None,
@@ -69,7 +225,13 @@ pub fn trigger(event: &str, payload: String) -> crate::Result<()> {
             .unwrap_or_default()
     };
 
-    if !channels.is_empty() {
+    let callbacks = rust_callbacks(event);
+
+    #[cfg(desktop)]
+    let has_subscribers = crate::events::has_subscribers();
+    #[cfg(mobile)]
+    let has_subscribers = false;
+    if !channels.is_empty() || !callbacks.is_empty() || has_subscribers {
         let value: serde_json::Value = serde_json::from_str(&payload).map_err(|e| {
             crate::Error::from(PluginInvokeError::InvokeRejected(ErrorResponse {
                 code: None,
@@ -80,6 +242,11 @@ pub fn trigger(event: &str, payload: String) -> crate::Result<()> {
         for channel in &channels {
             let _ = channel.send(value.clone());
         }
+        for callback in &callbacks {
+            callback(&value);
+        }
+        #[cfg(desktop)]
+        crate::events::publish(event, &value);
     }
     Ok(())
 }
@@ -92,6 +259,7 @@ pub fn trigger(event: &str, payload: String) -> crate::Result<()> {
 /// flushes the buffered tap") work without the app calling any extra command.
 // Tauri commands receive serde-deserialized owned values.
 #[allow(clippy::needless_pass_by_value)]
+#[cfg(desktop)]
 #[tauri::command]
 pub fn register_listener<R: Runtime>(
     app: AppHandle<R>,
@@ -127,6 +295,7 @@ pub fn register_listener<R: Runtime>(
 /// Remove a previously registered listener by event name and channel ID.
 // Tauri commands receive serde-deserialized owned values.
 #[allow(clippy::needless_pass_by_value)]
+#[cfg(desktop)]
 #[tauri::command]
 pub fn remove_listener(event: String, channel_id: u32) -> crate::Result<()> {
     let listeners = LISTENERS.get().ok_or_else(|| {
@@ -150,3 +319,59 @@ pub fn remove_listener(event: String, channel_id: u32) -> crate::Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(desktop)]
+    #[test]
+    fn test_parse_click_payload() {
+        let (id, data) = parse_click(&serde_json::json!({
+            "id": 42,
+            "data": { "route": "/inbox", "count": 3 },
+        }));
+        assert_eq!(id, 42);
+        assert_eq!(data["route"], "/inbox");
+        assert_eq!(data["count"], 3);
+
+        // Legacy Windows taps carry no id or data.
+        let (id, data) = parse_click(&serde_json::json!({ "id": null, "data": {} }));
+        assert_eq!(id, -1);
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn test_parse_channel_blocked_payload() {
+        assert_eq!(
+            parse_channel_blocked(&serde_json::json!({
+                "channelId": "promotions",
+                "importance": 0,
+            })),
+            Some(("promotions".to_string(), crate::Importance::None))
+        );
+        assert!(parse_channel_blocked(&serde_json::json!({ "channelId": "promotions" })).is_none());
+    }
+
+    #[test]
+    fn test_rust_listener_unregisters_on_drop() {
+        let event = "testRustListenerDrop";
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let recorded = calls.clone();
+        let handle = register_rust_listener(event, move |payload| {
+            recorded
+                .lock()
+                .expect("calls mutex poisoned")
+                .push(payload.clone());
+        });
+        assert_eq!(rust_callbacks(event).len(), 1);
+
+        for callback in rust_callbacks(event) {
+            callback(&serde_json::json!({ "id": 1 }));
+        }
+        assert_eq!(calls.lock().expect("calls mutex poisoned").len(), 1);
+
+        drop(handle);
+        assert!(rust_callbacks(event).is_empty());
+    }
+}
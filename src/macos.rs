@@ -4,7 +4,8 @@ use tauri::{
     plugin::{PermissionState, PluginApi},
 };
 
-use crate::models::{ActionType, ActiveNotification, PendingNotification};
+use crate::action_types;
+use crate::models::{ActionType, ActionTypesUpdate, ActiveNotification, PendingNotification};
 
 use std::{collections::HashMap, sync::Arc};
 
@@ -31,19 +32,41 @@ mod validation {
             })
             .ok_or_else(|| {
                 crate::error::PluginInvokeError::InvokeRejected(crate::error::ErrorResponse {
-                    code: None,
+                    code: Some("notBundled".to_string()),
                     message: Some("Notifications plugin requires the app to run from a .app bundle. You can enable notify-rust feature for development.".to_string()),
                     data: (),
                 })
                 .into()
             })
     }
+
+    /// Ensures every `file://` attachment can be read by the app. Outside
+    /// the app sandbox `UNUserNotificationCenter.add` fails without an
+    /// error; other URLs are left to the platform.
+    pub fn check_attachments(attachments: &[crate::Attachment]) -> crate::Result<()> {
+        for attachment in attachments {
+            let url = attachment.url();
+            if url.scheme() != "file" {
+                continue;
+            }
+            let path = url
+                .to_file_path()
+                .map_err(|()| crate::Error::InvalidArgument(format!("invalid file URL: {url}")))?;
+            if std::fs::File::open(&path).is_err() {
+                return Err(crate::Error::InvalidArgument(format!(
+                    "attachment not accessible: {}",
+                    path.display()
+                )));
+            }
+        }
+        Ok(())
+    }
 }
 
 #[swift_bridge::bridge]
 mod ffi {
     pub enum FFIResult {
-        Err(String), // error message from Swift
+        Err(String), // JSON `{code, message}` from Swift, see `rejected`
     }
 
     extern "Rust" {
@@ -62,8 +85,13 @@ mod ffi {
         async fn requestPermissions(&self) -> Result<String, FFIResult>;
         async fn registerForPushNotifications(&self) -> Result<String, FFIResult>;
         fn unregisterForPushNotifications(&self) -> Result<(), FFIResult>;
+        async fn getPushTokenCached(&self) -> Result<String, FFIResult>;
         async fn checkPermissions(&self) -> Result<String, FFIResult>;
+        async fn isDoNotDisturbActive(&self) -> Result<String, FFIResult>;
+        async fn getUnreadCount(&self) -> Result<String, FFIResult>;
         fn cancel(&self, args: String) -> Result<(), FFIResult>;
+        async fn dismiss(&self, args: String) -> Result<String, FFIResult>;
+        async fn clearScope(&self, args: String) -> Result<String, FFIResult>;
         fn cancelAll(&self) -> Result<(), FFIResult>;
         async fn getPending(&self) -> Result<String, FFIResult>;
         fn registerActionTypes(&self, args: String) -> Result<(), FFIResult>;
@@ -71,6 +99,7 @@ mod ffi {
         fn removeAllActive(&self) -> Result<(), FFIResult>;
         async fn getActive(&self) -> Result<String, FFIResult>;
         fn setClickListenerActive(&self, args: String) -> Result<(), FFIResult>;
+        fn doctor(&self) -> Result<String, FFIResult>;
     }
 }
 
@@ -80,6 +109,31 @@ impl std::fmt::Debug for ffi::NotificationPlugin {
     }
 }
 
+/// The error a Swift call was rejected with. Swift sends a JSON object with
+/// a stable `code` (`permissionDenied`, `attachmentInvalid`, ...) and the
+/// `message`; anything else is taken as a bare message without a code.
+fn rejected(payload: String) -> crate::Error {
+    #[derive(serde::Deserialize)]
+    struct Rejection {
+        code: String,
+        message: String,
+    }
+
+    let response = match serde_json::from_str::<Rejection>(&payload) {
+        Ok(rejection) => crate::error::ErrorResponse {
+            code: Some(rejection.code),
+            message: Some(rejection.message),
+            data: (),
+        },
+        Err(_) => crate::error::ErrorResponse {
+            code: None,
+            message: Some(payload),
+            data: (),
+        },
+    };
+    crate::error::PluginInvokeError::InvokeRejected(response).into()
+}
+
 /// Extension trait for parsing FFI responses from Swift into typed Rust results.
 trait ParseFfiResponse {
     /// Deserializes a JSON response into the target type, converting FFI errors
@@ -92,14 +146,7 @@ impl ParseFfiResponse for Result<String, ffi::FFIResult> {
         match self {
             Ok(json) => serde_json::from_str(&json)
                 .map_err(|e| crate::error::PluginInvokeError::CannotDeserializeResponse(e).into()),
-            Err(ffi::FFIResult::Err(msg)) => Err(crate::error::PluginInvokeError::InvokeRejected(
-                crate::error::ErrorResponse {
-                    code: None,
-                    message: Some(msg),
-                    data: (),
-                },
-            )
-            .into()),
+            Err(ffi::FFIResult::Err(payload)) => Err(rejected(payload)),
         }
     }
 }
@@ -112,14 +159,7 @@ impl ParseFfiVoidResponse for Result<(), ffi::FFIResult> {
     fn parse_void(self) -> crate::Result<()> {
         match self {
             Ok(()) => Ok(()),
-            Err(ffi::FFIResult::Err(msg)) => Err(crate::error::PluginInvokeError::InvokeRejected(
-                crate::error::ErrorResponse {
-                    code: None,
-                    message: Some(msg),
-                    data: (),
-                },
-            )
-            .into()),
+            Err(ffi::FFIResult::Err(payload)) => Err(rejected(payload)),
         }
     }
 }
@@ -128,14 +168,7 @@ impl ParseFfiVoidResponse for Result<i32, ffi::FFIResult> {
     fn parse_void(self) -> crate::Result<()> {
         match self {
             Ok(_) => Ok(()),
-            Err(ffi::FFIResult::Err(msg)) => Err(crate::error::PluginInvokeError::InvokeRejected(
-                crate::error::ErrorResponse {
-                    code: None,
-                    message: Some(msg),
-                    data: (),
-                },
-            )
-            .into()),
+            Err(ffi::FFIResult::Err(payload)) => Err(rejected(payload)),
         }
     }
 }
@@ -144,6 +177,11 @@ impl ParseFfiVoidResponse for Result<i32, ffi::FFIResult> {
 // Owned strings come straight from the Swift bridge.
 #[allow(clippy::needless_pass_by_value)]
 fn bridge_trigger(event: String, payload: String) -> Result<(), ffi::FFIResult> {
+    if event == "notificationClicked"
+        && let Ok(value) = serde_json::from_str::<serde_json::Value>(&payload)
+    {
+        crate::focus::on_click(crate::listeners::parse_click(&value).0);
+    }
     crate::listeners::trigger(&event, payload)
         .map_err(|e| ffi::FFIResult::Err(format!("Failed to trigger event '{event}': {e}")))
 }
@@ -161,7 +199,41 @@ pub fn init<R: Runtime, C: DeserializeOwned>(
 }
 
 impl<R: Runtime> crate::NotificationsBuilder<R> {
-    pub async fn show(self) -> crate::Result<()> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            err,
+            fields(
+                platform = crate::telemetry::BACKEND,
+                id = self.data.id,
+                channel = self.data.channel_id.as_deref(),
+                scheduled = self.data.schedule.is_some()
+            )
+        )
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn show(mut self) -> crate::Result<()> {
+        self.apply_defaults()?;
+        self.apply_quiet_hours();
+        self.check_extra_size()?;
+        self.check_platform_options()?;
+        self.resolve_flag_conflicts()?;
+        self.apply_inbox_lines()?;
+        self.report_ignored_fields();
+        validation::check_attachments(&self.data.attachments)?;
+        self.remember_focus_window();
+        if self.suppress_if_window_focused() {
+            return Ok(());
+        }
+        if let Some(result) = self.coalesce().await {
+            return result;
+        }
+        let shown = self.data.clone();
+        crate::dispatch::deliver_in_group(self.dispatcher(), shown, self.deliver()).await
+    }
+
+    async fn deliver(self) -> crate::Result<()> {
         validation::require_bundle()?;
 
         self.plugin
@@ -179,18 +251,41 @@ pub struct Notifications<R: Runtime> {
     plugin: Arc<ffi::NotificationPlugin>,
 }
 
+// SAFETY: the only field the compiler can't see through is the opaque Swift
+// `NotificationPlugin`. It is declared `#[swift_bridge(Sendable)]`: the
+// Swift side hops to the main actor for UI work and `UNUserNotificationCenter`
+// is thread-safe, so it may be called from any thread. `AppHandle` is
+// `Send + Sync`.
+unsafe impl<R: Runtime> Send for Notifications<R> {}
+// SAFETY: see `Send` above.
+unsafe impl<R: Runtime> Sync for Notifications<R> {}
+
 impl<R: Runtime> Notifications<R> {
     pub fn builder(&self) -> crate::NotificationsBuilder<R> {
         crate::NotificationsBuilder::new(self.app.clone(), self.plugin.clone())
     }
 
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn request_permission(&self) -> crate::Result<PermissionState> {
         validation::require_bundle()?;
 
+        let before = self.permission_state_fresh().await.ok();
         let response: crate::PermissionResponse = self.plugin.requestPermissions().await.parse()?;
+        crate::permission_cache::store(response.permission_state);
+        if before != Some(response.permission_state) {
+            let payload = serde_json::json!({ "permissionState": response.permission_state });
+            if let Err(e) = crate::listeners::trigger("permissionChanged", payload.to_string()) {
+                crate::telemetry::warning!("Failed to trigger permissionChanged: {e}");
+            }
+        }
         Ok(response.permission_state)
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn register_for_push_notifications(&self) -> crate::Result<String> {
         validation::require_bundle()?;
 
@@ -208,6 +303,24 @@ impl<R: Runtime> Notifications<R> {
         }
     }
 
+    /// The device token from the last successful
+    /// [`register_for_push_notifications`](Self::register_for_push_notifications),
+    /// kept in `UserDefaults`. No network call is made. `None` if the app
+    /// never registered.
+    #[must_use]
+    pub async fn get_push_token_cached(&self) -> Option<String> {
+        self.plugin
+            .getPushTokenCached()
+            .await
+            .parse::<crate::CachedPushTokenResponse>()
+            .ok()
+            .and_then(|r| r.token)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
     pub fn unregister_for_push_notifications(&self) -> crate::Result<()> {
         validation::require_bundle()?;
 
@@ -223,16 +336,107 @@ impl<R: Runtime> Notifications<R> {
         }
     }
 
-    pub async fn permission_state(&self) -> crate::Result<PermissionState> {
+    /// The `.app` bundle and, with the `push-notifications` feature, the
+    /// push entitlement.
+    pub(crate) async fn platform_diagnostics(&self) -> Vec<crate::Diagnostic> {
+        if validation::require_bundle().is_err() {
+            return vec![crate::Diagnostic::new(
+                crate::Severity::Error,
+                crate::DiagnosticCode::NotBundled,
+                "The app is not running from a .app bundle, which UserNotifications requires",
+                "Run the bundled app (tauri build), or enable the notify-rust feature for development",
+            )];
+        }
+        #[cfg(feature = "push-notifications")]
+        match self
+            .plugin
+            .doctor()
+            .parse::<crate::doctor::NativeDiagnostics>()
+        {
+            Ok(native) => return native.diagnostics(),
+            Err(e) => crate::telemetry::warning!("Native notification checks failed: {e}"),
+        }
+        Vec::new()
+    }
+
+    /// Reads the permission state from the platform, bypassing the cache of
+    /// [`permission_state`](Self::permission_state).
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn permission_state_fresh(&self) -> crate::Result<PermissionState> {
         validation::require_bundle()?;
 
         let response: crate::PermissionResponse = self.plugin.checkPermissions().await.parse()?;
         Ok(response.permission_state)
     }
 
-    pub fn register_action_types(&self, types: Vec<ActionType>) -> crate::Result<()> {
+    /// Whether a Focus is on, when the app may read the Focus status;
+    /// otherwise whether alerts are turned off for the app.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn is_do_not_disturb_active(&self) -> crate::Result<bool> {
         validation::require_bundle()?;
 
+        let response: crate::DoNotDisturbResponse =
+            self.plugin.isDoNotDisturbActive().await.parse()?;
+        Ok(response.active)
+    }
+
+    /// Number on the Dock badge, `0` without one. The plugin only clears
+    /// the badge, so the app sets it and keeps it current.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn get_unread_count(&self) -> crate::Result<u32> {
+        validation::require_bundle()?;
+
+        let response: crate::UnreadCountResponse = self.plugin.getUnreadCount().await.parse()?;
+        Ok(response.count)
+    }
+
+    /// Adds `types` to the registered action types, replacing the ones with
+    /// the same id. Does nothing if that leaves them as they were.
+    pub fn register_action_types(
+        &self,
+        types: Vec<ActionType>,
+    ) -> crate::Result<ActionTypesUpdate> {
+        crate::validate_action_types(&types)?;
+        self.update_action_types(|registered| action_types::merge(registered, types))
+    }
+
+    /// Replaces every registered action type with `types`.
+    pub fn set_notification_categories(
+        &self,
+        types: Vec<ActionType>,
+    ) -> crate::Result<ActionTypesUpdate> {
+        crate::validate_action_types(&types)?;
+        self.update_action_types(|registered| *registered = types)
+    }
+
+    /// Removes the registered action type `id`, if there is one.
+    pub fn unregister_action_type(&self, id: &str) -> crate::Result<ActionTypesUpdate> {
+        self.update_action_types(|registered| action_types::unregister(registered, id))
+    }
+
+    /// Removes every registered action type.
+    pub fn clear_action_types(&self) -> crate::Result<ActionTypesUpdate> {
+        self.update_action_types(Vec::clear)
+    }
+
+    /// Applies `change` to the registered action types and, if they changed,
+    /// hands them to macOS, which replaces its categories with them.
+    fn update_action_types(
+        &self,
+        change: impl FnOnce(&mut Vec<ActionType>),
+    ) -> crate::Result<ActionTypesUpdate> {
+        validation::require_bundle()?;
+
+        let this = Self {
+            app: self.app.clone(),
+            plugin: Arc::clone(&self.plugin),
+        };
+        action_types::schedule(&self.app, change, move |types| {
+            this.push_action_types(types)
+        })
+    }
+
+    fn push_action_types(&self, types: &[ActionType]) -> crate::Result<()> {
         let mut args = HashMap::new();
         args.insert("types", types);
         self.plugin
@@ -243,6 +447,17 @@ impl<R: Runtime> Notifications<R> {
             .parse_void()
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            err,
+            fields(
+                platform = crate::telemetry::BACKEND,
+                ids = ?notifications
+            )
+        )
+    )]
     pub fn remove_active(&self, notifications: Vec<i32>) -> crate::Result<()> {
         validation::require_bundle()?;
 
@@ -266,25 +481,72 @@ impl<R: Runtime> Notifications<R> {
             .parse_void()
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn active(&self) -> crate::Result<Vec<ActiveNotification>> {
         validation::require_bundle()?;
 
         self.plugin.getActive().await.parse()
     }
 
+    /// Same as [`active`](Self::active): Notification Center forgets a
+    /// notification once the user dismisses it.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn notification_history(&self) -> crate::Result<Vec<ActiveNotification>> {
+        self.active().await
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
     pub fn remove_all_active(&self) -> crate::Result<()> {
         validation::require_bundle()?;
 
         self.plugin.removeAllActive().parse_void()
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn pending(&self) -> crate::Result<Vec<PendingNotification>> {
         validation::require_bundle()?;
 
         self.plugin.getPending().await.parse()
     }
 
+    /// Number of pending notifications per thread identifier.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn pending_count_by_channel(&self) -> crate::Result<HashMap<String, usize>> {
+        let pending = self.pending().await?;
+        Ok(crate::count_pending_by_channel(&pending))
+    }
+
     /// Cancel pending notifications.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            err,
+            fields(
+                platform = crate::telemetry::BACKEND,
+                ids = ?notifications
+            )
+        )
+    )]
     pub fn cancel(&self, notifications: Vec<i32>) -> crate::Result<()> {
         validation::require_bundle()?;
 
@@ -298,7 +560,49 @@ impl<R: Runtime> Notifications<R> {
             .parse_void()
     }
 
+    /// Removes `id` from both the pending requests and the delivered
+    /// notifications.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND, id))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn dismiss(&self, id: i32) -> crate::Result<crate::DismissOutcome> {
+        validation::require_bundle()?;
+
+        self.plugin
+            .dismiss(
+                serde_json::to_string(&serde_json::json!({ "id": id }))
+                    .map_err(crate::error::PluginInvokeError::CannotSerializePayload)?,
+            )
+            .await
+            .parse()
+    }
+
+    /// Removes the pending requests and delivered notifications tagged with
+    /// `scope`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND, scope))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn clear_scope(&self, scope: &str) -> crate::Result<crate::ScopeClearance> {
+        validation::require_bundle()?;
+
+        self.plugin
+            .clearScope(
+                serde_json::to_string(&serde_json::json!({ "scope": scope }))
+                    .map_err(crate::error::PluginInvokeError::CannotSerializePayload)?,
+            )
+            .await
+            .parse()
+    }
+
     /// Cancel all pending notifications.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
     pub fn cancel_all(&self) -> crate::Result<()> {
         validation::require_bundle()?;
 
@@ -321,23 +625,138 @@ impl<R: Runtime> Notifications<R> {
     }
 
     /// Create a notification channel (not supported on macOS).
-    pub fn create_channel(&self, _channel: crate::Channel) -> crate::Result<()> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    pub fn create_channel(
+        &self,
+        _channel: crate::Channel,
+    ) -> crate::Result<crate::ChannelCreation> {
+        Err(crate::Error::Io(std::io::Error::other(
+            "Notification channels are not supported on macOS",
+        )))
+    }
+
+    /// Create several notification channels (not supported on macOS).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    pub fn ensure_channels(
+        &self,
+        _channels: Vec<crate::Channel>,
+    ) -> crate::Result<Vec<crate::ChannelCreation>> {
+        Err(crate::Error::Io(std::io::Error::other(
+            "Notification channels are not supported on macOS",
+        )))
+    }
+
+    /// Update a notification channel (not supported on macOS).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    pub fn update_channel(&self, _channel: crate::Channel) -> crate::Result<()> {
         Err(crate::Error::Io(std::io::Error::other(
             "Notification channels are not supported on macOS",
         )))
     }
 
     /// Delete a notification channel (not supported on macOS).
-    pub fn delete_channel(&self, _id: impl Into<String>) -> crate::Result<()> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    pub fn delete_channel(
+        &self,
+        _id: impl Into<String>,
+        _on_pending: crate::OnPendingPolicy,
+    ) -> crate::Result<crate::ChannelDeletion> {
+        Err(crate::Error::Io(std::io::Error::other(
+            "Notification channels are not supported on macOS",
+        )))
+    }
+
+    /// Watch for blocked channels (not supported on macOS).
+    pub fn register_channel_blocked_listener(&self) -> crate::Result<()> {
         Err(crate::Error::Io(std::io::Error::other(
             "Notification channels are not supported on macOS",
         )))
     }
 
     /// List notification channels (not supported on macOS).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
     pub fn list_channels(&self) -> crate::Result<Vec<crate::Channel>> {
         Err(crate::Error::Io(std::io::Error::other(
             "Notification channels are not supported on macOS",
         )))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_notifications_is_send_sync() {
+        // Tauri's managed state requires `Send + Sync`; checked at compile time.
+        const { assert_send_sync::<Notifications<tauri::Wry>>() };
+    }
+
+    #[test]
+    fn test_check_attachments() {
+        let readable = std::env::temp_dir().join(format!("attachment-{}.png", std::process::id()));
+        std::fs::write(&readable, b"png").expect("Failed to write attachment");
+        let attachment =
+            |url: &str| crate::Attachment::new("image", url.parse().expect("Failed to parse URL"));
+        let file_url = url::Url::from_file_path(&readable).expect("Failed to build file URL");
+
+        validation::check_attachments(&[
+            crate::Attachment::new("image", file_url),
+            attachment("https://example.com/image.png"),
+        ])
+        .expect("readable attachments rejected");
+
+        let missing = validation::check_attachments(&[attachment("file:///nonexistent/image.png")]);
+        assert!(matches!(
+            missing,
+            Err(crate::Error::InvalidArgument(message))
+                if message == "attachment not accessible: /nonexistent/image.png"
+        ));
+
+        std::fs::remove_file(&readable).expect("Failed to remove attachment");
+    }
+
+    #[test]
+    fn test_rejected_error_payloads() {
+        let coded = rejected(
+            r#"{"code":"permissionDenied","message":"Notification permissions not granted"}"#
+                .to_string(),
+        );
+        assert!(matches!(
+            &coded,
+            crate::Error::PluginInvoke(crate::error::PluginInvokeError::InvokeRejected(response))
+                if response.code.as_deref() == Some("permissionDenied")
+        ));
+        assert_eq!(
+            coded.to_string(),
+            "[permissionDenied] - Notification permissions not granted"
+        );
+
+        // Bridges built before error codes send the bare message.
+        let bare = rejected("Failed to decode JSON".to_string());
+        assert!(matches!(
+            &bare,
+            crate::Error::PluginInvoke(crate::error::PluginInvokeError::InvokeRejected(response))
+                if response.code.is_none()
+        ));
+        assert_eq!(bare.to_string(), "Failed to decode JSON");
+        assert_eq!(rejected("{}".to_string()).to_string(), "{}");
+    }
+}
@@ -0,0 +1,580 @@
+//! Disk cache for remote notification media (`http(s)://` icons and
+//! attachments).
+//!
+//! Files live under `<app cache dir>/notification-media`, named after a hash
+//! of their URL, next to an `index.json` that records size, validators and
+//! last use for every entry. The cache is bounded by
+//! [`MediaCacheConfig::max_bytes`]: inserting past the limit evicts the least
+//! recently used entries first. Entries older than
+//! [`MediaCacheConfig::max_age_secs`] are revalidated with
+//! `If-None-Match` / `If-Modified-Since` before being reused; if the server
+//! can't be reached the stale copy is served instead.
+//!
+//! Only the Windows and notify-rust backends fetch through the cache, so the
+//! module isn't built for the native macOS, iOS and Android backends, which
+//! hand remote URLs to the OS as is.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::telemetry;
+
+const INDEX_FILE: &str = "index.json";
+
+/// Limits for the remote media cache, read from `plugins.notifications.mediaCache`.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MediaCacheConfig {
+    /// Upper bound for the combined size of all cached files, in bytes.
+    pub max_bytes: u64,
+    /// Age after which an entry is revalidated with the server, in seconds.
+    pub max_age_secs: u64,
+}
+
+impl Default for MediaCacheConfig {
+    /// 50 MiB, revalidated after a week.
+    fn default() -> Self {
+        Self {
+            max_bytes: 50 * 1024 * 1024,
+            max_age_secs: 7 * 24 * 60 * 60,
+        }
+    }
+}
+
+/// Snapshot of the media cache for diagnostics. Counters cover the current
+/// process only.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaCacheStats {
+    pub(crate) entries: usize,
+    pub(crate) total_bytes: u64,
+    pub(crate) max_bytes: u64,
+    pub(crate) hits: u64,
+    pub(crate) misses: u64,
+    pub(crate) revalidations: u64,
+    pub(crate) evictions: u64,
+}
+
+impl MediaCacheStats {
+    #[must_use]
+    pub const fn entries(&self) -> usize {
+        self.entries
+    }
+
+    #[must_use]
+    pub const fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    #[must_use]
+    pub const fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    /// Lookups served from disk without contacting the server.
+    #[must_use]
+    pub const fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Lookups that had to download the file.
+    #[must_use]
+    pub const fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Stale entries the server confirmed unchanged (`304 Not Modified`).
+    #[must_use]
+    pub const fn revalidations(&self) -> u64 {
+        self.revalidations
+    }
+
+    /// Entries dropped to stay under `max_bytes`.
+    #[must_use]
+    pub const fn evictions(&self) -> u64 {
+        self.evictions
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry {
+    url: String,
+    file: String,
+    size: u64,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// Unix seconds of the last download or successful revalidation.
+    fetched_at: u64,
+    /// Logical clock value of the last use; lower means older.
+    last_used: u64,
+}
+
+/// In-memory view of `index.json`. Kept free of I/O so eviction and size
+/// accounting can be tested directly.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheIndex {
+    entries: HashMap<String, CacheEntry>,
+    clock: u64,
+    #[serde(skip)]
+    stats: MediaCacheStats,
+}
+
+impl CacheIndex {
+    fn total_bytes(&self) -> u64 {
+        self.entries.values().map(|e| e.size).sum()
+    }
+
+    const fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn touch(&mut self, key: &str) {
+        let now = self.tick();
+        if let Some(entry) = self.entries.get_mut(key) {
+            entry.last_used = now;
+        }
+    }
+
+    /// Stores `entry` as the most recently used one and evicts the least
+    /// recently used others until the total fits in `max_bytes`. Returns the
+    /// evicted entries so the caller can delete their files.
+    fn insert(&mut self, key: &str, mut entry: CacheEntry, max_bytes: u64) -> Vec<CacheEntry> {
+        entry.last_used = self.tick();
+        self.entries.insert(key.to_string(), entry);
+
+        let mut evicted = Vec::new();
+        while self.total_bytes() > max_bytes {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .filter(|(k, _)| **k != key)
+                .min_by_key(|(_, e)| e.last_used)
+                .map(|(k, _)| k.clone())
+            else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&oldest) {
+                evicted.push(entry);
+            }
+        }
+        self.stats.evictions += evicted.len() as u64;
+        evicted
+    }
+
+    fn snapshot(&self, max_bytes: u64) -> MediaCacheStats {
+        MediaCacheStats {
+            entries: self.entries.len(),
+            total_bytes: self.total_bytes(),
+            max_bytes,
+            ..self.stats
+        }
+    }
+}
+
+/// 64-bit FNV-1a. Stable across Rust releases, unlike `DefaultHasher`, so
+/// file names stay valid between app updates.
+fn cache_key(url: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in url.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{hash:016x}")
+}
+
+/// Keeps the URL's extension (when it looks like one) so consumers that sniff
+/// the file type by name keep working.
+fn file_name(key: &str, url: &str) -> String {
+    let path = url::Url::parse(url)
+        .map(|u| u.path().to_string())
+        .unwrap_or_default();
+    let ext = Path::new(&path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .filter(|e| e.len() <= 5 && e.chars().all(|c| c.is_ascii_alphanumeric()));
+    ext.map_or_else(
+        || key.to_string(),
+        |ext| format!("{key}.{}", ext.to_ascii_lowercase()),
+    )
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Whether `src` should go through the cache.
+#[must_use]
+pub fn is_remote(src: &str) -> bool {
+    let lower = src.to_ascii_lowercase();
+    lower.starts_with("http://") || lower.starts_with("https://")
+}
+
+fn io_err(e: impl std::fmt::Display) -> crate::Error {
+    crate::Error::Io(std::io::Error::other(format!("media cache: {e}")))
+}
+
+/// How long to wait for a media host to accept the connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Upper bound for a whole download, so one slow host can't hold up its
+/// notification for long.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub struct MediaCache {
+    dir: PathBuf,
+    config: MediaCacheConfig,
+    index: Arc<Mutex<CacheIndex>>,
+    client: reqwest::Client,
+}
+
+impl MediaCache {
+    /// Opens the cache in `dir`, dropping index entries whose file is gone.
+    /// Runs once during plugin setup.
+    pub fn open(dir: PathBuf, config: MediaCacheConfig) -> crate::Result<Self> {
+        let mut index: CacheIndex = std::fs::read(dir.join(INDEX_FILE))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        index.entries.retain(|_, e| dir.join(&e.file).is_file());
+        let client = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(io_err)?;
+        Ok(Self {
+            dir,
+            config,
+            index: Arc::new(Mutex::new(index)),
+            client,
+        })
+    }
+
+    fn lock(&self) -> crate::Result<MutexGuard<'_, CacheIndex>> {
+        self.index.lock().map_err(io_err)
+    }
+
+    /// Runs `f` with the cache directory and index on the blocking pool, so
+    /// file I/O never stalls the async runtime. The index stays locked for
+    /// the whole call, which keeps `index.json` in step with the files.
+    async fn on_disk<T, F>(&self, f: F) -> crate::Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Path, &mut CacheIndex) -> crate::Result<T> + Send + 'static,
+    {
+        let dir = self.dir.clone();
+        let index = Arc::clone(&self.index);
+        tauri::async_runtime::spawn_blocking(move || {
+            let mut index = index.lock().map_err(io_err)?;
+            f(&dir, &mut index)
+        })
+        .await
+        .map_err(io_err)?
+    }
+
+    /// Returns a local path for `url`, downloading or revalidating it first
+    /// when needed.
+    pub async fn fetch(&self, url: &str) -> crate::Result<PathBuf> {
+        let key = cache_key(url);
+        let cached = self.lock()?.entries.get(&key).cloned();
+
+        if let Some(entry) = &cached
+            && now_secs().saturating_sub(entry.fetched_at) < self.config.max_age_secs
+        {
+            let mut index = self.lock()?;
+            index.touch(&key);
+            index.stats.hits += 1;
+            drop(index);
+            return Ok(self.dir.join(&entry.file));
+        }
+
+        let mut request = self.client.get(url);
+        if let Some(entry) = &cached {
+            if let Some(etag) = &entry.etag {
+                request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &entry.last_modified {
+                request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = match request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+        {
+            Ok(response) => response,
+            Err(e) => {
+                // Better an outdated avatar than none at all.
+                if let Some(entry) = cached {
+                    telemetry::warning!("Revalidating {url} failed, using cached copy: {e}");
+                    return Ok(self.dir.join(entry.file));
+                }
+                return Err(io_err(e));
+            }
+        };
+
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED
+            && let Some(entry) = cached
+        {
+            self.on_disk(move |dir, index| {
+                if let Some(stored) = index.entries.get_mut(&key) {
+                    stored.fetched_at = now_secs();
+                }
+                index.touch(&key);
+                index.stats.revalidations += 1;
+                persist(dir, index);
+                Ok(())
+            })
+            .await?;
+            return Ok(self.dir.join(entry.file));
+        }
+
+        let header = |name: reqwest::header::HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = header(reqwest::header::ETAG);
+        let last_modified = header(reqwest::header::LAST_MODIFIED);
+        let bytes = self.read_capped(url, response).await?;
+        let size = bytes.len() as u64;
+
+        let file = file_name(&key, url);
+        let entry = CacheEntry {
+            url: url.to_string(),
+            file: file.clone(),
+            size,
+            etag,
+            last_modified,
+            fetched_at: now_secs(),
+            last_used: 0,
+        };
+        let max_bytes = self.config.max_bytes;
+        self.on_disk(move |dir, index| {
+            std::fs::create_dir_all(dir).map_err(io_err)?;
+            std::fs::write(dir.join(&entry.file), &bytes).map_err(io_err)?;
+            index.stats.misses += 1;
+            let evicted = index.insert(&key, entry, max_bytes);
+            remove_files(dir, &evicted);
+            persist(dir, index);
+            Ok(())
+        })
+        .await?;
+        Ok(self.dir.join(file))
+    }
+
+    /// Reads the body of `response`, giving up as soon as it is known to be
+    /// larger than the whole cache: up front from `Content-Length`, or while
+    /// streaming when the header is missing or wrong.
+    async fn read_capped(
+        &self,
+        url: &str,
+        mut response: reqwest::Response,
+    ) -> crate::Result<Vec<u8>> {
+        let max_bytes = self.config.max_bytes;
+        let too_large = |size: u64| {
+            io_err(format!(
+                "{url} is {size} bytes, larger than the whole cache ({max_bytes} bytes)"
+            ))
+        };
+        let expected = response.content_length().unwrap_or(0);
+        if expected > max_bytes {
+            return Err(too_large(expected));
+        }
+        let mut bytes = Vec::with_capacity(usize::try_from(expected).unwrap_or(0));
+        while let Some(chunk) = response.chunk().await.map_err(io_err)? {
+            let size = (bytes.len() + chunk.len()) as u64;
+            if size > max_bytes {
+                return Err(too_large(size));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(bytes)
+    }
+
+    /// Deletes every cached file and resets the index.
+    pub fn clear(&self) -> crate::Result<()> {
+        let mut index = self.lock()?;
+        let entries: Vec<CacheEntry> = index.entries.drain().map(|(_, e)| e).collect();
+        remove_files(&self.dir, &entries);
+        index.clock = 0;
+        index.stats = MediaCacheStats::default();
+        drop(index);
+        match std::fs::remove_file(self.dir.join(INDEX_FILE)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn stats(&self) -> crate::Result<MediaCacheStats> {
+        Ok(self.lock()?.snapshot(self.config.max_bytes))
+    }
+}
+
+fn persist(dir: &Path, index: &CacheIndex) {
+    let result = serde_json::to_vec(index)
+        .map_err(io_err)
+        .and_then(|bytes| std::fs::write(dir.join(INDEX_FILE), bytes).map_err(io_err));
+    if let Err(e) = result {
+        telemetry::warning!("Failed to write media cache index: {e}");
+    }
+}
+
+fn remove_files(dir: &Path, entries: &[CacheEntry]) {
+    for entry in entries {
+        let _ = std::fs::remove_file(dir.join(&entry.file));
+    }
+}
+
+/// Fetches every remote source in `srcs` through the app's cache. Sources
+/// that fail to download are logged and left out, so callers fall back to
+/// the original URL.
+pub async fn localize_all<'a, R: Runtime>(
+    app: &AppHandle<R>,
+    srcs: impl IntoIterator<Item = &'a str>,
+) -> HashMap<String, PathBuf> {
+    let mut local = HashMap::new();
+    let Some(cache) = app.try_state::<MediaCache>() else {
+        return local;
+    };
+    for src in srcs.into_iter().filter(|s| is_remote(s)) {
+        if local.contains_key(src) {
+            continue;
+        }
+        match cache.fetch(src).await {
+            Ok(path) => {
+                local.insert(src.to_string(), path);
+            }
+            Err(e) => telemetry::warning!("Failed to cache notification media {src}: {e}"),
+        }
+    }
+    local
+}
+
+pub fn clear<R: Runtime>(app: &AppHandle<R>) -> crate::Result<()> {
+    app.try_state::<MediaCache>()
+        .map_or(Ok(()), |cache| cache.clear())
+}
+
+pub fn stats<R: Runtime>(app: &AppHandle<R>) -> crate::Result<MediaCacheStats> {
+    app.try_state::<MediaCache>()
+        .map_or_else(|| Ok(MediaCacheStats::default()), |cache| cache.stats())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(url: &str, size: u64) -> CacheEntry {
+        CacheEntry {
+            url: url.to_string(),
+            file: file_name(&cache_key(url), url),
+            size,
+            etag: None,
+            last_modified: None,
+            fetched_at: 0,
+            last_used: 0,
+        }
+    }
+
+    #[test]
+    fn test_insert_tracks_total_size() {
+        let mut index = CacheIndex::default();
+        assert!(index.insert("a", entry("https://x/a", 10), 100).is_empty());
+        assert!(index.insert("b", entry("https://x/b", 20), 100).is_empty());
+        assert_eq!(index.total_bytes(), 30);
+
+        // Replacing an entry swaps its size instead of adding to it.
+        index.insert("a", entry("https://x/a", 5), 100);
+        assert_eq!(index.total_bytes(), 25);
+        assert_eq!(index.snapshot(100).entries, 2);
+    }
+
+    #[test]
+    fn test_insert_evicts_least_recently_used() {
+        let mut index = CacheIndex::default();
+        index.insert("a", entry("https://x/a", 40), 100);
+        index.insert("b", entry("https://x/b", 40), 100);
+        index.insert("c", entry("https://x/c", 10), 100);
+        // Using `a` makes `b` the oldest.
+        index.touch("a");
+
+        let evicted = index.insert("d", entry("https://x/d", 40), 100);
+        let urls: Vec<_> = evicted.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(urls, ["https://x/b"]);
+        assert_eq!(index.total_bytes(), 90);
+
+        let evicted = index.insert("e", entry("https://x/e", 60), 100);
+        let urls: Vec<_> = evicted.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(urls, ["https://x/c", "https://x/a"]);
+        assert_eq!(index.total_bytes(), 100);
+        assert_eq!(index.snapshot(100).evictions, 3);
+    }
+
+    #[test]
+    fn test_insert_never_evicts_new_entry() {
+        let mut index = CacheIndex::default();
+        index.insert("a", entry("https://x/a", 10), 100);
+        let evicted = index.insert("b", entry("https://x/b", 150), 100);
+        assert_eq!(evicted.len(), 1);
+        assert!(index.entries.contains_key("b"));
+    }
+
+    #[test]
+    fn test_index_roundtrip_keeps_lru_order() {
+        let mut index = CacheIndex::default();
+        index.insert("a", entry("https://x/a", 10), 100);
+        index.insert("b", entry("https://x/b", 10), 100);
+        index.touch("a");
+
+        let json = serde_json::to_vec(&index).expect("Failed to serialize index");
+        let mut restored: CacheIndex =
+            serde_json::from_slice(&json).expect("Failed to deserialize index");
+        let evicted = restored.insert("c", entry("https://x/c", 95), 100);
+        assert_eq!(evicted.len(), 2);
+        assert_eq!(evicted[0].url, "https://x/b");
+    }
+
+    #[test]
+    fn test_cache_key_is_stable() {
+        assert_eq!(cache_key(""), "cbf29ce484222325");
+        assert_eq!(cache_key("a"), "af63dc4c8601ec8c");
+        assert_ne!(
+            cache_key("https://x/avatar.png"),
+            cache_key("https://x/avatar.jpg")
+        );
+    }
+
+    #[test]
+    fn test_file_name_keeps_extension() {
+        assert_eq!(file_name("k", "https://x/avatar.PNG?s=64"), "k.png");
+        assert_eq!(file_name("k", "https://x/avatar"), "k");
+        assert_eq!(file_name("k", "https://x/a.tar-gz"), "k");
+    }
+
+    #[test]
+    fn test_is_remote() {
+        assert!(is_remote("https://example.com/a.png"));
+        assert!(is_remote("HTTP://example.com/a.png"));
+        assert!(!is_remote("file:///tmp/a.png"));
+        assert!(!is_remote("icon.png"));
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let config: MediaCacheConfig =
+            serde_json::from_str(r#"{"maxBytes": 1024}"#).expect("Failed to deserialize");
+        assert_eq!(config.max_bytes, 1024);
+        assert_eq!(config.max_age_secs, 604_800);
+    }
+}
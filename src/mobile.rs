@@ -4,10 +4,12 @@ use tauri::{
     plugin::{PermissionState, PluginApi, PluginHandle},
 };
 
+use crate::action_types;
 #[cfg(feature = "push-notifications")]
 use crate::models::PushNotificationResponse;
 use crate::models::{
-    ActionType, ActiveNotification, Channel, PendingNotification, PermissionResponse,
+    ActionType, ActionTypesUpdate, ActiveNotification, Channel, ChannelCreation,
+    PendingNotification, PermissionResponse,
 };
 
 use std::collections::HashMap;
@@ -15,6 +17,10 @@ use std::collections::HashMap;
 #[cfg(target_os = "android")]
 const PLUGIN_IDENTIFIER: &str = "app.tauri.notification";
 
+/// Error code the Android plugin rejects `show` with when neither the
+/// notification's channel nor its fallback exists.
+const CHANNEL_NOT_FOUND: &str = "channelNotFound";
+
 #[cfg(target_os = "ios")]
 tauri::ios_plugin_binding!(init_plugin_notification);
 
@@ -33,12 +39,55 @@ pub fn init<R: Runtime, C: DeserializeOwned>(
 }
 
 impl<R: Runtime> crate::NotificationsBuilder<R> {
-    pub async fn show(self) -> crate::Result<()> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            err,
+            fields(
+                platform = crate::telemetry::BACKEND,
+                id = self.data.id,
+                channel = self.data.channel_id.as_deref(),
+                scheduled = self.data.schedule.is_some()
+            )
+        )
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn show(mut self) -> crate::Result<()> {
+        self.apply_defaults()?;
+        self.apply_quiet_hours();
+        self.check_extra_size()?;
+        self.check_platform_options()?;
+        self.resolve_flag_conflicts()?;
+        self.apply_inbox_lines()?;
+        self.report_ignored_fields();
+        if self.suppress_if_window_focused() {
+            return Ok(());
+        }
+        if let Some(result) = self.coalesce().await {
+            return result;
+        }
+        let shown = self.data.clone();
+        crate::dispatch::deliver_in_group(self.dispatcher(), shown, self.deliver()).await
+    }
+
+    async fn deliver(self) -> crate::Result<()> {
+        use tauri::plugin::mobile::{ErrorResponse, PluginInvokeError};
+
         self.handle
             .run_mobile_plugin_async::<i32>("show", self.data)
             .await
             .map(|_| ())
-            .map_err(Into::into)
+            .map_err(|e| match e {
+                PluginInvokeError::InvokeRejected(ErrorResponse {
+                    code: Some(code),
+                    message,
+                    ..
+                }) if code == CHANNEL_NOT_FOUND => {
+                    crate::Error::NotFound(message.unwrap_or_default())
+                }
+                e => e.into(),
+            })
     }
 }
 
@@ -52,14 +101,37 @@ impl<R: Runtime> Notifications<R> {
         crate::NotificationsBuilder::new(self.0.clone())
     }
 
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn request_permission(&self) -> crate::Result<PermissionState> {
-        self.0
-            .run_mobile_plugin_async::<PermissionResponse>("requestPermissions", ())
-            .await
-            .map(|r| r.permission_state)
-            .map_err(Into::into)
+        self.request_permission_with(false).await
+    }
+
+    /// Android returns [`PermissionState::PromptWithRationale`] instead of
+    /// showing the system dialog after the user denied the permission once,
+    /// unless `skip_rationale` is set.
+    pub(crate) async fn request_permission_with(
+        &self,
+        skip_rationale: bool,
+    ) -> crate::Result<PermissionState> {
+        let response = self
+            .0
+            .run_mobile_plugin_async::<PermissionResponse>(
+                "requestPermissions",
+                serde_json::json!({ "skipRationale": skip_rationale }),
+            )
+            .await;
+        match &response {
+            Ok(response) => crate::permission_cache::store(response.permission_state),
+            Err(_) => crate::permission_cache::invalidate(),
+        }
+        response.map(|r| r.permission_state).map_err(Into::into)
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn register_for_push_notifications(&self) -> crate::Result<String> {
         #[cfg(feature = "push-notifications")]
         {
@@ -80,6 +152,23 @@ impl<R: Runtime> Notifications<R> {
         }
     }
 
+    /// The device token from the last successful
+    /// [`register_for_push_notifications`](Self::register_for_push_notifications),
+    /// kept in `SharedPreferences` on Android and `UserDefaults` on iOS. No
+    /// network call is made. `None` if the app never registered.
+    #[must_use]
+    pub async fn get_push_token_cached(&self) -> Option<String> {
+        self.0
+            .run_mobile_plugin_async::<crate::CachedPushTokenResponse>("getPushTokenCached", ())
+            .await
+            .ok()
+            .and_then(|r| r.token)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
     pub fn unregister_for_push_notifications(&self) -> crate::Result<()> {
         #[cfg(feature = "push-notifications")]
         {
@@ -95,7 +184,10 @@ impl<R: Runtime> Notifications<R> {
         }
     }
 
-    pub async fn permission_state(&self) -> crate::Result<PermissionState> {
+    /// Reads the permission state from the platform, bypassing the cache of
+    /// [`permission_state`](Self::permission_state).
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn permission_state_fresh(&self) -> crate::Result<PermissionState> {
         self.0
             .run_mobile_plugin_async::<PermissionResponse>("checkPermissions", ())
             .await
@@ -103,14 +195,95 @@ impl<R: Runtime> Notifications<R> {
             .map_err(Into::into)
     }
 
-    pub fn register_action_types(&self, types: Vec<ActionType>) -> crate::Result<()> {
-        let mut args = HashMap::new();
-        args.insert("types", types);
+    /// Whether Do Not Disturb is on. Android reports its interruption
+    /// filter; iOS reports the Focus status when the app may read it, and
+    /// otherwise whether alerts are turned off for the app.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn is_do_not_disturb_active(&self) -> crate::Result<bool> {
         self.0
-            .run_mobile_plugin("registerActionTypes", args)
+            .run_mobile_plugin_async::<crate::DoNotDisturbResponse>("isDoNotDisturbActive", ())
+            .await
+            .map(|r| r.active)
             .map_err(Into::into)
     }
 
+    /// Number of unread notifications: the notifications in the shade on
+    /// Android, the app icon badge on iOS.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn get_unread_count(&self) -> crate::Result<u32> {
+        #[cfg(target_os = "android")]
+        {
+            let active = self.active().await?;
+            Ok(u32::try_from(active.len()).unwrap_or(u32::MAX))
+        }
+        #[cfg(not(target_os = "android"))]
+        {
+            self.0
+                .run_mobile_plugin_async::<crate::UnreadCountResponse>("getUnreadCount", ())
+                .await
+                .map(|r| r.count)
+                .map_err(Into::into)
+        }
+    }
+
+    /// Adds `types` to the registered action types, replacing the ones with
+    /// the same id. Does nothing if that leaves them as they were.
+    pub fn register_action_types(
+        &self,
+        types: Vec<ActionType>,
+    ) -> crate::Result<ActionTypesUpdate> {
+        crate::validate_action_types(&types)?;
+        self.update_action_types(|registered| action_types::merge(registered, types))
+    }
+
+    /// Replaces every registered action type with `types`.
+    pub fn set_notification_categories(
+        &self,
+        types: Vec<ActionType>,
+    ) -> crate::Result<ActionTypesUpdate> {
+        crate::validate_action_types(&types)?;
+        self.update_action_types(|registered| *registered = types)
+    }
+
+    /// Removes the registered action type `id`, if there is one.
+    pub fn unregister_action_type(&self, id: &str) -> crate::Result<ActionTypesUpdate> {
+        self.update_action_types(|registered| action_types::unregister(registered, id))
+    }
+
+    /// Removes every registered action type.
+    pub fn clear_action_types(&self) -> crate::Result<ActionTypesUpdate> {
+        self.update_action_types(Vec::clear)
+    }
+
+    /// Applies `change` to the registered action types and, if they changed,
+    /// hands them to the platform, which replaces what it had. On Android,
+    /// types registered by a version of the plugin that didn't track them
+    /// are kept.
+    fn update_action_types(
+        &self,
+        change: impl FnOnce(&mut Vec<ActionType>),
+    ) -> crate::Result<ActionTypesUpdate> {
+        let handle = self.0.clone();
+        action_types::schedule(self.0.app(), change, move |types| {
+            let mut args = HashMap::new();
+            args.insert("types", types);
+            handle
+                .run_mobile_plugin("setNotificationCategories", args)
+                .map_err(Into::into)
+        })
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            err,
+            fields(
+                platform = crate::telemetry::BACKEND,
+                ids = ?notifications
+            )
+        )
+    )]
     pub fn remove_active(&self, notifications: Vec<i32>) -> crate::Result<()> {
         let mut args = HashMap::new();
         args.insert(
@@ -129,6 +302,11 @@ impl<R: Runtime> Notifications<R> {
             .map_err(Into::into)
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn active(&self) -> crate::Result<Vec<ActiveNotification>> {
         self.0
             .run_mobile_plugin_async("getActive", ())
@@ -136,12 +314,43 @@ impl<R: Runtime> Notifications<R> {
             .map_err(Into::into)
     }
 
+    /// Same as [`active`](Self::active): the platforms forget a notification
+    /// once the user dismisses it.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn notification_history(&self) -> crate::Result<Vec<ActiveNotification>> {
+        self.active().await
+    }
+
+    /// Launch source the native plugin derived from the launch intent
+    /// (Android) or the first notification response (iOS).
+    pub(crate) fn read_launch_source(&self) -> crate::LaunchSource {
+        self.0
+            .run_mobile_plugin("getLaunchSource", ())
+            .unwrap_or_else(|e| {
+                crate::telemetry::warning!("Failed to read the launch source: {e}");
+                crate::LaunchSource::Normal
+            })
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
     pub fn remove_all_active(&self) -> crate::Result<()> {
         self.0
-            .run_mobile_plugin("removeActive", ())
+            .run_mobile_plugin("removeAllActive", ())
             .map_err(Into::into)
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn pending(&self) -> crate::Result<Vec<PendingNotification>> {
         self.0
             .run_mobile_plugin_async("getPending", ())
@@ -149,14 +358,68 @@ impl<R: Runtime> Notifications<R> {
             .map_err(Into::into)
     }
 
+    /// Number of pending notifications per channel (Android) or thread
+    /// identifier (iOS).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn pending_count_by_channel(&self) -> crate::Result<HashMap<String, usize>> {
+        let pending = self.pending().await?;
+        Ok(crate::count_pending_by_channel(&pending))
+    }
+
     /// Cancel pending notifications.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            err,
+            fields(
+                platform = crate::telemetry::BACKEND,
+                ids = ?notifications
+            )
+        )
+    )]
     pub fn cancel(&self, notifications: Vec<i32>) -> crate::Result<()> {
         let mut args = HashMap::new();
         args.insert("notifications", notifications);
         self.0.run_mobile_plugin("cancel", args).map_err(Into::into)
     }
 
+    /// Removes `id` from both the scheduled and the shown notifications.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND, id))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn dismiss(&self, id: i32) -> crate::Result<crate::DismissOutcome> {
+        self.0
+            .run_mobile_plugin_async("dismiss", serde_json::json!({ "id": id }))
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Cancels the scheduled and removes the shown notifications tagged with
+    /// `scope`, including ones scheduled in earlier sessions.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND, scope))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn clear_scope(&self, scope: &str) -> crate::Result<crate::ScopeClearance> {
+        self.0
+            .run_mobile_plugin_async("clearScope", serde_json::json!({ "scope": scope }))
+            .await
+            .map_err(Into::into)
+    }
+
     /// Cancel all pending notifications.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
     pub fn cancel_all(&self) -> crate::Result<()> {
         self.0
             .run_mobile_plugin("cancelAll", ())
@@ -164,11 +427,83 @@ impl<R: Runtime> Notifications<R> {
     }
 
     #[allow(unused_variables, clippy::needless_pass_by_value)]
-    pub fn create_channel(&self, channel: Channel) -> crate::Result<()> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            err,
+            fields(
+                platform = crate::telemetry::BACKEND,
+                channel = channel.id()
+            )
+        )
+    )]
+    pub fn create_channel(&self, channel: Channel) -> crate::Result<ChannelCreation> {
+        #[cfg(target_os = "android")]
+        {
+            let existing = self.list_channels()?;
+            let outcome = ChannelCreation::compare(
+                &channel,
+                existing.iter().find(|other| other.id() == channel.id()),
+            );
+            if outcome == ChannelCreation::Created {
+                self.0.run_mobile_plugin::<()>("createChannel", channel)?;
+            }
+            Ok(outcome)
+        }
+        #[cfg(target_os = "ios")]
+        return Err(crate::Error::Io(std::io::Error::other(
+            "Channels are not supported on iOS",
+        )));
+    }
+
+    #[allow(unused_variables, clippy::needless_pass_by_value)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    pub fn ensure_channels(&self, channels: Vec<Channel>) -> crate::Result<Vec<ChannelCreation>> {
+        #[cfg(target_os = "android")]
+        {
+            let outcomes = crate::models::plan_channels(&channels, &self.list_channels()?);
+            let missing: Vec<Channel> = channels
+                .into_iter()
+                .zip(&outcomes)
+                .filter(|(_, outcome)| **outcome == ChannelCreation::Created)
+                .map(|(channel, _)| channel)
+                .collect();
+            if !missing.is_empty() {
+                let mut args = HashMap::new();
+                args.insert("channels", missing);
+                self.0.run_mobile_plugin::<()>("createChannels", args)?;
+            }
+            Ok(outcomes)
+        }
+        #[cfg(target_os = "ios")]
+        return Err(crate::Error::Io(std::io::Error::other(
+            "Channels are not supported on iOS",
+        )));
+    }
+
+    /// Registers `channel` over the existing one with its id. Android takes
+    /// the new name and description, and the importance only if it's lower.
+    #[allow(unused_variables, clippy::needless_pass_by_value)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            err,
+            fields(
+                platform = crate::telemetry::BACKEND,
+                channel = channel.id()
+            )
+        )
+    )]
+    pub fn update_channel(&self, channel: Channel) -> crate::Result<()> {
         #[cfg(target_os = "android")]
         return self
             .0
-            .run_mobile_plugin("createChannel", channel)
+            .run_mobile_plugin::<()>("createChannel", channel)
             .map_err(Into::into);
         #[cfg(target_os = "ios")]
         return Err(crate::Error::Io(std::io::Error::other(
@@ -177,13 +512,30 @@ impl<R: Runtime> Notifications<R> {
     }
 
     #[allow(unused_variables, clippy::needless_pass_by_value)]
-    pub fn delete_channel(&self, id: impl Into<String>) -> crate::Result<()> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    pub fn delete_channel(
+        &self,
+        id: impl Into<String>,
+        on_pending: crate::OnPendingPolicy,
+    ) -> crate::Result<crate::ChannelDeletion> {
         #[cfg(target_os = "android")]
         {
-            let mut args = HashMap::new();
-            args.insert("id", id.into());
+            let id = id.into();
+            if let crate::OnPendingPolicy::Reassign { to_channel } = &on_pending
+                && *to_channel == id
+            {
+                return Err(crate::Error::InvalidArgument(format!(
+                    "cannot reassign pending notifications of channel '{id}' to itself"
+                )));
+            }
             self.0
-                .run_mobile_plugin("deleteChannel", args)
+                .run_mobile_plugin(
+                    "deleteChannel",
+                    serde_json::json!({ "id": id, "onPending": on_pending }),
+                )
                 .map_err(Into::into)
         }
         #[cfg(target_os = "ios")]
@@ -192,6 +544,10 @@ impl<R: Runtime> Notifications<R> {
         )));
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
     pub fn list_channels(&self) -> crate::Result<Vec<Channel>> {
         #[cfg(target_os = "android")]
         return self
@@ -204,6 +560,123 @@ impl<R: Runtime> Notifications<R> {
         )));
     }
 
+    /// Hands a `notificationsCoalesced` payload to the native plugin, which
+    /// triggers it for the JS listeners.
+    pub(crate) fn emit_coalesced(&self, payload: &serde_json::Value) -> crate::Result<()> {
+        self.0
+            .run_mobile_plugin("emitNotificationsCoalesced", payload)
+            .map_err(Into::into)
+    }
+
+    /// Hands a `notificationFallback` payload to the native plugin, which
+    /// triggers it for the JS listeners.
+    pub(crate) fn emit_fallback(&self, payload: &serde_json::Value) -> crate::Result<()> {
+        self.0
+            .run_mobile_plugin("emitNotificationFallback", payload)
+            .map_err(Into::into)
+    }
+
+    /// Hands a `notificationWarning` payload to the native plugin, which
+    /// triggers it for the JS listeners.
+    pub(crate) fn emit_warning(&self, payload: &serde_json::Value) -> crate::Result<()> {
+        self.0
+            .run_mobile_plugin("emitNotificationWarning", payload)
+            .map_err(Into::into)
+    }
+
+    /// Hands the replacement for a firing scheduled notification to the
+    /// native plugin, see [`Notifications::update_scheduled_notification`].
+    #[cfg(target_os = "android")]
+    pub(crate) fn replace_scheduled(
+        &self,
+        id: i32,
+        data: &crate::NotificationData,
+    ) -> crate::Result<()> {
+        self.0
+            .run_mobile_plugin(
+                "updateScheduledNotification",
+                serde_json::json!({ "id": id, "notification": data }),
+            )
+            .map_err(Into::into)
+    }
+
+    /// Takes the `actionPerformed` payloads the native plugin recorded for
+    /// the interaction stats since the last call, cold-start ones included.
+    pub(crate) fn take_interactions(&self) -> crate::Result<Vec<serde_json::Value>> {
+        #[derive(serde::Deserialize)]
+        struct Interactions {
+            interactions: Vec<serde_json::Value>,
+        }
+        self.0
+            .run_mobile_plugin::<Interactions>("takeInteractions", ())
+            .map(|response| response.interactions)
+            .map_err(Into::into)
+    }
+
+    /// Makes the native plugin watch for channels the user blocks in the
+    /// system settings and forward them to
+    /// [`on_channel_blocked`](Self::on_channel_blocked) callbacks.
+    pub fn register_channel_blocked_listener(&self) -> crate::Result<()> {
+        #[cfg(target_os = "android")]
+        {
+            use std::sync::atomic::{AtomicBool, Ordering};
+
+            // One forwarding channel serves every Rust callback.
+            static FORWARDING: AtomicBool = AtomicBool::new(false);
+            if !FORWARDING.swap(true, Ordering::SeqCst) {
+                let handler: tauri::ipc::Channel = tauri::ipc::Channel::new(|body| {
+                    if let tauri::ipc::InvokeResponseBody::Json(payload) = body
+                        && let Err(e) = crate::listeners::trigger("channelBlocked", payload)
+                    {
+                        crate::telemetry::warning!("Failed to trigger channelBlocked: {e}");
+                    }
+                    Ok(())
+                });
+                let args = serde_json::json!({ "event": "channelBlocked", "handler": handler });
+                if let Err(e) = self.0.run_mobile_plugin::<()>("registerListener", args) {
+                    FORWARDING.store(false, Ordering::SeqCst);
+                    return Err(e.into());
+                }
+            }
+            self.0
+                .run_mobile_plugin("registerChannelBlockedListener", ())
+                .map_err(Into::into)
+        }
+        #[cfg(target_os = "ios")]
+        return Err(crate::Error::Io(std::io::Error::other(
+            "Channels are not supported on iOS",
+        )));
+    }
+
+    /// Android: the channels the notification defaults rely on. With the
+    /// `push-notifications` feature, Firebase on Android and the push
+    /// entitlement on iOS.
+    pub(crate) async fn platform_diagnostics(&self) -> Vec<crate::Diagnostic> {
+        #[allow(unused_mut)]
+        let mut diagnostics = Vec::new();
+        #[cfg(target_os = "android")]
+        if let Ok(channels) = self.list_channels() {
+            use tauri::Manager;
+            let referenced = self
+                .0
+                .app()
+                .try_state::<crate::channel_defaults::ChannelDefaults>()
+                .map(|defaults| defaults.referenced_channels())
+                .unwrap_or_default();
+            diagnostics.extend(crate::doctor::channel_diagnostics(&referenced, &channels));
+        }
+        #[cfg(feature = "push-notifications")]
+        match self
+            .0
+            .run_mobile_plugin_async::<crate::doctor::NativeDiagnostics>("doctor", ())
+            .await
+        {
+            Ok(native) => diagnostics.extend(native.diagnostics()),
+            Err(e) => crate::telemetry::warning!("Native notification checks failed: {e}"),
+        }
+        diagnostics
+    }
+
     /// Set click listener active state.
     /// Used internally to track if JS listener is registered.
     pub fn set_click_listener_active(&self, active: bool) -> crate::Result<()> {
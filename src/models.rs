@@ -1,6 +1,11 @@
-use std::{collections::HashMap, fmt::Display};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Display,
+};
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as DeError};
+use serde_repr::{Deserialize_repr, Serialize_repr};
 use tauri::plugin::PermissionState;
 
 use url::Url;
@@ -18,16 +23,66 @@ pub struct PushNotificationResponse {
     pub device_token: String,
 }
 
+/// Response of the native `isDoNotDisturbActive` command.
+#[derive(Debug, Deserialize)]
+pub struct DoNotDisturbResponse {
+    pub active: bool,
+}
+
+/// Response of the native `getUnreadCount` command.
+#[derive(Debug, Deserialize)]
+pub struct UnreadCountResponse {
+    pub count: u32,
+}
+
+/// Response of the native `getPushTokenCached` command. `token` is missing
+/// when the app never registered.
+#[derive(Debug, Default, Deserialize)]
+pub struct CachedPushTokenResponse {
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+/// Media type of an [`Attachment`], passed to iOS/macOS as
+/// `UNNotificationAttachmentOptionsTypeHintKey` instead of inferring it from
+/// the file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AttachmentType {
+    Image,
+    Video,
+    Audio,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Attachment {
     id: String,
     url: Url,
+    /// iOS/macOS only; other platforms ignore it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    type_hint: Option<AttachmentType>,
 }
 
 impl Attachment {
     pub fn new(id: impl Into<String>, url: Url) -> Self {
-        Self { id: id.into(), url }
+        Self {
+            id: id.into(),
+            url,
+            type_hint: None,
+        }
+    }
+
+    /// Sets the media type iOS/macOS use for the attachment.
+    #[must_use]
+    pub const fn with_type_hint(mut self, type_hint: AttachmentType) -> Self {
+        self.type_hint = Some(type_hint);
+        self
+    }
+
+    #[must_use]
+    pub const fn type_hint(&self) -> Option<AttachmentType> {
+        self.type_hint
     }
 
     #[must_use]
@@ -41,8 +96,87 @@ impl Attachment {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+/// A playback button of a [`MediaControls`] notification.
+///
+/// Pressing it emits `actionPerformed` with [`MediaAction::action_id`] as
+/// the `actionId`, e.g. `media:play`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MediaAction {
+    #[serde(rename = "media:previous")]
+    Previous,
+    #[serde(rename = "media:play")]
+    Play,
+    #[serde(rename = "media:pause")]
+    Pause,
+    #[serde(rename = "media:next")]
+    Next,
+}
+
+impl MediaAction {
+    /// The `actionId` reported when this button is pressed.
+    #[must_use]
+    pub const fn action_id(self) -> &'static str {
+        match self {
+            Self::Previous => "media:previous",
+            Self::Play => "media:play",
+            Self::Pause => "media:pause",
+            Self::Next => "media:next",
+        }
+    }
+}
+
+/// Most buttons Android shows in a collapsed media notification.
+const MAX_COMPACT_MEDIA_ACTIONS: usize = 3;
+
+/// Playback buttons for an ongoing media notification.
+///
+/// Android renders them with `MediaStyle`, tied to a media session the plugin
+/// manages; Windows shows them as toast buttons. iOS and macOS report the
+/// notification as unsupported, since playback controls belong to Now
+/// Playing there, and so does the notify-rust backend.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct MediaControls {
+    /// Buttons in display order.
+    pub actions: Vec<MediaAction>,
+    /// Indices into `actions` of the (at most three) buttons shown while the
+    /// notification is collapsed. Android only.
+    #[serde(default)]
+    pub show_in_compact: Vec<usize>,
+}
+
+impl MediaControls {
+    /// Fails with [`Error::InvalidArgument`](crate::Error::InvalidArgument)
+    /// when there are no actions or `show_in_compact` is out of range.
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.actions.is_empty() {
+            return Err(crate::Error::InvalidArgument(
+                "media controls need at least one action".to_string(),
+            ));
+        }
+        if self.show_in_compact.len() > MAX_COMPACT_MEDIA_ACTIONS {
+            return Err(crate::Error::InvalidArgument(format!(
+                "at most {MAX_COMPACT_MEDIA_ACTIONS} media actions can be shown in compact view"
+            )));
+        }
+        if let Some(index) = self
+            .show_in_compact
+            .iter()
+            .find(|&&index| index >= self.actions.len())
+        {
+            return Err(crate::Error::InvalidArgument(format!(
+                "compact media action index {index} is out of range for {} actions",
+                self.actions.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Rejects unknown fields, so a typo like `hours` fails instead of leaving
+/// the interval empty and firing right away.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct ScheduleInterval {
     pub year: Option<u8>,
     pub month: Option<u8>,
@@ -125,12 +259,16 @@ pub enum Schedule {
         date: time::OffsetDateTime,
         #[serde(default)]
         repeating: bool,
+        /// Deliver on time while an Android device dozes. No effect
+        /// elsewhere, see [`ScheduleCapabilities`].
         #[serde(default)]
         allow_while_idle: bool,
     },
     #[serde(rename_all = "camelCase")]
     Interval {
         interval: ScheduleInterval,
+        /// Deliver on time while an Android device dozes. No effect
+        /// elsewhere, see [`ScheduleCapabilities`].
         #[serde(default)]
         allow_while_idle: bool,
     },
@@ -138,22 +276,178 @@ pub enum Schedule {
     Every {
         interval: ScheduleEvery,
         count: u8,
+        /// Deliver on time while an Android device dozes. No effect
+        /// elsewhere, see [`ScheduleCapabilities`].
         #[serde(default)]
         allow_while_idle: bool,
     },
 }
 
+/// Which plugin build is running, from `Notifications::plugin_info`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginInfo {
+    /// Backend compiled into this build: `windows`, `macos`, `ios`,
+    /// `android` or `notify-rust`.
+    pub platform: &'static str,
+    /// Version of the plugin crate.
+    pub version: &'static str,
+}
+
+/// What scheduled notifications can be relied on for on this platform, from
+/// `Notifications::schedule_capabilities`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleCapabilities {
+    /// [`Schedule`]s are honored. notify-rust has no scheduler and shows
+    /// scheduled notifications right away.
+    pub scheduling: bool,
+    /// `allow_while_idle` has an effect. Android only: the other platforms
+    /// have no Doze mode to fire through.
+    pub allow_while_idle: bool,
+    /// A notification that came due while the device slept is delivered
+    /// once it wakes up, instead of being lost. Repeating schedules deliver
+    /// one notification for all the occurrences missed, except on Windows
+    /// with `missedFirePolicy: "skip"`.
+    pub delivered_after_sleep: bool,
+}
+
+impl ScheduleCapabilities {
+    /// Capabilities of the backend this build uses.
+    #[must_use]
+    pub const fn current() -> Self {
+        let scheduling = cfg!(any(
+            mobile,
+            all(
+                any(target_os = "macos", target_os = "windows"),
+                not(feature = "notify-rust")
+            )
+        ));
+        Self {
+            scheduling,
+            allow_while_idle: cfg!(target_os = "android"),
+            delivered_after_sleep: scheduling,
+        }
+    }
+}
+
+/// Wire format of `extra` between Rust and the native plugins.
+///
+/// The map travels as one JSON string instead of a nested object: Android
+/// flattens bundle values to strings and the Swift side decodes `extra` as
+/// `[String: String]`, both of which lose nested objects, arrays and numbers.
+/// Integers outside ±2^53 are sent as strings, since both platforms parse
+/// JSON numbers into doubles and would silently round them.
+mod extra_json {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Deserializer, Serializer, de::Error as _, ser::Error as _};
+    use serde_json::Value;
+
+    /// Largest integer every platform represents exactly (`Number.MAX_SAFE_INTEGER`).
+    const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+    fn stringify_unsafe_integers(value: Value) -> Value {
+        match value {
+            Value::Number(n) => {
+                let unsafe_int = n.as_u64().is_some_and(|v| v > MAX_SAFE_INTEGER)
+                    || n.as_i64()
+                        .is_some_and(|v| v.unsigned_abs() > MAX_SAFE_INTEGER);
+                if unsafe_int {
+                    Value::String(n.to_string())
+                } else {
+                    Value::Number(n)
+                }
+            }
+            Value::Array(items) => {
+                Value::Array(items.into_iter().map(stringify_unsafe_integers).collect())
+            }
+            Value::Object(map) => Value::Object(
+                map.into_iter()
+                    .map(|(k, v)| (k, stringify_unsafe_integers(v)))
+                    .collect(),
+            ),
+            other => other,
+        }
+    }
+
+    /// `extra` as an object, with integers beyond 2^53 turned into strings.
+    pub fn to_value(extra: &HashMap<String, Value>) -> Value {
+        Value::Object(
+            extra
+                .iter()
+                .map(|(k, v)| (k.clone(), stringify_unsafe_integers(v.clone())))
+                .collect(),
+        )
+    }
+
+    /// Encodes `extra` the way it is sent to the native plugins.
+    pub fn encode(extra: &HashMap<String, Value>) -> serde_json::Result<String> {
+        serde_json::to_string(&to_value(extra))
+    }
+
+    pub fn serialize<S: Serializer>(
+        extra: &HashMap<String, Value>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&encode(extra).map_err(S::Error::custom)?)
+    }
+
+    /// Accepts the JSON-string form as well as a plain object (what the JS
+    /// API and older native builds send) or `null`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<String, Value>, D::Error> {
+        match Value::deserialize(deserializer)? {
+            Value::Null => Ok(HashMap::new()),
+            Value::String(json) if json.is_empty() => Ok(HashMap::new()),
+            Value::String(json) => serde_json::from_str(&json).map_err(D::Error::custom),
+            Value::Object(map) => Ok(map.into_iter().collect()),
+            other => Err(D::Error::custom(format!(
+                "expected `extra` to be an object or a JSON string, got {other}"
+            ))),
+        }
+    }
+}
+
 // custom ISO-8601 serialization that does not use 6 digits for years.
 mod iso8601 {
+    use std::num::NonZeroU8;
+
     use serde::{Serialize, Serializer, ser::Error as _};
     use time::{
-        OffsetDateTime,
+        OffsetDateTime, UtcOffset,
         format_description::well_known::Iso8601,
-        format_description::well_known::iso8601::{Config, EncodedConfig},
+        format_description::well_known::iso8601::{Config, EncodedConfig, TimePrecision},
     };
 
     const SERDE_CONFIG: EncodedConfig = Config::DEFAULT.encode();
 
+    /// `Date.toISOString()` shape: UTC with millisecond precision, which is
+    /// what the Android plugin's `JS_DATE_FORMAT` parser accepts.
+    const JS_CONFIG: EncodedConfig = Config::DEFAULT
+        .set_time_precision(TimePrecision::Second {
+            decimal_digits: NonZeroU8::new(3),
+        })
+        .encode();
+
+    // `serialize_with` hands over a reference to the field itself.
+    #[allow(clippy::ref_option)]
+    pub fn serialize_js_option<S: Serializer>(
+        datetime: &Option<OffsetDateTime>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        datetime
+            .map(|datetime| {
+                datetime
+                    .to_offset(UtcOffset::UTC)
+                    .format(&Iso8601::<JS_CONFIG>)
+            })
+            .transpose()
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+
     pub fn serialize<S: Serializer>(
         datetime: &OffsetDateTime,
         serializer: S,
@@ -165,14 +459,84 @@ mod iso8601 {
     }
 }
 
+/// Whether `color` is a hex color accepted as an icon color: `#RGB`,
+/// `#RRGGBB` or `#AARRGGBB`.
+#[must_use]
+pub fn validate_icon_color(color: &str) -> bool {
+    color.strip_prefix('#').is_some_and(|hex| {
+        matches!(hex.len(), 3 | 6 | 8) && hex.bytes().all(|b| b.is_ascii_hexdigit())
+    })
+}
+
+/// Validates `color` and expands `#RGB` to `#RRGGBB`, the shortest form
+/// Android's `Color.parseColor` accepts.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn normalize_icon_color(color: &str) -> crate::Result<String> {
+    if !validate_icon_color(color) {
+        return Err(crate::Error::InvalidArgument(format!(
+            "invalid icon color '{color}': expected #RGB, #RRGGBB or #AARRGGBB"
+        )));
+    }
+    if color.len() == 4 {
+        return Ok(color
+            .chars()
+            .skip(1)
+            .fold(String::from("#"), |mut expanded, digit| {
+                expanded.push(digit);
+                expanded.push(digit);
+                expanded
+            }));
+    }
+    Ok(color.to_string())
+}
+
+mod icon_color {
+    use serde::{Deserialize, Deserializer, de::Error as _};
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<String>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|color| super::normalize_icon_color(&color).map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+/// `suppressIfFocused` is a window label, or `true` for any window.
+mod focus_target {
+    use serde::{Deserialize, Deserializer};
+
+    use crate::suppress::FocusTarget;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Target {
+        Any(bool),
+        Window(String),
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<FocusTarget>, D::Error> {
+        Ok(match Option::<Target>::deserialize(deserializer)? {
+            None | Some(Target::Any(false)) => None,
+            Some(Target::Any(true)) => Some(FocusTarget::AnyWindow),
+            Some(Target::Window(label)) => Some(FocusTarget::Window(label)),
+        })
+    }
+}
+
 // Each bool is an independent flag in the JS wire format; grouping them would change the JSON shape.
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NotificationData {
     #[serde(default = "default_id")]
     pub(crate) id: i32,
     pub(crate) channel_id: Option<String>,
+    /// Android only: channel used when `channel_id` doesn't exist.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) channel_id_fallback: Option<String>,
     pub(crate) title: Option<String>,
     pub(crate) body: Option<String>,
     pub(crate) schedule: Option<Schedule>,
@@ -182,15 +546,94 @@ pub struct NotificationData {
     pub(crate) group: Option<String>,
     #[serde(default)]
     pub(crate) group_summary: bool,
+    /// Orders the notification within its group, see
+    /// [`ActiveNotification::sort_key`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) sort_key: Option<String>,
     pub(crate) sound: Option<String>,
     #[serde(default)]
     pub(crate) inbox_lines: Vec<String>,
     pub(crate) icon: Option<String>,
     pub(crate) large_icon: Option<String>,
+    #[serde(default, deserialize_with = "icon_color::deserialize")]
     pub(crate) icon_color: Option<String>,
+    /// Windows only; never sent to the mobile plugins.
+    #[serde(default, skip_serializing)]
+    pub(crate) foreground_color: Option<(u8, u8, u8)>,
+    /// Desktop only: label of the window to focus when this notification is
+    /// clicked. Never sent to the native plugins.
+    #[serde(default, skip_serializing)]
+    pub(crate) focus_window: Option<String>,
+    /// Windows whose focus keeps the notification from being shown. Never
+    /// sent to the native plugins.
+    #[serde(
+        default,
+        skip_serializing,
+        deserialize_with = "focus_target::deserialize"
+    )]
+    pub(crate) suppress_if_focused: Option<crate::suppress::FocusTarget>,
     #[serde(default)]
     pub(crate) attachments: Vec<Attachment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) media_controls: Option<MediaControls>,
+    /// Deadline shown as a live count-down; the notification is re-posted
+    /// as an alarm once it passes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "iso8601::serialize_js_option",
+        deserialize_with = "time::serde::iso8601::option::deserialize"
+    )]
+    pub(crate) count_down_to: Option<time::OffsetDateTime>,
+    /// Filled fraction of a progress bar, from 0 to 1. Android and Windows;
+    /// ignored with [`count_down_to`](Self::count_down_to).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) progress: Option<f32>,
+    /// Account or profile the notification belongs to, for
+    /// [`Notifications::clear_scope`](crate::Notifications::clear_scope).
+    /// Kept with the persisted schedule and the delivered notification.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) scope: Option<String>,
+    /// Windows only: how many times an `Interval` schedule repeats after
+    /// its first delivery; `None` repeats until canceled. Never sent to the
+    /// native plugins.
+    #[serde(default, skip_serializing)]
+    pub(crate) max_repetitions: Option<u32>,
+    /// Windows only: drop the scheduled toast instead of showing it late
+    /// when its time passed before the app started again. Never sent to the
+    /// native plugins.
+    #[serde(default, skip_serializing)]
+    pub(crate) show_only_once: bool,
+    /// What to do with the notification during quiet hours; `None` uses the
+    /// configured policy. Never sent to the native plugins.
+    #[serde(default, skip_serializing)]
+    pub(crate) quiet_hours_policy: Option<crate::QuietHoursPolicy>,
+    /// Deliver without sound or heads-up. Set by quiet hours.
+    #[serde(default)]
+    pub(crate) quiet: bool,
+    /// Text accessibility services read when the notification is posted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) ticker: Option<String>,
+    /// Deliver a screen-reader announcement instead of a visual notification.
     #[serde(default)]
+    pub(crate) announce_only: bool,
+    /// Android only: keep the notification off bridged devices such as
+    /// watches.
+    #[serde(default)]
+    pub(crate) local_only: bool,
+    /// Android only: actions shown on a paired watch but not on the phone.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) wearable_actions: Vec<Action>,
+    /// Sent to the native plugins as a single JSON string (`extraJson`) so
+    /// nested values survive Android bundles and Swift's `Codable`.
+    #[serde(
+        default,
+        rename(serialize = "extraJson"),
+        alias = "extraJson",
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "extra_json::serialize",
+        deserialize_with = "extra_json::deserialize"
+    )]
     pub(crate) extra: HashMap<String, serde_json::Value>,
     #[serde(default)]
     pub(crate) ongoing: bool,
@@ -198,6 +641,117 @@ pub struct NotificationData {
     pub(crate) auto_cancel: bool,
     #[serde(default)]
     pub(crate) silent: bool,
+    /// Desktop only: keep the notification in the notification list after
+    /// it times out. Never sent to the native plugins.
+    #[serde(default, skip_serializing)]
+    pub(crate) persistent: bool,
+    /// Desktop only: keep the notification out of the notification list.
+    /// Never sent to the native plugins.
+    #[serde(default, skip_serializing)]
+    pub(crate) transient: bool,
+    /// Desktop only: keep the notification on screen until the user
+    /// dismisses it. Never sent to the native plugins.
+    #[serde(default, skip_serializing)]
+    pub(crate) require_interaction: bool,
+}
+
+/// Default for [`PluginConfig::max_extra_bytes`](crate::PluginConfig::max_extra_bytes).
+pub const DEFAULT_MAX_EXTRA_BYTES: usize = 64 * 1024;
+
+/// Most inbox lines Android shows.
+pub const MAX_INBOX_LINES: usize = 5;
+
+impl NotificationData {
+    /// `extra` as it appears in event payloads: a JSON object in which
+    /// integers that do not fit in an `f64` have been turned into strings.
+    #[must_use]
+    pub fn extra_payload(&self) -> serde_json::Value {
+        extra_json::to_value(&self.extra)
+    }
+
+    /// Drops empty inbox lines, then keeps the first [`MAX_INBOX_LINES`] and
+    /// returns how many were dropped over the limit, or fails with
+    /// [`Error::InvalidArgument`](crate::Error::InvalidArgument) if `strict`.
+    pub(crate) fn limit_inbox_lines(&mut self, strict: bool) -> crate::Result<usize> {
+        self.inbox_lines.retain(|line| !line.trim().is_empty());
+        let count = self.inbox_lines.len();
+        if count <= MAX_INBOX_LINES {
+            return Ok(0);
+        }
+        if strict {
+            return Err(crate::Error::InvalidArgument(format!(
+                "{count} inbox lines given, at most {MAX_INBOX_LINES} are supported"
+            )));
+        }
+        self.inbox_lines.truncate(MAX_INBOX_LINES);
+        Ok(count - MAX_INBOX_LINES)
+    }
+
+    /// Sets a missing title to `fallback`, except on announcements, which
+    /// read out only what they were given. Fails with
+    /// [`Error::InvalidArgument`](crate::Error::InvalidArgument) if `strict`
+    /// and there is no text at all.
+    pub(crate) fn fill_title(&mut self, fallback: Option<&str>, strict: bool) -> crate::Result<()> {
+        if self.title.is_some() {
+            return Ok(());
+        }
+        if strict && self.body.is_none() && self.inbox_lines.is_empty() {
+            return Err(crate::Error::InvalidArgument(
+                "a notification needs a title, body or inbox lines".to_string(),
+            ));
+        }
+        if !self.announce_only {
+            self.title = fallback.map(ToString::to_string);
+        }
+        Ok(())
+    }
+
+    /// Drops `auto_cancel` from an ongoing notification and returns whether
+    /// it did, or fails with
+    /// [`Error::InvalidArgument`](crate::Error::InvalidArgument) if `strict`.
+    /// Platforms disagree on the combination: Android keeps the notification,
+    /// Windows dismisses it on click.
+    pub(crate) fn resolve_ongoing_auto_cancel(&mut self, strict: bool) -> crate::Result<bool> {
+        if !(self.ongoing && self.auto_cancel) {
+            return Ok(false);
+        }
+        if strict {
+            return Err(crate::Error::InvalidArgument(
+                "a notification can't be both ongoing and auto-cancelled".to_string(),
+            ));
+        }
+        self.auto_cancel = false;
+        Ok(true)
+    }
+
+    /// Appends the inbox lines to the body, one per line, for platforms
+    /// without an inbox style.
+    pub(crate) fn fold_inbox_lines(&mut self) {
+        if self.inbox_lines.is_empty() {
+            return;
+        }
+        let lines = std::mem::take(&mut self.inbox_lines).join("\n");
+        self.body = Some(match self.body.take() {
+            Some(body) if !body.is_empty() => format!("{body}\n{lines}"),
+            _ => lines,
+        });
+    }
+
+    /// Fails with [`Error::ExtraTooLarge`](crate::Error::ExtraTooLarge) when
+    /// `extra`, encoded as it is sent to the native plugins, exceeds `limit`
+    /// bytes.
+    pub fn check_extra_size(&self, limit: usize) -> crate::Result<()> {
+        if self.extra.is_empty() {
+            return Ok(());
+        }
+        let size = extra_json::encode(&self.extra)
+            .map_err(|e| crate::Error::Io(std::io::Error::other(e)))?
+            .len();
+        if size > limit {
+            return Err(crate::Error::ExtraTooLarge { size, limit });
+        }
+        Ok(())
+    }
 }
 
 fn default_id() -> i32 {
@@ -209,6 +763,7 @@ impl Default for NotificationData {
         Self {
             id: default_id(),
             channel_id: None,
+            channel_id_fallback: None,
             title: None,
             body: None,
             schedule: None,
@@ -217,16 +772,35 @@ impl Default for NotificationData {
             action_type_id: None,
             group: None,
             group_summary: false,
+            sort_key: None,
             sound: None,
             inbox_lines: Vec::new(),
             icon: None,
             large_icon: None,
             icon_color: None,
+            foreground_color: None,
+            focus_window: None,
+            suppress_if_focused: None,
             attachments: Vec::new(),
+            media_controls: None,
+            count_down_to: None,
+            progress: None,
+            scope: None,
+            max_repetitions: None,
+            show_only_once: false,
+            quiet_hours_policy: None,
+            quiet: false,
+            ticker: None,
+            announce_only: false,
+            local_only: false,
+            wearable_actions: Vec::new(),
             extra: HashMap::default(),
             ongoing: false,
             auto_cancel: false,
             silent: false,
+            persistent: false,
+            transient: false,
+            require_interaction: false,
         }
     }
 }
@@ -238,6 +812,28 @@ pub struct PendingNotification {
     pub(crate) title: Option<String>,
     pub(crate) body: Option<String>,
     pub(crate) schedule: Schedule,
+    /// Android channel, or the thread identifier on iOS / macOS.
+    #[serde(default)]
+    pub(crate) channel_id: Option<String>,
+    /// Only reported on Windows; the mobile and macOS plugins omit it.
+    #[serde(default)]
+    pub(crate) tag: Option<String>,
+    /// Only reported on Windows; the mobile and macOS plugins omit it.
+    #[serde(default)]
+    pub(crate) group: Option<String>,
+    /// Only reported on macOS, as `extraJson`.
+    #[serde(
+        default,
+        alias = "extraJson",
+        deserialize_with = "extra_json::deserialize"
+    )]
+    pub(crate) extra: HashMap<String, serde_json::Value>,
+    /// Only reported on macOS.
+    #[serde(default)]
+    pub(crate) action_type_id: Option<String>,
+    /// Only reported on macOS.
+    #[serde(default)]
+    pub(crate) sound: Option<String>,
 }
 
 impl PendingNotification {
@@ -260,6 +856,242 @@ impl PendingNotification {
     pub const fn schedule(&self) -> &Schedule {
         &self.schedule
     }
+
+    #[must_use]
+    pub fn channel_id(&self) -> Option<&str> {
+        self.channel_id.as_deref()
+    }
+
+    #[must_use]
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+
+    #[must_use]
+    pub fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    #[must_use]
+    pub const fn extra(&self) -> &HashMap<String, serde_json::Value> {
+        &self.extra
+    }
+
+    #[must_use]
+    pub fn action_type_id(&self) -> Option<&str> {
+        self.action_type_id.as_deref()
+    }
+
+    #[must_use]
+    pub fn sound(&self) -> Option<&str> {
+        self.sound.as_deref()
+    }
+
+    /// Rebuilds the notification this entry was scheduled from, e.g. to
+    /// reschedule it with a different [`Schedule`].
+    ///
+    /// Only what `pending()` reports is carried over: id, title, body,
+    /// schedule, channel, (on Windows) group and (on macOS) extra, action
+    /// type and sound. Everything else — icon, attachments, ... — is left at
+    /// its default, since the platforms don't return it for scheduled
+    /// notifications.
+    #[must_use]
+    pub fn into_notification_data(self) -> NotificationData {
+        NotificationData {
+            id: self.id,
+            channel_id: self.channel_id,
+            title: self.title,
+            body: self.body,
+            schedule: Some(self.schedule),
+            group: self.group,
+            extra: self.extra,
+            action_type_id: self.action_type_id,
+            sound: self.sound,
+            ..NotificationData::default()
+        }
+    }
+}
+
+/// Key used by [`count_pending_by_channel`] for notifications scheduled
+/// without a channel. Matches the channel Android falls back to.
+// `pub(crate)` rather than `pub`: everything `pub` here is glob re-exported
+// from the crate root.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) const DEFAULT_CHANNEL_ID: &str = "default";
+
+/// Groups pending notifications by channel id. Entries without one (including
+/// everything on Windows, which has no channels) are counted under
+/// [`DEFAULT_CHANNEL_ID`].
+#[must_use]
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn count_pending_by_channel(pending: &[PendingNotification]) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for notification in pending {
+        let channel = notification
+            .channel_id
+            .as_deref()
+            .filter(|c| !c.is_empty())
+            .unwrap_or(DEFAULT_CHANNEL_ID);
+        *counts.entry(channel.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Where [`Notifications::dismiss`](crate::Notifications::dismiss) found the
+/// notification. Both are `false` when the id was unknown, which is not an
+/// error.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DismissOutcome {
+    /// It was scheduled and has been canceled.
+    pub was_pending: bool,
+    /// It was shown and has been removed.
+    pub was_active: bool,
+}
+
+/// How the app was started, from
+/// [`Notifications::launch_source`](crate::Notifications::launch_source).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum LaunchSource {
+    /// Not by a notification, or on a platform that can't tell.
+    #[default]
+    Normal,
+    /// By tapping a local notification. `id` is `None` when the platform
+    /// doesn't report it.
+    NotificationTap { id: Option<i32> },
+    /// By one of a local notification's actions. Windows doesn't report the
+    /// notification's `id` for actions.
+    NotificationAction { id: Option<i32>, action_id: String },
+    /// By tapping a push notification. `message_id` is the FCM message id,
+    /// when there is one.
+    Push { message_id: Option<String> },
+}
+
+/// How many notifications [`Notifications::clear_scope`](crate::Notifications::clear_scope)
+/// took away.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopeClearance {
+    /// Scheduled notifications canceled.
+    pub canceled: usize,
+    /// Shown notifications removed.
+    pub removed: usize,
+}
+
+/// Changes to a shown notification, for
+/// [`Notifications::patch_active`](crate::Notifications::patch_active).
+/// Fields left `None` keep their value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivePatch {
+    pub title: Option<String>,
+    pub body: Option<String>,
+    /// Filled fraction of the progress bar, from 0 to 1.
+    pub progress: Option<f32>,
+    /// Merged into the notification's extra payload.
+    pub extra: Option<HashMap<String, serde_json::Value>>,
+}
+
+impl ActivePatch {
+    /// Whether only the progress changes, which Windows can update in place.
+    #[must_use]
+    pub const fn is_progress_only(&self) -> bool {
+        self.progress.is_some()
+            && self.title.is_none()
+            && self.body.is_none()
+            && self.extra.is_none()
+    }
+
+    /// Applies the patch to `data`.
+    pub(crate) fn apply(self, data: &mut NotificationData) {
+        if let Some(title) = self.title {
+            data.title = Some(title);
+        }
+        if let Some(body) = self.body {
+            data.body = Some(body);
+        }
+        if let Some(progress) = self.progress {
+            data.progress = Some(progress.clamp(0.0, 1.0));
+        }
+        if let Some(extra) = self.extra {
+            data.extra.extend(extra);
+        }
+    }
+}
+
+/// How [`Notifications::patch_active`](crate::Notifications::patch_active)
+/// changed the notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PatchStrategy {
+    /// Updated where it is shown, without showing it again (Windows, for a
+    /// progress-only patch).
+    InPlace,
+    /// Replaced under the same id without alerting the user again.
+    ReplacedQuietly,
+    /// Removed and shown again; the user may be alerted again (notify-rust).
+    Reshown,
+    /// Replaced quietly, but rebuilt from what the platform reports about
+    /// it, as it wasn't shown in this session. Options the platform doesn't
+    /// report, such as actions and icons, are lost.
+    Rebuilt,
+}
+
+/// Outcome of each notification of a bulk call such as
+/// [`Notifications::cancel_detailed`](crate::Notifications::cancel_detailed).
+/// Both lists keep the order the ids were passed in.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkResult {
+    pub succeeded: Vec<i32>,
+    /// The ids that failed, with the error.
+    pub failed: Vec<(i32, String)>,
+}
+
+impl BulkResult {
+    /// Every id in `ids` with the outcome of the one call that handled them
+    /// all.
+    #[must_use]
+    pub fn all(ids: Vec<i32>, result: &crate::Result<()>) -> Self {
+        match result {
+            Ok(()) => Self {
+                succeeded: ids,
+                failed: Vec::new(),
+            },
+            Err(e) => Self {
+                succeeded: Vec::new(),
+                failed: ids.into_iter().map(|id| (id, e.to_string())).collect(),
+            },
+        }
+    }
+
+    /// Records the outcome for `id`.
+    pub fn push(&mut self, id: i32, result: crate::Result<()>) {
+        match result {
+            Ok(()) => self.succeeded.push(id),
+            Err(e) => self.failed.push((id, e.to_string())),
+        }
+    }
+
+    #[must_use]
+    pub const fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+impl FromIterator<(i32, crate::Result<()>)> for BulkResult {
+    fn from_iter<I: IntoIterator<Item = (i32, crate::Result<()>)>>(iter: I) -> Self {
+        let mut result = Self::default();
+        for (id, outcome) in iter {
+            result.push(id, outcome);
+        }
+        result
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -274,13 +1106,23 @@ pub struct ActiveNotification {
     pub(crate) group_summary: bool,
     #[serde(default)]
     pub(crate) data: HashMap<String, String>,
-    #[serde(default)]
+    /// The Apple plugins report this as `extraJson`; see [`NotificationData`].
+    #[serde(
+        default,
+        alias = "extraJson",
+        deserialize_with = "extra_json::deserialize"
+    )]
     pub(crate) extra: HashMap<String, serde_json::Value>,
     #[serde(default)]
     pub(crate) attachments: Vec<Attachment>,
     pub(crate) action_type_id: Option<String>,
     pub(crate) schedule: Option<Schedule>,
     pub(crate) sound: Option<String>,
+    #[serde(default)]
+    pub(crate) sort_key: Option<String>,
+    /// Unix time in milliseconds. Not reported on Windows and Linux.
+    #[serde(default)]
+    pub(crate) delivered_at: Option<i64>,
 }
 
 impl ActiveNotification {
@@ -302,6 +1144,8 @@ impl ActiveNotification {
             action_type_id: None,
             schedule: None,
             sound: None,
+            sort_key: None,
+            delivered_at: None,
         }
     }
 
@@ -335,11 +1179,26 @@ impl ActiveNotification {
         self.group_summary
     }
 
+    /// The notification's data payload as strings: the FCM data of a push
+    /// on Android.
+    #[deprecated(note = "use `merged_data`, which includes `extra` as well")]
     #[must_use]
     pub const fn data(&self) -> &HashMap<String, String> {
         &self.data
     }
 
+    /// [`extra`](Self::extra) merged with the string data payload (the FCM
+    /// data of a push on Android), whose values become JSON strings. On a
+    /// key in both, `extra` wins.
+    #[must_use]
+    pub fn merged_data(&self) -> HashMap<String, serde_json::Value> {
+        self.data
+            .iter()
+            .map(|(key, value)| (key.clone(), serde_json::Value::String(value.clone())))
+            .chain(self.extra.clone())
+            .collect()
+    }
+
     #[must_use]
     pub const fn extra(&self) -> &HashMap<String, serde_json::Value> {
         &self.extra
@@ -364,6 +1223,92 @@ impl ActiveNotification {
     pub fn sound(&self) -> Option<&str> {
         self.sound.as_deref()
     }
+
+    /// Key the notification is ordered by within its group. Notifications
+    /// with a key come first, in plain string order as on Android; the rest
+    /// follow newest first. Equal keys also go newest first.
+    #[must_use]
+    pub fn sort_key(&self) -> Option<&str> {
+        self.sort_key.as_deref()
+    }
+
+    /// When the notification was posted, in milliseconds since the Unix
+    /// epoch. `None` where the platform doesn't report it.
+    #[must_use]
+    pub const fn delivered_at(&self) -> Option<i64> {
+        self.delivered_at
+    }
+
+    /// Order of `self` and `other` within a group, see
+    /// [`sort_key`](Self::sort_key). Unknown delivery times sort last.
+    pub(crate) fn cmp_in_group(&self, other: &Self) -> Ordering {
+        let by_key = match (&self.sort_key, &other.sort_key) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        by_key.then_with(|| match (self.delivered_at, other.delivered_at) {
+            (Some(a), Some(b)) => b.cmp(&a),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        })
+    }
+}
+
+/// The active notifications of one group, see
+/// [`Notifications::active_grouped`](crate::Notifications::active_grouped).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveGroup {
+    group: Option<String>,
+    summary: Option<ActiveNotification>,
+    children: Vec<ActiveNotification>,
+}
+
+impl ActiveGroup {
+    /// Splits `notifications` by group, ordered by group id with the
+    /// ungrouped ones first. Children are sorted with
+    /// [`ActiveNotification::sort_key`]'s rules.
+    pub(crate) fn collect(notifications: Vec<ActiveNotification>) -> Vec<Self> {
+        let mut groups: BTreeMap<Option<String>, Self> = BTreeMap::new();
+        for notification in notifications {
+            let group = groups
+                .entry(notification.group.clone())
+                .or_insert_with_key(|group| Self {
+                    group: group.clone(),
+                    summary: None,
+                    children: Vec::new(),
+                });
+            if notification.group_summary && group.group.is_some() && group.summary.is_none() {
+                group.summary = Some(notification);
+            } else {
+                group.children.push(notification);
+            }
+        }
+        let mut groups: Vec<Self> = groups.into_values().collect();
+        for group in &mut groups {
+            group.children.sort_by(ActiveNotification::cmp_in_group);
+        }
+        groups
+    }
+
+    /// `None` for the notifications that aren't in a group.
+    #[must_use]
+    pub fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    #[must_use]
+    pub const fn summary(&self) -> Option<&ActiveNotification> {
+        self.summary.as_ref()
+    }
+
+    #[must_use]
+    pub fn children(&self) -> &[ActiveNotification] {
+        &self.children
+    }
 }
 
 // Each bool is an independent UNNotificationCategory option; grouping would change the JSON shape.
@@ -384,6 +1329,9 @@ pub struct ActionType {
     hidden_previews_show_subtitle: bool,
 }
 
+#[derive(Debug)]
+pub struct ActionTypeBuilder(ActionType);
+
 impl ActionType {
     pub fn new(id: impl Into<String>, actions: Vec<Action>) -> Self {
         Self {
@@ -397,6 +1345,13 @@ impl ActionType {
         }
     }
 
+    /// Starts an action type without actions. The options other than the
+    /// actions apply on iOS/macOS; Android uses the hidden-previews
+    /// placeholder and title for the lock screen.
+    pub fn builder(id: impl Into<String>) -> ActionTypeBuilder {
+        ActionTypeBuilder(Self::new(id, Vec::new()))
+    }
+
     #[must_use]
     pub fn id(&self) -> &str {
         &self.id
@@ -406,10 +1361,155 @@ impl ActionType {
     pub fn actions(&self) -> &[Action] {
         &self.actions
     }
-}
 
-// Each bool is an independent UNNotificationAction option; grouping would change the JSON shape.
-#[allow(clippy::struct_excessive_bools)]
+    /// Text shown in place of the body when the user has disabled previews
+    /// on iOS/macOS, or on the lock screen on Android. `%u` stands for the
+    /// number of notifications, `%%` for a percent sign.
+    #[must_use]
+    pub fn hidden_previews_body_placeholder(&self) -> Option<&str> {
+        self.hidden_previews_body_placeholder.as_deref()
+    }
+
+    /// Whether the title stays visible when previews are hidden.
+    #[must_use]
+    pub const fn hidden_previews_show_title(&self) -> bool {
+        self.hidden_previews_show_title
+    }
+
+    /// Whether the subtitle stays visible when previews are hidden
+    /// (iOS/macOS).
+    #[must_use]
+    pub const fn hidden_previews_show_subtitle(&self) -> bool {
+        self.hidden_previews_show_subtitle
+    }
+
+    /// Fails with [`Error::InvalidArgument`](crate::Error::InvalidArgument)
+    /// on the first action id that appears twice in this type, or on a
+    /// hidden-previews placeholder other than text with one `%u`. A
+    /// placeholder on a type without actions is logged.
+    pub fn validate(&self) -> crate::Result<()> {
+        let mut seen = HashSet::new();
+        for action in &self.actions {
+            if !seen.insert(action.id.as_str()) {
+                return Err(crate::Error::InvalidArgument(format!(
+                    "duplicate action id: {}",
+                    action.id
+                )));
+            }
+        }
+        if let Some(placeholder) = &self.hidden_previews_body_placeholder {
+            validate_placeholder(placeholder)?;
+            if self.actions.is_empty() {
+                crate::telemetry::warning!(
+                    "Action type {} has a hidden-previews placeholder but no actions",
+                    self.id
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks that `placeholder` holds at most one `%u` and no format specifier
+/// other than `%%`, as Apple formats it with the notification count.
+fn validate_placeholder(placeholder: &str) -> crate::Result<()> {
+    let mut counts = 0;
+    let mut chars = placeholder.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+        match chars.next() {
+            Some('%') => {}
+            Some('u') => counts += 1,
+            _ => {
+                return Err(crate::Error::InvalidArgument(format!(
+                    "hiddenPreviewsBodyPlaceholder may only use `%u` and `%%`: {placeholder}"
+                )));
+            }
+        }
+    }
+    if counts > 1 {
+        return Err(crate::Error::InvalidArgument(format!(
+            "hiddenPreviewsBodyPlaceholder may use `%u` once: {placeholder}"
+        )));
+    }
+    Ok(())
+}
+
+impl ActionTypeBuilder {
+    /// Appends `action`; buttons are shown in the order they are added.
+    #[must_use]
+    pub fn action(mut self, action: Action) -> Self {
+        self.0.actions.push(action);
+        self
+    }
+
+    /// Text shown instead of the body while previews are hidden, see
+    /// [`ActionType::hidden_previews_body_placeholder`].
+    #[must_use]
+    pub fn hidden_previews_body_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.0
+            .hidden_previews_body_placeholder
+            .replace(placeholder.into());
+        self
+    }
+
+    /// Reports dismissals of the notification as an action.
+    #[must_use]
+    pub const fn custom_dismiss_action(mut self, custom_dismiss_action: bool) -> Self {
+        self.0.custom_dismiss_action = custom_dismiss_action;
+        self
+    }
+
+    #[must_use]
+    pub const fn allow_in_car_play(mut self, allow_in_car_play: bool) -> Self {
+        self.0.allow_in_car_play = allow_in_car_play;
+        self
+    }
+
+    #[must_use]
+    pub const fn hidden_previews_show_title(mut self, show_title: bool) -> Self {
+        self.0.hidden_previews_show_title = show_title;
+        self
+    }
+
+    #[must_use]
+    pub const fn hidden_previews_show_subtitle(mut self, show_subtitle: bool) -> Self {
+        self.0.hidden_previews_show_subtitle = show_subtitle;
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> ActionType {
+        self.0
+    }
+}
+
+/// Runs [`ActionType::validate`] on every type, stopping at the first error.
+pub fn validate_action_types(types: &[ActionType]) -> crate::Result<()> {
+    types.iter().try_for_each(ActionType::validate)
+}
+
+/// What a change to the registered action types did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum ActionTypesUpdate {
+    /// The registered types already were these; the platform wasn't called.
+    Unchanged,
+    /// The types changed and are handed to the platform shortly, in one call
+    /// with the other changes made until then. If the platform rejects them,
+    /// the registered types go back to the ones it last took. iOS, macOS and
+    /// Android.
+    Scheduled,
+    /// The types changed and apply to the next notification shown. Windows
+    /// reads them when showing a toast, so there is nothing to hand over.
+    Applied,
+}
+
+// Each bool is an independent UNNotificationAction option; grouping would change the JSON shape.
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Action {
@@ -455,161 +1555,338 @@ impl Action {
     pub const fn foreground(&self) -> bool {
         self.foreground
     }
+
+    #[must_use]
+    pub const fn input(&self) -> bool {
+        self.input
+    }
+
+    #[must_use]
+    pub fn input_placeholder(&self) -> Option<&str> {
+        self.input_placeholder.as_deref()
+    }
+}
+
+/// How much a channel's notifications may interrupt, from `None` (never
+/// shown) to `High` (heads-up). Ordered from least to most intrusive, the
+/// order of the discriminants.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize_repr, Deserialize_repr,
+)]
+#[repr(u8)]
+pub enum Importance {
+    None = 0,
+    Min = 1,
+    Low = 2,
+    #[default]
+    Default = 3,
+    High = 4,
 }
 
-pub use android::*;
+/// How much of a notification shows on the lock screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
+#[repr(i8)]
+pub enum Visibility {
+    Secret = -1,
+    Private = 0,
+    Public = 1,
+}
+
+/// A notification channel.
+///
+/// The type exists on every platform so shared code can describe its
+/// channels once; only Android creates them. Settings that
+/// only Android applies (lights, vibration, lock screen visibility) are
+/// optional and read back with their Android defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Channel {
+    id: String,
+    name: String,
+    description: Option<String>,
+    sound: Option<String>,
+    lights: Option<bool>,
+    light_color: Option<String>,
+    vibration: Option<bool>,
+    importance: Option<Importance>,
+    visibility: Option<Visibility>,
+}
 
-mod android {
-    use serde::{Deserialize, Serialize};
-    use serde_repr::{Deserialize_repr, Serialize_repr};
+#[derive(Debug)]
+pub struct ChannelBuilder(Channel);
 
-    #[derive(Debug, Default, Clone, Copy, Serialize_repr, Deserialize_repr)]
-    #[repr(u8)]
-    pub enum Importance {
-        None = 0,
-        Min = 1,
-        Low = 2,
-        #[default]
-        Default = 3,
-        High = 4,
+impl Channel {
+    pub fn builder(id: impl Into<String>, name: impl Into<String>) -> ChannelBuilder {
+        ChannelBuilder(Self {
+            id: id.into(),
+            name: name.into(),
+            description: None,
+            sound: None,
+            lights: Some(false),
+            light_color: None,
+            vibration: Some(false),
+            importance: None,
+            visibility: None,
+        })
     }
 
-    #[derive(Debug, Clone, Copy, Serialize_repr, Deserialize_repr)]
-    #[repr(i8)]
-    pub enum Visibility {
-        Secret = -1,
-        Private = 0,
-        Public = 1,
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
     }
 
-    #[derive(Debug, Serialize, Deserialize)]
-    #[serde(rename_all = "camelCase")]
-    pub struct Channel {
-        id: String,
-        name: String,
-        description: Option<String>,
-        sound: Option<String>,
-        lights: Option<bool>,
-        light_color: Option<String>,
-        vibration: Option<bool>,
-        importance: Option<Importance>,
-        visibility: Option<Visibility>,
-    }
-
-    #[derive(Debug)]
-    pub struct ChannelBuilder(Channel);
-
-    impl Channel {
-        pub fn builder(id: impl Into<String>, name: impl Into<String>) -> ChannelBuilder {
-            ChannelBuilder(Self {
-                id: id.into(),
-                name: name.into(),
-                description: None,
-                sound: None,
-                lights: Some(false),
-                light_color: None,
-                vibration: Some(false),
-                importance: None,
-                visibility: None,
-            })
-        }
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 
-        #[must_use]
-        pub fn id(&self) -> &str {
-            &self.id
-        }
+    #[must_use]
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
 
-        #[must_use]
-        pub fn name(&self) -> &str {
-            &self.name
-        }
+    #[must_use]
+    pub fn sound(&self) -> Option<&str> {
+        self.sound.as_deref()
+    }
 
-        #[must_use]
-        pub fn description(&self) -> Option<&str> {
-            self.description.as_deref()
-        }
+    /// Whether the notification light blinks. Android only.
+    #[must_use]
+    pub fn lights(&self) -> bool {
+        self.lights.unwrap_or(false)
+    }
 
-        #[must_use]
-        pub fn sound(&self) -> Option<&str> {
-            self.sound.as_deref()
-        }
+    /// Color of the notification light. Android only.
+    #[must_use]
+    pub fn light_color(&self) -> Option<&str> {
+        self.light_color.as_deref()
+    }
 
-        #[must_use]
-        pub fn lights(&self) -> bool {
-            self.lights.unwrap_or(false)
-        }
+    /// Whether notifications vibrate. Android only.
+    #[must_use]
+    pub fn vibration(&self) -> bool {
+        self.vibration.unwrap_or(false)
+    }
 
-        #[must_use]
-        pub fn light_color(&self) -> Option<&str> {
-            self.light_color.as_deref()
-        }
+    #[must_use]
+    pub fn importance(&self) -> Importance {
+        self.importance.unwrap_or_default()
+    }
 
-        #[must_use]
-        pub fn vibration(&self) -> bool {
-            self.vibration.unwrap_or(false)
-        }
+    /// Lock screen visibility, `None` if left to the system. Android only.
+    #[must_use]
+    pub const fn visibility(&self) -> Option<Visibility> {
+        self.visibility
+    }
+}
 
-        #[must_use]
-        pub fn importance(&self) -> Importance {
-            self.importance.unwrap_or_default()
-        }
+impl ChannelBuilder {
+    #[must_use]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.0.description.replace(description.into());
+        self
+    }
 
-        #[must_use]
-        pub const fn visibility(&self) -> Option<Visibility> {
-            self.visibility
-        }
+    #[must_use]
+    pub fn sound(mut self, sound: impl Into<String>) -> Self {
+        self.0.sound.replace(sound.into());
+        self
     }
 
-    impl ChannelBuilder {
-        #[must_use]
-        pub fn description(mut self, description: impl Into<String>) -> Self {
-            self.0.description.replace(description.into());
-            self
-        }
+    #[must_use]
+    pub const fn lights(mut self, lights: bool) -> Self {
+        self.0.lights = Some(lights);
+        self
+    }
 
-        #[must_use]
-        pub fn sound(mut self, sound: impl Into<String>) -> Self {
-            self.0.sound.replace(sound.into());
-            self
-        }
+    #[must_use]
+    pub fn light_color(mut self, color: impl Into<String>) -> Self {
+        self.0.light_color.replace(color.into());
+        self
+    }
 
-        #[must_use]
-        pub const fn lights(mut self, lights: bool) -> Self {
-            self.0.lights = Some(lights);
-            self
-        }
+    #[must_use]
+    pub const fn vibration(mut self, vibration: bool) -> Self {
+        self.0.vibration = Some(vibration);
+        self
+    }
 
-        #[must_use]
-        pub fn light_color(mut self, color: impl Into<String>) -> Self {
-            self.0.light_color.replace(color.into());
-            self
-        }
+    #[must_use]
+    pub const fn importance(mut self, importance: Importance) -> Self {
+        self.0.importance = Some(importance);
+        self
+    }
 
-        #[must_use]
-        pub const fn vibration(mut self, vibration: bool) -> Self {
-            self.0.vibration = Some(vibration);
-            self
-        }
+    #[must_use]
+    pub const fn visibility(mut self, visibility: Visibility) -> Self {
+        self.0.visibility.replace(visibility);
+        self
+    }
 
-        #[must_use]
-        pub const fn importance(mut self, importance: Importance) -> Self {
-            self.0.importance = Some(importance);
-            self
-        }
+    #[must_use]
+    pub fn build(self) -> Channel {
+        self.0
+    }
+}
 
-        #[must_use]
-        pub const fn visibility(mut self, visibility: Visibility) -> Self {
-            self.0.visibility.replace(visibility);
-            self
-        }
+/// Starts from an existing channel, e.g. one returned by
+/// [`Notifications::get_channel`](crate::Notifications::get_channel), to
+/// change some of its settings.
+impl From<Channel> for ChannelBuilder {
+    fn from(channel: Channel) -> Self {
+        Self(channel)
+    }
+}
+
+/// Parses a channel in the JS `Channel` format, e.g. one stored in a
+/// config file.
+impl TryFrom<serde_json::Value> for Channel {
+    type Error = crate::Error;
+
+    fn try_from(value: serde_json::Value) -> crate::Result<Self> {
+        serde_json::from_value(value)
+            .map_err(|e| crate::Error::InvalidArgument(format!("invalid channel: {e}")))
+    }
+}
+
+/// Parses a channel like [`Channel`]'s `TryFrom`, to adjust it further.
+impl TryFrom<serde_json::Value> for ChannelBuilder {
+    type Error = crate::Error;
+
+    fn try_from(value: serde_json::Value) -> crate::Result<Self> {
+        Channel::try_from(value).map(Self)
+    }
+}
 
-        #[must_use]
-        pub fn build(self) -> Channel {
-            self.0
+/// What [`Notifications::create_channel`](crate::Notifications::create_channel)
+/// and [`Notifications::ensure_channels`](crate::Notifications::ensure_channels)
+/// did with a requested channel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "status",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum ChannelCreation {
+    /// No channel had its id; it was created.
+    Created,
+    /// A channel with its id and the same settings already existed.
+    Unchanged,
+    /// A channel with its id already existed with other settings, named
+    /// in `differing_fields` (`name`, `importance`, ...). It was left as
+    /// it is: most channel settings belong to the user once created.
+    ExistsWithDifferentSettings { differing_fields: Vec<String> },
+}
+
+impl ChannelCreation {
+    /// Compares `requested` with the `existing` channel of the same id,
+    /// as the platform reports it. Settings the platform doesn't report
+    /// (the light color) are not compared.
+    #[must_use]
+    pub fn compare(requested: &Channel, existing: Option<&Channel>) -> Self {
+        let Some(existing) = existing else {
+            return Self::Created;
+        };
+        let mut differing_fields = Vec::new();
+        let mut check = |field: &str, same: bool| {
+            if !same {
+                differing_fields.push(field.to_string());
+            }
+        };
+        check("name", requested.name == existing.name);
+        check(
+            "description",
+            requested.description() == existing.description(),
+        );
+        check("sound", same_sound(requested.sound(), existing.sound()));
+        check("lights", requested.lights() == existing.lights());
+        check(
+            "lightColor",
+            requested.light_color().is_none_or(|color| {
+                existing
+                    .light_color()
+                    .is_none_or(|existing| existing.eq_ignore_ascii_case(color))
+            }),
+        );
+        check("vibration", requested.vibration() == existing.vibration());
+        check(
+            "importance",
+            requested.importance() == existing.importance(),
+        );
+        // Channels are created `Private` unless asked otherwise.
+        check(
+            "visibility",
+            existing.visibility.is_none_or(|visibility| {
+                visibility == requested.visibility.unwrap_or(Visibility::Private)
+            }),
+        );
+        if differing_fields.is_empty() {
+            Self::Unchanged
+        } else {
+            Self::ExistsWithDifferentSettings { differing_fields }
         }
     }
 }
 
+/// Android reports a channel's sound as a URI: `android.resource://<package>/raw/<name>`
+/// for the sounds the plugin sets, the system default otherwise.
+fn same_sound(requested: Option<&str>, existing: Option<&str>) -> bool {
+    let existing = existing
+        .and_then(|uri| uri.rsplit_once("/raw/"))
+        .map(|(_, name)| name);
+    let requested = requested
+        .filter(|sound| !sound.is_empty())
+        .map(|sound| sound.rsplit_once('.').map_or(sound, |(stem, _)| stem));
+    requested == existing
+}
+
+/// The outcome of creating each of `requested` when `existing` channels
+/// are already there. A channel requested twice is compared against
+/// its first request the second time.
+#[allow(clippy::redundant_pub_crate)]
+#[cfg_attr(not(mobile), allow(dead_code))]
+pub(crate) fn plan_channels(requested: &[Channel], existing: &[Channel]) -> Vec<ChannelCreation> {
+    requested
+        .iter()
+        .enumerate()
+        .map(|(index, channel)| {
+            let found = existing
+                .iter()
+                .chain(&requested[..index])
+                .find(|other| other.id == channel.id);
+            ChannelCreation::compare(channel, found)
+        })
+        .collect()
+}
+
+/// What [`Notifications::delete_channel`](crate::Notifications::delete_channel)
+/// does with the pending notifications that target the deleted channel.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "policy",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum OnPendingPolicy {
+    /// Keep them scheduled. They are not delivered once the channel is
+    /// gone.
+    #[default]
+    Leave,
+    /// Cancel them.
+    Cancel,
+    /// Move them to `to_channel` and schedule them again.
+    Reassign { to_channel: String },
+}
+
+/// Pending notifications [`Notifications::delete_channel`](crate::Notifications::delete_channel)
+/// canceled or reassigned, by id.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelDeletion {
+    pub canceled: Vec<i32>,
+    pub reassigned: Vec<i32>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -640,6 +1917,316 @@ mod tests {
         assert_eq!(attachment.url.as_str(), "https://example.com/image.png");
     }
 
+    #[test]
+    fn test_attachment_type_hint() {
+        let url = Url::parse("file:///tmp/clip.mp4").expect("Failed to parse URL");
+        let attachment = Attachment::new("clip", url).with_type_hint(AttachmentType::Video);
+        let json = serde_json::to_value(&attachment).expect("Failed to serialize attachment");
+        assert_eq!(json["typeHint"], "video");
+
+        let attachment: Attachment = serde_json::from_value(serde_json::json!({
+            "id": "song",
+            "url": "file:///tmp/song.m4a",
+            "typeHint": "audio",
+        }))
+        .expect("Failed to deserialize attachment");
+        assert_eq!(attachment.type_hint(), Some(AttachmentType::Audio));
+
+        let url = Url::parse("file:///tmp/photo.png").expect("Failed to parse URL");
+        let json = serde_json::to_value(Attachment::new("photo", url))
+            .expect("Failed to serialize attachment");
+        assert!(json.get("typeHint").is_none());
+    }
+
+    #[test]
+    fn test_media_controls_serialization() {
+        let controls = MediaControls {
+            actions: vec![MediaAction::Previous, MediaAction::Pause, MediaAction::Next],
+            show_in_compact: vec![1],
+        };
+        let json = serde_json::to_value(&controls).expect("Failed to serialize media controls");
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "actions": ["media:previous", "media:pause", "media:next"],
+                "showInCompact": [1],
+            })
+        );
+        for action in &controls.actions {
+            assert_eq!(
+                serde_json::to_value(action).expect("Failed to serialize media action"),
+                action.action_id()
+            );
+        }
+
+        let data = NotificationData::default();
+        let json = serde_json::to_value(&data).expect("Failed to serialize notification");
+        assert!(json.get("mediaControls").is_none());
+    }
+
+    #[test]
+    fn test_media_controls_validation() {
+        let controls = |actions: Vec<MediaAction>, compact: Vec<usize>| MediaControls {
+            actions,
+            show_in_compact: compact,
+        };
+        assert!(
+            controls(vec![MediaAction::Play], vec![0])
+                .validate()
+                .is_ok()
+        );
+        assert!(controls(vec![], vec![]).validate().is_err());
+        assert!(
+            controls(vec![MediaAction::Play, MediaAction::Next], vec![2])
+                .validate()
+                .is_err_and(|e| e.to_string().contains("index 2"))
+        );
+        let all = vec![
+            MediaAction::Previous,
+            MediaAction::Play,
+            MediaAction::Pause,
+            MediaAction::Next,
+        ];
+        assert!(controls(all.clone(), vec![0, 1, 2]).validate().is_ok());
+        assert!(controls(all, vec![0, 1, 2, 3]).validate().is_err());
+    }
+
+    #[test]
+    fn test_count_down_to_uses_js_date_format() {
+        let deadline = time::OffsetDateTime::from_unix_timestamp_nanos(1_900_000_000_123_456_789)
+            .expect("valid timestamp")
+            .to_offset(time::UtcOffset::from_hms(2, 0, 0).expect("valid offset"));
+        let data = NotificationData {
+            count_down_to: Some(deadline),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&data).expect("Failed to serialize notification");
+        assert_eq!(json["countDownTo"], "2030-03-17T17:46:40.123Z");
+
+        let parsed: NotificationData = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "countDownTo": "2030-03-17T17:46:40.123Z",
+        }))
+        .expect("Failed to deserialize notification");
+        assert_eq!(
+            parsed
+                .count_down_to
+                .map(time::OffsetDateTime::unix_timestamp),
+            Some(1_900_000_000)
+        );
+
+        let json = serde_json::to_value(NotificationData::default())
+            .expect("Failed to serialize notification");
+        assert!(json.get("countDownTo").is_none());
+    }
+
+    #[test]
+    fn test_persistence_flags_stay_on_desktop() {
+        let parsed: NotificationData = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "persistent": true,
+            "transient": false,
+            "requireInteraction": true,
+        }))
+        .expect("Failed to deserialize notification");
+        assert!(parsed.persistent);
+        assert!(!parsed.transient);
+        assert!(parsed.require_interaction);
+
+        let json = serde_json::to_value(&parsed).expect("Failed to serialize notification");
+        assert!(json.get("persistent").is_none());
+        assert!(json.get("transient").is_none());
+        assert!(json.get("requireInteraction").is_none());
+    }
+
+    #[test]
+    fn test_accessibility_fields_serialization() {
+        let data = NotificationData {
+            ticker: Some("New message from Alice".to_string()),
+            announce_only: true,
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&data).expect("Failed to serialize notification");
+        assert_eq!(json["ticker"], "New message from Alice");
+        assert_eq!(json["announceOnly"], true);
+
+        let json = serde_json::to_value(NotificationData::default())
+            .expect("Failed to serialize notification");
+        assert!(json.get("ticker").is_none());
+        assert_eq!(json["announceOnly"], false);
+
+        let parsed: NotificationData = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "ticker": "Download finished",
+            "announceOnly": true,
+        }))
+        .expect("Failed to deserialize notification");
+        assert_eq!(parsed.ticker.as_deref(), Some("Download finished"));
+        assert!(parsed.announce_only);
+
+        let parsed: NotificationData = serde_json::from_value(serde_json::json!({ "id": 1 }))
+            .expect("Failed to deserialize notification");
+        assert!(parsed.ticker.is_none());
+        assert!(!parsed.announce_only);
+    }
+
+    #[test]
+    fn test_show_only_once_stays_on_the_rust_side() {
+        let parsed: NotificationData = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "showOnlyOnce": true,
+        }))
+        .expect("Failed to deserialize notification");
+        assert!(parsed.show_only_once);
+        let json = serde_json::to_value(&parsed).expect("Failed to serialize notification");
+        assert!(json.get("showOnlyOnce").is_none());
+    }
+
+    #[test]
+    fn test_wearable_fields_serialization() {
+        let data = NotificationData {
+            local_only: true,
+            wearable_actions: vec![Action::new("reply", "Reply", false)],
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&data).expect("Failed to serialize notification");
+        assert_eq!(json["localOnly"], true);
+        assert_eq!(json["wearableActions"][0]["id"], "reply");
+        assert_eq!(json["wearableActions"][0]["title"], "Reply");
+
+        let json = serde_json::to_value(NotificationData::default())
+            .expect("Failed to serialize notification");
+        assert_eq!(json["localOnly"], false);
+        assert!(json.get("wearableActions").is_none());
+
+        let parsed: NotificationData = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "localOnly": true,
+            "wearableActions": [{ "id": "reply", "title": "Reply", "input": true }],
+        }))
+        .expect("Failed to deserialize notification");
+        assert!(parsed.local_only);
+        assert_eq!(parsed.wearable_actions.len(), 1);
+        assert_eq!(parsed.wearable_actions[0].id(), "reply");
+        assert!(parsed.wearable_actions[0].input());
+
+        let parsed: NotificationData = serde_json::from_value(serde_json::json!({ "id": 1 }))
+            .expect("Failed to deserialize notification");
+        assert!(!parsed.local_only);
+        assert!(parsed.wearable_actions.is_empty());
+    }
+
+    /// Payload `register_action_types` hands to the Apple bridges. The Swift
+    /// test suites decode this exact string, so keep them in sync.
+    const APPLE_ACTION_TYPES_PAYLOAD: &str = r#"{"types":[{"id":"secret","actions":[],"hiddenPreviewsBodyPlaceholder":"Hidden message","customDismissAction":false,"allowInCarPlay":false,"hiddenPreviewsShowTitle":false,"hiddenPreviewsShowSubtitle":false}]}"#;
+
+    #[test]
+    fn test_action_type_hidden_previews_placeholder_payload() {
+        let action_type: ActionType = serde_json::from_value(serde_json::json!({
+            "id": "secret",
+            "actions": [],
+            "hiddenPreviewsBodyPlaceholder": "Hidden message",
+        }))
+        .expect("Failed to deserialize action type");
+
+        let mut args = HashMap::new();
+        args.insert("types", vec![action_type]);
+        let json = serde_json::to_string(&args).expect("Failed to serialize action types");
+        assert_eq!(json, APPLE_ACTION_TYPES_PAYLOAD);
+    }
+
+    #[test]
+    fn test_action_type_hidden_previews_placeholder_roundtrip() {
+        let args: HashMap<String, Vec<ActionType>> =
+            serde_json::from_str(APPLE_ACTION_TYPES_PAYLOAD)
+                .expect("Failed to deserialize action types payload");
+        let action_type = &args["types"][0];
+        assert_eq!(action_type.id(), "secret");
+        assert_eq!(
+            action_type.hidden_previews_body_placeholder(),
+            Some("Hidden message")
+        );
+    }
+
+    #[test]
+    fn test_action_type_hidden_previews_placeholder_format() {
+        let with_placeholder = |placeholder: &str| {
+            ActionType::builder("message")
+                .action(Action::new("reply", "Reply", false))
+                .hidden_previews_body_placeholder(placeholder)
+                .build()
+        };
+        assert!(with_placeholder("%u new messages").validate().is_ok());
+        assert!(with_placeholder("100%% private").validate().is_ok());
+        // Logged, not rejected.
+        assert!(
+            ActionType::builder("quiet")
+                .hidden_previews_body_placeholder("Hidden")
+                .build()
+                .validate()
+                .is_ok()
+        );
+
+        let err = with_placeholder("%@ from %u")
+            .validate()
+            .expect_err("only %u is formatted");
+        assert_eq!(
+            err.to_string(),
+            "hiddenPreviewsBodyPlaceholder may only use `%u` and `%%`: %@ from %u"
+        );
+        assert!(with_placeholder("%u of %u").validate().is_err());
+        assert!(with_placeholder("trailing %").validate().is_err());
+
+        let action_type = with_placeholder("%u new");
+        assert!(!action_type.hidden_previews_show_title());
+        assert!(!action_type.hidden_previews_show_subtitle());
+    }
+
+    #[test]
+    fn test_action_type_duplicate_action_id() {
+        let action_type = ActionType::new(
+            "reply",
+            vec![
+                Action::new("open", "Open", true),
+                Action::new("open", "Open again", false),
+            ],
+        );
+        let err = action_type
+            .validate()
+            .expect_err("duplicate id must be rejected");
+        assert!(matches!(err, crate::Error::InvalidArgument(_)));
+        assert_eq!(err.to_string(), "duplicate action id: open");
+
+        let err = validate_action_types(&[ActionType::new("empty", vec![]), action_type])
+            .expect_err("duplicate id must be rejected");
+        assert_eq!(err.to_string(), "duplicate action id: open");
+    }
+
+    #[test]
+    fn test_action_type_unique_action_ids() {
+        let action_type = ActionType::new(
+            "reply",
+            vec![
+                Action::new("open", "Open", true),
+                Action::new("dismiss", "Dismiss", false),
+            ],
+        );
+        assert!(action_type.validate().is_ok());
+        // The same id in different types is fine.
+        assert!(validate_action_types(&[action_type.clone(), action_type]).is_ok());
+    }
+
+    #[test]
+    fn test_schedule_capabilities() {
+        let capabilities = ScheduleCapabilities::current();
+        assert!(capabilities.scheduling || !capabilities.delivered_after_sleep);
+        assert!(capabilities.scheduling || !capabilities.allow_while_idle);
+
+        let json = serde_json::to_value(capabilities).expect("Failed to serialize");
+        assert!(json.get("allowWhileIdle").is_some());
+        assert!(json.get("deliveredAfterSleep").is_some());
+    }
+
     #[test]
     fn test_schedule_every_display() {
         assert_eq!(ScheduleEvery::Year.to_string(), "year");
@@ -732,6 +2319,17 @@ mod tests {
         assert!(json.contains("\"day\":25"));
     }
 
+    #[test]
+    fn test_schedule_interval_rejects_unknown_fields() {
+        let interval: ScheduleInterval =
+            serde_json::from_str(r#"{"hour": 9, "minute": 30}"#).expect("Failed to deserialize");
+        assert_eq!((interval.hour, interval.minute), (Some(9), Some(30)));
+
+        let err = serde_json::from_str::<ScheduleInterval>(r#"{"hours": 1}"#)
+            .expect_err("Typo should be rejected");
+        assert!(err.to_string().contains("unknown field `hours`"));
+    }
+
     #[test]
     fn test_notification_data_default() {
         let data = NotificationData::default();
@@ -744,28 +2342,247 @@ mod tests {
         assert!(!data.ongoing);
         assert!(!data.auto_cancel);
         assert!(!data.silent);
+        assert!(!data.announce_only);
+        assert!(!data.local_only);
+        assert!(data.wearable_actions.is_empty());
+        assert!(data.ticker.is_none());
+        assert!(!data.persistent);
+        assert!(!data.transient);
+        assert!(!data.require_interaction);
         assert!(data.inbox_lines.is_empty());
         assert!(data.attachments.is_empty());
         assert!(data.extra.is_empty());
     }
 
+    #[test]
+    fn test_action_type_builder() {
+        let action_type = ActionType::builder("message")
+            .action(Action::new("reply", "Reply", false))
+            .action(Action::new("open", "Open", true))
+            .hidden_previews_body_placeholder("New message")
+            .custom_dismiss_action(true)
+            .hidden_previews_show_title(true)
+            .build();
+        assert_eq!(action_type.id(), "message");
+        assert_eq!(action_type.actions()[1].id(), "open");
+
+        let json = serde_json::to_value(&action_type).expect("Failed to serialize action type");
+        assert_eq!(json["hiddenPreviewsBodyPlaceholder"], "New message");
+        assert_eq!(json["customDismissAction"], true);
+        assert_eq!(json["allowInCarPlay"], false);
+        assert_eq!(json["hiddenPreviewsShowTitle"], true);
+        assert_eq!(json["hiddenPreviewsShowSubtitle"], false);
+    }
+
+    #[test]
+    fn test_fill_title() {
+        let mut data = NotificationData {
+            body: Some("Body".to_string()),
+            ..Default::default()
+        };
+        data.fill_title(Some("My App"), true).expect("has a body");
+        assert_eq!(data.title.as_deref(), Some("My App"));
+
+        let mut titled = NotificationData {
+            title: Some("Title".to_string()),
+            ..Default::default()
+        };
+        titled
+            .fill_title(Some("My App"), true)
+            .expect("has a title");
+        assert_eq!(titled.title.as_deref(), Some("Title"));
+
+        let mut empty = NotificationData::default();
+        assert!(empty.clone().fill_title(Some("My App"), true).is_err());
+        empty.fill_title(Some("My App"), false).expect("lenient");
+        assert_eq!(empty.title.as_deref(), Some("My App"));
+
+        let mut announcement = NotificationData {
+            body: Some("Upload finished".to_string()),
+            announce_only: true,
+            ..Default::default()
+        };
+        announcement
+            .fill_title(Some("My App"), false)
+            .expect("has a body");
+        assert_eq!(announcement.title, None);
+    }
+
+    #[test]
+    fn test_resolve_ongoing_auto_cancel() {
+        let mut data = NotificationData {
+            ongoing: true,
+            auto_cancel: true,
+            ..Default::default()
+        };
+        assert!(data.clone().resolve_ongoing_auto_cancel(true).is_err());
+        assert!(data.resolve_ongoing_auto_cancel(false).expect("lenient"));
+        assert!(data.ongoing);
+        assert!(!data.auto_cancel);
+
+        let mut data = NotificationData {
+            auto_cancel: true,
+            ..Default::default()
+        };
+        assert!(!data.resolve_ongoing_auto_cancel(true).expect("no conflict"));
+        assert!(data.auto_cancel);
+    }
+
     #[test]
     fn test_notification_data_serialization() {
         let data = NotificationData {
             id: 123,
             title: Some("Test Title".to_string()),
             body: Some("Test Body".to_string()),
+            summary: Some("Test Summary".to_string()),
             ongoing: true,
             ..Default::default()
         };
 
         let json = serde_json::to_string(&data).expect("Failed to serialize notification data");
         assert!(json.contains("\"id\":123"));
+        assert!(json.contains("\"summary\":\"Test Summary\""));
         assert!(json.contains("\"title\":\"Test Title\""));
         assert!(json.contains("\"body\":\"Test Body\""));
         assert!(json.contains("\"ongoing\":true"));
     }
 
+    fn extra_roundtrip(extra: serde_json::Value) -> HashMap<String, serde_json::Value> {
+        let data = NotificationData {
+            extra: serde_json::from_value(extra).expect("Failed to build extra"),
+            ..Default::default()
+        };
+        let wire = serde_json::to_value(&data).expect("Failed to serialize notification data");
+        assert!(wire.get("extra").is_none());
+        let encoded = wire["extraJson"]
+            .as_str()
+            .expect("extra should be sent as a JSON string");
+
+        // What the native side echoes back, e.g. in `ActiveNotification`.
+        let active: ActiveNotification = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "extraJson": encoded,
+        }))
+        .expect("Failed to deserialize active notification");
+        let back: NotificationData =
+            serde_json::from_value(wire).expect("Failed to deserialize notification data");
+        assert_eq!(&back.extra, active.extra());
+        back.extra
+    }
+
+    #[test]
+    fn test_validate_icon_color() {
+        for valid in ["#f00", "#FF0000", "#80ff0000", "#AbCdEf"] {
+            assert!(validate_icon_color(valid), "{valid}");
+        }
+        for invalid in [
+            "notacolor",
+            "FF0000",
+            "#ff00",
+            "#gg0000",
+            "#",
+            "red",
+            "#ff0000ff0",
+        ] {
+            assert!(!validate_icon_color(invalid), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_icon_color_deserialization() {
+        let data: NotificationData = serde_json::from_value(serde_json::json!({
+            "iconColor": "#f80",
+        }))
+        .expect("Failed to deserialize notification data");
+        assert_eq!(data.icon_color.as_deref(), Some("#ff8800"));
+
+        let data: NotificationData = serde_json::from_value(serde_json::json!({}))
+            .expect("Failed to deserialize notification data");
+        assert!(data.icon_color.is_none());
+
+        let result: Result<NotificationData, _> =
+            serde_json::from_value(serde_json::json!({ "iconColor": "notacolor" }));
+        assert!(
+            result
+                .expect_err("invalid color should be rejected")
+                .to_string()
+                .contains("notacolor")
+        );
+    }
+
+    #[test]
+    fn test_extra_roundtrip_nested() {
+        let extra = serde_json::json!({
+            "user": {"id": 7, "roles": ["admin", "dev"], "profile": {"active": true}},
+            "items": [1, [2, 3], {"four": null}],
+        });
+        let back = extra_roundtrip(extra.clone());
+        assert_eq!(back["user"], extra["user"]);
+        assert_eq!(back["items"], extra["items"]);
+    }
+
+    #[test]
+    fn test_extra_roundtrip_unicode() {
+        let back = extra_roundtrip(serde_json::json!({
+            "greeting": "Grüße, 世界 👋",
+            "quote": "\"line\nbreak\"",
+        }));
+        assert_eq!(back["greeting"], "Grüße, 世界 👋");
+        assert_eq!(back["quote"], "\"line\nbreak\"");
+    }
+
+    #[test]
+    fn test_extra_roundtrip_unsafe_integers_become_strings() {
+        let back = extra_roundtrip(serde_json::json!({
+            "safe": 9_007_199_254_740_991_u64,
+            "big": 9_007_199_254_740_993_u64,
+            "negative": -9_007_199_254_740_993_i64,
+            "nested": [u64::MAX],
+            "float": 1.5,
+        }));
+        assert_eq!(back["safe"], 9_007_199_254_740_991_u64);
+        assert_eq!(back["big"], "9007199254740993");
+        assert_eq!(back["negative"], "-9007199254740993");
+        assert_eq!(back["nested"][0], "18446744073709551615");
+        assert_eq!(back["float"], 1.5);
+    }
+
+    #[test]
+    fn test_extra_accepts_object_and_empty() {
+        let data: NotificationData = serde_json::from_str(r#"{"id": 1, "extra": {"a": {"b": 1}}}"#)
+            .expect("Failed to deserialize object extra");
+        assert_eq!(data.extra["a"]["b"], 1);
+
+        let data: NotificationData = serde_json::from_str(r#"{"id": 1, "extra": null}"#)
+            .expect("Failed to deserialize null extra");
+        assert!(data.extra.is_empty());
+        let wire = serde_json::to_value(&data).expect("Failed to serialize");
+        assert!(wire.get("extraJson").is_none());
+
+        assert!(serde_json::from_str::<NotificationData>(r#"{"id": 1, "extra": 5}"#).is_err());
+    }
+
+    #[test]
+    fn test_extra_size_limit() {
+        let mut data = NotificationData::default();
+        assert!(data.check_extra_size(0).is_ok());
+
+        data.extra
+            .insert("blob".to_string(), serde_json::json!("x".repeat(100)));
+        // {"blob":"xxx…"} is 111 bytes.
+        assert!(data.check_extra_size(111).is_ok());
+        let err = data
+            .check_extra_size(110)
+            .expect_err("extra over the limit should be rejected");
+        assert!(matches!(
+            err,
+            crate::Error::ExtraTooLarge {
+                size: 111,
+                limit: 110
+            }
+        ));
+    }
+
     #[test]
     fn test_pending_notification_getters() {
         let json = r#"{
@@ -784,6 +2601,311 @@ mod tests {
     }
 
     #[test]
+    fn test_pending_notification_old_shape_roundtrip() {
+        // Payload as produced by the macOS/iOS/Android plugins, without tag/group.
+        let json = r#"{
+            "id": 7,
+            "title": "Old",
+            "schedule": {"at": {"date": "2030-01-01T00:00:00Z"}}
+        }"#;
+        let pending: PendingNotification =
+            serde_json::from_str(json).expect("Failed to deserialize pending notification");
+        assert_eq!(pending.tag(), None);
+        assert_eq!(pending.group(), None);
+
+        let value = serde_json::to_value(&pending).expect("Failed to serialize");
+        let back: PendingNotification =
+            serde_json::from_value(value).expect("Failed to deserialize roundtrip");
+        assert_eq!(back.id(), 7);
+        assert_eq!(back.title(), Some("Old"));
+        assert_eq!(back.tag(), None);
+        assert!(matches!(back.schedule(), Schedule::At { .. }));
+    }
+
+    #[test]
+    fn test_pending_notification_tag_group_roundtrip() {
+        let json = r#"{
+            "id": 8,
+            "schedule": {"at": {"date": "2030-01-01T00:00:00Z"}},
+            "tag": "8",
+            "group": "reminders"
+        }"#;
+        let pending: PendingNotification =
+            serde_json::from_str(json).expect("Failed to deserialize pending notification");
+        let value = serde_json::to_value(&pending).expect("Failed to serialize");
+        assert_eq!(value["tag"], "8");
+        assert_eq!(value["group"], "reminders");
+
+        let back: PendingNotification =
+            serde_json::from_value(value).expect("Failed to deserialize roundtrip");
+        assert_eq!(back.tag(), Some("8"));
+        assert_eq!(back.group(), Some("reminders"));
+    }
+
+    #[test]
+    fn test_suppress_if_focused_deserialization() {
+        let parse = |value: serde_json::Value| {
+            serde_json::from_value::<NotificationData>(serde_json::json!({
+                "suppressIfFocused": value
+            }))
+            .expect("valid options")
+            .suppress_if_focused
+        };
+        assert_eq!(
+            parse(serde_json::json!("chat")),
+            Some(crate::suppress::FocusTarget::Window("chat".to_string()))
+        );
+        assert_eq!(
+            parse(serde_json::json!(true)),
+            Some(crate::suppress::FocusTarget::AnyWindow)
+        );
+        assert_eq!(parse(serde_json::json!(false)), None);
+        assert_eq!(parse(serde_json::Value::Null), None);
+    }
+
+    /// The `notify` command deserializes JS options while Rust callers start
+    /// from `NotificationData::default()`; both must get the same defaults.
+    #[test]
+    fn test_js_options_match_builder_defaults() {
+        let from_js: NotificationData =
+            serde_json::from_value(serde_json::json!({ "title": "Hi" })).expect("valid options");
+        let from_rust = NotificationData {
+            id: from_js.id,
+            title: Some("Hi".to_string()),
+            ..NotificationData::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&from_js).expect("serialize"),
+            serde_json::to_value(&from_rust).expect("serialize")
+        );
+        // Fields that are never serialized.
+        assert_eq!(from_js.foreground_color, from_rust.foreground_color);
+        assert_eq!(from_js.focus_window, from_rust.focus_window);
+        assert_eq!(from_js.suppress_if_focused, from_rust.suppress_if_focused);
+        assert_eq!(from_js.max_repetitions, from_rust.max_repetitions);
+        assert_eq!(from_js.show_only_once, from_rust.show_only_once);
+        assert!(from_js.quiet_hours_policy.is_none());
+        assert_eq!(
+            (
+                from_js.persistent,
+                from_js.transient,
+                from_js.require_interaction
+            ),
+            (
+                from_rust.persistent,
+                from_rust.transient,
+                from_rust.require_interaction
+            )
+        );
+
+        let with_id: NotificationData =
+            serde_json::from_value(serde_json::json!({ "id": 7 })).expect("valid options");
+        assert_eq!(with_id.id, 7);
+    }
+
+    fn with_inbox_lines(lines: &[&str]) -> NotificationData {
+        NotificationData {
+            inbox_lines: lines.iter().map(ToString::to_string).collect(),
+            ..NotificationData::default()
+        }
+    }
+
+    #[test]
+    fn test_limit_inbox_lines() {
+        let five = ["1", "2", "3", "4", "5"];
+        let mut data = with_inbox_lines(&five);
+        assert_eq!(data.limit_inbox_lines(true).expect("five lines"), 0);
+        assert_eq!(data.inbox_lines, five);
+
+        let six = ["1", "2", "3", "4", "5", "6"];
+        let mut data = with_inbox_lines(&six);
+        assert!(matches!(
+            data.limit_inbox_lines(true),
+            Err(crate::Error::InvalidArgument(_))
+        ));
+        assert_eq!(data.limit_inbox_lines(false).expect("six lines"), 1);
+        assert_eq!(data.inbox_lines, five);
+
+        // Empty lines don't count towards the limit.
+        let mut data = with_inbox_lines(&["1", "", "2", "  ", "3", "4", "5"]);
+        assert_eq!(data.limit_inbox_lines(true).expect("blank lines"), 0);
+        assert_eq!(data.inbox_lines, five);
+    }
+
+    #[test]
+    fn test_fold_inbox_lines() {
+        let mut data = with_inbox_lines(&["Alice: hi", "Bob: hey"]);
+        data.fold_inbox_lines();
+        assert_eq!(data.body.as_deref(), Some("Alice: hi\nBob: hey"));
+        assert!(data.inbox_lines.is_empty());
+
+        let mut data = with_inbox_lines(&["Alice: hi"]);
+        data.body = Some("2 new messages".to_string());
+        data.fold_inbox_lines();
+        assert_eq!(data.body.as_deref(), Some("2 new messages\nAlice: hi"));
+
+        let mut data = with_inbox_lines(&[]);
+        data.fold_inbox_lines();
+        assert_eq!(data.body, None);
+    }
+
+    #[test]
+    fn test_pending_notification_extra_roundtrip() {
+        let data = NotificationData {
+            id: 9,
+            title: Some("Order".to_string()),
+            action_type_id: Some("reply".to_string()),
+            sound: Some("chime.aiff".to_string()),
+            extra: HashMap::from([
+                ("orderId".to_string(), serde_json::json!(12)),
+                ("items".to_string(), serde_json::json!(["a", "b"])),
+            ]),
+            ..NotificationData::default()
+        };
+        // What `schedule()` hands the macOS plugin, which keeps `extraJson`
+        // and the sound in the request's `userInfo`...
+        let sent = serde_json::to_value(&data).expect("Failed to serialize");
+        // ...and what its `getPending` reports back from there.
+        let reported = serde_json::json!({
+            "id": 9,
+            "title": "Order",
+            "body": "",
+            "schedule": {"at": {"date": "2030-01-01T00:00:00Z"}},
+            "actionTypeId": sent["actionTypeId"],
+            "sound": sent["sound"],
+            "extraJson": sent["extraJson"],
+        });
+        let pending: PendingNotification =
+            serde_json::from_value(reported).expect("Failed to deserialize pending notification");
+        assert_eq!(pending.extra(), &data.extra);
+        assert_eq!(pending.action_type_id(), Some("reply"));
+        assert_eq!(pending.sound(), Some("chime.aiff"));
+
+        let back = pending.into_notification_data();
+        assert_eq!(back.extra, data.extra);
+        assert_eq!(back.action_type_id, data.action_type_id);
+    }
+
+    #[test]
+    fn test_pending_notification_into_notification_data() {
+        let json = r#"{
+            "id": 42,
+            "title": "Reminder",
+            "body": "Stand up",
+            "schedule": {"at": {"date": "2030-01-01T00:00:00Z"}},
+            "channelId": "alerts",
+            "tag": "42",
+            "group": "daily"
+        }"#;
+        let pending: PendingNotification =
+            serde_json::from_str(json).expect("Failed to deserialize pending notification");
+
+        let data = pending.into_notification_data();
+        assert_eq!(data.id, 42);
+        assert_eq!(data.title.as_deref(), Some("Reminder"));
+        assert_eq!(data.body.as_deref(), Some("Stand up"));
+        assert_eq!(data.channel_id.as_deref(), Some("alerts"));
+        assert_eq!(data.group.as_deref(), Some("daily"));
+        assert!(matches!(data.schedule, Some(Schedule::At { .. })));
+        assert!(data.icon.is_none());
+        assert!(data.attachments.is_empty());
+    }
+
+    #[test]
+    fn test_count_pending_by_channel() {
+        let json = r#"[
+            {"id": 1, "schedule": {"at": {"date": "2030-01-01T00:00:00Z"}}, "channelId": "news"},
+            {"id": 2, "schedule": {"at": {"date": "2030-01-01T00:00:00Z"}}, "channelId": "news"},
+            {"id": 3, "schedule": {"at": {"date": "2030-01-01T00:00:00Z"}}, "channelId": ""},
+            {"id": 4, "schedule": {"at": {"date": "2030-01-01T00:00:00Z"}}}
+        ]"#;
+        let pending: Vec<PendingNotification> =
+            serde_json::from_str(json).expect("Failed to deserialize pending notifications");
+        assert_eq!(pending[0].channel_id(), Some("news"));
+
+        let counts = count_pending_by_channel(&pending);
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts["news"], 2);
+        assert_eq!(counts[DEFAULT_CHANNEL_ID], 2);
+        assert!(count_pending_by_channel(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_dismiss_outcome_deserialization() {
+        let outcome: DismissOutcome =
+            serde_json::from_str(r#"{"wasPending": false, "wasActive": true}"#)
+                .expect("Failed to deserialize dismiss outcome");
+        assert_eq!(
+            outcome,
+            DismissOutcome {
+                was_pending: false,
+                was_active: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_active_patch_apply() {
+        let mut data = NotificationData {
+            title: Some("Uploading".to_string()),
+            body: Some("Hellp".to_string()),
+            extra: HashMap::from([("file".to_string(), serde_json::json!("a.png"))]),
+            ..NotificationData::default()
+        };
+        let patch = ActivePatch {
+            body: Some("Hello".to_string()),
+            progress: Some(1.5),
+            extra: Some(HashMap::from([(
+                "done".to_string(),
+                serde_json::json!(true),
+            )])),
+            ..ActivePatch::default()
+        };
+        assert!(!patch.is_progress_only());
+        patch.apply(&mut data);
+
+        assert_eq!(data.title.as_deref(), Some("Uploading"));
+        assert_eq!(data.body.as_deref(), Some("Hello"));
+        assert_eq!(data.progress, Some(1.0));
+        assert_eq!(data.extra.len(), 2);
+
+        let progress: ActivePatch = serde_json::from_value(serde_json::json!({ "progress": 0.5 }))
+            .expect("Failed to deserialize patch");
+        assert!(progress.is_progress_only());
+    }
+
+    #[test]
+    fn test_launch_source_deserialization() {
+        let parse = |json: &str| {
+            serde_json::from_str::<LaunchSource>(json).expect("Failed to deserialize launch source")
+        };
+        assert_eq!(parse(r#"{"type": "normal"}"#), LaunchSource::Normal);
+        assert_eq!(
+            parse(r#"{"type": "notificationTap", "id": 7}"#),
+            LaunchSource::NotificationTap { id: Some(7) }
+        );
+        assert_eq!(
+            parse(r#"{"type": "notificationAction", "id": 7, "actionId": "reply"}"#),
+            LaunchSource::NotificationAction {
+                id: Some(7),
+                action_id: "reply".to_string(),
+            }
+        );
+        assert_eq!(
+            parse(r#"{"type": "push"}"#),
+            LaunchSource::Push { message_id: None }
+        );
+        assert_eq!(
+            serde_json::to_value(LaunchSource::Push {
+                message_id: Some("0:1".to_string())
+            })
+            .expect("Failed to serialize launch source"),
+            serde_json::json!({ "type": "push", "messageId": "0:1" })
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
     fn test_active_notification_getters() {
         let json = r#"{
             "id": 789,
@@ -801,11 +2923,145 @@ mod tests {
         assert_eq!(active.group(), Some("test_group"));
         assert!(active.group_summary());
         assert!(active.data().is_empty());
+        assert!(active.merged_data().is_empty());
         assert!(active.extra().is_empty());
         assert!(active.attachments().is_empty());
         assert!(active.action_type_id().is_none());
         assert!(active.schedule().is_none());
         assert!(active.sound().is_none());
+        assert!(active.sort_key().is_none());
+        assert!(active.delivered_at().is_none());
+    }
+
+    #[test]
+    fn test_bulk_result() {
+        let result: BulkResult = [
+            (1, Ok(())),
+            (2, Err(crate::Error::NotFound("no toast 2".to_string()))),
+            (3, Ok(())),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(result.succeeded, [1, 3]);
+        assert_eq!(result.failed, [(2, "no toast 2".to_string())]);
+        assert!(!result.is_success());
+        assert_eq!(
+            serde_json::to_value(&result).expect("Failed to serialize bulk result"),
+            serde_json::json!({ "succeeded": [1, 3], "failed": [[2, "no toast 2"]] })
+        );
+
+        let failed = BulkResult::all(
+            vec![4, 5],
+            &Err(crate::Error::Io(std::io::Error::other("bridge down"))),
+        );
+        assert!(failed.succeeded.is_empty());
+        assert_eq!(
+            failed.failed,
+            [
+                (4, "bridge down".to_string()),
+                (5, "bridge down".to_string())
+            ]
+        );
+        assert!(BulkResult::all(vec![6], &Ok(())).is_success());
+    }
+
+    #[test]
+    fn test_merged_data_prefers_extra() {
+        let json = r#"{
+            "id": 1,
+            "data": { "conversation": "42", "kind": "push" },
+            "extra": { "kind": { "nested": true }, "count": 3 }
+        }"#;
+        let active: ActiveNotification =
+            serde_json::from_str(json).expect("Failed to deserialize active notification");
+
+        let merged = active.merged_data();
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged["conversation"], serde_json::json!("42"));
+        assert_eq!(merged["kind"], serde_json::json!({ "nested": true }));
+        assert_eq!(merged["count"], serde_json::json!(3));
+    }
+
+    fn active(
+        id: i32,
+        group: Option<&str>,
+        sort_key: Option<&str>,
+        at: Option<i64>,
+    ) -> ActiveNotification {
+        ActiveNotification {
+            group: group.map(str::to_string),
+            sort_key: sort_key.map(str::to_string),
+            delivered_at: at,
+            ..ActiveNotification::new(id, None, None)
+        }
+    }
+
+    #[test]
+    fn test_active_group_children_order() {
+        let groups = ActiveGroup::collect(vec![
+            active(1, Some("chat"), None, Some(100)),
+            active(2, Some("chat"), Some("b"), Some(200)),
+            active(3, Some("chat"), None, Some(300)),
+            active(4, Some("chat"), Some("a"), Some(50)),
+            active(5, Some("chat"), Some("b"), Some(400)),
+            active(6, Some("chat"), None, None),
+        ]);
+        assert_eq!(groups.len(), 1);
+        let ids: Vec<i32> = groups[0]
+            .children()
+            .iter()
+            .map(ActiveNotification::id)
+            .collect();
+        // Keyed first in key order, newest first on ties; then the rest
+        // newest first, unknown delivery times last.
+        assert_eq!(ids, [4, 5, 2, 3, 1, 6]);
+    }
+
+    #[test]
+    fn test_active_group_collect() {
+        let summary = ActiveNotification {
+            group_summary: true,
+            ..active(10, Some("mail"), None, Some(1))
+        };
+        let groups = ActiveGroup::collect(vec![
+            active(1, Some("mail"), None, Some(5)),
+            active(2, None, None, Some(6)),
+            summary,
+            active(3, Some("chat"), None, Some(7)),
+        ]);
+        let names: Vec<Option<&str>> = groups.iter().map(ActiveGroup::group).collect();
+        assert_eq!(names, [None, Some("chat"), Some("mail")]);
+        assert!(groups[0].summary().is_none());
+        assert_eq!(groups[0].children()[0].id(), 2);
+        assert_eq!(groups[2].summary().map(ActiveNotification::id), Some(10));
+        assert_eq!(groups[2].children().len(), 1);
+
+        let json = serde_json::to_value(&groups[2]).expect("Failed to serialize group");
+        assert_eq!(json["group"], "mail");
+        assert_eq!(json["summary"]["id"], 10);
+        assert_eq!(json["children"][0]["deliveredAt"], 5);
+    }
+
+    #[test]
+    fn test_sort_key_serialization() {
+        let data = NotificationData {
+            sort_key: Some("0001".to_string()),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&data).expect("Failed to serialize notification");
+        assert_eq!(json["sortKey"], "0001");
+        let json = serde_json::to_value(NotificationData::default())
+            .expect("Failed to serialize notification");
+        assert!(json.get("sortKey").is_none());
+
+        let active: ActiveNotification = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "sortKey": "0001",
+            "deliveredAt": 1_700_000_000_000_i64,
+        }))
+        .expect("Failed to deserialize active notification");
+        assert_eq!(active.sort_key(), Some("0001"));
+        assert_eq!(active.delivered_at(), Some(1_700_000_000_000));
     }
 
     #[cfg(target_os = "android")]
@@ -885,6 +3141,108 @@ mod tests {
         assert_eq!(channel.visibility(), Some(Visibility::Public));
     }
 
+    #[test]
+    fn test_channel_try_from_json() {
+        let channel = Channel::try_from(serde_json::json!({
+            "id": "downloads",
+            "name": "Downloads",
+            "importance": 4,
+            "visibility": 1,
+            "vibration": true,
+        }))
+        .expect("valid channel");
+        assert_eq!(channel.id(), "downloads");
+        assert_eq!(channel.importance(), Importance::High);
+        assert_eq!(channel.visibility(), Some(Visibility::Public));
+        assert!(channel.vibration() && !channel.lights());
+
+        let channel = ChannelBuilder::try_from(serde_json::json!({
+            "id": "downloads",
+            "name": "Downloads",
+        }))
+        .expect("valid channel")
+        .sound("chime")
+        .build();
+        assert_eq!(channel.sound(), Some("chime"));
+
+        let missing_name = Channel::try_from(serde_json::json!({ "id": "downloads" }));
+        assert!(matches!(
+            missing_name,
+            Err(crate::Error::InvalidArgument(_))
+        ));
+    }
+
+    /// The JSON the Android plugin parses into its `Channel`.
+    #[test]
+    fn test_channel_json_snapshot() {
+        let channel = Channel::builder("alerts", "Alerts")
+            .description("Urgent")
+            .sound("alarm.wav")
+            .lights(true)
+            .light_color("#FF0000")
+            .vibration(true)
+            .importance(Importance::High)
+            .visibility(Visibility::Secret)
+            .build();
+        assert_eq!(
+            serde_json::to_string(&channel).expect("serialize channel"),
+            r##"{"id":"alerts","name":"Alerts","description":"Urgent","sound":"alarm.wav","lights":true,"lightColor":"#FF0000","vibration":true,"importance":4,"visibility":-1}"##
+        );
+
+        let channel = Channel::builder("general", "General").build();
+        assert_eq!(
+            serde_json::to_string(&channel).expect("serialize channel"),
+            r#"{"id":"general","name":"General","description":null,"sound":null,"lights":false,"lightColor":null,"vibration":false,"importance":null,"visibility":null}"#
+        );
+    }
+
+    #[test]
+    fn test_importance_order_follows_discriminant() {
+        let mut levels = [
+            Importance::High,
+            Importance::None,
+            Importance::Default,
+            Importance::Min,
+            Importance::Low,
+        ];
+        levels.sort();
+        assert_eq!(
+            levels,
+            [
+                Importance::None,
+                Importance::Min,
+                Importance::Low,
+                Importance::Default,
+                Importance::High,
+            ]
+        );
+        assert!(
+            levels
+                .windows(2)
+                .all(|pair| (pair[0] as u8) < (pair[1] as u8))
+        );
+        assert_eq!(Importance::Low.max(Importance::High), Importance::High);
+        assert_eq!(Importance::Min.min(Importance::None), Importance::None);
+    }
+
+    #[test]
+    fn test_channel_importance_change() {
+        assert!(Importance::None < Importance::Min);
+        assert!(Importance::Low < Importance::Default);
+        assert!(Importance::High > Importance::Default);
+
+        let channel = Channel::builder("downloads", "Downloads")
+            .description("Files")
+            .importance(Importance::High)
+            .build();
+        let lowered = ChannelBuilder::from(channel)
+            .importance(Importance::Low)
+            .build();
+        assert_eq!(lowered.id(), "downloads");
+        assert_eq!(lowered.description(), Some("Files"));
+        assert_eq!(lowered.importance(), Importance::Low);
+    }
+
     #[cfg(target_os = "android")]
     #[test]
     fn test_channel_builder_minimal() {
@@ -901,6 +3259,109 @@ mod tests {
         assert_eq!(channel.visibility(), None);
     }
 
+    #[test]
+    fn test_channel_creation_outcomes() {
+        let requested = Channel::builder("downloads", "Downloads")
+            .sound("chime.wav")
+            .importance(Importance::Low)
+            .build();
+        assert_eq!(
+            ChannelCreation::compare(&requested, None),
+            ChannelCreation::Created
+        );
+
+        // As Android's `listChannels` reports the channel it created.
+        let reported: Channel = serde_json::from_value(serde_json::json!({
+            "id": "downloads",
+            "name": "Downloads",
+            "sound": "android.resource://com.example.app/raw/chime",
+            "lights": false,
+            "vibration": false,
+            "importance": 2,
+            "visibility": 0,
+        }))
+        .expect("Failed to deserialize channel");
+        assert_eq!(
+            ChannelCreation::compare(&requested, Some(&reported)),
+            ChannelCreation::Unchanged
+        );
+
+        let changed = Channel::builder("downloads", "Transfers")
+            .importance(Importance::High)
+            .vibration(true)
+            .build();
+        assert_eq!(
+            ChannelCreation::compare(&changed, Some(&reported)),
+            ChannelCreation::ExistsWithDifferentSettings {
+                differing_fields: vec![
+                    "name".to_string(),
+                    "sound".to_string(),
+                    "vibration".to_string(),
+                    "importance".to_string(),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_plan_channels() {
+        let existing = [Channel::builder("messages", "Messages").build()];
+        let requested = [
+            Channel::builder("messages", "Messages").build(),
+            Channel::builder("downloads", "Downloads").build(),
+            Channel::builder("downloads", "Downloads")
+                .lights(true)
+                .build(),
+        ];
+        assert_eq!(
+            plan_channels(&requested, &existing),
+            vec![
+                ChannelCreation::Unchanged,
+                ChannelCreation::Created,
+                ChannelCreation::ExistsWithDifferentSettings {
+                    differing_fields: vec!["lights".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_channel_creation_serialization() {
+        assert_eq!(
+            serde_json::to_value(ChannelCreation::Created).expect("Failed to serialize"),
+            serde_json::json!({ "status": "created" })
+        );
+        assert_eq!(
+            serde_json::to_value(ChannelCreation::ExistsWithDifferentSettings {
+                differing_fields: vec!["name".to_string()],
+            })
+            .expect("Failed to serialize"),
+            serde_json::json!({
+                "status": "existsWithDifferentSettings",
+                "differingFields": ["name"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_on_pending_policy_serialization() {
+        assert_eq!(
+            serde_json::to_value(OnPendingPolicy::default()).expect("Failed to serialize"),
+            serde_json::json!({ "policy": "leave" })
+        );
+        let policy: OnPendingPolicy = serde_json::from_value(serde_json::json!({
+            "policy": "reassign",
+            "toChannel": "general",
+        }))
+        .expect("Failed to deserialize");
+        assert_eq!(
+            policy,
+            OnPendingPolicy::Reassign {
+                to_channel: "general".to_string(),
+            }
+        );
+    }
+
     #[test]
     fn test_schedule_at_serialization() {
         use time::OffsetDateTime;
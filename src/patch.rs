@@ -0,0 +1,87 @@
+//! Options of the latest shown notifications, for
+//! [`Notifications::patch_active`](crate::Notifications::patch_active).
+//!
+//! No platform hands back everything a notification was shown with, so
+//! every backend's `show` keeps a copy once it reached the OS. A patched
+//! notification is shown again from that copy; one shown by an earlier
+//! session is rebuilt from what the platform reports about it instead.
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, PoisonError};
+
+use crate::{ActiveNotification, NotificationData};
+
+/// Shown notifications remembered, oldest dropped first.
+const SHOWN_LIMIT: usize = 256;
+
+static SHOWN: Mutex<VecDeque<NotificationData>> = Mutex::new(VecDeque::new());
+
+/// Remembers `data` as shown, replacing an earlier notification with its id.
+pub fn remember(data: NotificationData) {
+    let mut shown = SHOWN.lock().unwrap_or_else(PoisonError::into_inner);
+    insert(&mut shown, data);
+}
+
+/// The options notification `id` was last shown with in this session.
+pub fn shown(id: i32) -> Option<NotificationData> {
+    SHOWN
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|data| data.id == id)
+        .cloned()
+}
+
+fn insert(shown: &mut VecDeque<NotificationData>, data: NotificationData) {
+    shown.retain(|shown| shown.id != data.id);
+    if shown.len() == SHOWN_LIMIT {
+        shown.pop_front();
+    }
+    shown.push_back(data);
+}
+
+/// Options to show `active` again with, for one not shown in this session.
+pub fn rebuild(active: ActiveNotification) -> NotificationData {
+    NotificationData {
+        id: active.id,
+        title: active.title,
+        body: active.body,
+        group: active.group,
+        group_summary: active.group_summary,
+        sort_key: active.sort_key,
+        sound: active.sound,
+        attachments: active.attachments,
+        action_type_id: active.action_type_id,
+        extra: active.extra,
+        ..NotificationData::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(id: i32, title: &str) -> NotificationData {
+        NotificationData {
+            id,
+            title: Some(title.to_string()),
+            ..NotificationData::default()
+        }
+    }
+
+    #[test]
+    fn test_insert_replaces_and_drops_oldest() {
+        let mut shown = VecDeque::new();
+        for id in 0..SHOWN_LIMIT {
+            insert(&mut shown, data(i32::try_from(id).expect("id"), "first"));
+        }
+        insert(&mut shown, data(0, "again"));
+        assert_eq!(shown.len(), SHOWN_LIMIT);
+        assert_eq!(shown.back().and_then(|d| d.title.as_deref()), Some("again"));
+
+        insert(&mut shown, data(-1, "new"));
+        assert_eq!(shown.len(), SHOWN_LIMIT);
+        assert!(shown.iter().all(|d| d.id != 1));
+        assert!(shown.iter().any(|d| d.id == 0));
+    }
+}
@@ -0,0 +1,182 @@
+//! Cache of the permission state behind
+//! [`Notifications::permission_state`](crate::Notifications::permission_state),
+//! kept for [`PluginConfig::permission_cache_ttl`](crate::PluginConfig::permission_cache_ttl).
+//!
+//! Reading the state is a round trip to the native side (a
+//! `UNUserNotificationCenter` settings query on iOS), too slow to repeat for
+//! every notification. The cached state is dropped when the app is resumed
+//! or a window gains focus (the user may be back from the system settings),
+//! and replaced by the state the native side answers a permission request
+//! with and on `permissionChanged`.
+
+use std::{
+    future::Future,
+    sync::{Mutex, MutexGuard, OnceLock, PoisonError},
+    time::{Duration, Instant},
+};
+
+use tauri::plugin::PermissionState;
+
+use crate::telemetry;
+
+/// How long a read permission state is reused, unless configured.
+pub const DEFAULT_PERMISSION_CACHE_TTL: Duration = Duration::from_secs(30);
+
+static CACHE: OnceLock<PermissionCache> = OnceLock::new();
+
+#[derive(Debug)]
+struct PermissionCache {
+    ttl: Duration,
+    entry: Mutex<Option<(Instant, PermissionState)>>,
+}
+
+/// Turns caching on. A zero `ttl` leaves it off.
+pub fn init(ttl: Duration) {
+    if !ttl.is_zero() {
+        let _ = CACHE.set(PermissionCache::new(ttl));
+    }
+}
+
+/// The cached state, or the one `read` returns, which is then cached.
+/// Failed reads aren't cached.
+pub async fn get_or_read<F>(read: F) -> crate::Result<PermissionState>
+where
+    F: Future<Output = crate::Result<PermissionState>>,
+{
+    match CACHE.get() {
+        Some(cache) => cache.get_or_read(read).await,
+        None => read.await,
+    }
+}
+
+/// Caches `state`, as the platform just reported it.
+#[cfg_attr(
+    not(any(mobile, all(target_os = "macos", not(feature = "notify-rust")))),
+    allow(dead_code)
+)]
+pub fn store(state: PermissionState) {
+    if let Some(cache) = CACHE.get() {
+        cache.store(state, Instant::now());
+    }
+}
+
+/// Drops the cached state, so the next read asks the platform.
+pub fn invalidate() {
+    if let Some(cache) = CACHE.get() {
+        cache.invalidate();
+    }
+}
+
+/// Caches the state a `permissionChanged` event reports. Called by
+/// [`crate::listeners::trigger`] for every event.
+pub fn observe(event: &str, payload: &str) {
+    let Some(cache) = CACHE.get() else {
+        return;
+    };
+    if event != "permissionChanged" {
+        return;
+    }
+    let state = serde_json::from_str::<serde_json::Value>(payload)
+        .ok()
+        .and_then(|payload| serde_json::from_value(payload.get("permissionState")?.clone()).ok());
+    let Some(state) = state else {
+        telemetry::warning!("Malformed permissionChanged payload: {payload}");
+        cache.invalidate();
+        return;
+    };
+    cache.store(state, Instant::now());
+}
+
+impl PermissionCache {
+    const fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: Mutex::new(None),
+        }
+    }
+
+    fn entry(&self) -> MutexGuard<'_, Option<(Instant, PermissionState)>> {
+        self.entry.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn get(&self, now: Instant) -> Option<PermissionState> {
+        self.entry()
+            .filter(|(at, _)| now.duration_since(*at) < self.ttl)
+            .map(|(_, state)| state)
+    }
+
+    fn store(&self, state: PermissionState, now: Instant) {
+        *self.entry() = Some((now, state));
+    }
+
+    fn invalidate(&self) {
+        *self.entry() = None;
+    }
+
+    async fn get_or_read<F>(&self, read: F) -> crate::Result<PermissionState>
+    where
+        F: Future<Output = crate::Result<PermissionState>>,
+    {
+        if let Some(state) = self.get(Instant::now()) {
+            return Ok(state);
+        }
+        let state = read.await?;
+        self.store(state, Instant::now());
+        Ok(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// Mock bridge call counting how often the platform is asked.
+    #[allow(clippy::unused_async)]
+    async fn read(calls: &AtomicUsize) -> crate::Result<PermissionState> {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Ok(PermissionState::Granted)
+    }
+
+    #[test]
+    fn test_reads_within_ttl_skip_the_bridge() {
+        tauri::async_runtime::block_on(async {
+            let cache = PermissionCache::new(Duration::from_secs(30));
+            let calls = AtomicUsize::new(0);
+            for _ in 0..10 {
+                let state = cache.get_or_read(read(&calls)).await;
+                assert_eq!(state.expect("read failed"), PermissionState::Granted);
+            }
+            assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+            cache.invalidate();
+            cache.get_or_read(read(&calls)).await.expect("read failed");
+            assert_eq!(calls.load(Ordering::SeqCst), 2);
+        });
+    }
+
+    #[test]
+    fn test_entry_expires_after_ttl() {
+        let cache = PermissionCache::new(Duration::from_secs(30));
+        let start = Instant::now();
+        cache.store(PermissionState::Denied, start);
+        assert_eq!(
+            cache.get(start + Duration::from_secs(29)),
+            Some(PermissionState::Denied)
+        );
+        assert_eq!(cache.get(start + Duration::from_secs(30)), None);
+    }
+
+    #[test]
+    fn test_failed_read_is_not_cached() {
+        tauri::async_runtime::block_on(async {
+            let cache = PermissionCache::new(Duration::from_secs(30));
+            let failed = cache
+                .get_or_read(async { Err(crate::Error::Io(std::io::Error::other("no bridge"))) })
+                .await;
+            assert!(failed.is_err());
+            assert_eq!(cache.get(Instant::now()), None);
+        });
+    }
+}
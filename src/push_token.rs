@@ -0,0 +1,78 @@
+//! The last push token handed out by `register_for_push_notifications` on
+//! desktop (the WNS channel URI on Windows, the `UnifiedPush` endpoint on
+//! Linux), kept in `<app data dir>/push-token` so
+//! `get_push_token_cached` can return it without registering again.
+
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::telemetry;
+
+const FILE_NAME: &str = "push-token";
+
+fn path<R: Runtime>(app: &AppHandle<R>) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join(FILE_NAME))
+}
+
+/// Records `token`. Failures are logged: the token was still delivered to
+/// the caller, it just won't be cached.
+pub fn store<R: Runtime>(app: &AppHandle<R>, token: &str) {
+    let Some(path) = path(app) else {
+        return;
+    };
+    if let Err(e) = write(&path, token) {
+        telemetry::warning!("Failed to cache push token: {e}");
+    }
+}
+
+/// The last stored token, or `None` if none was stored since the last
+/// [`clear`].
+pub fn load<R: Runtime>(app: &AppHandle<R>) -> Option<String> {
+    read(&path(app)?)
+}
+
+pub fn clear<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(path) = path(app) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+fn write(path: &Path, token: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, token)
+}
+
+fn read(path: &Path) -> Option<String> {
+    let token = std::fs::read_to_string(path).ok()?;
+    let token = token.trim();
+    (!token.is_empty()).then(|| token.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_then_read() {
+        let dir = std::env::temp_dir().join(format!("push-token-test-{}", std::process::id()));
+        let path = dir.join(FILE_NAME);
+        assert_eq!(read(&path), None);
+
+        write(&path, "https://push.example/endpoint/abc").expect("Failed to write token");
+        assert_eq!(
+            read(&path).as_deref(),
+            Some("https://push.example/endpoint/abc")
+        );
+
+        write(&path, "  \n").expect("Failed to write token");
+        assert_eq!(read(&path), None);
+
+        std::fs::remove_dir_all(&dir).expect("Failed to remove test dir");
+    }
+}
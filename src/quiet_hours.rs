@@ -0,0 +1,312 @@
+//! Quiet hours: a daily window, set with [`PluginConfig::quiet_hours`], in
+//! which `show()` keeps notifications from alerting.
+//!
+//! A notification that would be delivered inside the window is either
+//! deferred to the end of the window, by scheduling it for then, or delivered
+//! quietly, without sound or heads-up. Deferred notifications go through the
+//! platform scheduler like any other scheduled notification, so they survive
+//! app restarts.
+//!
+//! [`PluginConfig::quiet_hours`]: crate::PluginConfig::quiet_hours
+
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Deserializer, Serialize, de::Error as _};
+use time::{OffsetDateTime, Time, UtcOffset};
+
+use crate::{NotificationData, Schedule, ScheduleCapabilities};
+
+/// Whether this backend has a scheduler to defer notifications with.
+/// notify-rust shows everything right away.
+const CAN_DEFER: bool = ScheduleCapabilities::current().scheduling;
+
+/// What `show()` does with a notification that falls in quiet hours.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QuietHoursPolicy {
+    /// Schedule it for the end of quiet hours. Delivered quietly instead
+    /// with notify-rust, which can't schedule.
+    #[default]
+    Defer,
+    /// Deliver it now, without sound or heads-up.
+    Silent,
+    /// Deliver it as usual, e.g. for urgent notifications.
+    Bypass,
+}
+
+/// Daily window in which notifications don't alert.
+///
+/// ```json
+/// { "start": "22:00", "end": "07:00", "allowCategories": ["incoming_call"] }
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuietHours {
+    /// Start of the window, as `HH:MM`.
+    #[serde(deserialize_with = "deserialize_time")]
+    pub start: Time,
+    /// End of the window, as `HH:MM`. Earlier than `start` for a window
+    /// that spans midnight; equal to it for no window at all.
+    #[serde(deserialize_with = "deserialize_time")]
+    pub end: Time,
+    /// UTC offset `start` and `end` are in, as `+HH:MM`, `-HH:MM` or `UTC`.
+    /// Defaults to the system's offset when `show()` runs, so the window
+    /// follows daylight saving time, or to UTC where it can't be read (e.g.
+    /// on Linux once the app has spawned threads). Time zone names are not
+    /// supported.
+    #[serde(default, deserialize_with = "deserialize_offset")]
+    pub timezone: Option<UtcOffset>,
+    /// Action type ids (the notification category on iOS and macOS) that
+    /// alert during quiet hours anyway.
+    #[serde(default)]
+    pub allow_categories: Vec<String>,
+    /// Policy of the notifications that don't set one with
+    /// [`NotificationsBuilder::quiet_hours_policy`](crate::NotificationsBuilder::quiet_hours_policy).
+    #[serde(default)]
+    pub policy: QuietHoursPolicy,
+}
+
+impl QuietHours {
+    /// End of the quiet window `at` falls in, or `None` when it falls
+    /// outside. `local` is used when no [`timezone`](Self::timezone) is set.
+    #[must_use]
+    pub fn window_end(&self, at: OffsetDateTime, local: UtcOffset) -> Option<OffsetDateTime> {
+        let at = at.to_offset(self.timezone.unwrap_or(local));
+        let time = at.time();
+        let end_on = |date: time::Date| date.with_time(self.end).assume_offset(at.offset());
+        match self.start.cmp(&self.end) {
+            Ordering::Less => (self.start <= time && time < self.end).then(|| end_on(at.date())),
+            // Spans midnight: ends tomorrow once it started, today before.
+            Ordering::Greater if time >= self.start => at.date().next_day().map(end_on),
+            Ordering::Greater => (time < self.end).then(|| end_on(at.date())),
+            Ordering::Equal => None,
+        }
+    }
+
+    /// Defers or quiets `data` when it would be delivered during quiet
+    /// hours. Recurring schedules, count-downs and announcements are left
+    /// alone: they can't be moved to a single later time.
+    pub(crate) fn apply(&self, data: &mut NotificationData, now: OffsetDateTime, local: UtcOffset) {
+        if data.announce_only
+            || data
+                .action_type_id
+                .as_ref()
+                .is_some_and(|category| self.allow_categories.contains(category))
+        {
+            return;
+        }
+        let policy = data.quiet_hours_policy.unwrap_or(self.policy);
+        if policy == QuietHoursPolicy::Bypass {
+            return;
+        }
+        let at = match &data.schedule {
+            None => now,
+            Some(Schedule::At {
+                date,
+                repeating: false,
+                ..
+            }) => *date,
+            Some(_) => return,
+        };
+        let Some(end) = self.window_end(at, local) else {
+            return;
+        };
+        if policy == QuietHoursPolicy::Defer && CAN_DEFER && data.count_down_to.is_none() {
+            data.schedule = Some(Schedule::At {
+                date: end,
+                repeating: false,
+                allow_while_idle: true,
+            });
+        } else {
+            data.quiet = true;
+        }
+    }
+}
+
+fn deserialize_time<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Time, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    let invalid = || D::Error::custom(format!("invalid time '{value}': expected HH:MM"));
+    let (hour, minute) = value.split_once(':').ok_or_else(invalid)?;
+    let hour = hour.parse().map_err(|_| invalid())?;
+    let minute = minute.parse().map_err(|_| invalid())?;
+    Time::from_hms(hour, minute, 0).map_err(|_| invalid())
+}
+
+fn deserialize_offset<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<UtcOffset>, D::Error> {
+    let Some(value) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    if value.eq_ignore_ascii_case("utc") || value == "Z" {
+        return Ok(Some(UtcOffset::UTC));
+    }
+    let invalid = || {
+        D::Error::custom(format!(
+            "invalid timezone '{value}': expected +HH:MM, -HH:MM or UTC"
+        ))
+    };
+    let (sign, rest) = match value.split_at_checked(1).ok_or_else(invalid)? {
+        ("+", rest) => (1, rest),
+        ("-", rest) => (-1, rest),
+        _ => return Err(invalid()),
+    };
+    let (hours, minutes) = rest.split_once(':').ok_or_else(invalid)?;
+    let hours: i8 = hours.parse().map_err(|_| invalid())?;
+    let minutes: i8 = minutes.parse().map_err(|_| invalid())?;
+    UtcOffset::from_hms(sign * hours, sign * minutes, 0)
+        .map(Some)
+        .map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::{datetime, offset};
+
+    use super::*;
+
+    fn quiet_hours(start: &str, end: &str) -> QuietHours {
+        serde_json::from_value(serde_json::json!({
+            "start": start,
+            "end": end,
+            "timezone": "+02:00",
+            "allowCategories": ["incoming_call"],
+        }))
+        .expect("valid quiet hours")
+    }
+
+    #[test]
+    fn test_window_end_across_midnight() {
+        let quiet = quiet_hours("22:00", "07:00");
+        let end = |at| quiet.window_end(at, UtcOffset::UTC);
+
+        assert_eq!(end(datetime!(2025-03-10 21:59 +02:00)), None);
+        assert_eq!(
+            end(datetime!(2025-03-10 22:00 +02:00)),
+            Some(datetime!(2025-03-11 07:00 +02:00))
+        );
+        assert_eq!(
+            end(datetime!(2025-03-10 23:59:59 +02:00)),
+            Some(datetime!(2025-03-11 07:00 +02:00))
+        );
+        assert_eq!(
+            end(datetime!(2025-03-11 00:00 +02:00)),
+            Some(datetime!(2025-03-11 07:00 +02:00))
+        );
+        assert_eq!(end(datetime!(2025-03-11 07:00 +02:00)), None);
+        // The window is in the configured offset, not the instant's.
+        assert_eq!(
+            end(datetime!(2025-03-10 20:30 UTC)),
+            Some(datetime!(2025-03-11 07:00 +02:00))
+        );
+    }
+
+    #[test]
+    fn test_window_end_same_day_and_empty() {
+        let quiet = quiet_hours("13:00", "14:30");
+        let end = |at| quiet.window_end(at, UtcOffset::UTC);
+        assert_eq!(end(datetime!(2025-03-10 12:59 +02:00)), None);
+        assert_eq!(
+            end(datetime!(2025-03-10 13:00 +02:00)),
+            Some(datetime!(2025-03-10 14:30 +02:00))
+        );
+        assert_eq!(end(datetime!(2025-03-10 14:30 +02:00)), None);
+
+        let empty = quiet_hours("07:00", "07:00");
+        assert_eq!(
+            empty.window_end(datetime!(2025-03-10 07:00 +02:00), UtcOffset::UTC),
+            None
+        );
+
+        let local = QuietHours {
+            timezone: None,
+            ..quiet_hours("22:00", "07:00")
+        };
+        assert_eq!(
+            local.window_end(datetime!(2025-03-10 21:30 UTC), offset!(+1)),
+            Some(datetime!(2025-03-11 07:00 +01:00))
+        );
+    }
+
+    #[test]
+    fn test_apply_policies() {
+        let quiet = quiet_hours("22:00", "07:00");
+        let now = datetime!(2025-03-10 23:00 +02:00);
+        let apply = |data: NotificationData| {
+            let mut data = data;
+            quiet.apply(&mut data, now, UtcOffset::UTC);
+            data
+        };
+
+        let deferred = apply(NotificationData::default());
+        if CAN_DEFER {
+            assert!(matches!(
+                deferred.schedule,
+                Some(Schedule::At { date, .. }) if date == datetime!(2025-03-11 07:00 +02:00)
+            ));
+            assert!(!deferred.quiet);
+        } else {
+            assert!(deferred.schedule.is_none() && deferred.quiet);
+        }
+
+        let silent = apply(NotificationData {
+            quiet_hours_policy: Some(QuietHoursPolicy::Silent),
+            ..Default::default()
+        });
+        assert!(silent.schedule.is_none() && silent.quiet);
+
+        for data in [
+            NotificationData {
+                quiet_hours_policy: Some(QuietHoursPolicy::Bypass),
+                ..Default::default()
+            },
+            NotificationData {
+                action_type_id: Some("incoming_call".to_string()),
+                ..Default::default()
+            },
+            NotificationData {
+                schedule: Some(Schedule::At {
+                    date: datetime!(2025-03-11 09:00 +02:00),
+                    repeating: false,
+                    allow_while_idle: false,
+                }),
+                ..Default::default()
+            },
+        ] {
+            let kept = apply(data);
+            assert!(!kept.quiet);
+            assert!(!matches!(
+                kept.schedule,
+                Some(Schedule::At { date, .. }) if date == datetime!(2025-03-11 07:00 +02:00)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_quiet_hours_deserialization() {
+        let quiet = quiet_hours("22:00", "07:30");
+        assert_eq!(quiet.start, time::macros::time!(22:00));
+        assert_eq!(quiet.end, time::macros::time!(07:30));
+        assert_eq!(quiet.timezone, Some(offset!(+2)));
+        assert_eq!(quiet.policy, QuietHoursPolicy::Defer);
+
+        let parse = |timezone: &str| {
+            serde_json::from_value::<QuietHours>(serde_json::json!({
+                "start": "22:00",
+                "end": "07:00",
+                "timezone": timezone,
+            }))
+            .map(|quiet| quiet.timezone)
+        };
+        assert_eq!(parse("UTC").ok(), Some(Some(UtcOffset::UTC)));
+        assert_eq!(parse("-05:30").ok(), Some(Some(offset!(-5:30))));
+        assert!(parse("Europe/Berlin").is_err());
+        assert!(
+            serde_json::from_value::<QuietHours>(serde_json::json!({
+                "start": "25:00",
+                "end": "07:00",
+            }))
+            .is_err()
+        );
+    }
+}
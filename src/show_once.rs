@@ -0,0 +1,123 @@
+//! Scheduled Windows toasts marked
+//! [`show_only_once`](crate::NotificationsBuilder::show_only_once), kept in
+//! `<app data dir>/show-once.json` with the Unix time they are due.
+//!
+//! Windows keeps scheduled toasts across app restarts. Once a toast's time
+//! has passed it counts as delivered: on the next startup the plugin pulls it
+//! from the schedule, so one that was missed isn't shown late.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::telemetry;
+
+const FILE_NAME: &str = "show-once.json";
+
+/// Serializes the read-modify-write of the file across concurrent `show`s.
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn path<R: Runtime>(app: &AppHandle<R>) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join(FILE_NAME))
+}
+
+/// Records that toast `id` is due at `at` (Unix seconds).
+pub fn remember<R: Runtime>(app: &AppHandle<R>, id: i32, at: i64) {
+    update(app, |entries| entries.insert(id, at) != Some(at));
+}
+
+/// Forgets `id`, for a notification shown again without `show_only_once`.
+pub fn forget<R: Runtime>(app: &AppHandle<R>, id: i32) {
+    update(app, |entries| entries.remove(&id).is_some());
+}
+
+/// Removes and returns the ids due at or before `now` (Unix seconds).
+pub fn take_due<R: Runtime>(app: &AppHandle<R>, now: i64) -> Vec<i32> {
+    let mut taken = Vec::new();
+    update(app, |entries| {
+        taken = due(entries, now);
+        !taken.is_empty()
+    });
+    taken
+}
+
+/// Runs `update` on the stored entries and writes them back if it returns
+/// `true`. Failures are logged: at worst a missed toast still shows late.
+fn update<R: Runtime>(app: &AppHandle<R>, update: impl FnOnce(&mut HashMap<i32, i64>) -> bool) {
+    let Some(path) = path(app) else {
+        return;
+    };
+    let _guard = LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let mut entries = read(&path);
+    if update(&mut entries)
+        && let Err(e) = write(&path, &entries)
+    {
+        telemetry::warning!("Failed to store show-once notifications: {e}");
+    }
+}
+
+/// Removes the entries due at or before `now` from `entries`, returning
+/// their ids in ascending order.
+fn due(entries: &mut HashMap<i32, i64>, now: i64) -> Vec<i32> {
+    let mut ids: Vec<i32> = entries
+        .iter()
+        .filter(|&(_, &at)| at <= now)
+        .map(|(&id, _)| id)
+        .collect();
+    ids.sort_unstable();
+    for id in &ids {
+        entries.remove(id);
+    }
+    ids
+}
+
+fn write(path: &Path, entries: &HashMap<i32, i64>) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_vec(entries).map_err(std::io::Error::other)?;
+    std::fs::write(path, json)
+}
+
+fn read(path: &Path) -> HashMap<i32, i64> {
+    std::fs::read(path)
+        .ok()
+        .and_then(|json| serde_json::from_slice(&json).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_due() {
+        let mut entries = HashMap::from([(3, 100), (1, 50), (2, 200), (4, 100)]);
+        assert_eq!(due(&mut entries, 100), [1, 3, 4]);
+        assert_eq!(entries, HashMap::from([(2, 200)]));
+        assert!(due(&mut entries, 100).is_empty());
+    }
+
+    #[test]
+    fn test_write_then_read() {
+        let dir = std::env::temp_dir().join(format!("show-once-test-{}", std::process::id()));
+        let path = dir.join(FILE_NAME);
+        assert!(read(&path).is_empty());
+
+        let entries = HashMap::from([(7, 1_700_000_000), (-3, 1_800_000_000)]);
+        write(&path, &entries).expect("Failed to write entries");
+        assert_eq!(read(&path), entries);
+
+        std::fs::write(&path, "not json").expect("Failed to write file");
+        assert!(read(&path).is_empty());
+
+        std::fs::remove_dir_all(&dir).expect("Failed to remove test dir");
+    }
+}
@@ -0,0 +1,130 @@
+//! Synthetic user interactions for end-to-end tests (`test-utils` feature).
+//!
+//! CI can't click real OS notifications, so [`Notifications::simulate_click`]
+//! and [`Notifications::simulate_action`] look the notification up in the
+//! backend's active store and fire the same `notificationClicked` /
+//! `actionPerformed` events through [`crate::listeners`] that a real
+//! interaction produces. `WebDriver` suites reach them through the
+//! `simulate_click` / `simulate_action` commands.
+
+use serde_json::{Map, Value, json};
+use tauri::Runtime;
+
+use crate::{ActiveNotification, Notifications};
+
+/// `notificationClicked` payload: the notification id plus its data, in the
+/// same `{ id, data }` shape the backends emit.
+fn click_payload(notification: &ActiveNotification) -> Value {
+    let mut data: Map<String, Value> = notification
+        .data
+        .iter()
+        .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+        .collect();
+    data.extend(
+        notification
+            .extra
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone())),
+    );
+    json!({ "id": notification.id, "data": data })
+}
+
+/// `actionPerformed` payload, as emitted for a button press (or `"tap"`).
+fn action_payload(
+    notification: &ActiveNotification,
+    action_id: &str,
+    input_value: Option<String>,
+) -> Value {
+    json!({
+        "actionId": action_id,
+        "inputValue": input_value.map_or(Value::Null, Value::String),
+        "notification": notification,
+    })
+}
+
+impl<R: Runtime> Notifications<R> {
+    /// Finds notification `id` among the active ones. Backends without an
+    /// active store (notify-rust outside Linux) get a bare notification
+    /// carrying only the id, so the events still fire.
+    async fn simulated_target(&self, id: i32) -> crate::Result<ActiveNotification> {
+        let Ok(active) = self.active().await else {
+            return Ok(ActiveNotification::new(id, None, None));
+        };
+        active.into_iter().find(|n| n.id == id).ok_or_else(|| {
+            crate::Error::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("no active notification with id {id}"),
+            ))
+        })
+    }
+
+    /// Fires `notificationClicked` (and the matching `"tap"` `actionPerformed`)
+    /// for active notification `id`, as if the user had clicked it.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn simulate_click(&self, id: i32) -> crate::Result<()> {
+        let notification = self.simulated_target(id).await?;
+        crate::listeners::trigger(
+            "actionPerformed",
+            action_payload(&notification, "tap", None).to_string(),
+        )?;
+        crate::listeners::trigger(
+            "notificationClicked",
+            click_payload(&notification).to_string(),
+        )
+    }
+
+    /// Fires `actionPerformed` for active notification `id`, as if the user
+    /// had pressed the action button `action_id` (with `input_value` for
+    /// text-input actions).
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn simulate_action(
+        &self,
+        id: i32,
+        action_id: &str,
+        input_value: Option<String>,
+    ) -> crate::Result<()> {
+        let notification = self.simulated_target(id).await?;
+        crate::listeners::trigger(
+            "actionPerformed",
+            action_payload(&notification, action_id, input_value).to_string(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification() -> ActiveNotification {
+        let mut notification =
+            ActiveNotification::new(7, Some("Title".to_string()), Some("Body".to_string()));
+        notification
+            .data
+            .insert("source".to_string(), "test".to_string());
+        notification
+            .extra
+            .insert("order".to_string(), json!({ "id": 42 }));
+        notification
+    }
+
+    #[test]
+    fn test_click_payload_merges_data_and_extra() {
+        let payload = click_payload(&notification());
+        assert_eq!(
+            payload,
+            json!({ "id": 7, "data": { "source": "test", "order": { "id": 42 } } })
+        );
+    }
+
+    #[test]
+    fn test_action_payload_shape() {
+        let payload = action_payload(&notification(), "reply", Some("hi".to_string()));
+        assert_eq!(payload["actionId"], "reply");
+        assert_eq!(payload["inputValue"], "hi");
+        assert_eq!(payload["notification"]["id"], 7);
+        assert_eq!(payload["notification"]["title"], "Title");
+
+        let payload = action_payload(&notification(), "tap", None);
+        assert_eq!(payload["inputValue"], Value::Null);
+    }
+}
@@ -0,0 +1,500 @@
+//! Opt-in interaction counters, enabled with
+//! [`PluginConfig::stats`](crate::PluginConfig::stats) and kept in
+//! `<app data dir>/notification-stats.json`.
+//!
+//! Every backend's `show` counts a notification as delivered once it reached
+//! the OS. Clicks, dismissals and actions are counted from the events the
+//! backends trigger, cold-start activations included; on mobile the native
+//! plugins record them until [`Notifications::stats`] collects them. Counters
+//! are kept per UTC day, channel and group.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+use time::{OffsetDateTime, UtcOffset};
+
+use crate::{NotificationData, Notifications, telemetry};
+
+const FILE_NAME: &str = "notification-stats.json";
+
+/// Delivered notifications remembered to attribute interactions with them
+/// to their channel and group.
+const RECENT_LIMIT: usize = 512;
+
+static STATS: OnceLock<Stats> = OnceLock::new();
+
+/// Counters summed over a [`StatsRange`], from [`Notifications::stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationStats {
+    /// Notifications handed to the OS. Scheduled notifications count when
+    /// they are scheduled.
+    pub delivered: u64,
+    /// Taps on a notification itself.
+    pub clicked: u64,
+    /// Notifications the user dismissed: toasts on Windows, notifications
+    /// of an action type with `customDismissAction` on iOS and macOS.
+    pub dismissed: u64,
+    /// Uses of each action, by action id.
+    pub action_counts: BTreeMap<String, u64>,
+}
+
+impl NotificationStats {
+    fn add(&mut self, other: &Self) {
+        self.delivered += other.delivered;
+        self.clicked += other.clicked;
+        self.dismissed += other.dismissed;
+        for (action, count) in &other.action_counts {
+            *self.action_counts.entry(action.clone()).or_default() += count;
+        }
+    }
+}
+
+/// What [`Notifications::stats`] sums up. Counters are kept per UTC day, so
+/// `since` and `until` take in the whole days they fall on. The default
+/// covers everything recorded.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsRange {
+    /// First day to count.
+    #[serde(
+        default,
+        deserialize_with = "time::serde::iso8601::option::deserialize"
+    )]
+    pub since: Option<OffsetDateTime>,
+    /// Last day to count.
+    #[serde(
+        default,
+        deserialize_with = "time::serde::iso8601::option::deserialize"
+    )]
+    pub until: Option<OffsetDateTime>,
+    /// Only count notifications on this channel.
+    pub channel_id: Option<String>,
+    /// Only count notifications of this group.
+    pub group: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Key {
+    channel_id: Option<String>,
+    group: Option<String>,
+}
+
+impl Key {
+    fn of(data: &NotificationData) -> Self {
+        Self {
+            channel_id: data.channel_id.clone(),
+            group: data.group.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Bucket {
+    #[serde(flatten)]
+    key: Key,
+    #[serde(flatten)]
+    counts: NotificationStats,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Store {
+    /// Counters by Julian day (UTC), one bucket per channel and group.
+    days: BTreeMap<i32, Vec<Bucket>>,
+    /// Channel and group of the latest delivered notifications, oldest
+    /// first.
+    recent: VecDeque<(i32, Key)>,
+}
+
+/// The counters, shared by `show` and the event paths.
+#[derive(Debug)]
+pub struct Stats {
+    path: Option<PathBuf>,
+    store: Mutex<Store>,
+}
+
+/// A notification on its way to the OS, counted as delivered by
+/// [`record`](Self::record) once it got there.
+pub struct Delivery {
+    id: i32,
+    key: Key,
+}
+
+impl Delivery {
+    pub fn of(data: &NotificationData) -> Self {
+        Self {
+            id: data.id,
+            key: Key::of(data),
+        }
+    }
+
+    pub fn record(self) {
+        if let Some(stats) = STATS.get() {
+            stats.record_delivered(self.id, self.key, OffsetDateTime::now_utc());
+        }
+    }
+}
+
+/// Turns counting on, loading the counters stored in the app data dir. A
+/// missing or unreadable file starts out empty.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    let path = app
+        .path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join(FILE_NAME));
+    let _ = STATS.set(Stats::new(path));
+}
+
+/// Counts the interaction an event stands for, if counting is on. Called by
+/// [`crate::listeners::trigger`] for every event.
+pub fn observe(event: &str, payload: &str) {
+    let Some(stats) = STATS.get() else {
+        return;
+    };
+    if !matches!(event, "actionPerformed" | "notificationDismissed") {
+        return;
+    }
+    match serde_json::from_str(payload) {
+        Ok(payload) => stats.observe(event, &payload, OffsetDateTime::now_utc()),
+        Err(e) => telemetry::warning!("Not counting malformed {event} payload: {e}"),
+    }
+}
+
+impl Stats {
+    fn new(path: Option<PathBuf>) -> Self {
+        let store = path.as_deref().map(read).unwrap_or_default();
+        Self {
+            path,
+            store: Mutex::new(store),
+        }
+    }
+
+    fn store(&self) -> std::sync::MutexGuard<'_, Store> {
+        self.store
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn persist(&self, store: &Store) {
+        if let Some(path) = &self.path
+            && let Err(e) = write(path, store)
+        {
+            telemetry::warning!("Failed to store notification stats in {path:?}: {e}");
+        }
+    }
+
+    fn count(
+        store: &mut Store,
+        key: Key,
+        now: OffsetDateTime,
+        update: impl FnOnce(&mut NotificationStats),
+    ) {
+        let buckets = store.days.entry(julian_day(now)).or_default();
+        let index = buckets
+            .iter()
+            .position(|bucket| bucket.key == key)
+            .unwrap_or_else(|| {
+                buckets.push(Bucket {
+                    key,
+                    counts: NotificationStats::default(),
+                });
+                buckets.len() - 1
+            });
+        update(&mut buckets[index].counts);
+    }
+
+    fn record_delivered(&self, id: i32, key: Key, now: OffsetDateTime) {
+        let mut store = self.store();
+        store.recent.retain(|(recent, _)| *recent != id);
+        if store.recent.len() == RECENT_LIMIT {
+            store.recent.pop_front();
+        }
+        store.recent.push_back((id, key.clone()));
+        Self::count(&mut store, key, now, |counts| counts.delivered += 1);
+        self.persist(&store);
+        drop(store);
+    }
+
+    fn observe(&self, event: &str, payload: &serde_json::Value, now: OffsetDateTime) {
+        let (id, action) = match event {
+            "actionPerformed" => (
+                payload.pointer("/notification/id"),
+                payload.get("actionId").and_then(serde_json::Value::as_str),
+            ),
+            "notificationDismissed" => (payload.get("id"), Some("dismiss")),
+            _ => return,
+        };
+        let Some(action) = action else {
+            return;
+        };
+        let id = id
+            .and_then(serde_json::Value::as_i64)
+            .and_then(|id| i32::try_from(id).ok());
+        let mut store = self.store();
+        let key = id
+            .and_then(|id| store.recent.iter().rev().find(|(recent, _)| *recent == id))
+            .map(|(_, key)| key.clone())
+            .unwrap_or_default();
+        Self::count(&mut store, key, now, |counts| match action {
+            "tap" => counts.clicked += 1,
+            "dismiss" => counts.dismissed += 1,
+            action => *counts.action_counts.entry(action.to_string()).or_default() += 1,
+        });
+        self.persist(&store);
+        drop(store);
+    }
+
+    fn sum(&self, range: &StatsRange) -> NotificationStats {
+        let since = range.since.map_or(i32::MIN, julian_day);
+        let until = range.until.map_or(i32::MAX, julian_day);
+        let mut total = NotificationStats::default();
+        if since > until {
+            return total;
+        }
+        let store = self.store();
+        for bucket in store
+            .days
+            .range(since..=until)
+            .flat_map(|(_, buckets)| buckets)
+        {
+            let matches = |filter: &Option<String>, value: &Option<String>| {
+                filter.is_none() || filter == value
+            };
+            if matches(&range.channel_id, &bucket.key.channel_id)
+                && matches(&range.group, &bucket.key.group)
+            {
+                total.add(&bucket.counts);
+            }
+        }
+        drop(store);
+        total
+    }
+
+    fn reset(&self) -> crate::Result<()> {
+        let mut store = self.store();
+        store.days.clear();
+        if let Some(path) = &self.path {
+            write(path, &store)?;
+        }
+        drop(store);
+        Ok(())
+    }
+}
+
+const fn julian_day(at: OffsetDateTime) -> i32 {
+    at.to_offset(UtcOffset::UTC).date().to_julian_day()
+}
+
+fn write(path: &Path, store: &Store) -> crate::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_vec(store).map_err(std::io::Error::other)?;
+    std::fs::write(path, json)?;
+    Ok(())
+}
+
+fn read(path: &Path) -> Store {
+    let Ok(json) = std::fs::read(path) else {
+        return Store::default();
+    };
+    serde_json::from_slice(&json).unwrap_or_else(|e| {
+        telemetry::warning!("Ignoring unreadable notification stats in {path:?}: {e}");
+        Store::default()
+    })
+}
+
+fn enabled() -> crate::Result<&'static Stats> {
+    STATS.get().ok_or_else(|| {
+        crate::Error::Io(std::io::Error::other(
+            "notification stats are disabled; set `stats` in the plugin config",
+        ))
+    })
+}
+
+impl<R: Runtime> Notifications<R> {
+    /// Delivery and interaction counters over `range`. Fails unless
+    /// [`PluginConfig::stats`](crate::PluginConfig::stats) is set.
+    pub fn stats(&self, range: &StatsRange) -> crate::Result<NotificationStats> {
+        let stats = enabled()?;
+        #[cfg(mobile)]
+        self.collect_interactions();
+        Ok(stats.sum(range))
+    }
+
+    /// Clears every counter. Fails unless
+    /// [`PluginConfig::stats`](crate::PluginConfig::stats) is set.
+    pub fn reset_stats(&self) -> crate::Result<()> {
+        let stats = enabled()?;
+        #[cfg(mobile)]
+        self.collect_interactions();
+        stats.reset()
+    }
+
+    /// Counts the interactions the native plugin recorded since the last
+    /// call, including the one that launched the app.
+    #[cfg(mobile)]
+    fn collect_interactions(&self) {
+        match self.take_interactions() {
+            Ok(interactions) => {
+                for interaction in interactions {
+                    observe("actionPerformed", &interaction.to_string());
+                }
+            }
+            Err(e) => telemetry::warning!("Failed to collect notification interactions: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn notification(id: i32, channel_id: &str, group: Option<&str>) -> NotificationData {
+        NotificationData {
+            id,
+            channel_id: Some(channel_id.to_string()),
+            group: group.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    /// Mock backend: delivers notifications and triggers the events the
+    /// real backends do, feeding both into `stats` the way `show` and
+    /// `listeners::trigger` do.
+    struct MockBackend<'a> {
+        stats: &'a Stats,
+        now: OffsetDateTime,
+    }
+
+    impl MockBackend<'_> {
+        fn show(&self, data: &NotificationData) {
+            let delivery = Delivery::of(data);
+            self.stats
+                .record_delivered(delivery.id, delivery.key, self.now);
+        }
+
+        fn activate(&self, id: i32, action_id: &str) {
+            let payload = serde_json::json!({
+                "actionId": action_id,
+                "inputValue": null,
+                "notification": { "id": id },
+            });
+            self.stats.observe("actionPerformed", &payload, self.now);
+        }
+
+        fn dismiss(&self, id: i32) {
+            let payload = serde_json::json!({ "id": id });
+            self.stats
+                .observe("notificationDismissed", &payload, self.now);
+        }
+    }
+
+    #[test]
+    fn test_counters_by_channel_and_group() {
+        let stats = Stats::new(None);
+        let backend = MockBackend {
+            stats: &stats,
+            now: datetime!(2025-03-10 12:00 UTC),
+        };
+        backend.show(&notification(1, "chat", Some("alice")));
+        backend.show(&notification(2, "chat", Some("bob")));
+        backend.show(&notification(3, "updates", None));
+        backend.activate(1, "tap");
+        backend.activate(2, "reply");
+        backend.activate(2, "reply");
+        backend.dismiss(3);
+        // A cold-start activation of a notification from before the stats
+        // were on is counted without a channel or group.
+        backend.activate(99, "tap");
+
+        let all = stats.sum(&StatsRange::default());
+        assert_eq!(all.delivered, 3);
+        assert_eq!(all.clicked, 2);
+        assert_eq!(all.dismissed, 1);
+        assert_eq!(
+            all.action_counts,
+            BTreeMap::from([("reply".to_string(), 2)])
+        );
+
+        let chat = stats.sum(&StatsRange {
+            channel_id: Some("chat".to_string()),
+            ..Default::default()
+        });
+        assert_eq!((chat.delivered, chat.clicked, chat.dismissed), (2, 1, 0));
+
+        let bob = stats.sum(&StatsRange {
+            group: Some("bob".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(bob.delivered, 1);
+        assert_eq!(bob.action_counts.get("reply"), Some(&2));
+    }
+
+    #[test]
+    fn test_range_and_reset() {
+        let stats = Stats::new(None);
+        let mut backend = MockBackend {
+            stats: &stats,
+            now: datetime!(2025-03-10 23:30 UTC),
+        };
+        backend.show(&notification(1, "chat", None));
+        backend.now = datetime!(2025-03-11 00:30 UTC);
+        backend.show(&notification(2, "chat", None));
+        backend.activate(1, "tap");
+
+        let day = |since, until| StatsRange {
+            since: Some(since),
+            until: Some(until),
+            ..Default::default()
+        };
+        // Days are UTC: 01:00 at +02:00 on the 11th is still the 10th.
+        let tenth = stats.sum(&day(
+            datetime!(2025-03-11 01:00 +02:00),
+            datetime!(2025-03-11 01:00 +02:00),
+        ));
+        assert_eq!((tenth.delivered, tenth.clicked), (1, 0));
+        let eleventh = stats.sum(&day(
+            datetime!(2025-03-11 08:00 UTC),
+            datetime!(2025-03-11 20:00 UTC),
+        ));
+        assert_eq!((eleventh.delivered, eleventh.clicked), (1, 1));
+
+        stats.reset().expect("reset without a file");
+        assert_eq!(
+            stats.sum(&StatsRange::default()),
+            NotificationStats::default()
+        );
+        // Notifications delivered before the reset are still attributed.
+        backend.activate(2, "tap");
+        assert_eq!(stats.sum(&StatsRange::default()).clicked, 1);
+    }
+
+    #[test]
+    fn test_persistence() {
+        let dir = std::env::temp_dir().join(format!("notification-stats-{}", std::process::id()));
+        let path = dir.join(FILE_NAME);
+        let stats = Stats::new(Some(path.clone()));
+        let backend = MockBackend {
+            stats: &stats,
+            now: datetime!(2025-03-10 12:00 UTC),
+        };
+        backend.show(&notification(1, "chat", Some("alice")));
+        backend.activate(1, "tap");
+
+        let reloaded = Stats::new(Some(path));
+        let chat = reloaded.sum(&StatsRange {
+            channel_id: Some("chat".to_string()),
+            ..Default::default()
+        });
+        assert_eq!((chat.delivered, chat.clicked), (1, 1));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
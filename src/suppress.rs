@@ -0,0 +1,101 @@
+//! Hands a notification to the app instead of the OS while the window it
+//! belongs to is focused, see
+//! [`NotificationsBuilder::suppress_if_focused`](crate::NotificationsBuilder::suppress_if_focused).
+//!
+//! Decided in the shared part of `show`, before any backend sees the
+//! notification, so every platform behaves the same.
+
+use tauri::{AppHandle, Manager, Runtime};
+
+#[cfg(mobile)]
+use crate::NotificationsExt;
+use crate::{NotificationData, telemetry};
+
+/// Event emitted instead of showing a suppressed notification.
+pub const FALLBACK_EVENT: &str = "notificationFallback";
+
+/// The windows whose focus keeps a notification from being shown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FocusTarget {
+    AnyWindow,
+    /// The window with this label.
+    Window(String),
+}
+
+impl FocusTarget {
+    fn label(&self) -> Option<&str> {
+        match self {
+            Self::AnyWindow => None,
+            Self::Window(label) => Some(label),
+        }
+    }
+}
+
+/// Label of the app's focused window, if `target` selects it.
+pub fn focused_window<R: Runtime>(app: &AppHandle<R>, target: &FocusTarget) -> Option<String> {
+    select_focused(
+        target.label(),
+        app.webview_windows()
+            .into_iter()
+            .map(|(label, window)| (window.is_focused().unwrap_or(false), label)),
+    )
+}
+
+/// The first label of a focused window among `windows` that `target`
+/// selects.
+fn select_focused(
+    target: Option<&str>,
+    windows: impl IntoIterator<Item = (bool, String)>,
+) -> Option<String> {
+    windows
+        .into_iter()
+        .find(|(focused, label)| *focused && target.is_none_or(|target| target == label))
+        .map(|(_, label)| label)
+}
+
+/// Emits [`FALLBACK_EVENT`] with `data`, which wasn't shown because `window`
+/// is focused.
+pub fn emit_fallback<R: Runtime>(app: &AppHandle<R>, data: &NotificationData, window: &str) {
+    let payload = serde_json::json!({
+        "reason": "windowFocused",
+        "window": window,
+        "notification": data,
+    });
+    if let Err(e) = crate::listeners::trigger(FALLBACK_EVENT, payload.to_string()) {
+        telemetry::warning!("Failed to emit {FALLBACK_EVENT}: {e}");
+    }
+    #[cfg(mobile)]
+    if let Err(e) = app.notifications().emit_fallback(&payload) {
+        telemetry::warning!("Failed to forward {FALLBACK_EVENT}: {e}");
+    }
+    #[cfg(not(mobile))]
+    let _ = app;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn windows(focused: &str) -> Vec<(bool, String)> {
+        ["main", "chat", "settings"]
+            .into_iter()
+            .map(|label| (label == focused, label.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_select_focused_window() {
+        assert_eq!(
+            select_focused(Some("chat"), windows("chat")).as_deref(),
+            Some("chat")
+        );
+        assert_eq!(select_focused(Some("chat"), windows("main")), None);
+        assert_eq!(
+            select_focused(None, windows("settings")).as_deref(),
+            Some("settings")
+        );
+        // Nothing focused: the app is in the background.
+        assert_eq!(select_focused(None, windows("")), None);
+        assert_eq!(select_focused(Some("gone"), windows("main")), None);
+    }
+}
@@ -0,0 +1,75 @@
+//! Logging facade shared by every backend.
+//!
+//! With the `tracing` feature enabled, events go through `tracing` and the
+//! public `Notifications` / `NotificationsBuilder` operations are wrapped in
+//! spans (see the `#[cfg_attr(feature = "tracing", tracing::instrument(..))]`
+//! attributes in each backend). `tracing` is built with its `log` feature, so
+//! apps that only install a `log` logger (e.g. `tauri-plugin-log`) still see
+//! both the events and the span enter/exit records. Without the feature the
+//! macros forward straight to `log`.
+
+/// Name of the backend compiled into this build. Recorded as the `platform`
+/// field on every span.
+#[cfg(all(desktop, any(feature = "notify-rust", target_os = "linux")))]
+pub const BACKEND: &str = "notify-rust";
+#[cfg(all(target_os = "macos", not(feature = "notify-rust")))]
+pub const BACKEND: &str = "macos";
+#[cfg(all(target_os = "windows", not(feature = "notify-rust")))]
+pub const BACKEND: &str = "windows";
+#[cfg(target_os = "android")]
+pub const BACKEND: &str = "android";
+#[cfg(target_os = "ios")]
+pub const BACKEND: &str = "ios";
+
+// Not every backend logs at every level; `error!` is only used on Windows.
+#[allow(unused_macros)]
+macro_rules! error {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        ::tracing::error!($($arg)+);
+        #[cfg(not(feature = "tracing"))]
+        ::log::error!($($arg)+);
+    }};
+}
+
+macro_rules! warning {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        ::tracing::warn!($($arg)+);
+        #[cfg(not(feature = "tracing"))]
+        ::log::warn!($($arg)+);
+    }};
+}
+
+#[allow(unused_macros)]
+macro_rules! info {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        ::tracing::info!($($arg)+);
+        #[cfg(not(feature = "tracing"))]
+        ::log::info!($($arg)+);
+    }};
+}
+
+macro_rules! debug {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        ::tracing::debug!($($arg)+);
+        #[cfg(not(feature = "tracing"))]
+        ::log::debug!($($arg)+);
+    }};
+}
+
+#[allow(unused_imports)]
+pub(crate) use {debug, error, info, warning};
+
+/// Records an error code reported by the OS notification API. Under
+/// `tracing` this becomes an event on the current span, so the code lands
+/// next to the notification id of the operation that failed.
+#[allow(dead_code)]
+pub fn os_error(code: &str, message: &str) {
+    #[cfg(feature = "tracing")]
+    ::tracing::warn!(os_error_code = code, "{message}");
+    #[cfg(not(feature = "tracing"))]
+    ::log::debug!("OS notification error {code}: {message}");
+}
@@ -0,0 +1,128 @@
+//! Repeating schedules and count-downs of the Windows backend, kept in
+//! `<app data dir>/windows-timers.json`.
+//!
+//! Windows fires a scheduled toast once and keeps it across app restarts,
+//! but scheduling the next occurrence of a repeating schedule, and moving
+//! the progress bar of a count-down toast, are the plugin's job. Storing
+//! where each one stands lets a restarted app carry on from the occurrence
+//! or alarm Windows still holds.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::telemetry;
+
+const FILE_NAME: &str = "windows-timers.json";
+
+/// A repeating schedule whose current occurrence is in the Windows
+/// schedule under the notification's id as tag.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Repeat {
+    /// Toast XML every occurrence is scheduled with.
+    pub xml: String,
+    pub group: Option<String>,
+    /// Unix time of the occurrence in the Windows schedule.
+    pub next: i64,
+    /// Seconds between two occurrences.
+    pub period_secs: i64,
+    /// Repetitions left after `next`; `None` repeats forever.
+    pub remaining: Option<u32>,
+}
+
+/// A count-down toast whose alarm toast is in the Windows schedule for
+/// `deadline`, under the same tag and group.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CountDown {
+    pub group: Option<String>,
+    /// Unix time of the alarm.
+    pub deadline: i64,
+    /// Seconds from showing the toast to `deadline`: the full progress bar.
+    pub total_secs: u64,
+}
+
+/// Everything the timer worker keeps going, by notification id.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Timers {
+    pub repeats: HashMap<i32, Repeat>,
+    pub count_downs: HashMap<i32, CountDown>,
+}
+
+pub fn path<R: Runtime>(app: &AppHandle<R>) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join(FILE_NAME))
+}
+
+/// The stored timers, or none when the file is missing or unreadable.
+pub fn load(path: Option<&Path>) -> Timers {
+    path.and_then(|path| std::fs::read(path).ok())
+        .and_then(|json| serde_json::from_slice(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Writes `timers` back. Failures are logged: at worst a restart loses the
+/// timers started since the last successful write.
+pub fn save(path: Option<&Path>, timers: &Timers) {
+    let Some(path) = path else {
+        return;
+    };
+    if let Err(e) = write(path, timers) {
+        telemetry::warning!("Failed to store notification timers: {e}");
+    }
+}
+
+fn write(path: &Path, timers: &Timers) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_vec(timers).map_err(std::io::Error::other)?;
+    std::fs::write(path, json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_then_load() {
+        let dir = std::env::temp_dir().join(format!("timers-test-{}", std::process::id()));
+        let path = dir.join(FILE_NAME);
+        assert_eq!(load(Some(&path)), Timers::default());
+        assert_eq!(load(None), Timers::default());
+
+        let timers = Timers {
+            repeats: HashMap::from([(
+                7,
+                Repeat {
+                    xml: "<toast/>".to_string(),
+                    group: Some("chat".to_string()),
+                    next: 1_700_000_000,
+                    period_secs: 3600,
+                    remaining: Some(2),
+                },
+            )]),
+            count_downs: HashMap::from([(
+                -3,
+                CountDown {
+                    group: None,
+                    deadline: 1_700_000_300,
+                    total_secs: 300,
+                },
+            )]),
+        };
+        save(Some(&path), &timers);
+        assert_eq!(load(Some(&path)), timers);
+
+        std::fs::write(&path, "not json").expect("Failed to write file");
+        assert_eq!(load(Some(&path)), Timers::default());
+
+        std::fs::remove_dir_all(&dir).expect("Failed to remove test dir");
+    }
+}
@@ -133,7 +133,9 @@ impl UnifiedPushState {
         match reply {
             zbus::fdo::RequestNameReply::PrimaryOwner
             | zbus::fdo::RequestNameReply::AlreadyOwner => {
-                log::info!("UnifiedPush connector listening on D-Bus name '{connector_bus_name}'");
+                crate::telemetry::info!(
+                    "UnifiedPush connector listening on D-Bus name '{connector_bus_name}'"
+                );
             }
             zbus::fdo::RequestNameReply::InQueue => {
                 return Err(io_err(format!(
@@ -351,7 +353,9 @@ impl ConnectorService {
             .as_ref()
             .is_some_and(|a| a.client_token == token);
         if !token_matches {
-            log::warn!("UnifiedPush Message received for unknown token; ignoring (possible spoof)");
+            crate::telemetry::warning!(
+                "UnifiedPush Message received for unknown token; ignoring (possible spoof)"
+            );
             return;
         }
         handle_message(&state, &token, &message, &id);
@@ -414,7 +418,7 @@ fn handle_message(state: &UnifiedPushState, _token: &str, message: &[u8], _id: &
     });
 
     if let Err(e) = crate::listeners::trigger("notification", payload.to_string()) {
-        log::warn!("Failed to dispatch push notification to listeners: {e}");
+        crate::telemetry::warning!("Failed to dispatch push notification to listeners: {e}");
     }
 
     // Route the toast display through the displayer callback supplied by
@@ -1,8 +1,13 @@
 //! Windows implementation for notifications plugin using native Windows Toast API.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ffi::c_void;
-use std::sync::{Arc, RwLock, Weak};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Condvar, Mutex, PoisonError, RwLock, Weak};
+use std::time::Duration;
 
 use nt_time::FileTime;
 use serde::de::DeserializeOwned;
@@ -11,30 +16,50 @@ use tauri::{
     plugin::{PermissionState, PluginApi},
 };
 use windows::ApplicationModel::Package;
-use windows::Data::Xml::Dom::XmlDocument;
+use windows::Data::Xml::Dom::{XmlDocument, XmlElement};
 use windows::Foundation::{DateTime, TypedEventHandler};
 #[cfg(feature = "push-notifications")]
 use windows::Networking::PushNotifications::{
     PushNotificationChannel, PushNotificationChannelManager,
 };
+pub use windows::UI::Notifications::NotificationSetting;
 use windows::UI::Notifications::{
-    NotificationSetting, ScheduledToastNotification, ToastActivatedEventArgs, ToastNotification,
-    ToastNotificationManager, ToastNotifier,
+    NotificationData as ToastData, NotificationUpdateResult, ScheduledToastNotification,
+    ToastActivatedEventArgs, ToastDismissalReason, ToastDismissedEventArgs, ToastNotification,
+    ToastNotificationHistory, ToastNotificationManager, ToastNotifier,
+};
+use windows::Win32::Foundation::{
+    CLASS_E_NOAGGREGATION, E_INVALIDARG, ERROR_FILE_NOT_FOUND, ERROR_SUCCESS, HANDLE, S_FALSE, S_OK,
 };
-use windows::Win32::Foundation::{CLASS_E_NOAGGREGATION, E_INVALIDARG, S_FALSE, S_OK};
 use windows::Win32::System::Com::{
     CLSCTX_LOCAL_SERVER, COINIT_APARTMENTTHREADED, CoInitializeEx, CoRegisterClassObject,
     IClassFactory, IClassFactory_Impl, REGCLS_MULTIPLEUSE,
 };
+use windows::Win32::System::Power::{
+    DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS, HPOWERNOTIFY, PowerRegisterSuspendResumeNotification,
+};
+use windows::Win32::System::Registry::{
+    HKEY, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, KEY_READ, KEY_WRITE, REG_OPTION_NON_VOLATILE,
+    REG_SZ, RegCloseKey, RegCreateKeyExW, RegDeleteTreeW, RegDeleteValueW, RegOpenKeyExW,
+    RegSetValueExW,
+};
 use windows::Win32::UI::Notifications::{
     INotificationActivationCallback, INotificationActivationCallback_Impl,
     NOTIFICATION_USER_INPUT_DATA,
 };
-use windows::core::{BOOL, GUID, HSTRING, Interface, PCWSTR, Ref, implement};
+use windows::Win32::UI::Shell::{
+    QUERY_USER_NOTIFICATION_STATE, QUNS_BUSY, QUNS_PRESENTATION_MODE, QUNS_QUIET_TIME,
+    QUNS_RUNNING_D3D_FULL_SCREEN, SHQueryUserNotificationState,
+};
+use windows::Win32::UI::WindowsAndMessaging::{DEVICE_NOTIFY_CALLBACK, PBT_APMRESUMEAUTOMATIC};
+use windows::core::{BOOL, GUID, HRESULT, HSTRING, Interface, PCWSTR, Ref, implement};
 
-use crate::WindowsConfig;
-use crate::error::{ErrorResponse, PluginInvokeError};
+use crate::action_types;
+use crate::error::WindowsErrorKind;
 use crate::models::*;
+use crate::telemetry;
+use crate::timers::{self, Timers};
+use crate::{MissedFirePolicy, RetryPolicy, WindowsConfig};
 
 /// True when the current process has MSIX package identity.
 ///
@@ -81,7 +106,7 @@ fn resolve_toast_image_src<R: Runtime>(
         return Some(input.to_string());
     }
     if lower.starts_with("data:") {
-        log::warn!(
+        telemetry::warning!(
             "Ignoring notification image data: URI: Windows toast schema doesn't \
              accept inline base64; write the bytes to a file and pass a file:/// URI"
         );
@@ -101,7 +126,7 @@ fn resolve_toast_image_src<R: Runtime>(
             return Some(path_to_file_uri(&resolved));
         }
     }
-    log::warn!(
+    telemetry::warning!(
         "Ignoring notification image {input:?}: not a supported URI scheme, not an \
          absolute path, and not resolvable as a Tauri resource"
     );
@@ -131,23 +156,64 @@ fn parse_clsid(raw: &str) -> windows::core::Result<GUID> {
     Ok(GUID::from_u128(parsed.as_u128()))
 }
 
+/// The kind of `err`. `Show` / `AddToSchedule` fail with a transient kind
+/// while the notification platform is still coming up (typically right after
+/// login); only those are retried.
+fn error_kind(err: &windows::core::Error) -> WindowsErrorKind {
+    WindowsErrorKind::from_hresult(err.code().0)
+}
+
+/// Diagnostic for a notifier that couldn't be created or queried, by the kind
+/// of error it failed with.
+fn unavailable_diagnostic(err: &crate::Error) -> crate::Diagnostic {
+    let kind = match err {
+        crate::Error::Windows { kind, .. } => Some(*kind),
+        _ => None,
+    };
+    let (severity, code, fix_hint) = match kind {
+        Some(kind) if kind.is_transient() => (
+            crate::Severity::Warning,
+            crate::DiagnosticCode::ToastsUnavailable,
+            "The notification platform is still starting; run the check again shortly",
+        ),
+        Some(WindowsErrorKind::Disabled) => (
+            crate::Severity::Error,
+            crate::DiagnosticCode::ToastsDisabled,
+            "Turn notifications on in Settings > System > Notifications",
+        ),
+        Some(WindowsErrorKind::AccessDenied) => (
+            crate::Severity::Error,
+            crate::DiagnosticCode::ToastsUnavailable,
+            "Run the app in the signed-in user's session; services and other users' sessions can't show toasts",
+        ),
+        _ => (
+            crate::Severity::Error,
+            crate::DiagnosticCode::ToastsUnavailable,
+            "Install the app so its AppUserModelID is registered, or set windows.requireRegisteredAppId to false",
+        ),
+    };
+    crate::Diagnostic::new(severity, code, err.to_string(), fix_hint)
+}
+
 // Enable `?` operator for windows::core::Error
 impl From<windows::core::Error> for crate::Error {
     fn from(err: windows::core::Error) -> Self {
-        crate::Error::from(PluginInvokeError::InvokeRejected(ErrorResponse {
-            code: Some(format!("0x{:08X}", err.code().0)),
-            message: Some(err.message().to_string()),
-            data: (),
-        }))
+        let code = format!("0x{:08X}", err.code().0);
+        let message = err.message().to_string();
+        telemetry::os_error(&code, &message);
+        crate::Error::Windows {
+            code,
+            hresult: err.code().0,
+            kind: error_kind(&err),
+            message,
+        }
     }
 }
 
 /// Shared plugin state wrapped in Arc for thread-safe access.
 pub struct WindowsPlugin {
-    app_id: String,
+    identity: RwLock<ToastIdentity>,
     packaged: bool,
-    notifier: ToastNotifier,
-    action_types: RwLock<HashMap<String, ActionType>>,
     click_listener_active: RwLock<bool>,
     /// Cold-start activation payloads queued before any JS listener has
     /// subscribed. Drained synchronously the first time a `notificationClicked`
@@ -158,10 +224,59 @@ pub struct WindowsPlugin {
     /// `None` when COM activator wasn't registered (unpackaged or no CLSID in
     /// config).
     _com_cookie: RwLock<Option<u32>>,
+    /// Backoff for transient `Show` / `AddToSchedule` failures.
+    retry: RetryPolicy,
+    /// What repeating schedules do with occurrences missed during sleep.
+    missed_fire_policy: MissedFirePolicy,
+    /// Longest the timer worker waits before re-reading the wall clock.
+    clock_check: Duration,
+    /// Repeating `Interval` schedules and count-down toasts by notification
+    /// id. Driven by the timer worker (see [`run_timers`]) and mirrored to
+    /// `timers_path` so they carry on after a restart.
+    timers: Mutex<Timers>,
+    /// Where `timers` is stored; `None` without an app data dir.
+    timers_path: Option<PathBuf>,
+    /// Wakes the timer worker to re-read `timers` and the wall clock.
+    wake_timers: Sender<()>,
+    /// Tags of toasts the user dismissed. Windows can keep them in the
+    /// history; `active` leaves them out, `notification_history` doesn't.
+    dismissed: RwLock<HashSet<String>>,
     #[cfg(feature = "push-notifications")]
     push_channel: RwLock<Option<PushNotificationChannel>>,
 }
 
+/// AUMID that toasts are shown and looked up under, with the notifier
+/// created for it. Both are replaced together by `set_app_id`.
+struct ToastIdentity {
+    app_id: String,
+    /// `Err` with the reason toasts can't be shown under `app_id`, see
+    /// [`WindowsConfig::require_registered_app_id`].
+    notifier: Result<ToastNotifier, String>,
+}
+
+impl ToastIdentity {
+    fn new(app_id: String, packaged: bool) -> windows::core::Result<Self> {
+        let notifier = if packaged {
+            ToastNotificationManager::CreateToastNotifier()?
+        } else {
+            ToastNotificationManager::CreateToastNotifierWithId(&HSTRING::from(&app_id))?
+        };
+        Ok(Self {
+            app_id,
+            notifier: Ok(notifier),
+        })
+    }
+
+    /// An identity that fails every toast call with
+    /// [`Error::PermissionDenied`](crate::Error::PermissionDenied).
+    const fn unavailable(app_id: String, reason: String) -> Self {
+        Self {
+            app_id,
+            notifier: Err(reason),
+        }
+    }
+}
+
 /// COM activator that receives toast activations from Action Center, including
 /// the cold-start case where Windows launches the exe via the manifest's
 /// `windows.toastNotificationActivation` extension.
@@ -187,7 +302,7 @@ struct ToastActivatorFactory {
 impl std::fmt::Debug for WindowsPlugin {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("WindowsPlugin")
-            .field("app_id", &self.app_id)
+            .field("app_id", &self.app_id())
             .field("packaged", &self.packaged)
             .finish_non_exhaustive()
     }
@@ -210,8 +325,8 @@ struct DecodedActivation {
 
 fn decode_activation(invoked_args: &str, inputs: &HashMap<String, String>) -> DecodedActivation {
     let input_value = inputs
-        .values()
-        .next()
+        .get(TOAST_INPUT_ID)
+        .or_else(|| inputs.values().next())
         .cloned()
         .map_or(serde_json::Value::Null, serde_json::Value::String);
 
@@ -255,6 +370,50 @@ fn decode_activation(invoked_args: &str, inputs: &HashMap<String, String>) -> De
     }
 }
 
+/// How long [`Notifications::read_launch_source`] waits for the activation
+/// that started the process to reach the COM activator.
+const LAUNCH_ACTIVATION_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Set by the first [`init`]. The COM activator, the resume listener and
+/// the app identity belong to the process, so a second plugin instance
+/// would register them again.
+static IS_INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+/// Marks `initialized`, failing if it already was.
+fn claim_init(initialized: &AtomicBool) -> crate::Result<()> {
+    initialized
+        .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+        .map(drop)
+        .map_err(|_| {
+            crate::Error::InvalidArgument("notifications plugin already initialized".to_string())
+        })
+}
+
+/// The first activation the COM activator received, as a launch source.
+static FIRST_ACTIVATION: (Mutex<Option<crate::LaunchSource>>, Condvar) =
+    (Mutex::new(None), Condvar::new());
+
+/// Launch source of an activation with `invoked_args`, decoded the way
+/// [`decode_activation`] does.
+fn activation_launch_source(invoked_args: &str) -> crate::LaunchSource {
+    let launch = serde_json::from_str::<serde_json::Value>(invoked_args)
+        .ok()
+        .filter(serde_json::Value::is_object);
+    match launch {
+        Some(launch) => crate::LaunchSource::NotificationTap {
+            id: launch
+                .get("id")
+                .and_then(serde_json::Value::as_i64)
+                .and_then(|id| i32::try_from(id).ok()),
+        },
+        None if invoked_args.is_empty() => crate::LaunchSource::NotificationTap { id: None },
+        None => crate::LaunchSource::NotificationAction {
+            id: None,
+            action_id: invoked_args.to_string(),
+        },
+    }
+}
+
 impl INotificationActivationCallback_Impl for ToastActivator_Impl {
     fn Activate(
         &self,
@@ -276,10 +435,18 @@ impl INotificationActivationCallback_Impl for ToastActivator_Impl {
             }
         }
 
+        let (first, activated) = &FIRST_ACTIVATION;
+        first
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_or_insert_with(|| activation_launch_source(&invoked));
+        activated.notify_all();
+
         let decoded = decode_activation(&invoked, &inputs);
         let _ = crate::listeners::trigger("actionPerformed", decoded.action.to_string());
 
         if let Some(click_payload) = decoded.click {
+            crate::focus::on_click(crate::listeners::parse_click(&click_payload).0);
             // Deliver live OR buffer — never both. Buffering when a listener is
             // already subscribed causes duplicate events on the next re-subscribe
             // (hot reload, route change).
@@ -318,20 +485,61 @@ impl IClassFactory_Impl for ToastActivatorFactory_Impl {
 }
 
 impl WindowsPlugin {
-    fn action_types(&self) -> crate::Result<HashMap<String, ActionType>> {
-        Ok(self
-            .action_types
-            .read()
-            .map_err(|_| crate::Error::Io(std::io::Error::other("Lock poisoned")))?
-            .clone())
+    fn identity(&self) -> std::sync::RwLockReadGuard<'_, ToastIdentity> {
+        match self.identity.read() {
+            Ok(identity) => identity,
+            Err(poisoned) => poisoned.into_inner(),
+        }
     }
 
-    fn action_types_mut(
-        &self,
-    ) -> crate::Result<std::sync::RwLockWriteGuard<'_, HashMap<String, ActionType>>> {
-        self.action_types
-            .write()
-            .map_err(|_| crate::Error::Io(std::io::Error::other("Lock poisoned")))
+    fn app_id(&self) -> String {
+        self.identity().app_id.clone()
+    }
+
+    fn notifier(&self) -> crate::Result<ToastNotifier> {
+        self.identity()
+            .notifier
+            .clone()
+            .map_err(crate::Error::PermissionDenied)
+    }
+
+    /// Shows the toasts of `group` that sort before the just-shown `tag`
+    /// again, so Action Center, which lists the newest toast first, shows
+    /// the group in sort-key order. Re-posted toasts skip the popup and, like
+    /// missed repeats, reach only the COM activator when clicked.
+    fn reorder_group(&self, notifier: &ToastNotifier, group: &str, tag: &str) -> crate::Result<()> {
+        let history = ToastNotificationManager::History()?;
+        let toasts = if self.packaged {
+            history.GetHistory()?
+        } else {
+            history.GetHistoryWithId(&HSTRING::from(self.app_id()))?
+        };
+        let mut children = Vec::new();
+        let mut contents = HashMap::new();
+        for i in 0..toasts.Size()? {
+            let toast = toasts.GetAt(i)?;
+            if toast_group(&toast).as_deref() != Some(group) {
+                continue;
+            }
+            let Ok(content) = toast.Content() else {
+                continue;
+            };
+            let child_tag = toast.Tag()?.to_string_lossy();
+            children.push(ActiveNotification {
+                tag: Some(child_tag.clone()),
+                sort_key: launch_field(&content, "sortKey"),
+                ..ActiveNotification::new(child_tag.parse().unwrap_or(0), None, None)
+            });
+            contents.insert(child_tag, content);
+        }
+        for child_tag in repost_order(children, tag) {
+            let toast = ToastNotification::CreateToastNotification(&contents[&child_tag])?;
+            toast.SetTag(&HSTRING::from(child_tag.as_str()))?;
+            toast.SetGroup(&HSTRING::from(group))?;
+            toast.SetSuppressPopup(true)?;
+            notifier.Show(&toast)?;
+        }
+        Ok(())
     }
 
     fn is_click_listener_active(&self) -> crate::Result<bool> {
@@ -357,15 +565,230 @@ impl WindowsPlugin {
         let drained: Vec<serde_json::Value> = match self.pending_clicks.write() {
             Ok(mut buf) => std::mem::take(&mut *buf),
             Err(e) => {
-                log::error!("pending_clicks lock poisoned during drain: {e}");
+                telemetry::error!("pending_clicks lock poisoned during drain: {e}");
                 return;
             }
         };
         for payload in drained {
             if let Err(e) = crate::listeners::trigger("notificationClicked", payload.to_string()) {
-                log::error!("Failed to dispatch buffered click: {e}");
+                telemetry::error!("Failed to dispatch buffered click: {e}");
+            }
+        }
+    }
+
+    /// Hands a failed delivery to a background retry loop when `err` is
+    /// transient and the policy allows another attempt, so `show` can return
+    /// right away with the toast queued. Otherwise returns `err` for the
+    /// caller to propagate.
+    ///
+    /// A queued toast is announced with `notificationQueued` before this
+    /// returns. The loop then emits `notification` once `deliver` succeeds,
+    /// or `notificationFailed` after the final attempt fails.
+    fn queue_retry<F>(
+        &self,
+        id: i32,
+        err: windows::core::Error,
+        event_payload: serde_json::Value,
+        deliver: F,
+    ) -> crate::Result<()>
+    where
+        F: Fn() -> windows::core::Result<Option<NotificationSetting>> + Send + 'static,
+    {
+        if !error_kind(&err).is_transient() || self.retry.attempts <= 1 {
+            return Err(err.into());
+        }
+        telemetry::info!(
+            "Notification {id} failed with transient error 0x{:08X}; retrying in the background",
+            err.code().0
+        );
+        let queued = serde_json::json!({
+            "id": id,
+            "code": format!("0x{:08X}", err.code().0),
+            "hresult": err.code().0,
+            "kind": error_kind(&err),
+            "attempts": self.retry.attempts,
+        });
+        if let Err(e) = crate::listeners::trigger("notificationQueued", queued.to_string()) {
+            telemetry::error!("Failed to trigger notificationQueued: {e}");
+        }
+        let policy = self.retry;
+        tauri::async_runtime::spawn_blocking(move || {
+            let mut last_error = err;
+            for retry in 1..policy.attempts {
+                std::thread::sleep(policy.delay(retry));
+                match deliver() {
+                    Ok(setting) => {
+                        trigger_delivered(event_payload, setting);
+                        return;
+                    }
+                    Err(e) => {
+                        let transient = error_kind(&e).is_transient();
+                        last_error = e;
+                        if !transient {
+                            break;
+                        }
+                    }
+                }
+            }
+            telemetry::error!("Notification {id} could not be delivered: {last_error}");
+            let payload = serde_json::json!({
+                "id": id,
+                "code": format!("0x{:08X}", last_error.code().0),
+                "hresult": last_error.code().0,
+                "kind": error_kind(&last_error),
+                "error": last_error.message().to_string(),
+            });
+            if let Err(e) = crate::listeners::trigger("notificationFailed", payload.to_string()) {
+                telemetry::error!("Failed to trigger notificationFailed: {e}");
+            }
+        });
+        Ok(())
+    }
+
+    /// Keeps the progress bar of count-down toast `id` in step with the time
+    /// left until `deadline`, replacing any count-down already stored for
+    /// `id`. The alarm toast in the Windows schedule for the deadline takes
+    /// over from there.
+    fn start_count_down(&self, id: i32, group: Option<String>, deadline: time::OffsetDateTime) {
+        let mut timers = self.timers_mut();
+        timers.count_downs.insert(
+            id,
+            timers::CountDown {
+                group,
+                deadline: deadline.unix_timestamp(),
+                total_secs: time_until(deadline).as_secs(),
+            },
+        );
+        timers::save(self.timers_path.as_deref(), &timers);
+        drop(timers);
+        self.resync_repeats();
+    }
+
+    fn dismissed_mut(&self) -> std::sync::RwLockWriteGuard<'_, HashSet<String>> {
+        match self.dismissed.write() {
+            Ok(dismissed) => dismissed,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Stops updating the count-downs of the notifications `cancel` matches
+    /// and returns their ids.
+    fn stop_count_downs(&self, cancel: impl Fn(i32) -> bool) -> Vec<i32> {
+        let mut timers = self.timers_mut();
+        let stopped: Vec<i32> = timers
+            .count_downs
+            .keys()
+            .copied()
+            .filter(|&id| cancel(id))
+            .collect();
+        if !stopped.is_empty() {
+            for id in &stopped {
+                timers.count_downs.remove(id);
+            }
+            timers::save(self.timers_path.as_deref(), &timers);
+        }
+        stopped
+    }
+
+    fn timers_mut(&self) -> std::sync::MutexGuard<'_, Timers> {
+        self.timers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Keeps the repeating schedule of notification `id` going from the
+    /// occurrence at `repeat.next`, which must already be in the Windows
+    /// schedule. Replaces any schedule already stored for `id`.
+    fn start_repeat(&self, id: i32, repeat: timers::Repeat) {
+        let mut timers = self.timers_mut();
+        timers.repeats.insert(id, repeat);
+        timers::save(self.timers_path.as_deref(), &timers);
+        drop(timers);
+        self.resync_repeats();
+    }
+
+    /// Has the timer worker recompute every stored timer against the wall
+    /// clock now (see [`due_repeats`]), e.g. after the machine resumed from
+    /// sleep.
+    fn resync_repeats(&self) {
+        let _ = self.wake_timers.send(());
+    }
+
+    /// Stops the repeating schedules `cancel` matches. Occurrences already
+    /// in the schedule are left to `cancel` to remove.
+    fn stop_repeats(&self, cancel: impl Fn(i32) -> bool) {
+        let mut timers = self.timers_mut();
+        let before = timers.repeats.len();
+        timers.repeats.retain(|&id, _| !cancel(id));
+        if timers.repeats.len() != before {
+            timers::save(self.timers_path.as_deref(), &timers);
+        }
+    }
+
+    /// Handles the stored repeats and count-downs that are due, and returns
+    /// how long the timer worker may wait before the next one is.
+    fn run_due_timers(&self) -> Duration {
+        let notifier = match self.notifier() {
+            Ok(notifier) => notifier,
+            Err(e) => {
+                telemetry::debug!("Not running notification timers: {e}");
+                return self.clock_check;
+            }
+        };
+        let mut timers = self.timers_mut();
+        let (mut wait, repeats_changed) = self.run_due_repeats(&notifier, &mut timers);
+        let count_downs_changed = update_count_downs(&notifier, &mut timers);
+        if !timers.count_downs.is_empty() {
+            wait = wait.min(COUNT_DOWN_TICK);
+        }
+        if repeats_changed || count_downs_changed {
+            timers::save(self.timers_path.as_deref(), &timers);
+        }
+        wait
+    }
+
+    /// Handles the repeating schedules whose occurrence fired by now, as
+    /// [`due_repeats`] works them out from the stored positions. Returns how
+    /// long the timer worker may wait before the next occurrence is due, and
+    /// whether `timers` changed.
+    fn run_due_repeats(&self, notifier: &ToastNotifier, timers: &mut Timers) -> (Duration, bool) {
+        let stored = timers.repeats.len();
+        let (due, mut wait) = due_repeats(
+            timers,
+            time::OffsetDateTime::now_utc(),
+            self.missed_fire_policy,
+            self.clock_check,
+        );
+        if due.is_empty() {
+            return (wait, timers.repeats.len() != stored);
+        }
+        for DueRepeat {
+            id,
+            catch_up,
+            reschedule,
+        } in due
+        {
+            let scheduled = {
+                let Some(repeat) = timers.repeats.get(&id) else {
+                    continue;
+                };
+                let toast = ScheduledToast::of(id, repeat);
+                if catch_up && let Err(e) = toast.show(notifier) {
+                    telemetry::error!("Failed to show the missed occurrence of {id}: {e}");
+                }
+                reschedule.map(|at| toast.schedule(notifier, at).map(|()| at))
+            };
+            match scheduled {
+                Some(Ok(at)) => wait = wait.min(time_until(at)),
+                Some(Err(e)) => {
+                    telemetry::error!("Failed to schedule the next occurrence of {id}: {e}");
+                    timers.repeats.remove(&id);
+                }
+                // No repetitions left.
+                None => {
+                    timers.repeats.remove(&id);
+                }
             }
         }
+        (wait, true)
     }
 
     fn open_push_channel(&self) -> crate::Result<String> {
@@ -412,31 +835,611 @@ impl WindowsPlugin {
     }
 }
 
+/// How often a count-down toast's progress bar is refreshed.
+const COUNT_DOWN_TICK: Duration = Duration::from_secs(1);
+
+/// Time left until `deadline`, zero once it has passed.
+fn time_until(deadline: time::OffsetDateTime) -> Duration {
+    (deadline - time::OffsetDateTime::now_utc())
+        .try_into()
+        .unwrap_or_default()
+}
+
+/// Moves the progress bar of every stored count-down toast to the time
+/// left until its deadline. Drops the count-downs whose deadline passed,
+/// whose toast is gone (dismissed by the user, or cleared while the app was
+/// closed) or that failed to update; the alarm still fires for them.
+/// Returns whether any was dropped.
+fn update_count_downs(notifier: &ToastNotifier, timers: &mut Timers) -> bool {
+    let stored = timers.count_downs.len();
+    timers.count_downs.retain(|&id, count_down| {
+        let deadline = time::OffsetDateTime::from_unix_timestamp(count_down.deadline)
+            .unwrap_or(time::OffsetDateTime::UNIX_EPOCH);
+        let remaining = time_until(deadline);
+        if remaining.is_zero() {
+            return false;
+        }
+        let total = Duration::from_secs(count_down.total_secs);
+        let tag = HSTRING::from(id.to_string());
+        let updated = count_down_data(remaining, total, count_down_sequence(remaining, total))
+            .and_then(|data| match count_down.group.as_deref() {
+                Some(group) => notifier.UpdateWithTagAndGroup(&data, &tag, &HSTRING::from(group)),
+                None => notifier.UpdateWithTag(&data, &tag),
+            });
+        match updated {
+            Ok(NotificationUpdateResult::Succeeded) => true,
+            Ok(_) => false,
+            Err(e) => {
+                telemetry::error!("Failed to update count-down of notification {id}: {e}");
+                false
+            }
+        }
+    });
+    timers.count_downs.len() != stored
+}
+
+/// Sequence number of a count-down update: the toast was shown with 1, and
+/// every later second counts up from 2, so an update computed after a
+/// restart still supersedes the ones before it.
+fn count_down_sequence(remaining: Duration, total: Duration) -> u32 {
+    u32::try_from(total.saturating_sub(remaining).as_secs())
+        .unwrap_or(u32::MAX)
+        .saturating_add(2)
+}
+
+/// String field `key` (`scope` or `sortKey`) that `build_toast_xml` wrote
+/// into the `launch=` attribute of `content`.
+fn launch_field(content: &XmlDocument, key: &str) -> Option<String> {
+    let launch = content
+        .DocumentElement()
+        .and_then(|toast| toast.GetAttribute(&HSTRING::from("launch")))
+        .ok()?;
+    field_of_launch(&launch.to_string_lossy(), key)
+}
+
+/// Title and body of a shown toast: its first two `<text>` elements, not
+/// counting the `summary` attribution line.
+fn toast_texts(content: &XmlDocument) -> windows::core::Result<(Option<String>, Option<String>)> {
+    let elements = content.GetElementsByTagName(&HSTRING::from("text"))?;
+    let mut texts = Vec::new();
+    for i in 0..elements.Size()? {
+        let element = elements.GetAt(i)?.cast::<XmlElement>()?;
+        if element.GetAttribute(&HSTRING::from("placement"))? != "attribution" {
+            texts.push(element.InnerText()?.to_string_lossy());
+        }
+    }
+    let mut texts = texts.into_iter();
+    Ok((texts.next(), texts.next()))
+}
+
+/// `placement` and `src` of each `<image>` element of a shown toast.
+fn toast_images(content: &XmlDocument) -> windows::core::Result<Vec<(String, String)>> {
+    let images = content.GetElementsByTagName(&HSTRING::from("image"))?;
+    let mut result = Vec::new();
+    for i in 0..images.Size()? {
+        let image = images.GetAt(i)?.cast::<XmlElement>()?;
+        result.push((
+            image
+                .GetAttribute(&HSTRING::from("placement"))?
+                .to_string_lossy(),
+            image.GetAttribute(&HSTRING::from("src"))?.to_string_lossy(),
+        ));
+    }
+    Ok(result)
+}
+
+/// The attachments a toast was shown with, from its `(placement, src)`
+/// images in document order. The app logo is the notification's `icon`,
+/// not an attachment. Attachment ids aren't kept in the toast, so they are
+/// numbered; remote images cached by the `media-cache` feature come back as
+/// the cached file.
+fn toast_attachments(images: Vec<(String, String)>) -> Vec<Attachment> {
+    images
+        .into_iter()
+        .filter(|(placement, _)| placement != "appLogoOverride")
+        .filter_map(|(_, src)| url::Url::parse(&src).ok())
+        .enumerate()
+        .map(|(index, url)| Attachment::new(index.to_string(), url))
+        .collect()
+}
+
+fn field_of_launch(launch: &str, key: &str) -> Option<String> {
+    serde_json::from_str::<serde_json::Value>(launch)
+        .ok()?
+        .get(key)?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Tags of the `children` of a group to show again after `shown` was
+/// posted, in posting order: the ones sorting before it, last first. Empty
+/// when none of them has a sort key.
+fn repost_order(mut children: Vec<ActiveNotification>, shown: &str) -> Vec<String> {
+    if children.iter().all(|child| child.sort_key.is_none()) {
+        return Vec::new();
+    }
+    children.sort_by(ActiveNotification::cmp_in_group);
+    let Some(position) = children
+        .iter()
+        .position(|child| child.tag.as_deref() == Some(shown))
+    else {
+        return Vec::new();
+    };
+    children.truncate(position);
+    children
+        .into_iter()
+        .rev()
+        .filter_map(|child| child.tag)
+        .collect()
+}
+
+/// The part of `ToastNotificationHistory` that removal needs, so matching
+/// tags and groups can be tested without WinRT.
+trait ToastHistory {
+    /// `(tag, group)` of every toast of this app in Action Center.
+    fn entries(&self) -> crate::Result<Vec<(String, Option<String>)>>;
+    fn remove(&self, tag: &str, group: Option<&str>) -> crate::Result<()>;
+}
+
+/// Action Center history, scoped to `app_id` for unpackaged apps.
+struct WinToastHistory {
+    history: ToastNotificationHistory,
+    /// `None` when packaged: the package identity scopes the calls.
+    app_id: Option<HSTRING>,
+}
+
+impl ToastHistory for WinToastHistory {
+    fn entries(&self) -> crate::Result<Vec<(String, Option<String>)>> {
+        let list = match &self.app_id {
+            Some(app_id) => self.history.GetHistoryWithId(app_id)?,
+            None => self.history.GetHistory()?,
+        };
+        let mut entries = Vec::new();
+        for i in 0..list.Size()? {
+            let notification = list.GetAt(i)?;
+            entries.push((
+                notification.Tag()?.to_string_lossy(),
+                toast_group(&notification),
+            ));
+        }
+        Ok(entries)
+    }
+
+    fn remove(&self, tag: &str, group: Option<&str>) -> crate::Result<()> {
+        let tag = HSTRING::from(tag);
+        match (&self.app_id, group) {
+            (None, None) => self.history.Remove(&tag)?,
+            (None, Some(group)) => self.history.RemoveGroupedTag(&tag, &HSTRING::from(group))?,
+            // There is no `RemoveWithId`; an empty group is the ungrouped
+            // toast of that app.
+            (Some(app_id), group) => self.history.RemoveGroupedTagWithId(
+                &tag,
+                &HSTRING::from(group.unwrap_or_default()),
+                app_id,
+            )?,
+        }
+        Ok(())
+    }
+}
+
+/// Group of a delivered toast. Windows reports ungrouped toasts with an
+/// empty group.
+fn toast_group(notification: &ToastNotification) -> Option<String> {
+    notification
+        .Group()
+        .ok()
+        .filter(|g| !g.is_empty())
+        .map(|g| g.to_string_lossy())
+}
+
+/// Removes every toast whose tag is exactly `tag`, with the group it was
+/// shown under; Windows ignores a removal whose group doesn't match. A tag
+/// missing from `entries` is still removed as ungrouped, in case the toast
+/// arrived after the listing.
+fn remove_tag(
+    history: &impl ToastHistory,
+    entries: &[(String, Option<String>)],
+    tag: &str,
+) -> crate::Result<()> {
+    let mut found = false;
+    for (_, group) in entries.iter().filter(|(t, _)| t == tag) {
+        history.remove(tag, group.as_deref())?;
+        found = true;
+    }
+    if !found {
+        history.remove(tag, None)?;
+    }
+    Ok(())
+}
+
+/// Removes the toast of each id from `history`, reading it only once.
+fn remove_ids(history: &impl ToastHistory, ids: Vec<i32>) -> crate::Result<crate::BulkResult> {
+    let entries = history.entries()?;
+    Ok(ids
+        .into_iter()
+        .map(|id| (id, remove_tag(history, &entries, &id.to_string())))
+        .collect())
+}
+
+/// The part of the toast schedule that canceling by group needs, so the
+/// matching can be tested without WinRT.
+trait ToastSchedule {
+    /// `(tag, group)` of every scheduled toast of this app.
+    fn entries(&self) -> crate::Result<Vec<(String, Option<String>)>>;
+    /// Removes the toast at `index` in [`entries`](Self::entries).
+    fn remove(&self, index: usize) -> crate::Result<()>;
+}
+
+/// Snapshot of the notifier's schedule, taken when it's created.
+struct WinToastSchedule {
+    notifier: ToastNotifier,
+    scheduled: Vec<ScheduledToastNotification>,
+}
+
+impl WinToastSchedule {
+    fn new(notifier: ToastNotifier) -> crate::Result<Self> {
+        let scheduled = notifier
+            .GetScheduledToastNotifications()?
+            .into_iter()
+            .collect();
+        Ok(Self {
+            notifier,
+            scheduled,
+        })
+    }
+}
+
+impl ToastSchedule for WinToastSchedule {
+    fn entries(&self) -> crate::Result<Vec<(String, Option<String>)>> {
+        self.scheduled
+            .iter()
+            .map(|notification| {
+                let group = notification
+                    .Group()
+                    .ok()
+                    .filter(|g| !g.is_empty())
+                    .map(|g| g.to_string_lossy());
+                Ok((notification.Tag()?.to_string_lossy(), group))
+            })
+            .collect()
+    }
+
+    fn remove(&self, index: usize) -> crate::Result<()> {
+        self.notifier.RemoveFromSchedule(&self.scheduled[index])?;
+        Ok(())
+    }
+}
+
+/// Removes every toast scheduled under `group` and returns their ids.
+/// Ungrouped toasts never match, and neither does a group that only
+/// starts with `group`.
+fn cancel_group(schedule: &impl ToastSchedule, group: &str) -> crate::Result<Vec<i32>> {
+    let mut canceled = Vec::new();
+    for (index, (tag, _)) in schedule
+        .entries()?
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, g))| g.as_deref() == Some(group))
+    {
+        schedule.remove(index)?;
+        if let Ok(id) = tag.parse::<i32>() {
+            canceled.push(id);
+        }
+    }
+    Ok(canceled)
+}
+
+/// What it takes to schedule another occurrence of a toast.
+struct ScheduledToast<'a> {
+    xml: &'a str,
+    tag: HSTRING,
+    group: Option<HSTRING>,
+}
+
+impl<'a> ScheduledToast<'a> {
+    /// The toast of stored repeating schedule `id`.
+    fn of(id: i32, repeat: &'a timers::Repeat) -> Self {
+        Self {
+            xml: &repeat.xml,
+            tag: HSTRING::from(id.to_string()),
+            group: repeat.group.as_deref().map(HSTRING::from),
+        }
+    }
+
+    fn document(&self) -> crate::Result<XmlDocument> {
+        let xml = XmlDocument::new()?;
+        xml.LoadXml(&HSTRING::from(self.xml))?;
+        Ok(xml)
+    }
+
+    fn schedule(&self, notifier: &ToastNotifier, at: time::OffsetDateTime) -> crate::Result<()> {
+        let scheduled = ScheduledToastNotification::CreateScheduledToastNotification(
+            &self.document()?,
+            unix_to_windows_datetime(at)?,
+        )?;
+        scheduled.SetTag(&self.tag)?;
+        if let Some(group) = &self.group {
+            scheduled.SetGroup(group)?;
+        }
+        notifier.AddToSchedule(&scheduled)?;
+        Ok(())
+    }
+
+    /// Shows the toast right away, for an occurrence that was missed.
+    fn show(&self, notifier: &ToastNotifier) -> crate::Result<()> {
+        let toast = ToastNotification::CreateToastNotification(&self.document()?)?;
+        toast.SetTag(&self.tag)?;
+        if let Some(group) = &self.group {
+            toast.SetGroup(group)?;
+        }
+        notifier.Show(&toast)?;
+        Ok(())
+    }
+}
+
+/// Default of [`WindowsConfig::clock_check_interval_secs`].
+const DEFAULT_CLOCK_CHECK_SECS: u64 = 60;
+
+/// Where a repeating schedule stands: the occurrence that's in the Windows
+/// schedule and how many repetitions are left after it.
+struct RepeatCursor {
+    next: time::OffsetDateTime,
+    period: time::Duration,
+    remaining: Option<u32>,
+}
+
+/// What a repeat loop does after reading the wall clock.
+#[derive(Debug, PartialEq, Eq)]
+enum RepeatStep {
+    /// The scheduled occurrence is still ahead.
+    Wait(Duration),
+    /// The scheduled occurrence fired. Shows one toast for the occurrences
+    /// missed since if `catch_up`, then schedules the one at `reschedule`,
+    /// or stops when there are no repetitions left.
+    Fire {
+        catch_up: bool,
+        reschedule: Option<time::OffsetDateTime>,
+    },
+    /// No repetitions left.
+    Done,
+}
+
+impl RepeatCursor {
+    fn of(repeat: &timers::Repeat) -> Self {
+        Self {
+            next: time::OffsetDateTime::from_unix_timestamp(repeat.next)
+                .unwrap_or(time::OffsetDateTime::UNIX_EPOCH),
+            period: time::Duration::seconds(repeat.period_secs),
+            remaining: repeat.remaining,
+        }
+    }
+
+    /// Writes the position back into the stored schedule.
+    const fn store(&self, repeat: &mut timers::Repeat) {
+        repeat.next = self.next.unix_timestamp();
+        repeat.remaining = self.remaining;
+    }
+
+    /// Advances past every occurrence due at `now`. Calling it again with
+    /// the same `now` waits, so an occurrence is handled exactly once
+    /// however often the loop is woken up.
+    fn step(&mut self, now: time::OffsetDateTime, policy: MissedFirePolicy) -> RepeatStep {
+        if self.remaining == Some(0) {
+            return RepeatStep::Done;
+        }
+        if now < self.next {
+            return RepeatStep::Wait((self.next - now).try_into().unwrap_or_default());
+        }
+        let (following, missed) = catch_up(self.next, self.period, now);
+        if missed > 0 {
+            telemetry::info!("Repeating notification missed {missed} occurrence(s)");
+        }
+        let remaining = self
+            .remaining
+            .map(|remaining| remaining.saturating_sub(missed));
+        let reschedule = (remaining != Some(0)).then_some(following);
+        self.next = following;
+        self.remaining = match reschedule {
+            Some(_) => remaining.map(|remaining| remaining - 1),
+            None => Some(0),
+        };
+        RepeatStep::Fire {
+            catch_up: missed > 0 && policy == MissedFirePolicy::FireOnce,
+            reschedule,
+        }
+    }
+}
+
+/// A stored repeat whose scheduled occurrence fired, see [`RepeatStep::Fire`].
+#[derive(Debug, PartialEq, Eq)]
+struct DueRepeat {
+    id: i32,
+    catch_up: bool,
+    reschedule: Option<time::OffsetDateTime>,
+}
+
+/// Re-reads every stored repeat against the wall clock at `now`, e.g. after
+/// a resume from sleep or an app restart. Advances the ones whose
+/// occurrence fired, drops those with no repetitions left, and returns the
+/// fired ones with how long the timer worker may wait, at most `max_wait`.
+/// Stored positions move past `now`, so resyncing again at the same time
+/// returns nothing: each occurrence is handled once however often the
+/// worker is woken.
+fn due_repeats(
+    timers: &mut Timers,
+    now: time::OffsetDateTime,
+    policy: MissedFirePolicy,
+    max_wait: Duration,
+) -> (Vec<DueRepeat>, Duration) {
+    let mut due = Vec::new();
+    let mut wait = max_wait;
+    timers.repeats.retain(|&id, repeat| {
+        let mut cursor = RepeatCursor::of(repeat);
+        let step = cursor.step(now, policy);
+        cursor.store(repeat);
+        match step {
+            RepeatStep::Wait(until) => wait = wait.min(until),
+            RepeatStep::Done => return false,
+            RepeatStep::Fire {
+                catch_up,
+                reschedule,
+            } => due.push(DueRepeat {
+                id,
+                catch_up,
+                reschedule,
+            }),
+        }
+        true
+    });
+    due.sort_unstable_by_key(|d| d.id);
+    (due, wait)
+}
+
+/// Next occurrence after `now` of a schedule repeating every `period` whose
+/// occurrence at `fired` was delivered, and how many occurrences in between
+/// came due as well, e.g. while the machine slept.
+fn catch_up(
+    fired: time::OffsetDateTime,
+    period: time::Duration,
+    now: time::OffsetDateTime,
+) -> (time::OffsetDateTime, u32) {
+    let elapsed = (now - fired).whole_nanoseconds();
+    let missed =
+        u32::try_from((elapsed / period.whole_nanoseconds().max(1)).max(0)).unwrap_or(u32::MAX);
+    (fired + period * missed.saturating_add(1), missed)
+}
+
+/// Time between two occurrences of a repeating schedule: a week for a
+/// weekday schedule, the interval itself otherwise. `None` for schedules
+/// that don't repeat on Windows, including an empty interval.
+fn repeat_period(schedule: &Schedule) -> Option<time::Duration> {
+    match schedule {
+        Schedule::Interval {
+            interval: ScheduleInterval {
+                weekday: Some(_), ..
+            },
+            ..
+        } => Some(time::Duration::WEEK),
+        Schedule::Interval { interval, .. } => {
+            let period = interval_duration(interval);
+            period.is_positive().then_some(period)
+        }
+        Schedule::At { .. } | Schedule::Every { .. } => None,
+    }
+}
+
+/// The interval's day, hour, minute and second fields as one duration.
+fn interval_duration(interval: &ScheduleInterval) -> time::Duration {
+    time::Duration::days(i64::from(interval.day.unwrap_or(0)))
+        + time::Duration::hours(i64::from(interval.hour.unwrap_or(0)))
+        + time::Duration::minutes(i64::from(interval.minute.unwrap_or(0)))
+        + time::Duration::seconds(i64::from(interval.second.unwrap_or(0)))
+}
+
+/// `NotificationData` feeding the count-down progress bar bindings.
+fn count_down_data(
+    remaining: Duration,
+    total: Duration,
+    sequence: u32,
+) -> windows::core::Result<ToastData> {
+    let data = ToastData::new()?;
+    let values = data.Values()?;
+    for (key, value) in count_down_bindings(remaining, total) {
+        values.Insert(&HSTRING::from(key), &HSTRING::from(value))?;
+    }
+    data.SetSequenceNumber(sequence)?;
+    Ok(data)
+}
+
+/// `NotificationData` feeding the progress bar binding of a toast shown with
+/// [`progress`](crate::NotificationsBuilder::progress). Sequence number 0
+/// always applies.
+fn progress_data(fraction: f32) -> windows::core::Result<ToastData> {
+    let data = ToastData::new()?;
+    data.Values()?.Insert(
+        &HSTRING::from("progressValue"),
+        &HSTRING::from(format!("{fraction:.3}")),
+    )?;
+    Ok(data)
+}
+
 pub fn init<R: Runtime, C: DeserializeOwned>(
     app: &AppHandle<R>,
     _api: PluginApi<R, C>,
     windows_config: WindowsConfig,
+    app_id: Option<String>,
 ) -> crate::Result<Notifications<R>> {
-    let app_id = app.config().identifier.clone();
     let packaged = is_packaged();
-    let notifier = if packaged {
-        ToastNotificationManager::CreateToastNotifier()?
-    } else {
-        ToastNotificationManager::CreateToastNotifierWithId(&HSTRING::from(&app_id))?
+    if packaged && app_id.is_some() {
+        telemetry::warning!("Ignoring `windowsAppId`: packaged apps use their package's AUMID");
+    }
+    let app_id = match app_id {
+        Some(app_id) => {
+            crate::validate_windows_app_id(&app_id)?;
+            app_id
+        }
+        None => app.config().identifier.clone(),
     };
+    claim_init(&IS_INITIALIZED)?;
+    let timers_path = timers::path(app);
+    let (wake_timers, timers_woken) = std::sync::mpsc::channel();
+
+    // Registered before the identity is resolved so that it counts for
+    // `require_registered_app_id`.
+    if windows_config.register_app_identity && !packaged {
+        let display_name = app
+            .config()
+            .product_name
+            .clone()
+            .unwrap_or_else(|| app_id.clone());
+        let icon = windows_config
+            .app_identity_icon
+            .as_deref()
+            .and_then(|icon| resolve_identity_icon(app, icon));
+        if let Err(e) = write_app_identity(&app_id, &display_name, icon.as_deref()) {
+            telemetry::warning!("Failed to register app identity for toasts: {e}");
+        }
+    }
 
     let plugin = Arc::new(WindowsPlugin {
-        app_id,
+        identity: RwLock::new(resolve_toast_identity(
+            app_id,
+            packaged,
+            windows_config.require_registered_app_id,
+        )),
         packaged,
-        notifier,
-        action_types: RwLock::new(HashMap::new()),
         click_listener_active: RwLock::new(false),
         pending_clicks: RwLock::new(Vec::new()),
         _com_cookie: RwLock::new(None),
+        retry: windows_config.retry,
+        missed_fire_policy: windows_config.missed_fire_policy,
+        clock_check: Duration::from_secs(
+            windows_config
+                .clock_check_interval_secs
+                .unwrap_or(DEFAULT_CLOCK_CHECK_SECS)
+                .max(1),
+        ),
+        timers: Mutex::new(timers::load(timers_path.as_deref())),
+        timers_path,
+        wake_timers,
+        dismissed: RwLock::new(HashSet::new()),
         #[cfg(feature = "push-notifications")]
         push_channel: RwLock::new(None),
     });
 
+    // Also picks up the repeats and count-downs stored by the last run.
+    let worker = Arc::downgrade(&plugin);
+    if let Err(e) = std::thread::Builder::new()
+        .name("notification-timers".into())
+        .spawn(move || run_timers(&worker, &timers_woken))
+    {
+        telemetry::error!("Failed to start the notification timer thread: {e}");
+    }
+
+    if let Err(e) = register_resume_listener(&plugin) {
+        telemetry::warning!("Failed to listen for resume from sleep: {e}");
+    }
+
     if packaged {
         if let Some(clsid_str) = windows_config.toast_activator_clsid.as_deref() {
             match register_toast_activator(&plugin, clsid_str) {
@@ -444,10 +1447,12 @@ pub fn init<R: Runtime, C: DeserializeOwned>(
                     if let Ok(mut slot) = plugin._com_cookie.write() {
                         *slot = Some(cookie);
                     }
-                    log::info!("Toast activator registered (clsid={clsid_str}, cookie={cookie})");
+                    telemetry::info!(
+                        "Toast activator registered (clsid={clsid_str}, cookie={cookie})"
+                    );
                 }
                 Err(e) => {
-                    log::error!(
+                    telemetry::error!(
                         "Failed to register toast activator (clsid={clsid_str}): {e}; \
                          Action Center clicks will fall back to shortcut launch without payload"
                     );
@@ -456,12 +1461,217 @@ pub fn init<R: Runtime, C: DeserializeOwned>(
         }
     }
 
-    Ok(Notifications {
+    let notifications = Notifications {
         app: app.clone(),
         plugin,
-    })
-}
-
+    };
+    // `show_only_once` toasts due before this start were shown or missed;
+    // either way they must not show now.
+    let due = crate::show_once::take_due(app, time::OffsetDateTime::now_utc().unix_timestamp());
+    if !due.is_empty()
+        && let Err(e) = notifications.cancel(due)
+    {
+        telemetry::warning!("Failed to cancel expired show-once notifications: {e}");
+    }
+
+    Ok(notifications)
+}
+
+/// Runs the repeating schedules and count-downs of `plugin` until it's
+/// dropped. One thread serves all of them. Waits never exceed the clock
+/// check interval, since they don't advance while the machine sleeps, and
+/// are cut short whenever the schedules change or the machine resumes.
+fn run_timers(plugin: &Weak<WindowsPlugin>, woken: &Receiver<()>) {
+    while let Some(wait) = plugin.upgrade().map(|plugin| plugin.run_due_timers()) {
+        if woken.recv_timeout(wait) == Err(RecvTimeoutError::Disconnected) {
+            return;
+        }
+    }
+}
+
+/// Has the timer worker re-read the wall clock as soon as the machine
+/// resumes from sleep, rather than at its next check. The registration is
+/// kept for the process lifetime, like the COM activator's.
+fn register_resume_listener(plugin: &Arc<WindowsPlugin>) -> crate::Result<()> {
+    unsafe extern "system" fn on_power_event(
+        context: *const c_void,
+        kind: u32,
+        _setting: *const c_void,
+    ) -> u32 {
+        if kind == PBT_APMRESUMEAUTOMATIC {
+            // `context` is the `Weak` leaked below, which is never released.
+            let plugin = std::mem::ManuallyDrop::new(unsafe {
+                Weak::from_raw(context.cast::<WindowsPlugin>())
+            });
+            if let Some(plugin) = plugin.upgrade() {
+                plugin.resync_repeats();
+            }
+        }
+        ERROR_SUCCESS.0
+    }
+
+    let parameters = Box::leak(Box::new(DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS {
+        Callback: Some(on_power_event),
+        Context: Weak::into_raw(Arc::downgrade(plugin)).cast_mut().cast(),
+    }));
+    let mut registration = HPOWERNOTIFY::default();
+    unsafe {
+        PowerRegisterSuspendResumeNotification(
+            DEVICE_NOTIFY_CALLBACK,
+            HANDLE(std::ptr::from_mut(parameters).cast()),
+            &raw mut registration,
+        )
+    }
+    .ok()?;
+    Ok(())
+}
+
+/// The identity toasts use from plugin setup on. When the notifier can't be
+/// created, or the AUMID of an unpackaged app isn't registered and
+/// `require_registered` is set, toast calls fail with
+/// [`Error::PermissionDenied`](crate::Error::PermissionDenied) instead of
+/// plugin setup failing.
+fn resolve_toast_identity(
+    app_id: String,
+    packaged: bool,
+    require_registered: bool,
+) -> ToastIdentity {
+    if !packaged && !app_id_registered(&app_id) {
+        let reason = format!(
+            "AppUserModelID {app_id:?} is not registered; set `registerAppIdentity` or \
+             install a Start Menu shortcut carrying it"
+        );
+        if require_registered {
+            telemetry::warning!("Toasts disabled: {reason}");
+            return ToastIdentity::unavailable(app_id, reason);
+        }
+        telemetry::info!("{reason}; toasts may not be shown");
+    }
+    match ToastIdentity::new(app_id.clone(), packaged) {
+        Ok(identity) => identity,
+        Err(e) => {
+            let reason = format!("failed to create a toast notifier for {app_id:?}: {e}");
+            telemetry::error!("Toasts disabled: {reason}");
+            ToastIdentity::unavailable(app_id, reason)
+        }
+    }
+}
+
+/// Whether `app_id` has an `AppUserModelId` registry key, for the current
+/// user or the machine. Start Menu shortcuts carrying the AUMID aren't
+/// inspected.
+fn app_id_registered(app_id: &str) -> bool {
+    let subkey = HSTRING::from(app_identity_key(app_id));
+    [HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE]
+        .into_iter()
+        .any(|root| {
+            let mut key = HKEY::default();
+            let opened = unsafe {
+                RegOpenKeyExW(root, PCWSTR(subkey.as_ptr()), None, KEY_READ, &raw mut key)
+            }
+            .is_ok();
+            if opened {
+                let _ = unsafe { RegCloseKey(key) };
+            }
+            opened
+        })
+}
+
+/// Absolute paths are used as-is; relative ones are looked up among the
+/// app's resources.
+fn resolve_identity_icon<R: Runtime>(app: &AppHandle<R>, icon: &str) -> Option<std::path::PathBuf> {
+    let path = std::path::Path::new(icon);
+    if path.is_absolute() {
+        return Some(path.to_path_buf());
+    }
+    match app
+        .path()
+        .resolve(icon, tauri::path::BaseDirectory::Resource)
+    {
+        Ok(resolved) if resolved.exists() => Some(resolved),
+        _ => {
+            telemetry::warning!("Ignoring app identity icon {icon:?}: not found in resources");
+            None
+        }
+    }
+}
+
+/// Registry key Windows reads an unpackaged app's toast display name and
+/// icon from.
+fn app_identity_key(app_id: &str) -> String {
+    format!("Software\\Classes\\AppUserModelId\\{app_id}")
+}
+
+/// `REG_SZ` data: UTF-16LE with a terminating NUL.
+fn reg_sz(value: &str) -> Vec<u8> {
+    value
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .flat_map(u16::to_le_bytes)
+        .collect()
+}
+
+/// Writes `DisplayName` and `IconUri` under
+/// `HKCU\Software\Classes\AppUserModelId\{app_id}`. `IconUri` is removed
+/// when `icon` is `None` so a previously registered icon doesn't linger.
+fn write_app_identity(
+    app_id: &str,
+    display_name: &str,
+    icon: Option<&std::path::Path>,
+) -> windows::core::Result<()> {
+    let subkey = HSTRING::from(app_identity_key(app_id));
+    let mut key = HKEY::default();
+    unsafe {
+        RegCreateKeyExW(
+            HKEY_CURRENT_USER,
+            PCWSTR(subkey.as_ptr()),
+            None,
+            PCWSTR::null(),
+            REG_OPTION_NON_VOLATILE,
+            KEY_WRITE,
+            None,
+            &raw mut key,
+            None,
+        )
+    }
+    .ok()?;
+    let set = |name: &str, value: &str| {
+        let name = HSTRING::from(name);
+        unsafe {
+            RegSetValueExW(
+                key,
+                PCWSTR(name.as_ptr()),
+                None,
+                REG_SZ,
+                Some(reg_sz(value).as_slice()),
+            )
+        }
+        .ok()
+    };
+    let result = set("DisplayName", display_name).and_then(|()| match icon {
+        Some(icon) => set("IconUri", &icon.display().to_string()),
+        None => {
+            let name = HSTRING::from("IconUri");
+            match unsafe { RegDeleteValueW(key, PCWSTR(name.as_ptr())) } {
+                ERROR_FILE_NOT_FOUND => Ok(()),
+                error => error.ok(),
+            }
+        }
+    });
+    let _ = unsafe { RegCloseKey(key) };
+    result
+}
+
+/// Removes the key written by [`write_app_identity`]; a missing key is not
+/// an error.
+fn delete_app_identity(app_id: &str) -> windows::core::Result<()> {
+    let subkey = HSTRING::from(app_identity_key(app_id));
+    match unsafe { RegDeleteTreeW(HKEY_CURRENT_USER, PCWSTR(subkey.as_ptr())) } {
+        ERROR_FILE_NOT_FOUND => Ok(()),
+        error => error.ok(),
+    }
+}
+
 /// Initialize COM for the current thread (apartment-threaded) and register a
 /// `ToastActivatorFactory` for the given CLSID. Returns the
 /// `CoRegisterClassObject` cookie on success.
@@ -493,155 +1703,423 @@ fn register_toast_activator(
     }
 }
 
-impl<R: Runtime> crate::NotificationsBuilder<R> {
-    /// Build toast notification XML using DOM API (safer than string concatenation).
-    fn build_toast_xml(
-        &self,
-        action_types: &HashMap<String, ActionType>,
-    ) -> crate::Result<XmlDocument> {
-        let doc = XmlDocument::new()?;
-
-        // Create root <toast>
-        let toast = doc.CreateElement(&HSTRING::from("toast"))?;
-        doc.AppendChild(&toast)?;
-
-        // Encode notification id + extras into `launch=` so the click payload
-        // survives a cold-start activation (the COM `Activate` callback only
-        // receives the launch string; the in-process `Activated` handler
-        // delivers the same string in `ToastActivatedEventArgs.Arguments`).
-        let launch = serde_json::json!({
-            "id": self.data.id,
-            "data": self.data.extra,
-        });
-        toast.SetAttribute(
-            &HSTRING::from("launch"),
-            &HSTRING::from(launch.to_string().as_str()),
-        )?;
-
-        // Create <visual><binding template="ToastGeneric">
-        let visual = doc.CreateElement(&HSTRING::from("visual"))?;
-        let binding = doc.CreateElement(&HSTRING::from("binding"))?;
-        binding.SetAttribute(&HSTRING::from("template"), &HSTRING::from("ToastGeneric"))?;
+/// `id` of the text box `render_toast_xml` adds for actions with `input`.
+const TOAST_INPUT_ID: &str = "userText";
+
+/// Text typed into the toast's reply box, from an in-process activation.
+fn activation_input(args: &ToastActivatedEventArgs) -> Option<String> {
+    let value = args
+        .UserInput()
+        .ok()?
+        .Lookup(&HSTRING::from(TOAST_INPUT_ID))
+        .ok()?;
+    HSTRING::try_from(&value)
+        .ok()
+        .map(|text| text.to_string_lossy())
+}
 
-        // Add <text> elements for title/body
-        if let Some(title) = &self.data.title {
-            let text = doc.CreateElement(&HSTRING::from("text"))?;
-            text.SetInnerText(&HSTRING::from(title.as_str()))?;
-            binding.AppendChild(&text)?;
+/// Escape the five XML special characters so user-supplied strings can be
+/// spliced into element text and attribute values.
+fn escape_xml(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
         }
+    }
+    out
+}
+
+/// Button text for a media control; toasts have no built-in playback icons.
+const fn media_action_label(action: MediaAction) -> &'static str {
+    match action {
+        MediaAction::Previous => "Previous",
+        MediaAction::Play => "Play",
+        MediaAction::Pause => "Pause",
+        MediaAction::Next => "Next",
+    }
+}
+
+/// Time left on a count-down as `m:ss`, or `h:mm:ss` from an hour up.
+fn format_remaining(secs: u64) -> String {
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+/// Values for the count-down progress bar's data bindings: the bar empties
+/// as `remaining` runs down from `total`.
+fn count_down_bindings(remaining: Duration, total: Duration) -> [(&'static str, String); 2] {
+    let fraction = if total.is_zero() {
+        0.0
+    } else {
+        (remaining.as_secs_f64() / total.as_secs_f64()).clamp(0.0, 1.0)
+    };
+    // Round up so a fresh five-minute timer reads 5:00, not 4:59.
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    [
+        ("progressValue", format!("{fraction:.3}")),
+        (
+            "progressValueString",
+            format!("{} remaining", format_remaining(secs)),
+        ),
+    ]
+}
+
+/// Render the toast XML for `data`.
+///
+/// Kept free of `WinRT` calls so the layout can be snapshot-tested; `show`
+/// feeds the result to `XmlDocument::LoadXml`. `resolve_image` maps the
+/// user-supplied icon/attachment strings to a URI the toast schema accepts
+/// (see `resolve_toast_image_src`) — returning `None` drops that image so the
+/// rest of the toast still renders instead of falling back to
+/// "New notification".
+fn build_toast_xml(
+    data: &NotificationData,
+    action_types: &HashMap<String, ActionType>,
+    resolve_image: impl Fn(&str) -> Option<String>,
+) -> String {
+    render_toast_xml(data, action_types, resolve_image, false)
+}
+
+/// Render the toast that replaces a count-down once its deadline passes: the
+/// alarm scenario keeps it on screen with a looping alarm sound until the
+/// user dismisses it.
+fn build_alarm_toast_xml(
+    data: &NotificationData,
+    action_types: &HashMap<String, ActionType>,
+    resolve_image: impl Fn(&str) -> Option<String>,
+) -> String {
+    render_toast_xml(data, action_types, resolve_image, true)
+}
+
+fn render_toast_xml(
+    data: &NotificationData,
+    action_types: &HashMap<String, ActionType>,
+    resolve_image: impl Fn(&str) -> Option<String>,
+    alarm: bool,
+) -> String {
+    // Encode notification id + extras into `launch=` so the click payload
+    // survives a cold-start activation (the COM `Activate` callback only
+    // receives the launch string; the in-process `Activated` handler
+    // delivers the same string in `ToastActivatedEventArgs.Arguments`).
+    // A struct rather than `json!` keeps the key order stable.
+    // `scope` rides along so `clear_scope` can find the toast in the
+    // schedule and the history, even from a later session.
+    // So does `sort_key`, for re-posting the group in order.
+    #[derive(serde::Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Launch<'a> {
+        id: i32,
+        data: &'a HashMap<String, serde_json::Value>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        scope: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        sort_key: Option<&'a str>,
+    }
+    let launch = serde_json::to_string(&Launch {
+        id: data.id,
+        data: &data.extra,
+        scope: data.scope.as_deref(),
+        sort_key: data.sort_key.as_deref(),
+    })
+    .unwrap_or_default();
+
+    let mut xml = format!("<toast launch=\"{}\"", escape_xml(&launch));
+    // Windows has no summary notification; `group_summary` + `group` is the
+    // closest equivalent. The reminder scenario keeps the toast on screen
+    // until the user acts on it instead of sliding into Action Center, which
+    // is also what `require_interaction` asks for.
+    if alarm {
+        xml.push_str(" scenario=\"alarm\"");
+    } else if data.group_summary || data.require_interaction {
+        xml.push_str(" scenario=\"reminder\"");
+    }
+    // Toasts stay in Action Center regardless; the closest thing to a
+    // persistent notification is a longer popup.
+    if data.persistent {
+        xml.push_str(" duration=\"long\"");
+    }
+    xml.push('>');
+    xml.push_str("<visual><binding template=\"ToastGeneric\"");
+    if let Some((r, g, b)) = data.foreground_color {
+        let _ = write!(xml, " hint-foregroundColor=\"#{r:02X}{g:02X}{b:02X}\"");
+    }
+    xml.push('>');
+
+    // Narrator reads the `<text>` elements in document order, so the title
+    // must come first. Truncation (`hint-maxLines`) only affects what is
+    // drawn; the full text is still read out.
+    if let Some(title) = &data.title {
+        let _ = write!(xml, "<text>{}</text>", escape_xml(title));
+    }
+
+    if let Some(body) = &data.body {
+        let _ = write!(xml, "<text>{}</text>", escape_xml(body));
+    }
+
+    // Skip when identical to `body`: WinRT renders each `<text>` on its
+    // own line, so duplicating it just shows the same string twice in the
+    // expanded view (issue #231).
+    if let Some(large_body) = &data.large_body
+        && data.body.as_ref() != Some(large_body)
+    {
+        let _ = write!(xml, "<text>{}</text>", escape_xml(large_body));
+    }
+
+    // The attribution line is drawn in small print below the other text,
+    // where Android puts the summary.
+    if let Some(summary) = &data.summary {
+        let _ = write!(
+            xml,
+            "<text placement=\"attribution\">{}</text>",
+            escape_xml(summary)
+        );
+    }
+
+    // Filled in through `NotificationData` bindings (see `count_down_bindings`).
+    if data.count_down_to.is_some() && !alarm {
+        xml.push_str(
+            "<progress status=\"Timer\" value=\"{progressValue}\" \
+             valueStringOverride=\"{progressValueString}\"/>",
+        );
+    } else if data.progress.is_some() && !alarm {
+        // Bound as well, so `update_progress` can move it in place.
+        xml.push_str("<progress status=\"\" value=\"{progressValue}\"/>");
+    }
+
+    if let Some(src) = data.icon.as_deref().and_then(&resolve_image) {
+        let _ = write!(
+            xml,
+            "<image placement=\"appLogoOverride\" src=\"{}\"/>",
+            escape_xml(&src)
+        );
+    }
 
-        if let Some(body) = &self.data.body {
-            let text = doc.CreateElement(&HSTRING::from("text"))?;
-            text.SetInnerText(&HSTRING::from(body.as_str()))?;
-            binding.AppendChild(&text)?;
+    // The first resolvable attachment becomes the hero image; the rest are
+    // rendered inline.
+    let mut hero_slot_taken = false;
+    for attachment in &data.attachments {
+        let Some(src) = resolve_image(attachment.url().as_str()) else {
+            continue;
+        };
+        if hero_slot_taken {
+            let _ = write!(xml, "<image src=\"{}\"/>", escape_xml(&src));
+        } else {
+            let _ = write!(
+                xml,
+                "<image placement=\"hero\" src=\"{}\"/>",
+                escape_xml(&src)
+            );
+            hero_slot_taken = true;
         }
+    }
 
-        // Skip when identical to `body`: WinRT renders each `<text>` on its
-        // own line, so duplicating it just shows the same string twice in the
-        // expanded view (issue #231).
-        if let Some(large_body) = &self.data.large_body
-            && self.data.body.as_ref() != Some(large_body)
+    xml.push_str("</binding></visual>");
+
+    let media_actions = data
+        .media_controls
+        .as_ref()
+        .map_or(&[][..], |controls| controls.actions.as_slice());
+    let action_type = data
+        .action_type_id
+        .as_ref()
+        .and_then(|id| action_types.get(id));
+    // Windows only honours the alarm and reminder scenarios on toasts with
+    // a button.
+    let dismiss_button = alarm || data.require_interaction;
+    if dismiss_button || !media_actions.is_empty() || action_type.is_some() {
+        xml.push_str("<actions>");
+        // Inputs must precede the buttons. Windows shows one reply box, with
+        // each action that takes input as a send button next to it.
+        if let Some(action) = action_type
+            .map_or(&[][..], ActionType::actions)
+            .iter()
+            .find(|action| action.input())
         {
-            let text = doc.CreateElement(&HSTRING::from("text"))?;
-            text.SetInnerText(&HSTRING::from(large_body.as_str()))?;
-            binding.AppendChild(&text)?;
-        }
-
-        // Add icon if specified. Drop silently when the user-supplied string
-        // can't be coerced into a Windows-accepted URI scheme — otherwise the
-        // whole toast falls back to "New notification".
-        if let Some(icon) = &self.data.icon {
-            if let Some(src) = resolve_toast_image_src(&self.app, icon, self.plugin.packaged) {
-                let image = doc.CreateElement(&HSTRING::from("image"))?;
-                image.SetAttribute(
-                    &HSTRING::from("placement"),
-                    &HSTRING::from("appLogoOverride"),
-                )?;
-                image.SetAttribute(&HSTRING::from("src"), &HSTRING::from(src.as_str()))?;
-                binding.AppendChild(&image)?;
+            let _ = write!(xml, "<input id=\"{TOAST_INPUT_ID}\" type=\"text\"");
+            if let Some(placeholder) = action.input_placeholder() {
+                let _ = write!(xml, " placeHolderContent=\"{}\"", escape_xml(placeholder));
             }
+            xml.push_str("/>");
         }
-
-        // Add attachments as images. Same URI resolution applies.
-        let mut hero_slot_taken = false;
-        for attachment in self.data.attachments.iter() {
-            let Some(src) =
-                resolve_toast_image_src(&self.app, attachment.url().as_str(), self.plugin.packaged)
-            else {
-                continue;
+        // Media buttons come first and activate in the background so pressing
+        // play/pause doesn't bring the app forward.
+        for action in media_actions {
+            let _ = write!(
+                xml,
+                "<action content=\"{}\" arguments=\"{}\" activationType=\"background\"/>",
+                media_action_label(*action),
+                action.action_id(),
+            );
+        }
+        for action in action_type.map_or(&[][..], ActionType::actions) {
+            let activation_type = if action.foreground() {
+                "foreground"
+            } else {
+                "background"
             };
-            let image = doc.CreateElement(&HSTRING::from("image"))?;
-            if !hero_slot_taken {
-                image.SetAttribute(&HSTRING::from("placement"), &HSTRING::from("hero"))?;
-                hero_slot_taken = true;
-            }
-            image.SetAttribute(&HSTRING::from("src"), &HSTRING::from(src.as_str()))?;
-            binding.AppendChild(&image)?;
-        }
-
-        visual.AppendChild(&binding)?;
-        toast.AppendChild(&visual)?;
-
-        // Add <actions> if action_type_id specified
-        if let Some(action_type_id) = &self.data.action_type_id {
-            if let Some(action_type) = action_types.get(action_type_id) {
-                let actions = doc.CreateElement(&HSTRING::from("actions"))?;
-                for action in action_type.actions() {
-                    let action_el = doc.CreateElement(&HSTRING::from("action"))?;
-                    action_el
-                        .SetAttribute(&HSTRING::from("content"), &HSTRING::from(action.title()))?;
-                    action_el
-                        .SetAttribute(&HSTRING::from("arguments"), &HSTRING::from(action.id()))?;
-                    let activation_type = if action.foreground() {
-                        "foreground"
-                    } else {
-                        "background"
-                    };
-                    action_el.SetAttribute(
-                        &HSTRING::from("activationType"),
-                        &HSTRING::from(activation_type),
-                    )?;
-                    actions.AppendChild(&action_el)?;
-                }
-                toast.AppendChild(&actions)?;
+            let _ = write!(
+                xml,
+                "<action content=\"{}\" arguments=\"{}\" activationType=\"{activation_type}\"",
+                escape_xml(action.title()),
+                escape_xml(action.id()),
+            );
+            if action.input() {
+                let _ = write!(xml, " hint-inputId=\"{TOAST_INPUT_ID}\"");
             }
+            xml.push_str("/>");
         }
-
-        // Add <audio> element for silent or custom sound
-        if self.data.silent {
-            let audio = doc.CreateElement(&HSTRING::from("audio"))?;
-            audio.SetAttribute(&HSTRING::from("silent"), &HSTRING::from("true"))?;
-            toast.AppendChild(&audio)?;
-        } else if let Some(sound) = &self.data.sound {
-            let audio = doc.CreateElement(&HSTRING::from("audio"))?;
-            audio.SetAttribute(&HSTRING::from("src"), &HSTRING::from(sound.as_str()))?;
-            toast.AppendChild(&audio)?;
+        if dismiss_button {
+            xml.push_str("<action content=\"\" arguments=\"dismiss\" activationType=\"system\"/>");
         }
+        xml.push_str("</actions>");
+    }
+
+    if data.silent || data.quiet {
+        xml.push_str("<audio silent=\"true\"/>");
+    } else if let Some(sound) = &data.sound {
+        let _ = write!(xml, "<audio src=\"{}\"/>", escape_xml(sound));
+    } else if alarm {
+        xml.push_str("<audio src=\"ms-winsoundevent:Notification.Looping.Alarm\" loop=\"true\"/>");
+    }
+
+    xml.push_str("</toast>");
+    xml
+}
 
-        Ok(doc)
+impl<R: Runtime> crate::NotificationsBuilder<R> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            err,
+            fields(
+                platform = crate::telemetry::BACKEND,
+                id = self.data.id,
+                channel = self.data.channel_id.as_deref(),
+                scheduled = self.data.schedule.is_some()
+            )
+        )
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn show(mut self) -> crate::Result<()> {
+        self.apply_defaults()?;
+        self.apply_quiet_hours();
+        self.check_extra_size()?;
+        self.check_platform_options()?;
+        self.resolve_flag_conflicts()?;
+        self.apply_inbox_lines()?;
+        self.report_ignored_fields();
+        self.remember_focus_window();
+        if self.suppress_if_window_focused() {
+            return Ok(());
+        }
+        if let Some(result) = self.coalesce().await {
+            return result;
+        }
+        // Unpackaged apps can't load `http(s)` image sources, and packaged
+        // ones would re-download them for every toast. Fetched before the
+        // dispatcher so a slow host only holds up this toast.
+        #[cfg(feature = "media-cache")]
+        let cached = crate::media_cache::localize_all(
+            &self.app,
+            self.data
+                .icon
+                .as_deref()
+                .into_iter()
+                .chain(self.data.attachments.iter().map(|a| a.url().as_str())),
+        )
+        .await;
+        #[cfg(not(feature = "media-cache"))]
+        let cached = HashMap::new();
+        let shown = self.data.clone();
+        crate::dispatch::deliver_in_group(self.dispatcher(), shown, self.deliver(cached)).await
     }
 
-    pub async fn show(self) -> crate::Result<()> {
-        let action_types = self.plugin.action_types()?;
-        let toast_xml = self.build_toast_xml(&action_types)?;
+    /// `cached` maps remote image sources to their copy in the media cache.
+    async fn deliver(self, cached: HashMap<String, std::path::PathBuf>) -> crate::Result<()> {
+        let action_types = action_types::by_id();
+        let resolve_image = |src: &str| {
+            if let Some(path) = cached.get(src) {
+                return Some(path_to_file_uri(path));
+            }
+            resolve_toast_image_src(&self.app, src, self.plugin.packaged)
+        };
+        let xml = build_toast_xml(&self.data, &action_types, resolve_image);
+        let toast_xml = XmlDocument::new()?;
+        toast_xml.LoadXml(&HSTRING::from(xml.as_str()))?;
 
         let tag = HSTRING::from(self.data.id.to_string());
         let group = self.data.group.as_ref().map(|g| HSTRING::from(g.as_str()));
+        let payload = serde_json::json!({
+            "id": self.data.id,
+            "title": self.data.title,
+            "body": self.data.body,
+            "actionTypeId": self.data.action_type_id,
+            "extra": self.data.extra_payload(),
+        });
+
+        // The same id shown again replaces a repeating schedule.
+        self.plugin.stop_repeats(|id| id == self.data.id);
 
         // Check if this is a scheduled notification
         if let Some(schedule) = &self.data.schedule {
-            let delivery_time = schedule_to_datetime(schedule)?;
+            let first = schedule_to_time(schedule)?;
+            if self.data.show_only_once {
+                if repeat_period(schedule).is_some() {
+                    return Err(crate::Error::InvalidArgument(
+                        "show_only_once can't be combined with a repeating schedule".to_string(),
+                    ));
+                }
+                crate::show_once::remember(&self.app, self.data.id, first.unix_timestamp());
+            } else {
+                crate::show_once::forget(&self.app, self.data.id);
+            }
             let scheduled = ScheduledToastNotification::CreateScheduledToastNotification(
                 &toast_xml,
-                delivery_time,
+                unix_to_windows_datetime(first)?,
             )?;
 
             scheduled.SetTag(&tag)?;
             if let Some(g) = &group {
                 scheduled.SetGroup(g)?;
             }
+            // Straight to Action Center during quiet hours.
+            if self.data.quiet {
+                scheduled.SetSuppressPopup(true)?;
+            }
+
+            let notifier = self.plugin.notifier()?;
+            if let Err(e) = notifier.AddToSchedule(&scheduled) {
+                return self.plugin.queue_retry(self.data.id, e, payload, move || {
+                    notifier.AddToSchedule(&scheduled)?;
+                    Ok(notifier.Setting().ok())
+                });
+            }
 
-            self.plugin.notifier.AddToSchedule(&scheduled)?;
+            // Scheduled toasts fire once; repeat by scheduling each next
+            // occurrence ourselves.
+            if let Some(period) = repeat_period(schedule) {
+                self.plugin.start_repeat(
+                    self.data.id,
+                    timers::Repeat {
+                        xml,
+                        group: self.data.group.clone(),
+                        next: first.unix_timestamp(),
+                        period_secs: period.whole_seconds(),
+                        remaining: self.data.max_repetitions,
+                    },
+                );
+            }
         } else {
             // Immediate notification
             let toast = ToastNotification::CreateToastNotification(&toast_xml)?;
@@ -649,8 +2127,19 @@ impl<R: Runtime> crate::NotificationsBuilder<R> {
             if let Some(g) = &group {
                 toast.SetGroup(g)?;
             }
+            if self.data.quiet {
+                toast.SetSuppressPopup(true)?;
+            }
+            if let Some(deadline) = self.data.count_down_to {
+                let remaining = time_until(deadline);
+                toast.SetData(&count_down_data(remaining, remaining, 1)?)?;
+            } else if let Some(fraction) = self.data.progress {
+                toast.SetData(&progress_data(fraction)?)?;
+            }
 
-            if self.plugin.is_click_listener_active()? {
+            // Also needed without JS listeners to focus the window on click.
+            let focuses_window = self.data.focus_window.is_some() || crate::focus::has_default();
+            if focuses_window || self.plugin.is_click_listener_active()? {
                 let notification = ActiveNotification {
                     id: self.data.id,
                     tag: Some(self.data.id.to_string()),
@@ -664,6 +2153,8 @@ impl<R: Runtime> crate::NotificationsBuilder<R> {
                     action_type_id: self.data.action_type_id.clone(),
                     schedule: self.data.schedule.clone(),
                     sound: self.data.sound.clone(),
+                    sort_key: self.data.sort_key.clone(),
+                    delivered_at: None,
                 };
 
                 toast.Activated(&TypedEventHandler::new(
@@ -692,16 +2183,20 @@ impl<R: Runtime> crate::NotificationsBuilder<R> {
                                     arguments.to_string()
                                 };
 
+                                if is_tap {
+                                    crate::focus::on_click(notification.id);
+                                }
+
                                 let payload = serde_json::json!({
                                     "actionId": action_id,
-                                    "inputValue": null,
+                                    "inputValue": activation_input(&activated),
                                     "notification": notification,
                                 });
                                 if let Err(e) = crate::listeners::trigger(
                                     "actionPerformed",
                                     payload.to_string(),
                                 ) {
-                                    log::error!("Failed to trigger actionPerformed: {e}");
+                                    telemetry::error!("Failed to trigger actionPerformed: {e}");
                                 }
 
                                 if is_tap {
@@ -713,7 +2208,7 @@ impl<R: Runtime> crate::NotificationsBuilder<R> {
                                         "notificationClicked",
                                         click_payload.to_string(),
                                     ) {
-                                        log::error!("Failed to trigger notificationClicked: {e}");
+                                        telemetry::error!("Failed to trigger notificationClicked: {e}");
                                     }
                                 }
                             }
@@ -723,40 +2218,162 @@ impl<R: Runtime> crate::NotificationsBuilder<R> {
                 ))?;
             }
 
-            self.plugin.notifier.Show(&toast)?;
-        }
+            // A toast shown again under the same tag is active again.
+            self.plugin.dismissed_mut().remove(&tag.to_string_lossy());
+            let id = self.data.id;
+            let plugin = Arc::downgrade(&self.plugin);
+            toast.Dismissed(&TypedEventHandler::new(
+                move |_: Ref<'_, ToastNotification>, args: Ref<'_, ToastDismissedEventArgs>| {
+                    // `TimedOut` only moves the toast to Action Center.
+                    let reason = (*args).as_ref().and_then(|args| args.Reason().ok());
+                    if reason == Some(ToastDismissalReason::UserCanceled) {
+                        if let Some(plugin) = plugin.upgrade() {
+                            plugin.dismissed_mut().insert(id.to_string());
+                        }
+                        let payload = serde_json::json!({ "id": id });
+                        if let Err(e) =
+                            crate::listeners::trigger("notificationDismissed", payload.to_string())
+                        {
+                            telemetry::error!("Failed to trigger notificationDismissed: {e}");
+                        }
+                    }
+                    Ok(())
+                },
+            ))?;
+
+            let notifier = self.plugin.notifier()?;
+            if let Err(e) = notifier.Show(&toast) {
+                return self.plugin.queue_retry(self.data.id, e, payload, move || {
+                    notifier.Show(&toast)?;
+                    Ok(notifier.Setting().ok())
+                });
+            }
+            let reordered = self.data.group.as_deref().map_or(Ok(()), |g| {
+                self.plugin
+                    .reorder_group(&notifier, g, &tag.to_string_lossy())
+            });
+            if let Err(e) = reordered {
+                telemetry::warning!("Failed to reorder toast group: {e}");
+            }
 
-        // Trigger notification event
-        let payload = serde_json::json!({
-            "id": self.data.id,
-            "title": self.data.title,
-            "body": self.data.body,
-            "actionTypeId": self.data.action_type_id,
-            "extra": self.data.extra,
-        });
-        if let Err(e) = crate::listeners::trigger("notification", payload.to_string()) {
-            log::error!("Failed to trigger notification: {e}");
+            if let Some(deadline) = self.data.count_down_to {
+                // Same tag and group, so the alarm replaces the count-down.
+                let alarm_xml = XmlDocument::new()?;
+                alarm_xml.LoadXml(&HSTRING::from(
+                    build_alarm_toast_xml(&self.data, &action_types, resolve_image).as_str(),
+                ))?;
+                let alarm = ScheduledToastNotification::CreateScheduledToastNotification(
+                    &alarm_xml,
+                    unix_to_windows_datetime(deadline)?,
+                )?;
+                alarm.SetTag(&tag)?;
+                if let Some(g) = &group {
+                    alarm.SetGroup(g)?;
+                }
+                notifier.AddToSchedule(&alarm)?;
+                self.plugin
+                    .start_count_down(self.data.id, self.data.group.clone(), deadline);
+            }
         }
 
+        let setting = self
+            .plugin
+            .notifier()
+            .ok()
+            .and_then(|notifier| notifier.Setting().ok());
+        trigger_delivered(payload, setting);
+
         Ok(())
     }
 }
 
+/// Emits `notification` for a toast Windows accepted, with the app's
+/// notification `setting` as `windowsSetting`. Windows accepts toasts it
+/// won't display while notifications are turned off, so those are only
+/// logged. An unreadable setting counts as enabled.
+fn trigger_delivered(mut payload: serde_json::Value, setting: Option<NotificationSetting>) {
+    if let Some(setting) = setting {
+        if setting != NotificationSetting::Enabled {
+            telemetry::warning!(
+                "Notification {} was accepted but not displayed: notifications are {}",
+                payload["id"],
+                setting_name(setting)
+            );
+            return;
+        }
+        payload["windowsSetting"] = setting_name(setting).into();
+    }
+    if let Err(e) = crate::listeners::trigger("notification", payload.to_string()) {
+        telemetry::error!("Failed to trigger notification: {e}");
+    }
+}
+
+/// `setting` in camelCase, as reported in `windowsSetting`.
+fn setting_name(setting: NotificationSetting) -> &'static str {
+    match setting {
+        NotificationSetting::Enabled => "enabled",
+        NotificationSetting::DisabledForApplication => "disabledForApplication",
+        NotificationSetting::DisabledForUser => "disabledForUser",
+        NotificationSetting::DisabledByGroupPolicy => "disabledByGroupPolicy",
+        NotificationSetting::DisabledByManifest => "disabledByManifest",
+        _ => "unknown",
+    }
+}
+
+/// First time after `now` that falls on `weekday` (1 = Sunday ... 7 =
+/// Saturday, as on iOS and Android) at the interval's hour, minute and second
+/// (each defaulting to 0), in `now`'s offset.
+fn next_weekday_occurrence(
+    now: time::OffsetDateTime,
+    weekday: u8,
+    interval: &ScheduleInterval,
+) -> crate::Result<time::OffsetDateTime> {
+    if !(1..=7).contains(&weekday) {
+        return Err(crate::Error::InvalidArgument(format!(
+            "weekday must be between 1 (Sunday) and 7 (Saturday), got {weekday}"
+        )));
+    }
+    let time_of_day = time::Time::from_hms(
+        interval.hour.unwrap_or(0),
+        interval.minute.unwrap_or(0),
+        interval.second.unwrap_or(0),
+    )
+    .map_err(|e| crate::Error::InvalidArgument(format!("invalid schedule time: {e}")))?;
+    let target = time::Weekday::Sunday.nth_next(weekday - 1);
+    let days_ahead =
+        (7 + target.number_days_from_sunday() - now.weekday().number_days_from_sunday()) % 7;
+    let mut next = now.replace_time(time_of_day) + time::Duration::days(i64::from(days_ahead));
+    if next <= now {
+        next += time::Duration::weeks(1);
+    }
+    Ok(next)
+}
+
 /// Convert Schedule to Windows DateTime.
 fn schedule_to_datetime(schedule: &Schedule) -> crate::Result<DateTime> {
+    unix_to_windows_datetime(schedule_to_time(schedule)?)
+}
+
+/// First delivery time of `schedule`.
+fn schedule_to_time(schedule: &Schedule) -> crate::Result<time::OffsetDateTime> {
     let now = time::OffsetDateTime::now_utc();
 
     let delivery_time = match schedule {
         Schedule::At { date, .. } => *date,
-        Schedule::Interval { interval, .. } => {
-            // Build duration from interval fields
-            let seconds = interval.second.unwrap_or(0) as i64;
-            let minutes = interval.minute.unwrap_or(0) as i64;
-            let hours = interval.hour.unwrap_or(0) as i64;
-            let days = interval.day.unwrap_or(0) as i64;
-            let total_seconds = seconds + minutes * 60 + hours * 3600 + days * 86400;
-            now + time::Duration::seconds(total_seconds)
+        Schedule::Interval {
+            interval:
+                interval @ ScheduleInterval {
+                    weekday: Some(weekday),
+                    ..
+                },
+            ..
+        } => {
+            // Weekly recurrence: a wall-clock time, so use the local offset.
+            let local_now =
+                time::UtcOffset::current_local_offset().map_or(now, |offset| now.to_offset(offset));
+            next_weekday_occurrence(local_now, *weekday, interval)?
         }
+        Schedule::Interval { interval, .. } => now + interval_duration(interval),
         Schedule::Every {
             interval, count, ..
         } => {
@@ -774,7 +2391,7 @@ fn schedule_to_datetime(schedule: &Schedule) -> crate::Result<DateTime> {
         }
     };
 
-    unix_to_windows_datetime(delivery_time)
+    Ok(delivery_time)
 }
 
 /// Convert a Unix timestamp to Windows DateTime (FILETIME).
@@ -799,6 +2416,21 @@ fn windows_datetime_to_unix(dt: DateTime) -> crate::Result<time::OffsetDateTime>
     Ok(utc.into())
 }
 
+/// Like [`windows_datetime_to_unix`], but clamps values `time` can't
+/// represent to the nearest bound instead of failing. The flag reports whether
+/// clamping happened.
+fn windows_datetime_to_unix_clamped(dt: DateTime) -> (time::OffsetDateTime, bool) {
+    if let Ok(date) = windows_datetime_to_unix(dt) {
+        return (date, false);
+    }
+    let bound = if dt.UniversalTime < 0 {
+        time::UtcDateTime::try_from(FileTime::NT_TIME_EPOCH).unwrap_or(time::UtcDateTime::MIN)
+    } else {
+        time::UtcDateTime::MAX
+    };
+    (bound.into(), true)
+}
+
 pub struct Notifications<R: Runtime> {
     #[allow(dead_code)]
     app: AppHandle<R>,
@@ -819,22 +2451,178 @@ impl<R: Runtime> Notifications<R> {
         self.plugin.drain_pending_clicks();
     }
 
+    /// Launch source from the activation that started the process. COM
+    /// starts the exe with `-Embedding` when a toast activates an app that
+    /// isn't running, and delivers the activation to the toast activator
+    /// right after; this waits up to [`LAUNCH_ACTIVATION_TIMEOUT`] for it.
+    pub(crate) fn read_launch_source(&self) -> crate::LaunchSource {
+        if !std::env::args().any(|arg| arg.eq_ignore_ascii_case("-Embedding")) {
+            return crate::LaunchSource::Normal;
+        }
+        let (first, activated) = &FIRST_ACTIVATION;
+        let first = first.lock().unwrap_or_else(PoisonError::into_inner);
+        let (first, _) = activated
+            .wait_timeout_while(first, LAUNCH_ACTIVATION_TIMEOUT, |first| first.is_none())
+            .unwrap_or_else(PoisonError::into_inner);
+        first
+            .clone()
+            .unwrap_or(crate::LaunchSource::NotificationTap { id: None })
+    }
+
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn request_permission(&self) -> crate::Result<PermissionState> {
         // Windows doesn't have a runtime permission prompt like mobile
         // We can only check the current state
-        self.permission_state().await
+        self.permission_state_fresh().await
+    }
+
+    /// Registers the display name and icon Windows shows on this app's
+    /// toasts. Unpackaged apps otherwise appear under their raw
+    /// AppUserModelID (the bundle identifier) with no icon. Writes
+    /// `DisplayName` and `IconUri` under
+    /// `HKCU\Software\Classes\AppUserModelId\<identifier>`; toasts shown
+    /// afterwards pick them up. Packaged (MSIX) apps take both from their
+    /// manifest, so this does nothing for them.
+    ///
+    /// `icon` should be an absolute path to a `.png` or `.ico` file.
+    /// Plugin setup does the same when
+    /// [`WindowsConfig::register_app_identity`] is set.
+    pub fn register_app_identity(
+        &self,
+        display_name: &str,
+        icon: Option<&std::path::Path>,
+    ) -> crate::Result<()> {
+        if self.plugin.packaged {
+            return Ok(());
+        }
+        if display_name.trim().is_empty() {
+            return Err(crate::Error::InvalidArgument(
+                "app identity display name must not be empty".to_string(),
+            ));
+        }
+        write_app_identity(&self.plugin.app_id(), display_name, icon)?;
+        Ok(())
+    }
+
+    /// Removes what [`register_app_identity`](Self::register_app_identity)
+    /// wrote, e.g. from an uninstaller hook. Succeeds if nothing was
+    /// registered.
+    pub fn unregister_app_identity(&self) -> crate::Result<()> {
+        if self.plugin.packaged {
+            return Ok(());
+        }
+        delete_app_identity(&self.plugin.app_id())?;
+        Ok(())
+    }
+
+    /// Shows and looks up later toasts under `app_id` instead of
+    /// [`PluginConfig::windows_app_id`](crate::PluginConfig::windows_app_id)
+    /// or the bundle identifier, e.g. when the installer's Start Menu
+    /// shortcut carries an AUMID that is only known at runtime. Call it
+    /// before the first notification: toasts already shown stay under the
+    /// previous id. Packaged apps always use their package's AUMID.
+    pub fn set_app_id(&self, app_id: impl Into<String>) -> crate::Result<()> {
+        if self.plugin.packaged {
+            return Err(crate::Error::Io(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "Packaged apps use their package's AppUserModelID",
+            )));
+        }
+        let app_id = app_id.into();
+        crate::validate_windows_app_id(&app_id)?;
+        let identity = ToastIdentity::new(app_id, false)?;
+        *self
+            .plugin
+            .identity
+            .write()
+            .map_err(|_| crate::Error::Io(std::io::Error::other("Lock poisoned")))? = identity;
+        Ok(())
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn register_for_push_notifications(&self) -> crate::Result<String> {
-        self.plugin.open_push_channel()
+        let uri = self.plugin.open_push_channel()?;
+        crate::push_token::store(&self.app, &uri);
+        Ok(uri)
     }
 
+    /// The channel URI from the last successful
+    /// [`register_for_push_notifications`](Self::register_for_push_notifications),
+    /// read from disk without contacting WNS. `None` if the app never
+    /// registered or has unregistered since.
+    #[must_use]
+    pub async fn get_push_token_cached(&self) -> Option<String> {
+        crate::push_token::load(&self.app)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
     pub fn unregister_for_push_notifications(&self) -> crate::Result<()> {
-        self.plugin.close_push_channel()
+        self.plugin.close_push_channel()?;
+        crate::push_token::clear(&self.app);
+        Ok(())
+    }
+
+    /// The toast notifier of the app's AppUserModelID and its setting.
+    pub(crate) async fn platform_diagnostics(&self) -> Vec<crate::Diagnostic> {
+        let notifier = match self.plugin.notifier() {
+            Ok(notifier) => notifier,
+            Err(e) => return vec![unavailable_diagnostic(&e)],
+        };
+        let (reason, fix_hint) = match notifier.Setting() {
+            Ok(NotificationSetting::DisabledForApplication) => (
+                "turned off for this app",
+                "Turn the app's notifications on in Settings > System > Notifications",
+            ),
+            Ok(NotificationSetting::DisabledForUser) => (
+                "turned off for all apps",
+                "Turn notifications on in Settings > System > Notifications",
+            ),
+            Ok(NotificationSetting::DisabledByGroupPolicy) => (
+                "disabled by group policy",
+                "Ask the administrator to allow toast notifications",
+            ),
+            Ok(NotificationSetting::DisabledByManifest) => (
+                "disabled by the app manifest",
+                "Allow toasts in the app manifest (uap:VisualElements ToastCapable)",
+            ),
+            Ok(_) => return Vec::new(),
+            Err(e) => return vec![unavailable_diagnostic(&e.into())],
+        };
+        vec![crate::Diagnostic::new(
+            crate::Severity::Error,
+            crate::DiagnosticCode::ToastsDisabled,
+            format!("Toast notifications are {reason}"),
+            fix_hint,
+        )]
+    }
+
+    /// The app's toast setting as Windows reports it. Unlike
+    /// [`permission_state`](Self::permission_state), which reports every
+    /// disabled setting as denied, it tells the user turning toasts off
+    /// apart from a group policy or the app manifest. Fails with
+    /// [`Error::PermissionDenied`](crate::Error::PermissionDenied) when the
+    /// app has no toast notifier.
+    pub fn toast_notification_manager_setting(&self) -> crate::Result<NotificationSetting> {
+        Ok(self.plugin.notifier()?.Setting()?)
     }
 
-    pub async fn permission_state(&self) -> crate::Result<PermissionState> {
-        match self.plugin.notifier.Setting()? {
+    /// Reads the permission state from the platform, bypassing the cache of
+    /// [`permission_state`](Self::permission_state).
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn permission_state_fresh(&self) -> crate::Result<PermissionState> {
+        let setting = match self.toast_notification_manager_setting() {
+            Ok(setting) => setting,
+            Err(crate::Error::PermissionDenied(_)) => return Ok(PermissionState::Denied),
+            Err(e) => return Err(e),
+        };
+        match setting {
             NotificationSetting::Enabled => Ok(PermissionState::Granted),
             NotificationSetting::DisabledForApplication
             | NotificationSetting::DisabledForUser
@@ -844,64 +2632,200 @@ impl<R: Runtime> Notifications<R> {
         }
     }
 
-    pub fn register_action_types(&self, types: Vec<ActionType>) -> crate::Result<()> {
-        let mut action_types = self.plugin.action_types_mut()?;
-        for action_type in types {
-            action_types.insert(action_type.id().to_string(), action_type);
-        }
-        Ok(())
-    }
-
-    pub fn remove_active(&self, notifications: Vec<i32>) -> crate::Result<()> {
-        let history = ToastNotificationManager::History()?;
-        let app_id = &self.plugin.app_id;
-        for id in notifications {
-            let tag = HSTRING::from(id.to_string());
-            // Use app-scoped removal with empty group (consistent with GetHistoryWithId usage)
-            let res = if self.plugin.packaged {
-                history.RemoveGroupedTag(&tag, &HSTRING::new())
-            } else {
-                history.RemoveGroupedTagWithId(&tag, &HSTRING::new(), &HSTRING::from(app_id))
-            };
-            if let Err(e) = res {
-                log::error!("Failed to remove notification {id}: {e}");
-            }
-        }
-        Ok(())
+    /// Whether Windows is holding back toasts: the user is busy, presenting,
+    /// running a full-screen app or still in the first hour after sign-in.
+    /// Focus assist rules are not reported.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn is_do_not_disturb_active(&self) -> crate::Result<bool> {
+        let state = unsafe { SHQueryUserNotificationState() }?;
+        Ok(suppresses_notifications(state))
     }
 
-    pub async fn active(&self) -> crate::Result<Vec<ActiveNotification>> {
+    /// Number of this app's toasts in Action Center, including the ones
+    /// [`active`](Self::active) leaves out because the user dismissed them.
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn get_unread_count(&self) -> crate::Result<u32> {
         let history = ToastNotificationManager::History()?;
-        let notifications = if self.plugin.packaged {
+        let toasts = if self.plugin.packaged {
             history.GetHistory()?
         } else {
-            history.GetHistoryWithId(&HSTRING::from(&self.plugin.app_id))?
+            history.GetHistoryWithId(&HSTRING::from(self.plugin.app_id()))?
         };
+        Ok(toasts.Size()?)
+    }
 
-        let mut result = Vec::new();
-        for i in 0..notifications.Size()? {
-            let notification = notifications.GetAt(i)?;
-            let tag = notification.Tag()?.to_string_lossy();
-            let id = tag.parse::<i32>().unwrap_or(0);
-            let group = notification.Group().ok().map(|s| s.to_string_lossy());
-
-            // Extract title/body from XML content
-            let (title, body) = if let Ok(content) = notification.Content() {
-                let text_elements = content.GetElementsByTagName(&HSTRING::from("text"))?;
-                let title = text_elements
-                    .GetAt(0)
-                    .ok()
-                    .and_then(|el| el.InnerText().ok())
-                    .map(|s| s.to_string_lossy());
-                let body = text_elements
-                    .GetAt(1)
-                    .ok()
-                    .and_then(|el| el.InnerText().ok())
-                    .map(|s| s.to_string_lossy());
-                (title, body)
-            } else {
-                (None, None)
-            };
+    /// Adds `types` to the registered action types, replacing the ones with
+    /// the same id. Toasts look their buttons up when shown, so there is
+    /// nothing to hand to Windows.
+    pub fn register_action_types(
+        &self,
+        types: Vec<ActionType>,
+    ) -> crate::Result<ActionTypesUpdate> {
+        crate::validate_action_types(&types)?;
+        Ok(action_types::apply(|registered| {
+            action_types::merge(registered, types)
+        }))
+    }
+
+    /// Replaces every registered action type with `types`.
+    pub fn set_notification_categories(
+        &self,
+        types: Vec<ActionType>,
+    ) -> crate::Result<ActionTypesUpdate> {
+        crate::validate_action_types(&types)?;
+        Ok(action_types::apply(|registered| *registered = types))
+    }
+
+    /// Removes the registered action type `id`, if there is one.
+    pub fn unregister_action_type(&self, id: &str) -> crate::Result<ActionTypesUpdate> {
+        Ok(action_types::apply(|registered| {
+            action_types::unregister(registered, id)
+        }))
+    }
+
+    /// Removes every registered action type.
+    pub fn clear_action_types(&self) -> crate::Result<ActionTypesUpdate> {
+        Ok(action_types::apply(Vec::clear))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            err,
+            fields(
+                platform = crate::telemetry::BACKEND,
+                ids = ?notifications
+            )
+        )
+    )]
+    pub fn remove_active(&self, notifications: Vec<i32>) -> crate::Result<()> {
+        for (id, e) in self.remove_active_detailed(notifications)?.failed {
+            telemetry::error!("Failed to remove notification {id}: {e}");
+        }
+        Ok(())
+    }
+
+    /// [`remove_active`](Self::remove_active) with the outcome of each id.
+    /// Fails as a whole only when the Action Center history can't be read.
+    pub fn remove_active_detailed(
+        &self,
+        notifications: Vec<i32>,
+    ) -> crate::Result<crate::BulkResult> {
+        remove_ids(&self.toast_history()?, notifications)
+    }
+
+    /// Removes the delivered toast carrying `tag`, which need not be a
+    /// numeric notification id (e.g. toasts shown by other code paths of the
+    /// same app).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    pub fn remove_active_by_tag(&self, tag: impl Into<String>) -> crate::Result<()> {
+        let history = self.toast_history()?;
+        let entries = history.entries()?;
+        remove_tag(&history, &entries, &tag.into())
+    }
+
+    /// Moves the progress bar of the shown toast `id` to `fraction` in
+    /// place. Returns `false` if the toast is gone.
+    pub(crate) fn update_progress(
+        &self,
+        id: i32,
+        group: Option<&str>,
+        fraction: f32,
+    ) -> crate::Result<bool> {
+        let notifier = self.plugin.notifier()?;
+        let data = progress_data(fraction)?;
+        let tag = HSTRING::from(id.to_string());
+        let updated = match group {
+            Some(group) => notifier.UpdateWithTagAndGroup(&data, &tag, &HSTRING::from(group))?,
+            None => notifier.UpdateWithTag(&data, &tag)?,
+        };
+        Ok(updated == NotificationUpdateResult::Succeeded)
+    }
+
+    fn toast_history(&self) -> crate::Result<WinToastHistory> {
+        Ok(WinToastHistory {
+            history: ToastNotificationManager::History()?,
+            app_id: (!self.plugin.packaged).then(|| HSTRING::from(self.plugin.app_id())),
+        })
+    }
+
+    /// The toast XML `show()` hands to Windows for `data`, to find out why a
+    /// notification looks wrong. Channel and config defaults are not applied
+    /// and remote images are not cached. Fails if Windows can't parse it.
+    pub fn toast_notification_xml(&self, data: &NotificationData) -> crate::Result<String> {
+        let action_types = action_types::by_id();
+        let xml = build_toast_xml(data, &action_types, |src| {
+            resolve_toast_image_src(&self.app, src, self.plugin.packaged)
+        });
+        XmlDocument::new()?.LoadXml(&HSTRING::from(xml.as_str()))?;
+        Ok(xml)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn active(&self) -> crate::Result<Vec<ActiveNotification>> {
+        let history = self.history()?;
+        let mut dismissed = self.plugin.dismissed_mut();
+        // Tags no longer in the history were removed or replaced.
+        dismissed.retain(|tag| {
+            history
+                .iter()
+                .any(|n| n.tag.as_deref() == Some(tag.as_str()))
+        });
+        Ok(history
+            .into_iter()
+            .filter(|n| n.tag.as_ref().is_none_or(|tag| !dismissed.contains(tag)))
+            .collect())
+    }
+
+    /// Every toast of this app in Action Center, including the ones the user
+    /// already dismissed that [`active`](Self::active) leaves out. Useful for
+    /// an in-app inbox.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn notification_history(&self) -> crate::Result<Vec<ActiveNotification>> {
+        self.history()
+    }
+
+    fn history(&self) -> crate::Result<Vec<ActiveNotification>> {
+        let history = ToastNotificationManager::History()?;
+        // Both calls list every toast of the app, whatever its group: the
+        // group only narrows `Remove*` calls, and there is no per-group
+        // query. Toasts shown under another AppUserModelID, or while the
+        // app had none, belong to that id and aren't listed.
+        let notifications = if self.plugin.packaged {
+            history.GetHistory()?
+        } else {
+            history.GetHistoryWithId(&HSTRING::from(self.plugin.app_id()))?
+        };
+
+        let mut result = Vec::new();
+        for i in 0..notifications.Size()? {
+            let notification = notifications.GetAt(i)?;
+            let tag = notification.Tag()?.to_string_lossy();
+            let id = tag.parse::<i32>().unwrap_or(0);
+            let group = toast_group(&notification);
+
+            // Extract title/body, images and the sort key from XML content
+            let (title, body, attachments, sort_key) = if let Ok(content) = notification.Content() {
+                let (title, body) = toast_texts(&content)?;
+                let attachments = toast_images(&content)
+                    .map(toast_attachments)
+                    .unwrap_or_default();
+                (title, body, attachments, launch_field(&content, "sortKey"))
+            } else {
+                (None, None, Vec::new(), None)
+            };
 
             result.push(ActiveNotification {
                 id,
@@ -912,28 +2836,39 @@ impl<R: Runtime> Notifications<R> {
                 group_summary: false,
                 data: HashMap::new(),
                 extra: HashMap::new(),
-                attachments: Vec::new(),
+                attachments,
                 action_type_id: None,
                 schedule: None,
                 sound: None,
+                sort_key,
+                delivered_at: None,
             });
         }
 
         Ok(result)
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
     pub fn remove_all_active(&self) -> crate::Result<()> {
         let history = ToastNotificationManager::History()?;
         if self.plugin.packaged {
             history.Clear()?;
         } else {
-            history.ClearWithId(&HSTRING::from(&self.plugin.app_id))?;
+            history.ClearWithId(&HSTRING::from(self.plugin.app_id()))?;
         }
         Ok(())
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
     pub async fn pending(&self) -> crate::Result<Vec<PendingNotification>> {
-        let scheduled = self.plugin.notifier.GetScheduledToastNotifications()?;
+        let scheduled = self.plugin.notifier()?.GetScheduledToastNotifications()?;
         let mut result = Vec::new();
 
         for i in 0..scheduled.Size()? {
@@ -942,75 +2877,293 @@ impl<R: Runtime> Notifications<R> {
             let id = tag.parse::<i32>().unwrap_or(0);
 
             let (title, body) = if let Ok(content) = notification.Content() {
-                let text_elements = content.GetElementsByTagName(&HSTRING::from("text"))?;
-                let title = text_elements
-                    .GetAt(0)
-                    .ok()
-                    .and_then(|el| el.InnerText().ok())
-                    .map(|s| s.to_string_lossy());
-                let body = text_elements
-                    .GetAt(1)
-                    .ok()
-                    .and_then(|el| el.InnerText().ok())
-                    .map(|s| s.to_string_lossy());
-                (title, body)
+                toast_texts(&content)?
             } else {
                 (None, None)
             };
 
-            // Convert Windows DateTime back to Schedule::At
-            let schedule = notification.DeliveryTime().ok().and_then(|dt| {
-                windows_datetime_to_unix(dt).ok().map(|date| Schedule::At {
+            let group = notification
+                .Group()
+                .ok()
+                .filter(|g| !g.is_empty())
+                .map(|g| g.to_string_lossy());
+
+            // Convert Windows DateTime back to Schedule::At. Out-of-range
+            // values are clamped so the entry still shows up.
+            let date = match notification.DeliveryTime() {
+                Ok(dt) => {
+                    let (date, clamped) = windows_datetime_to_unix_clamped(dt);
+                    if clamped {
+                        telemetry::warning!(
+                            "Pending notification {tag}: delivery time {} out of range, clamped to {date}",
+                            dt.UniversalTime
+                        );
+                    }
+                    date
+                }
+                Err(e) => {
+                    telemetry::warning!(
+                        "Pending notification {tag}: delivery time unavailable ({e}), using now"
+                    );
+                    time::OffsetDateTime::now_utc()
+                }
+            };
+
+            result.push(PendingNotification {
+                id,
+                title,
+                body,
+                schedule: Schedule::At {
                     date,
                     repeating: false,
                     allow_while_idle: false,
-                })
+                },
+                channel_id: None,
+                tag: Some(tag),
+                group,
+                extra: HashMap::new(),
+                action_type_id: None,
+                sound: None,
             });
-
-            // PendingNotification requires schedule (not Option), skip if we can't extract it
-            if let Some(schedule) = schedule {
-                result.push(PendingNotification {
-                    id,
-                    title,
-                    body,
-                    schedule,
-                });
-            }
         }
 
         Ok(result)
     }
 
+    /// Number of pending notifications per channel. Windows has no channels,
+    /// so every scheduled toast is counted under `"default"`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn pending_count_by_channel(&self) -> crate::Result<HashMap<String, usize>> {
+        let pending = self.pending().await?;
+        Ok(crate::count_pending_by_channel(&pending))
+    }
+
+    /// Deletes every file in the remote media cache.
+    #[cfg(feature = "media-cache")]
+    pub fn clear_media_cache(&self) -> crate::Result<()> {
+        crate::media_cache::clear(&self.app)
+    }
+
+    /// Size and hit/miss counters of the remote media cache.
+    #[cfg(feature = "media-cache")]
+    pub fn media_cache_stats(&self) -> crate::Result<crate::MediaCacheStats> {
+        crate::media_cache::stats(&self.app)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            err,
+            fields(
+                platform = crate::telemetry::BACKEND,
+                ids = ?notifications
+            )
+        )
+    )]
     pub fn cancel(&self, notifications: Vec<i32>) -> crate::Result<()> {
-        let scheduled = self.plugin.notifier.GetScheduledToastNotifications()?;
-        let ids_to_cancel: std::collections::HashSet<_> = notifications.into_iter().collect();
+        for (id, e) in self.cancel_detailed(notifications)?.failed {
+            telemetry::error!("Failed to cancel notification {id}: {e}");
+        }
+        Ok(())
+    }
 
+    /// [`cancel`](Self::cancel) with the outcome of each id. An id with
+    /// nothing scheduled counts as canceled; one whose count-down toast can't
+    /// be removed fails. Fails as a whole only when the schedule can't be
+    /// read.
+    pub fn cancel_detailed(&self, notifications: Vec<i32>) -> crate::Result<crate::BulkResult> {
+        let notifier = self.plugin.notifier()?;
+        let scheduled = notifier.GetScheduledToastNotifications()?;
+        let ids_to_cancel: std::collections::HashSet<_> = notifications.iter().copied().collect();
+        self.plugin.stop_repeats(|id| ids_to_cancel.contains(&id));
+        let count_downs = self
+            .plugin
+            .stop_count_downs(|id| ids_to_cancel.contains(&id));
+
+        let mut errors = HashMap::new();
         for i in 0..scheduled.Size()? {
-            if let Ok(notification) = scheduled.GetAt(i) {
-                if let Ok(tag) = notification.Tag() {
-                    if let Ok(id) = tag.to_string_lossy().parse::<i32>() {
-                        if ids_to_cancel.contains(&id) {
-                            if let Err(e) = self.plugin.notifier.RemoveFromSchedule(&notification) {
-                                log::error!("Failed to cancel notification {id}: {e}");
-                            }
-                        }
-                    }
-                }
+            if let Ok(notification) = scheduled.GetAt(i)
+                && let Ok(tag) = notification.Tag()
+                && let Ok(id) = tag.to_string_lossy().parse::<i32>()
+                && ids_to_cancel.contains(&id)
+                && let Err(e) = notifier.RemoveFromSchedule(&notification)
+            {
+                errors.insert(id, crate::Error::from(e).to_string());
             }
         }
-        Ok(())
+        // A frozen progress bar would outlive the canceled alarm.
+        let removal_errors = match self.remove_active_detailed(count_downs.clone()) {
+            Ok(removed) => removed.failed,
+            Err(e) => {
+                let message = e.to_string();
+                count_downs
+                    .into_iter()
+                    .map(|id| (id, message.clone()))
+                    .collect()
+            }
+        };
+        for (id, e) in removal_errors {
+            errors.entry(id).or_insert(e);
+        }
+        let mut result = crate::BulkResult::default();
+        for id in notifications {
+            match errors.remove(&id) {
+                Some(e) => result.failed.push((id, e)),
+                None => result.succeeded.push(id),
+            }
+        }
+        Ok(result)
+    }
+
+    /// Cancels every toast scheduled under `group` (see
+    /// [`NotificationsBuilder::group`](crate::NotificationsBuilder::group)) and
+    /// returns their ids. Delivered toasts of the group are left alone.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND, group))
+    )]
+    pub fn cancel_by_group(&self, group: &str) -> crate::Result<Vec<i32>> {
+        if group.is_empty() {
+            return Err(crate::Error::InvalidArgument(
+                "group must not be empty".to_string(),
+            ));
+        }
+        let schedule = WinToastSchedule::new(self.plugin.notifier()?)?;
+        let canceled = cancel_group(&schedule, group)?;
+        self.plugin.stop_repeats(|id| canceled.contains(&id));
+        let count_downs = self.plugin.stop_count_downs(|id| canceled.contains(&id));
+        self.remove_active(count_downs)?;
+        Ok(canceled)
+    }
+
+    /// Cancels the scheduled toast `id` and removes the delivered one, with a
+    /// single pass over the schedule and one over the history. A toast the
+    /// user already dismissed is removed too, but doesn't count as active
+    /// (see [`active`](Self::active)).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND, id))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn dismiss(&self, id: i32) -> crate::Result<crate::DismissOutcome> {
+        let notifier = self.plugin.notifier()?;
+        let tag = HSTRING::from(id.to_string());
+
+        self.plugin.stop_repeats(|other| other == id);
+        let mut was_pending = false;
+        let scheduled = notifier.GetScheduledToastNotifications()?;
+        for i in 0..scheduled.Size()? {
+            let notification = scheduled.GetAt(i)?;
+            if notification.Tag()? == tag {
+                notifier.RemoveFromSchedule(&notification)?;
+                was_pending = true;
+            }
+        }
+        // A running count-down still has its completion ahead of it.
+        was_pending |= !self.plugin.stop_count_downs(|other| other == id).is_empty();
+
+        let history = ToastNotificationManager::History()?;
+        let delivered = if self.plugin.packaged {
+            history.GetHistory()?
+        } else {
+            history.GetHistoryWithId(&HSTRING::from(self.plugin.app_id()))?
+        };
+        let mut in_history = false;
+        for i in 0..delivered.Size()? {
+            if delivered.GetAt(i)?.Tag()? == tag {
+                in_history = true;
+                break;
+            }
+        }
+        let was_active = in_history && !self.plugin.dismissed_mut().remove(&id.to_string());
+        if in_history {
+            self.remove_active(vec![id])?;
+        }
+
+        Ok(crate::DismissOutcome {
+            was_pending,
+            was_active,
+        })
+    }
+
+    /// Cancels the scheduled toasts and removes the delivered ones whose
+    /// `launch=` payload carries `scope`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND, scope))
+    )]
+    #[must_use = "this future does nothing unless awaited"]
+    pub async fn clear_scope(&self, scope: &str) -> crate::Result<crate::ScopeClearance> {
+        let in_scope = |content: windows::core::Result<XmlDocument>| {
+            content
+                .ok()
+                .and_then(|content| launch_field(&content, "scope"))
+                .is_some_and(|s| s == scope)
+        };
+        let notifier = self.plugin.notifier()?;
+
+        let mut canceled = Vec::new();
+        let scheduled = notifier.GetScheduledToastNotifications()?;
+        for i in 0..scheduled.Size()? {
+            let notification = scheduled.GetAt(i)?;
+            if !in_scope(notification.Content()) {
+                continue;
+            }
+            notifier.RemoveFromSchedule(&notification)?;
+            if let Ok(id) = notification.Tag()?.to_string_lossy().parse::<i32>() {
+                canceled.push(id);
+            }
+        }
+        self.plugin.stop_repeats(|id| canceled.contains(&id));
+
+        let history = ToastNotificationManager::History()?;
+        let delivered = if self.plugin.packaged {
+            history.GetHistory()?
+        } else {
+            history.GetHistoryWithId(&HSTRING::from(self.plugin.app_id()))?
+        };
+        let mut removed = Vec::new();
+        for i in 0..delivered.Size()? {
+            let notification = delivered.GetAt(i)?;
+            if !in_scope(notification.Content()) {
+                continue;
+            }
+            if let Ok(id) = notification.Tag()?.to_string_lossy().parse::<i32>() {
+                removed.push(id);
+            }
+        }
+        self.plugin.stop_count_downs(|id| removed.contains(&id));
+        let count = removed.len();
+        self.remove_active(removed)?;
+
+        Ok(crate::ScopeClearance {
+            canceled: canceled.len(),
+            removed: count,
+        })
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
     pub fn cancel_all(&self) -> crate::Result<()> {
-        let scheduled = self.plugin.notifier.GetScheduledToastNotifications()?;
+        let notifier = self.plugin.notifier()?;
+        let scheduled = notifier.GetScheduledToastNotifications()?;
+        self.plugin.stop_repeats(|_| true);
+        let count_downs = self.plugin.stop_count_downs(|_| true);
         for i in 0..scheduled.Size()? {
             if let Ok(notification) = scheduled.GetAt(i) {
-                if let Err(e) = self.plugin.notifier.RemoveFromSchedule(&notification) {
-                    log::error!("Failed to cancel scheduled notification: {e}");
+                if let Err(e) = notifier.RemoveFromSchedule(&notification) {
+                    telemetry::error!("Failed to cancel scheduled notification: {e}");
                 }
             }
         }
-        Ok(())
+        self.remove_active(count_downs)
     }
 
     pub fn set_click_listener_active(&self, active: bool) -> crate::Result<()> {
@@ -1018,20 +3171,71 @@ impl<R: Runtime> Notifications<R> {
     }
 
     /// Create a notification channel (not supported on Windows).
-    pub fn create_channel(&self, _channel: crate::Channel) -> crate::Result<()> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    pub fn create_channel(
+        &self,
+        _channel: crate::Channel,
+    ) -> crate::Result<crate::ChannelCreation> {
+        Err(crate::Error::Io(std::io::Error::other(
+            "Notification channels are not supported on Windows",
+        )))
+    }
+
+    /// Create several notification channels (not supported on Windows).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    pub fn ensure_channels(
+        &self,
+        _channels: Vec<crate::Channel>,
+    ) -> crate::Result<Vec<crate::ChannelCreation>> {
+        Err(crate::Error::Io(std::io::Error::other(
+            "Notification channels are not supported on Windows",
+        )))
+    }
+
+    /// Update a notification channel (not supported on Windows).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    pub fn update_channel(&self, _channel: crate::Channel) -> crate::Result<()> {
         Err(crate::Error::Io(std::io::Error::other(
             "Notification channels are not supported on Windows",
         )))
     }
 
     /// Delete a notification channel (not supported on Windows).
-    pub fn delete_channel(&self, _id: impl Into<String>) -> crate::Result<()> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
+    pub fn delete_channel(
+        &self,
+        _id: impl Into<String>,
+        _on_pending: crate::OnPendingPolicy,
+    ) -> crate::Result<crate::ChannelDeletion> {
+        Err(crate::Error::Io(std::io::Error::other(
+            "Notification channels are not supported on Windows",
+        )))
+    }
+
+    /// Watch for blocked channels (not supported on Windows).
+    pub fn register_channel_blocked_listener(&self) -> crate::Result<()> {
         Err(crate::Error::Io(std::io::Error::other(
             "Notification channels are not supported on Windows",
         )))
     }
 
     /// List notification channels (not supported on Windows).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(platform = crate::telemetry::BACKEND))
+    )]
     pub fn list_channels(&self) -> crate::Result<Vec<crate::Channel>> {
         Err(crate::Error::Io(std::io::Error::other(
             "Notification channels are not supported on Windows",
@@ -1039,9 +3243,19 @@ impl<R: Runtime> Notifications<R> {
     }
 }
 
+/// Whether toasts are held back while the user is in `state`. A locked
+/// screen (`QUNS_NOT_PRESENT`) still queues them for later.
+fn suppresses_notifications(state: QUERY_USER_NOTIFICATION_STATE) -> bool {
+    matches!(
+        state,
+        QUNS_BUSY | QUNS_RUNNING_D3D_FULL_SCREEN | QUNS_PRESENTATION_MODE | QUNS_QUIET_TIME
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use windows::Win32::Foundation::E_ACCESSDENIED;
 
     /// PowerShell App User Model ID - always available on Windows.
     const POWERSHELL_APP_ID: &str =
@@ -1082,6 +3296,24 @@ mod tests {
         assert!(diff < 100, "Roundtrip diff: {}ns", diff);
     }
 
+    #[test]
+    fn test_windows_datetime_clamped() {
+        let in_range = unix_to_windows_datetime(time::macros::datetime!(2024-06-15 14:30:45 UTC))
+            .expect("Failed to convert to Windows datetime");
+        let (_, clamped) = windows_datetime_to_unix_clamped(in_range);
+        assert!(!clamped);
+
+        let (date, clamped) = windows_datetime_to_unix_clamped(DateTime { UniversalTime: -1 });
+        assert!(clamped);
+        assert_eq!(date.year(), 1601);
+
+        let (date, clamped) = windows_datetime_to_unix_clamped(DateTime {
+            UniversalTime: i64::MAX,
+        });
+        assert!(clamped);
+        assert_eq!(date, time::OffsetDateTime::from(time::UtcDateTime::MAX));
+    }
+
     #[test]
     fn test_schedule_at_conversion() {
         let target = time::macros::datetime!(2025-12-25 10:00:00 UTC);
@@ -1120,6 +3352,475 @@ mod tests {
         assert!((actual - expected).abs() <= 2);
     }
 
+    #[test]
+    fn test_toast_attachments() {
+        let images = vec![
+            (
+                "appLogoOverride".to_string(),
+                "file:///C:/icons/app.png".to_string(),
+            ),
+            (
+                "hero".to_string(),
+                "https://example.com/hero.png".to_string(),
+            ),
+            (String::new(), "ms-appx:///Assets/inline.png".to_string()),
+            (String::new(), "not a url".to_string()),
+        ];
+        let attachments = toast_attachments(images);
+        let urls: Vec<&str> = attachments.iter().map(|a| a.url().as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://example.com/hero.png",
+                "ms-appx:///Assets/inline.png"
+            ]
+        );
+        assert_eq!(attachments[1].id(), "1");
+    }
+
+    #[test]
+    fn test_field_of_launch() {
+        let data = NotificationData {
+            scope: Some("alice@example.com".to_string()),
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert!(xml.starts_with(
+            "<toast launch=\"{&quot;id&quot;:7,&quot;data&quot;:{},&quot;scope&quot;:&quot;alice@example.com&quot;}\""
+        ));
+
+        assert_eq!(
+            field_of_launch(r#"{"id":7,"data":{},"scope":"alice@example.com"}"#, "scope")
+                .as_deref(),
+            Some("alice@example.com")
+        );
+        assert_eq!(field_of_launch(r#"{"id":7,"data":{}}"#, "scope"), None);
+        assert_eq!(field_of_launch("reply", "scope"), None);
+
+        let data = NotificationData {
+            sort_key: Some("0001".to_string()),
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert!(xml.starts_with(
+            "<toast launch=\"{&quot;id&quot;:7,&quot;data&quot;:{},&quot;sortKey&quot;:&quot;0001&quot;}\""
+        ));
+    }
+
+    /// Action Center stand-in that, like Windows, only removes a toast when
+    /// both tag and group match.
+    struct FakeHistory(std::cell::RefCell<Vec<(String, Option<String>)>>);
+
+    impl ToastHistory for FakeHistory {
+        fn entries(&self) -> crate::Result<Vec<(String, Option<String>)>> {
+            Ok(self.0.borrow().clone())
+        }
+
+        fn remove(&self, tag: &str, group: Option<&str>) -> crate::Result<()> {
+            self.0
+                .borrow_mut()
+                .retain(|(t, g)| !(t == tag && g.as_deref() == group));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_remove_tag_uses_group() {
+        let history = FakeHistory(std::cell::RefCell::new(vec![
+            ("1".to_string(), None),
+            ("2".to_string(), Some("chat".to_string())),
+            ("2".to_string(), Some("mail".to_string())),
+            ("20".to_string(), Some("chat".to_string())),
+            ("02".to_string(), None),
+        ]));
+        let entries = history.entries().expect("entries");
+
+        remove_tag(&history, &entries, "2").expect("remove 2");
+        remove_tag(&history, &entries, "1").expect("remove 1");
+        // Not listed: falls back to an ungrouped removal, which is a no-op.
+        remove_tag(&history, &entries, "3").expect("remove 3");
+
+        assert_eq!(
+            history.entries().expect("entries"),
+            vec![
+                ("20".to_string(), Some("chat".to_string())),
+                ("02".to_string(), None),
+            ]
+        );
+    }
+
+    /// [`FakeHistory`] whose removal of the listed tags fails.
+    struct FailingHistory(FakeHistory, &'static [&'static str]);
+
+    impl ToastHistory for FailingHistory {
+        fn entries(&self) -> crate::Result<Vec<(String, Option<String>)>> {
+            self.0.entries()
+        }
+
+        fn remove(&self, tag: &str, group: Option<&str>) -> crate::Result<()> {
+            if self.1.contains(&tag) {
+                return Err(crate::Error::Io(std::io::Error::other(format!(
+                    "cannot remove {tag}"
+                ))));
+            }
+            self.0.remove(tag, group)
+        }
+    }
+
+    #[test]
+    fn test_activation_launch_source() {
+        assert_eq!(
+            activation_launch_source(r#"{"id":7,"data":{}}"#),
+            crate::LaunchSource::NotificationTap { id: Some(7) }
+        );
+        assert_eq!(
+            activation_launch_source(""),
+            crate::LaunchSource::NotificationTap { id: None }
+        );
+        assert_eq!(
+            activation_launch_source("reply"),
+            crate::LaunchSource::NotificationAction {
+                id: None,
+                action_id: "reply".to_string(),
+            }
+        );
+    }
+
+    /// Schedule stand-in recording which indices were removed.
+    struct FakeSchedule {
+        entries: Vec<(String, Option<String>)>,
+        removed: std::cell::RefCell<Vec<usize>>,
+    }
+
+    impl ToastSchedule for FakeSchedule {
+        fn entries(&self) -> crate::Result<Vec<(String, Option<String>)>> {
+            Ok(self.entries.clone())
+        }
+
+        fn remove(&self, index: usize) -> crate::Result<()> {
+            self.removed.borrow_mut().push(index);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_cancel_group_matches_exact_group() {
+        let schedule = FakeSchedule {
+            entries: vec![
+                ("1".to_string(), Some("semester-2024".to_string())),
+                ("2".to_string(), None),
+                ("3".to_string(), Some("semester-2024-extra".to_string())),
+                ("4".to_string(), Some("semester-2024".to_string())),
+                ("not-an-id".to_string(), Some("semester-2024".to_string())),
+                ("6".to_string(), Some("Semester-2024".to_string())),
+            ],
+            removed: std::cell::RefCell::default(),
+        };
+
+        let canceled = cancel_group(&schedule, "semester-2024").expect("schedule readable");
+
+        assert_eq!(canceled, [1, 4]);
+        assert_eq!(*schedule.removed.borrow(), [0, 3, 4]);
+        assert!(
+            cancel_group(&schedule, "other")
+                .expect("schedule readable")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_remove_ids_reports_each_failure() {
+        let history = FailingHistory(
+            FakeHistory(std::cell::RefCell::new(vec![
+                ("1".to_string(), None),
+                ("2".to_string(), Some("chat".to_string())),
+                ("3".to_string(), None),
+            ])),
+            &["2", "4"],
+        );
+
+        let result = remove_ids(&history, vec![4, 3, 2, 1]).expect("history readable");
+
+        assert_eq!(result.succeeded, [3, 1]);
+        assert_eq!(
+            result.failed,
+            [
+                (4, "cannot remove 4".to_string()),
+                (2, "cannot remove 2".to_string()),
+            ]
+        );
+        assert_eq!(
+            history.entries().expect("entries"),
+            vec![("2".to_string(), Some("chat".to_string()))]
+        );
+    }
+
+    #[test]
+    fn test_repeat_period() {
+        let interval = |interval| Schedule::Interval {
+            interval,
+            allow_while_idle: false,
+        };
+
+        assert_eq!(
+            repeat_period(&interval(ScheduleInterval {
+                hour: Some(1),
+                minute: Some(30),
+                ..Default::default()
+            })),
+            Some(time::Duration::minutes(90))
+        );
+        assert_eq!(
+            repeat_period(&interval(ScheduleInterval {
+                weekday: Some(2),
+                hour: Some(9),
+                ..Default::default()
+            })),
+            Some(time::Duration::WEEK)
+        );
+        assert_eq!(repeat_period(&interval(ScheduleInterval::default())), None);
+        assert_eq!(
+            repeat_period(&Schedule::At {
+                date: time::OffsetDateTime::UNIX_EPOCH,
+                repeating: true,
+                allow_while_idle: false,
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_catch_up() {
+        let fired = time::macros::datetime!(2025-03-10 09:00 UTC);
+        let hour = time::Duration::HOUR;
+
+        // Woke up before the next occurrence: nothing missed.
+        assert_eq!(
+            catch_up(
+                fired,
+                hour,
+                time::macros::datetime!(2025-03-10 09:00:05 UTC)
+            ),
+            (time::macros::datetime!(2025-03-10 10:00 UTC), 0)
+        );
+        // Slept through 10:00, 11:00 and 12:00.
+        assert_eq!(
+            catch_up(fired, hour, time::macros::datetime!(2025-03-10 12:30 UTC)),
+            (time::macros::datetime!(2025-03-10 13:00 UTC), 3)
+        );
+        // A clock that went backwards doesn't count as missed.
+        assert_eq!(
+            catch_up(fired, hour, time::macros::datetime!(2025-03-10 08:00 UTC)),
+            (time::macros::datetime!(2025-03-10 10:00 UTC), 0)
+        );
+    }
+
+    #[test]
+    fn test_repeat_cursor_clock_jump() {
+        let at = |hour: i64, minute: i64| {
+            time::macros::datetime!(2025-03-10 00:00 UTC)
+                + time::Duration::minutes(hour * 60 + minute)
+        };
+        let mut cursor = RepeatCursor {
+            next: at(9, 0),
+            period: time::Duration::HOUR,
+            remaining: Some(5),
+        };
+        let fire_once = MissedFirePolicy::FireOnce;
+
+        assert_eq!(
+            cursor.step(at(8, 30), fire_once),
+            RepeatStep::Wait(Duration::from_secs(30 * 60))
+        );
+        // On time: the next occurrence is scheduled, nothing to catch up on.
+        assert_eq!(
+            cursor.step(at(9, 0), fire_once),
+            RepeatStep::Fire {
+                catch_up: false,
+                reschedule: Some(at(10, 0)),
+            }
+        );
+        // The clock jumps from 9:00 to 12:30 (10:00 was delivered by Windows
+        // on wake, 11:00 and 12:00 were missed): one catch-up toast.
+        assert_eq!(
+            cursor.step(at(12, 30), fire_once),
+            RepeatStep::Fire {
+                catch_up: true,
+                reschedule: Some(at(13, 0)),
+            }
+        );
+        // The wake-up and the periodic check both see 12:30: only the first
+        // one fires.
+        assert_eq!(
+            cursor.step(at(12, 30), fire_once),
+            RepeatStep::Wait(Duration::from_secs(30 * 60))
+        );
+        assert_eq!(
+            cursor.step(at(13, 0), fire_once),
+            RepeatStep::Fire {
+                catch_up: false,
+                reschedule: Some(at(14, 0)),
+            }
+        );
+        // The five repetitions after 9:00: 10:00, 13:00 and 14:00 were
+        // scheduled, 11:00 and 12:00 missed.
+        assert_eq!(cursor.remaining, Some(0));
+        assert_eq!(cursor.step(at(14, 0), fire_once), RepeatStep::Done);
+    }
+
+    #[test]
+    fn test_repeat_cursor_skip_and_exhausted() {
+        let start = time::macros::datetime!(2025-03-10 09:00 UTC);
+        let mut cursor = RepeatCursor {
+            next: start,
+            period: time::Duration::HOUR,
+            remaining: Some(2),
+        };
+        // Missed occurrences use up the remaining repetitions.
+        assert_eq!(
+            cursor.step(start + time::Duration::hours(3), MissedFirePolicy::FireOnce),
+            RepeatStep::Fire {
+                catch_up: true,
+                reschedule: None,
+            }
+        );
+        assert_eq!(
+            cursor.step(start + time::Duration::hours(3), MissedFirePolicy::FireOnce),
+            RepeatStep::Done
+        );
+
+        let mut cursor = RepeatCursor {
+            next: start,
+            period: time::Duration::HOUR,
+            remaining: None,
+        };
+        assert_eq!(
+            cursor.step(start + time::Duration::hours(3), MissedFirePolicy::Skip),
+            RepeatStep::Fire {
+                catch_up: false,
+                reschedule: Some(start + time::Duration::hours(4)),
+            }
+        );
+    }
+
+    #[test]
+    fn test_repeat_cursor_stored_position() {
+        let start = time::macros::datetime!(2025-03-10 09:00 UTC);
+        let mut repeat = timers::Repeat {
+            xml: "<toast/>".to_string(),
+            group: None,
+            next: start.unix_timestamp(),
+            period_secs: 3600,
+            remaining: Some(2),
+        };
+        let mut cursor = RepeatCursor::of(&repeat);
+        assert_eq!(cursor.next, start);
+        assert_eq!(cursor.period, time::Duration::HOUR);
+        cursor.step(start, MissedFirePolicy::FireOnce);
+        cursor.store(&mut repeat);
+        assert_eq!(repeat.next, (start + time::Duration::HOUR).unix_timestamp());
+        assert_eq!(repeat.remaining, Some(1));
+    }
+
+    #[test]
+    fn test_due_repeats_clock_jump() {
+        let start = time::macros::datetime!(2025-03-10 09:00 UTC);
+        let repeat = |remaining| timers::Repeat {
+            xml: "<toast/>".to_string(),
+            group: None,
+            next: start.unix_timestamp(),
+            period_secs: 3600,
+            remaining,
+        };
+        let mut timers = Timers {
+            repeats: HashMap::from([(1, repeat(None)), (2, repeat(Some(1)))]),
+            count_downs: HashMap::new(),
+        };
+        let max_wait = Duration::from_secs(60);
+
+        // Before the first occurrence nothing fires.
+        let (due, wait) = due_repeats(
+            &mut timers,
+            start - time::Duration::seconds(30),
+            MissedFirePolicy::FireOnce,
+            max_wait,
+        );
+        assert!(due.is_empty());
+        assert_eq!(wait, Duration::from_secs(30));
+
+        // Slept through three hours: one catch-up toast each, and only the
+        // schedule with repetitions left is scheduled again.
+        let woke = start + time::Duration::minutes(150);
+        let (due, wait) = due_repeats(&mut timers, woke, MissedFirePolicy::FireOnce, max_wait);
+        assert_eq!(
+            due,
+            [
+                DueRepeat {
+                    id: 1,
+                    catch_up: true,
+                    reschedule: Some(start + time::Duration::hours(3)),
+                },
+                DueRepeat {
+                    id: 2,
+                    catch_up: true,
+                    reschedule: None,
+                },
+            ]
+        );
+        assert_eq!(wait, max_wait);
+        assert_eq!(
+            timers.repeats[&1].next,
+            (start + time::Duration::hours(3)).unix_timestamp()
+        );
+
+        // A second wake-up at the same time fires nothing again, and drops
+        // the exhausted schedule.
+        let (due, wait) = due_repeats(&mut timers, woke, MissedFirePolicy::FireOnce, max_wait);
+        assert!(due.is_empty());
+        assert_eq!(wait, Duration::from_secs(30 * 60).min(max_wait));
+        assert_eq!(timers.repeats.keys().copied().collect::<Vec<_>>(), [1]);
+    }
+
+    #[test]
+    fn test_next_weekday_occurrence() {
+        // Wednesday 2025-01-15 10:00:00 +01:00.
+        let now = time::OffsetDateTime::new_in_offset(
+            time::Date::from_calendar_date(2025, time::Month::January, 15).expect("valid date"),
+            time::Time::from_hms(10, 0, 0).expect("valid time"),
+            time::UtcOffset::from_hms(1, 0, 0).expect("valid offset"),
+        );
+        let at = |weekday, hour| {
+            let interval = ScheduleInterval {
+                weekday: Some(weekday),
+                hour: Some(hour),
+                ..Default::default()
+            };
+            next_weekday_occurrence(now, weekday, &interval).expect("valid weekday schedule")
+        };
+
+        // Later the same day.
+        let next = at(4, 11);
+        assert_eq!((next.date(), next.hour()), (now.date(), 11));
+        assert_eq!(next.offset(), now.offset());
+        // Earlier the same day rolls over to next week.
+        let next = at(4, 9);
+        assert_eq!(next.weekday(), time::Weekday::Wednesday);
+        assert_eq!((next.day(), next.hour()), (22, 9));
+        // 1 = Sunday, 7 = Saturday.
+        assert_eq!(at(1, 9).day(), 19);
+        assert_eq!(at(7, 9).day(), 18);
+        assert_eq!(at(3, 9).weekday(), time::Weekday::Tuesday);
+
+        let interval = ScheduleInterval::default();
+        assert!(next_weekday_occurrence(now, 0, &interval).is_err());
+        assert!(next_weekday_occurrence(now, 8, &interval).is_err());
+        let interval = ScheduleInterval {
+            hour: Some(24),
+            ..Default::default()
+        };
+        assert!(next_weekday_occurrence(now, 2, &interval).is_err());
+    }
+
     #[test]
     fn test_schedule_every_variants() {
         let cases = [
@@ -1156,6 +3857,199 @@ mod tests {
         }
     }
 
+    // ==================== Retry Policy Tests ====================
+
+    #[test]
+    fn test_retry_policy_default_delays() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.attempts, 3);
+        assert_eq!(policy.delay(1), std::time::Duration::from_millis(1500));
+        assert_eq!(policy.delay(2), std::time::Duration::from_millis(3000));
+        assert_eq!(policy.delay(3), std::time::Duration::from_millis(4000));
+    }
+
+    #[test]
+    fn test_retry_policy_delay_saturates() {
+        let policy = RetryPolicy {
+            attempts: 100,
+            base_delay_ms: u64::MAX / 2,
+            max_delay_ms: 10_000,
+        };
+        assert_eq!(policy.delay(80), std::time::Duration::from_millis(10_000));
+    }
+
+    #[test]
+    fn test_retry_policy_deserialization() {
+        let config: WindowsConfig =
+            serde_json::from_str(r#"{"retry": {"attempts": 5, "baseDelayMs": 200}}"#)
+                .expect("Failed to deserialize Windows config");
+        assert_eq!(config.retry.attempts, 5);
+        assert_eq!(config.retry.base_delay_ms, 200);
+        assert_eq!(config.retry.max_delay_ms, 4000);
+    }
+
+    #[test]
+    fn test_second_init_rejected() {
+        let initialized = AtomicBool::new(false);
+        assert!(claim_init(&initialized).is_ok());
+        let err = claim_init(&initialized).expect_err("second init must fail");
+        assert!(matches!(err, crate::Error::InvalidArgument(_)));
+        assert_eq!(err.to_string(), "notifications plugin already initialized");
+    }
+
+    #[test]
+    fn test_setting_name() {
+        assert_eq!(setting_name(NotificationSetting::Enabled), "enabled");
+        assert_eq!(
+            setting_name(NotificationSetting::DisabledByGroupPolicy),
+            "disabledByGroupPolicy"
+        );
+    }
+
+    #[test]
+    fn test_error_kinds() {
+        let cases = [
+            (0x803E_0105, WindowsErrorKind::PlatformUnavailable),
+            (0x803E_0108, WindowsErrorKind::NotPosted),
+            (0x8001_0001, WindowsErrorKind::Busy),
+            (0x8001_010A, WindowsErrorKind::Busy),
+            (0x803E_0111, WindowsErrorKind::Disabled),
+            (0x803E_0114, WindowsErrorKind::Disabled),
+            (0x8007_0005, WindowsErrorKind::AccessDenied),
+            (0x803E_0117, WindowsErrorKind::AccessDenied),
+            (0x8007_0490, WindowsErrorKind::NotFound),
+            (0x8007_0002, WindowsErrorKind::NotFound),
+            (0x803E_0115, WindowsErrorKind::TooLarge),
+            (0x803E_0116, WindowsErrorKind::TooLarge),
+            (0x8007_0057, WindowsErrorKind::InvalidArgument),
+            (0x8000_4005, WindowsErrorKind::Other),
+        ];
+        for (hresult, kind) in cases {
+            let err = windows::core::Error::from(HRESULT(u32::cast_signed(hresult)));
+            assert_eq!(error_kind(&err), kind, "0x{hresult:08X}");
+            assert_eq!(
+                kind.is_transient(),
+                matches!(
+                    hresult,
+                    0x803E_0105 | 0x803E_0108 | 0x8001_0001 | 0x8001_010A
+                ),
+                "0x{hresult:08X}"
+            );
+        }
+        assert_eq!(
+            error_kind(&windows::core::Error::from(E_INVALIDARG)),
+            WindowsErrorKind::InvalidArgument
+        );
+
+        match crate::Error::from(windows::core::Error::from(E_ACCESSDENIED)) {
+            crate::Error::Windows {
+                code,
+                hresult,
+                kind,
+                ..
+            } => {
+                assert_eq!(code, "0x80070005");
+                assert_eq!(hresult, E_ACCESSDENIED.0);
+                assert_eq!(kind, WindowsErrorKind::AccessDenied);
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    // ==================== App Identity Tests ====================
+
+    #[test]
+    fn test_app_identity_key() {
+        assert_eq!(
+            app_identity_key("com.example.app"),
+            "Software\\Classes\\AppUserModelId\\com.example.app"
+        );
+    }
+
+    #[test]
+    fn test_reg_sz_is_nul_terminated_utf16() {
+        assert_eq!(reg_sz("Hé"), vec![b'H', 0, 0xE9, 0, 0, 0]);
+        assert_eq!(reg_sz(""), vec![0, 0]);
+    }
+
+    #[test]
+    fn test_app_identity_config_deserialization() {
+        let config: WindowsConfig = serde_json::from_str(
+            r#"{"registerAppIdentity": true, "appIdentityIcon": "icons/toast.png"}"#,
+        )
+        .expect("Failed to deserialize Windows config");
+        assert!(config.register_app_identity);
+        assert_eq!(config.app_identity_icon.as_deref(), Some("icons/toast.png"));
+        assert!(!config.require_registered_app_id);
+
+        let config: WindowsConfig = serde_json::from_str(r#"{"requireRegisteredAppId": true}"#)
+            .expect("Failed to deserialize Windows config");
+        assert!(config.require_registered_app_id);
+
+        let config: WindowsConfig =
+            serde_json::from_str("{}").expect("Failed to deserialize Windows config");
+        assert!(!config.register_app_identity);
+        assert_eq!(config.app_identity_icon, None);
+    }
+
+    /// Reads a `REG_SZ` value under the identity key, `None` if it is missing.
+    fn read_app_identity_value(app_id: &str, name: &str) -> Option<String> {
+        use windows::Win32::System::Registry::{RRF_RT_REG_SZ, RegGetValueW};
+
+        let subkey = HSTRING::from(app_identity_key(app_id));
+        let name = HSTRING::from(name);
+        let mut buffer = [0u16; 512];
+        let mut size = u32::try_from(std::mem::size_of_val(&buffer)).ok()?;
+        unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                PCWSTR(subkey.as_ptr()),
+                PCWSTR(name.as_ptr()),
+                RRF_RT_REG_SZ,
+                None,
+                Some(buffer.as_mut_ptr().cast()),
+                Some(&raw mut size),
+            )
+        }
+        .ok()
+        .ok()?;
+        let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        Some(String::from_utf16_lossy(&buffer[..len]))
+    }
+
+    #[test]
+    fn test_app_identity_registration_roundtrip() {
+        let app_id = format!("TauriPluginNotifications.Test.{}", std::process::id());
+        let icon = std::env::temp_dir().join("toast-identity.png");
+
+        assert!(!app_id_registered(&app_id));
+        write_app_identity(&app_id, "Identity Test", Some(&icon))
+            .expect("Failed to write app identity");
+        assert!(app_id_registered(&app_id));
+        assert_eq!(
+            read_app_identity_value(&app_id, "DisplayName").as_deref(),
+            Some("Identity Test")
+        );
+        assert_eq!(
+            read_app_identity_value(&app_id, "IconUri"),
+            Some(icon.display().to_string())
+        );
+
+        // Toasts are looked up by AUMID, so a notifier for the registered id
+        // is what picks up the display name and icon.
+        let notifier = ToastNotificationManager::CreateToastNotifierWithId(&HSTRING::from(&app_id));
+        assert!(notifier.is_ok(), "Failed: {:?}", notifier.err());
+
+        // Re-registering without an icon drops the old one.
+        write_app_identity(&app_id, "Identity Test", None).expect("Failed to write app identity");
+        assert_eq!(read_app_identity_value(&app_id, "IconUri"), None);
+
+        delete_app_identity(&app_id).expect("Failed to delete app identity");
+        assert_eq!(read_app_identity_value(&app_id, "DisplayName"), None);
+        assert!(!app_id_registered(&app_id));
+        delete_app_identity(&app_id).expect("Deleting a missing identity must succeed");
+    }
+
     // ==================== Toast Notifier Tests ====================
 
     #[test]
@@ -1165,6 +4059,30 @@ mod tests {
         assert!(result.is_ok(), "Failed: {:?}", result.err());
     }
 
+    #[test]
+    fn test_toast_identity_uses_app_id_override() {
+        let identity = ToastIdentity::new("Company.Product.Desktop".to_string(), false)
+            .expect("Failed to create notifier for custom AUMID");
+        assert_eq!(identity.app_id, "Company.Product.Desktop");
+    }
+
+    #[test]
+    fn test_unregistered_app_id_disables_toasts_when_required() {
+        let app_id = format!(
+            "TauriPluginNotifications.Unregistered.{}",
+            std::process::id()
+        );
+
+        let identity = resolve_toast_identity(app_id.clone(), false, true);
+        assert!(matches!(
+            identity.notifier,
+            Err(ref reason) if reason.contains(&app_id)
+        ));
+
+        let identity = resolve_toast_identity(app_id, false, false);
+        assert!(identity.notifier.is_ok());
+    }
+
     // ==================== XML Building Tests ====================
 
     #[test]
@@ -1267,6 +4185,486 @@ mod tests {
         );
     }
 
+    // ==================== Toast XML Snapshot Tests ====================
+
+    // Signature must match the `resolve_image` callback.
+    #[allow(clippy::unnecessary_wraps)]
+    fn passthrough_image(src: &str) -> Option<String> {
+        Some(src.to_string())
+    }
+
+    fn snapshot_data() -> NotificationData {
+        NotificationData {
+            id: 7,
+            title: Some("Title".to_string()),
+            body: Some("Body".to_string()),
+            ..Default::default()
+        }
+    }
+
+    const LAUNCH_ID_7: &str = "{&quot;id&quot;:7,&quot;data&quot;:{}}";
+
+    #[test]
+    fn test_toast_xml_snapshot_title_body() {
+        let xml = build_toast_xml(&snapshot_data(), &HashMap::new(), passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\"><visual><binding template=\"ToastGeneric\">\
+                 <text>Title</text><text>Body</text></binding></visual></toast>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_text_order() {
+        let data = NotificationData {
+            large_body: Some("Expanded".to_string()),
+            ticker: Some("Ticker".to_string()),
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        let title = xml.find("<text>Title</text>").expect("title text");
+        let body = xml.find("<text>Body</text>").expect("body text");
+        let expanded = xml.find("<text>Expanded</text>").expect("large body text");
+        assert!(title < body && body < expanded);
+        // Windows has no ticker; it must not show up as an extra line.
+        assert!(!xml.contains("Ticker"));
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_escapes_text() {
+        let data = NotificationData {
+            title: Some("Tom & \"Jerry\" <3".to_string()),
+            body: None,
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\"><visual><binding template=\"ToastGeneric\">\
+                 <text>Tom &amp; &quot;Jerry&quot; &lt;3</text></binding></visual></toast>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_large_body() {
+        let data = NotificationData {
+            large_body: Some("Expanded".to_string()),
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\"><visual><binding template=\"ToastGeneric\">\
+                 <text>Title</text><text>Body</text><text>Expanded</text>\
+                 </binding></visual></toast>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_summary() {
+        let data = NotificationData {
+            large_body: Some("Expanded".to_string()),
+            summary: Some("via Mail & Chat".to_string()),
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\"><visual><binding template=\"ToastGeneric\">\
+                 <text>Title</text><text>Body</text><text>Expanded</text>\
+                 <text placement=\"attribution\">via Mail &amp; Chat</text>\
+                 </binding></visual></toast>"
+            )
+        );
+
+        let document = XmlDocument::new().expect("Failed to create XmlDocument");
+        let without_body = NotificationData { body: None, ..data };
+        document
+            .LoadXml(&HSTRING::from(build_toast_xml(
+                &without_body,
+                &HashMap::new(),
+                passthrough_image,
+            )))
+            .expect("Failed to load toast XML");
+        let (title, body) = toast_texts(&document).expect("Failed to read toast text");
+        assert_eq!(title.as_deref(), Some("Title"));
+        assert_eq!(body.as_deref(), Some("Expanded"));
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_large_body_same_as_body() {
+        let data = NotificationData {
+            large_body: Some("Body".to_string()),
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\"><visual><binding template=\"ToastGeneric\">\
+                 <text>Title</text><text>Body</text></binding></visual></toast>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_icon() {
+        let data = NotificationData {
+            icon: Some("file:///C:/icons/app.png".to_string()),
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\"><visual><binding template=\"ToastGeneric\">\
+                 <text>Title</text><text>Body</text>\
+                 <image placement=\"appLogoOverride\" src=\"file:///C:/icons/app.png\"/>\
+                 </binding></visual></toast>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_unresolvable_icon_dropped() {
+        let data = NotificationData {
+            icon: Some("ic_notify".to_string()),
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), |_| None);
+        assert!(!xml.contains("<image"));
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_hero_image() {
+        let hero = url::Url::parse("https://example.com/hero.png").expect("Failed to parse URL");
+        let inline =
+            url::Url::parse("https://example.com/inline.png").expect("Failed to parse URL");
+        let data = NotificationData {
+            attachments: vec![
+                Attachment::new("hero", hero),
+                Attachment::new("inline", inline),
+            ],
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\"><visual><binding template=\"ToastGeneric\">\
+                 <text>Title</text><text>Body</text>\
+                 <image placement=\"hero\" src=\"https://example.com/hero.png\"/>\
+                 <image src=\"https://example.com/inline.png\"/>\
+                 </binding></visual></toast>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_actions() {
+        let reply: Action = serde_json::from_value(serde_json::json!({
+            "id": "reply",
+            "title": "Reply",
+            "input": true,
+            "inputPlaceholder": "Type a reply",
+        }))
+        .expect("Failed to deserialize action");
+        let mut action_types = HashMap::new();
+        action_types.insert(
+            "msg".to_string(),
+            ActionType::new("msg", vec![Action::new("open", "Open", true), reply]),
+        );
+        let data = NotificationData {
+            action_type_id: Some("msg".to_string()),
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &action_types, passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\"><visual><binding template=\"ToastGeneric\">\
+                 <text>Title</text><text>Body</text></binding></visual>\
+                 <actions>\
+                 <input id=\"userText\" type=\"text\" placeHolderContent=\"Type a reply\"/>\
+                 <action content=\"Open\" arguments=\"open\" activationType=\"foreground\"/>\
+                 <action content=\"Reply\" arguments=\"reply\" activationType=\"background\" \
+                 hint-inputId=\"userText\"/>\
+                 </actions></toast>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_media_controls() {
+        let mut action_types = HashMap::new();
+        action_types.insert(
+            "player".to_string(),
+            ActionType::new("player", vec![Action::new("open", "Open", true)]),
+        );
+        let data = NotificationData {
+            action_type_id: Some("player".to_string()),
+            media_controls: Some(MediaControls {
+                actions: vec![MediaAction::Pause, MediaAction::Next],
+                show_in_compact: vec![0],
+            }),
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &action_types, passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\"><visual><binding template=\"ToastGeneric\">\
+                 <text>Title</text><text>Body</text></binding></visual>\
+                 <actions>\
+                 <action content=\"Pause\" arguments=\"media:pause\" activationType=\"background\"/>\
+                 <action content=\"Next\" arguments=\"media:next\" activationType=\"background\"/>\
+                 <action content=\"Open\" arguments=\"open\" activationType=\"foreground\"/>\
+                 </actions></toast>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_count_down() {
+        let data = NotificationData {
+            count_down_to: Some(time::OffsetDateTime::UNIX_EPOCH),
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\"><visual><binding template=\"ToastGeneric\">\
+                 <text>Title</text><text>Body</text>\
+                 <progress status=\"Timer\" value=\"{{progressValue}}\" \
+                 valueStringOverride=\"{{progressValueString}}\"/>\
+                 </binding></visual></toast>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_progress() {
+        let data = NotificationData {
+            progress: Some(0.5),
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\"><visual><binding template=\"ToastGeneric\">\
+                 <text>Title</text><text>Body</text>\
+                 <progress status=\"\" value=\"{{progressValue}}\"/>\
+                 </binding></visual></toast>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_count_down_alarm() {
+        let data = NotificationData {
+            count_down_to: Some(time::OffsetDateTime::UNIX_EPOCH),
+            group_summary: true,
+            ..snapshot_data()
+        };
+        let xml = build_alarm_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\" scenario=\"alarm\"><visual>\
+                 <binding template=\"ToastGeneric\">\
+                 <text>Title</text><text>Body</text></binding></visual>\
+                 <actions>\
+                 <action content=\"\" arguments=\"dismiss\" activationType=\"system\"/>\
+                 </actions>\
+                 <audio src=\"ms-winsoundevent:Notification.Looping.Alarm\" loop=\"true\"/>\
+                 </toast>"
+            )
+        );
+
+        let data = NotificationData {
+            sound: Some("ms-winsoundevent:Notification.Looping.Call".to_string()),
+            ..data
+        };
+        let xml = build_alarm_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert!(
+            xml.ends_with("<audio src=\"ms-winsoundevent:Notification.Looping.Call\"/></toast>")
+        );
+    }
+
+    #[test]
+    fn test_count_down_sequence() {
+        let total = Duration::from_secs(300);
+        assert_eq!(count_down_sequence(total, total), 2);
+        assert_eq!(
+            count_down_sequence(Duration::from_millis(299_500), total),
+            2
+        );
+        assert_eq!(count_down_sequence(Duration::from_secs(60), total), 242);
+        // More time left than in total, e.g. after the clock was set back.
+        assert_eq!(count_down_sequence(Duration::from_secs(400), total), 2);
+    }
+
+    #[test]
+    fn test_count_down_bindings() {
+        let total = Duration::from_secs(300);
+        assert_eq!(
+            count_down_bindings(total, total),
+            [
+                ("progressValue", "1.000".to_string()),
+                ("progressValueString", "5:00 remaining".to_string()),
+            ]
+        );
+        assert_eq!(
+            count_down_bindings(Duration::from_millis(74_500), total),
+            [
+                ("progressValue", "0.248".to_string()),
+                ("progressValueString", "1:15 remaining".to_string()),
+            ]
+        );
+        assert_eq!(
+            count_down_bindings(Duration::ZERO, Duration::ZERO)[0].1,
+            "0.000"
+        );
+        assert_eq!(format_remaining(3 * 3600 + 5 * 60 + 9), "3:05:09");
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_unknown_action_type() {
+        let data = NotificationData {
+            action_type_id: Some("missing".to_string()),
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert!(!xml.contains("<actions>"));
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_silent() {
+        let data = NotificationData {
+            silent: true,
+            sound: Some("ms-winsoundevent:Notification.Mail".to_string()),
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\"><visual><binding template=\"ToastGeneric\">\
+                 <text>Title</text><text>Body</text></binding></visual>\
+                 <audio silent=\"true\"/></toast>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_sound() {
+        let data = NotificationData {
+            sound: Some("ms-winsoundevent:Notification.Mail".to_string()),
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\"><visual><binding template=\"ToastGeneric\">\
+                 <text>Title</text><text>Body</text></binding></visual>\
+                 <audio src=\"ms-winsoundevent:Notification.Mail\"/></toast>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_group_summary_scenario() {
+        let data = NotificationData {
+            group: Some("inbox".to_string()),
+            group_summary: true,
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\" scenario=\"reminder\"><visual>\
+                 <binding template=\"ToastGeneric\"><text>Title</text><text>Body</text>\
+                 </binding></visual></toast>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_require_interaction() {
+        let data = NotificationData {
+            require_interaction: true,
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\" scenario=\"reminder\"><visual>\
+                 <binding template=\"ToastGeneric\"><text>Title</text><text>Body</text>\
+                 </binding></visual><actions>\
+                 <action content=\"\" arguments=\"dismiss\" activationType=\"system\"/>\
+                 </actions></toast>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_persistent() {
+        let data = NotificationData {
+            persistent: true,
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\" duration=\"long\"><visual>\
+                 <binding template=\"ToastGeneric\"><text>Title</text><text>Body</text>\
+                 </binding></visual></toast>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_foreground_color() {
+        let data = NotificationData {
+            foreground_color: Some((0x1E, 0x90, 0xFF)),
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        assert_eq!(
+            xml,
+            format!(
+                "<toast launch=\"{LAUNCH_ID_7}\"><visual>\
+                 <binding template=\"ToastGeneric\" hint-foregroundColor=\"#1E90FF\">\
+                 <text>Title</text><text>Body</text></binding></visual></toast>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_toast_xml_snapshot_loads_into_dom() {
+        let data = NotificationData {
+            title: Some("Tom & Jerry".to_string()),
+            ..snapshot_data()
+        };
+        let xml = build_toast_xml(&data, &HashMap::new(), passthrough_image);
+        let doc = XmlDocument::new().expect("Failed to create XmlDocument");
+        doc.LoadXml(&HSTRING::from(xml.as_str()))
+            .expect("Generated toast XML must be well-formed");
+    }
+
     // ==================== Action Types Tests ====================
 
     #[test]
@@ -1310,4 +4708,41 @@ mod tests {
         assert_eq!(r.len(), 2);
         assert!(r.contains_key("confirm") && r.contains_key("reply"));
     }
+
+    #[test]
+    fn test_suppresses_notifications() {
+        use windows::Win32::UI::Shell::{QUNS_ACCEPTS_NOTIFICATIONS, QUNS_APP, QUNS_NOT_PRESENT};
+
+        assert!(suppresses_notifications(QUNS_BUSY));
+        assert!(suppresses_notifications(QUNS_RUNNING_D3D_FULL_SCREEN));
+        assert!(suppresses_notifications(QUNS_PRESENTATION_MODE));
+        assert!(suppresses_notifications(QUNS_QUIET_TIME));
+        assert!(!suppresses_notifications(QUNS_ACCEPTS_NOTIFICATIONS));
+        assert!(!suppresses_notifications(QUNS_NOT_PRESENT));
+        assert!(!suppresses_notifications(QUNS_APP));
+    }
+
+    #[test]
+    fn test_repost_order() {
+        let child = |tag: &str, sort_key: Option<&str>| ActiveNotification {
+            tag: Some(tag.to_string()),
+            sort_key: sort_key.map(str::to_string),
+            ..ActiveNotification::new(tag.parse().expect("numeric tag"), None, None)
+        };
+        // Group order is 1, 4, 2, 5, 3: the toasts before the new one (2)
+        // go up again, last first, so 1 ends up on top.
+        let children = vec![
+            child("1", Some("a")),
+            child("2", Some("c")),
+            child("3", None),
+            child("4", Some("b")),
+            child("5", Some("d")),
+        ];
+        assert_eq!(repost_order(children.clone(), "2"), ["4", "1"]);
+        assert!(repost_order(children.clone(), "1").is_empty());
+        assert!(repost_order(children, "9").is_empty());
+
+        let unkeyed = vec![child("1", None), child("2", None)];
+        assert!(repost_order(unkeyed, "2").is_empty());
+    }
 }